@@ -0,0 +1,648 @@
+use core::alloc::Allocator;
+use core::fmt;
+use core::ops::{Deref, DerefMut};
+
+use super::Hole;
+use crate::alloc::Global;
+use crate::vec::Vec;
+
+/// Marks the end of the list of vacant slots.
+const NO_SLOT: usize = usize::MAX;
+
+/// A priority queue implemented with a binary heap, whose elements can be
+/// reprioritized or removed after they were pushed.
+///
+/// Like [`BinaryHeap`], this is a max-heap. Unlike it, [`push`] returns a
+/// [`Handle`] that keeps referring to the pushed element while it moves
+/// around in the heap. The handle can be used to look at the element with
+/// [`get`], to change it in place with [`change_priority`], or to take it out
+/// with [`remove`], all in *O*(log(*n*)) time. This is the "decrease-key"
+/// operation that algorithms like Dijkstra's shortest path or timer queues
+/// rely on.
+///
+/// A handle stops being valid once its element leaves the heap, whether by
+/// [`pop`], [`remove`] or [`clear`]. Operations given such a stale handle
+/// return `None` rather than act on whichever element took its place. It is
+/// a logic error to pass a handle to a heap other than the one that returned
+/// it.
+///
+/// It is a logic error for an item to be modified in such a way that the
+/// item's ordering relative to any other item, as determined by the [`Ord`]
+/// trait, changes while it is in the heap, other than through
+/// [`change_priority`]. The behavior resulting from such a logic error is not
+/// specified, but will be encapsulated to the `IndexedBinaryHeap` that
+/// observed the logic error and not result in undefined behavior.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(indexed_binary_heap)]
+/// use std::cmp::Reverse;
+/// use std::collections::binary_heap::IndexedBinaryHeap;
+///
+/// // Timers ordered by their deadline, earliest first.
+/// let mut timers = IndexedBinaryHeap::new();
+/// let a = timers.push(Reverse((30, "a")));
+/// let b = timers.push(Reverse((10, "b")));
+/// let c = timers.push(Reverse((20, "c")));
+///
+/// // Cancel `c` and postpone `b`.
+/// assert_eq!(timers.remove(c), Some(Reverse((20, "c"))));
+/// timers.change_priority(b).unwrap().0.0 = 40;
+///
+/// assert_eq!(timers.pop(), Some(Reverse((30, "a"))));
+/// assert_eq!(timers.pop(), Some(Reverse((40, "b"))));
+/// assert_eq!(timers.pop(), None);
+///
+/// // Popped and removed elements can no longer be reached.
+/// assert!(!timers.contains(a) && timers.get(c).is_none());
+/// ```
+///
+/// [`BinaryHeap`]: super::BinaryHeap
+/// [`push`]: IndexedBinaryHeap::push
+/// [`pop`]: IndexedBinaryHeap::pop
+/// [`get`]: IndexedBinaryHeap::get
+/// [`change_priority`]: IndexedBinaryHeap::change_priority
+/// [`remove`]: IndexedBinaryHeap::remove
+/// [`clear`]: IndexedBinaryHeap::clear
+#[unstable(feature = "indexed_binary_heap", issue = "none")]
+pub struct IndexedBinaryHeap<
+    T,
+    #[unstable(feature = "allocator_api", issue = "32838")] A: Allocator = Global,
+> {
+    data: Vec<Node<T>, A>,
+    slots: Vec<Slot, A>,
+    // Head of the list of vacant slots, threaded through `Slot::link`.
+    free: usize,
+}
+
+/// A reference to an element of an [`IndexedBinaryHeap`].
+///
+/// This `struct` is returned by [`IndexedBinaryHeap::push`] and stays valid
+/// until the element leaves the heap.
+#[unstable(feature = "indexed_binary_heap", issue = "none")]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Handle {
+    slot: usize,
+    generation: usize,
+}
+
+/// Structure wrapping a mutable reference to an element of an
+/// [`IndexedBinaryHeap`].
+///
+/// When dropped, it moves the element to its new place in the heap.
+///
+/// This `struct` is created by the [`change_priority`] method on
+/// [`IndexedBinaryHeap`]. See its documentation for more.
+///
+/// [`change_priority`]: IndexedBinaryHeap::change_priority
+#[unstable(feature = "indexed_binary_heap", issue = "none")]
+pub struct PriorityMut<
+    'a,
+    T: 'a + Ord,
+    #[unstable(feature = "allocator_api", issue = "32838")] A: Allocator = Global,
+> {
+    heap: &'a mut IndexedBinaryHeap<T, A>,
+    pos: usize,
+}
+
+#[derive(Clone)]
+struct Node<T> {
+    value: T,
+    slot: usize,
+}
+
+#[derive(Clone)]
+struct Slot {
+    // Odd while the slot holds an element, even while it is vacant. Bumped
+    // on every change of state, so that handles to past elements go stale.
+    generation: usize,
+    // The element's position in `data` while occupied, or the next vacant
+    // slot while vacant.
+    link: usize,
+}
+
+#[unstable(feature = "indexed_binary_heap", issue = "none")]
+impl<T: Clone, A: Allocator + Clone> Clone for IndexedBinaryHeap<T, A> {
+    fn clone(&self) -> Self {
+        IndexedBinaryHeap { data: self.data.clone(), slots: self.slots.clone(), free: self.free }
+    }
+}
+
+#[unstable(feature = "indexed_binary_heap", issue = "none")]
+impl<T> Default for IndexedBinaryHeap<T> {
+    /// Creates an empty `IndexedBinaryHeap<T>`.
+    #[inline]
+    fn default() -> IndexedBinaryHeap<T> {
+        IndexedBinaryHeap::new()
+    }
+}
+
+#[unstable(feature = "indexed_binary_heap", issue = "none")]
+impl<T: fmt::Debug, A: Allocator> fmt::Debug for IndexedBinaryHeap<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.data.iter().map(|node| &node.value)).finish()
+    }
+}
+
+#[unstable(feature = "indexed_binary_heap", issue = "none")]
+impl<T: Ord + fmt::Debug, A: Allocator> fmt::Debug for PriorityMut<'_, T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("PriorityMut").field(&self.heap.data[self.pos].value).finish()
+    }
+}
+
+#[unstable(feature = "indexed_binary_heap", issue = "none")]
+impl<T: Ord, A: Allocator> Drop for PriorityMut<'_, T, A> {
+    fn drop(&mut self) {
+        // SAFETY: PriorityMut is only instantiated for a valid position, and
+        // the heap cannot shrink while we borrow it.
+        unsafe { self.heap.sift(self.pos) };
+    }
+}
+
+#[unstable(feature = "indexed_binary_heap", issue = "none")]
+impl<T: Ord, A: Allocator> Deref for PriorityMut<'_, T, A> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        &self.heap.data[self.pos].value
+    }
+}
+
+#[unstable(feature = "indexed_binary_heap", issue = "none")]
+impl<T: Ord, A: Allocator> DerefMut for PriorityMut<'_, T, A> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.heap.data[self.pos].value
+    }
+}
+
+impl<T> IndexedBinaryHeap<T> {
+    /// Creates an empty `IndexedBinaryHeap` as a max-heap.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(indexed_binary_heap)]
+    /// use std::collections::binary_heap::IndexedBinaryHeap;
+    ///
+    /// let mut heap = IndexedBinaryHeap::new();
+    /// heap.push(4);
+    /// ```
+    #[unstable(feature = "indexed_binary_heap", issue = "none")]
+    #[must_use]
+    pub const fn new() -> IndexedBinaryHeap<T> {
+        IndexedBinaryHeap { data: Vec::new(), slots: Vec::new(), free: NO_SLOT }
+    }
+
+    /// Creates an empty `IndexedBinaryHeap` with at least the specified
+    /// capacity.
+    ///
+    /// The heap will be able to hold at least `capacity` elements without
+    /// reallocating. If `capacity` is zero, the heap will not allocate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(indexed_binary_heap)]
+    /// use std::collections::binary_heap::IndexedBinaryHeap;
+    ///
+    /// let mut heap = IndexedBinaryHeap::with_capacity(10);
+    /// heap.push(4);
+    /// ```
+    #[unstable(feature = "indexed_binary_heap", issue = "none")]
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> IndexedBinaryHeap<T> {
+        IndexedBinaryHeap::with_capacity_in(capacity, Global)
+    }
+}
+
+impl<T, A: Allocator + Clone> IndexedBinaryHeap<T, A> {
+    /// Creates an empty `IndexedBinaryHeap` as a max-heap, using `A` as
+    /// allocator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(allocator_api, indexed_binary_heap)]
+    ///
+    /// use std::alloc::System;
+    /// use std::collections::binary_heap::IndexedBinaryHeap;
+    ///
+    /// let mut heap = IndexedBinaryHeap::new_in(System);
+    /// heap.push(4);
+    /// ```
+    #[unstable(feature = "indexed_binary_heap", issue = "none")]
+    #[must_use]
+    pub fn new_in(alloc: A) -> IndexedBinaryHeap<T, A> {
+        IndexedBinaryHeap {
+            data: Vec::new_in(alloc.clone()),
+            slots: Vec::new_in(alloc),
+            free: NO_SLOT,
+        }
+    }
+
+    /// Creates an empty `IndexedBinaryHeap` with at least the specified
+    /// capacity, using `A` as allocator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(allocator_api, indexed_binary_heap)]
+    ///
+    /// use std::alloc::System;
+    /// use std::collections::binary_heap::IndexedBinaryHeap;
+    ///
+    /// let mut heap = IndexedBinaryHeap::with_capacity_in(10, System);
+    /// heap.push(4);
+    /// ```
+    #[unstable(feature = "indexed_binary_heap", issue = "none")]
+    #[must_use]
+    pub fn with_capacity_in(capacity: usize, alloc: A) -> IndexedBinaryHeap<T, A> {
+        IndexedBinaryHeap {
+            data: Vec::with_capacity_in(capacity, alloc.clone()),
+            slots: Vec::with_capacity_in(capacity, alloc),
+            free: NO_SLOT,
+        }
+    }
+}
+
+impl<T: Ord, A: Allocator> IndexedBinaryHeap<T, A> {
+    /// Pushes an item onto the heap and returns a handle to it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(indexed_binary_heap)]
+    /// use std::collections::binary_heap::IndexedBinaryHeap;
+    ///
+    /// let mut heap = IndexedBinaryHeap::new();
+    /// let three = heap.push(3);
+    /// heap.push(5);
+    ///
+    /// assert_eq!(heap.peek(), Some(&5));
+    /// assert_eq!(heap.get(three), Some(&3));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(log(*n*)), not counting the occasional reallocation.
+    #[unstable(feature = "indexed_binary_heap", issue = "none")]
+    pub fn push(&mut self, item: T) -> Handle {
+        // Make room first, so that a failing allocation leaves no occupied
+        // slot without an element behind.
+        self.data.reserve(1);
+        let pos = self.data.len();
+        let slot = if self.free != NO_SLOT {
+            let slot = self.free;
+            self.free = self.slots[slot].link;
+            slot
+        } else {
+            self.slots.push(Slot { generation: 0, link: NO_SLOT });
+            self.slots.len() - 1
+        };
+        let entry = &mut self.slots[slot];
+        entry.generation = entry.generation.wrapping_add(1);
+        entry.link = pos;
+        let handle = Handle { slot, generation: entry.generation };
+
+        self.data.push(Node { value: item, slot });
+        // SAFETY: pos is the index of the element we just pushed.
+        unsafe { self.sift_up(pos) };
+        handle
+    }
+
+    /// Removes the greatest item from the heap and returns it, or `None` if
+    /// it is empty. Its handle is no longer valid afterwards.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(indexed_binary_heap)]
+    /// use std::collections::binary_heap::IndexedBinaryHeap;
+    ///
+    /// let mut heap = IndexedBinaryHeap::new();
+    /// let one = heap.push(1);
+    /// heap.push(3);
+    ///
+    /// assert_eq!(heap.pop(), Some(3));
+    /// assert_eq!(heap.pop(), Some(1));
+    /// assert_eq!(heap.pop(), None);
+    /// assert!(!heap.contains(one));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The worst case cost of `pop` on a heap containing *n* elements is *O*(log(*n*)).
+    #[unstable(feature = "indexed_binary_heap", issue = "none")]
+    pub fn pop(&mut self) -> Option<T> {
+        if self.data.is_empty() { None } else { Some(self.remove_at(0)) }
+    }
+
+    /// Returns a mutable reference to the element of `handle`, or `None` if
+    /// the handle is no longer valid.
+    ///
+    /// The element may be changed in any way through the returned
+    /// [`PriorityMut`]. Once it is dropped, the element is moved up or down
+    /// to restore the heap order, so this covers both increasing and
+    /// decreasing its priority.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(indexed_binary_heap)]
+    /// use std::collections::binary_heap::IndexedBinaryHeap;
+    ///
+    /// let mut heap = IndexedBinaryHeap::new();
+    /// let one = heap.push(1);
+    /// let five = heap.push(5);
+    /// heap.push(3);
+    ///
+    /// *heap.change_priority(one).unwrap() = 7;
+    /// *heap.change_priority(five).unwrap() = 2;
+    ///
+    /// assert_eq!(heap.pop(), Some(7));
+    /// assert_eq!(heap.pop(), Some(3));
+    /// assert_eq!(heap.pop(), Some(2));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// If the item is modified then the worst case time complexity is
+    /// *O*(log(*n*)), otherwise it's *O*(1).
+    #[unstable(feature = "indexed_binary_heap", issue = "none")]
+    pub fn change_priority(&mut self, handle: Handle) -> Option<PriorityMut<'_, T, A>> {
+        let pos = self.position(handle)?;
+        Some(PriorityMut { heap: self, pos })
+    }
+
+    /// Removes the element of `handle` from the heap and returns it, or
+    /// `None` if the handle is no longer valid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(indexed_binary_heap)]
+    /// use std::collections::binary_heap::IndexedBinaryHeap;
+    ///
+    /// let mut heap = IndexedBinaryHeap::new();
+    /// heap.push(1);
+    /// let five = heap.push(5);
+    ///
+    /// assert_eq!(heap.remove(five), Some(5));
+    /// assert_eq!(heap.remove(five), None);
+    /// assert_eq!(heap.pop(), Some(1));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// *O*(log(*n*)).
+    #[unstable(feature = "indexed_binary_heap", issue = "none")]
+    pub fn remove(&mut self, handle: Handle) -> Option<T> {
+        let pos = self.position(handle)?;
+        Some(self.remove_at(pos))
+    }
+
+    /// Removes the element at `pos`, fills its place with the last element
+    /// and moves that one to where it belongs.
+    fn remove_at(&mut self, pos: usize) -> T {
+        let node = self.data.swap_remove(pos);
+        let entry = &mut self.slots[node.slot];
+        entry.generation = entry.generation.wrapping_add(1);
+        entry.link = self.free;
+        self.free = node.slot;
+
+        if pos < self.data.len() {
+            self.slots[self.data[pos].slot].link = pos;
+            // SAFETY: pos < self.data.len() was just checked.
+            unsafe { self.sift(pos) };
+        }
+        node.value
+    }
+
+    /// Moves the element at `pos` up or down, wherever it belongs.
+    ///
+    /// # Safety
+    ///
+    /// The caller must guarantee that `pos < self.len()`.
+    unsafe fn sift(&mut self, pos: usize) {
+        // SAFETY: The caller guarantees that pos < self.len().
+        if unsafe { self.sift_up(pos) } == pos {
+            // SAFETY: The element did not move, so pos is still valid.
+            unsafe { self.sift_down(pos) };
+        }
+    }
+
+    // The sifting below mirrors `BinaryHeap`, but also records every move in
+    // the slot table. Both the moved element and the sifted element are
+    // recorded at each step, so the table stays accurate even if a
+    // comparison panics halfway.
+
+    /// # Safety
+    ///
+    /// The caller must guarantee that `pos < self.len()`.
+    ///
+    /// Returns the new position of the element.
+    unsafe fn sift_up(&mut self, pos: usize) -> usize {
+        // SAFETY: The caller guarantees that pos < self.len().
+        let mut hole = unsafe { Hole::new(&mut self.data, pos) };
+
+        while hole.pos() > 0 {
+            let parent = (hole.pos() - 1) / 2;
+
+            // SAFETY: hole.pos() > 0, so parent < hole.pos() is a valid index
+            //  and != hole.pos().
+            let parent_node = unsafe { hole.get(parent) };
+            if hole.element().value <= parent_node.value {
+                break;
+            }
+
+            self.slots[parent_node.slot].link = hole.pos();
+            self.slots[hole.element().slot].link = parent;
+            // SAFETY: Same as above.
+            unsafe { hole.move_to(parent) };
+        }
+
+        hole.pos()
+    }
+
+    /// Take an element at `pos` and move it down the heap,
+    /// while its children are larger.
+    ///
+    /// # Safety
+    ///
+    /// The caller must guarantee that `pos < self.len()`.
+    unsafe fn sift_down(&mut self, pos: usize) {
+        let end = self.data.len();
+        // SAFETY: The caller guarantees that pos < self.len().
+        let mut hole = unsafe { Hole::new(&mut self.data, pos) };
+        let mut child = 2 * hole.pos() + 1;
+
+        // Loop invariant: child == 2 * hole.pos() + 1.
+        while child < end {
+            // compare with the greater of the two children, if there are two
+            // SAFETY: child < end and, when checked, child + 1 < end, so they
+            //  are valid indexes distinct from hole.pos().
+            if child + 1 < end && unsafe { hole.get(child).value <= hole.get(child + 1).value } {
+                child += 1;
+            }
+
+            // if we are already in order, stop.
+            // SAFETY: child is a valid index distinct from hole.pos().
+            let child_node = unsafe { hole.get(child) };
+            if hole.element().value >= child_node.value {
+                break;
+            }
+
+            self.slots[child_node.slot].link = hole.pos();
+            self.slots[hole.element().slot].link = child;
+            // SAFETY: same as above.
+            unsafe { hole.move_to(child) };
+            child = 2 * hole.pos() + 1;
+        }
+    }
+}
+
+impl<T, A: Allocator> IndexedBinaryHeap<T, A> {
+    /// Returns the greatest item in the heap, or `None` if it is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(indexed_binary_heap)]
+    /// use std::collections::binary_heap::IndexedBinaryHeap;
+    ///
+    /// let mut heap = IndexedBinaryHeap::new();
+    /// assert_eq!(heap.peek(), None);
+    ///
+    /// heap.push(1);
+    /// heap.push(5);
+    /// heap.push(2);
+    /// assert_eq!(heap.peek(), Some(&5));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// Cost is *O*(1) in the worst case.
+    #[unstable(feature = "indexed_binary_heap", issue = "none")]
+    #[must_use]
+    pub fn peek(&self) -> Option<&T> {
+        self.data.first().map(|node| &node.value)
+    }
+
+    /// Returns the element of `handle`, or `None` if the handle is no longer
+    /// valid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(indexed_binary_heap)]
+    /// use std::collections::binary_heap::IndexedBinaryHeap;
+    ///
+    /// let mut heap = IndexedBinaryHeap::new();
+    /// let two = heap.push(2);
+    ///
+    /// assert_eq!(heap.get(two), Some(&2));
+    /// heap.pop();
+    /// assert_eq!(heap.get(two), None);
+    /// ```
+    #[unstable(feature = "indexed_binary_heap", issue = "none")]
+    #[must_use]
+    pub fn get(&self, handle: Handle) -> Option<&T> {
+        self.position(handle).map(|pos| &self.data[pos].value)
+    }
+
+    /// Returns `true` if the element of `handle` is still in the heap.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(indexed_binary_heap)]
+    /// use std::collections::binary_heap::IndexedBinaryHeap;
+    ///
+    /// let mut heap = IndexedBinaryHeap::new();
+    /// let two = heap.push(2);
+    ///
+    /// assert!(heap.contains(two));
+    /// heap.clear();
+    /// assert!(!heap.contains(two));
+    /// ```
+    #[unstable(feature = "indexed_binary_heap", issue = "none")]
+    #[must_use]
+    pub fn contains(&self, handle: Handle) -> bool {
+        self.position(handle).is_some()
+    }
+
+    /// Returns the length of the heap.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(indexed_binary_heap)]
+    /// use std::collections::binary_heap::IndexedBinaryHeap;
+    ///
+    /// let mut heap = IndexedBinaryHeap::new();
+    /// heap.push(1);
+    /// heap.push(3);
+    ///
+    /// assert_eq!(heap.len(), 2);
+    /// ```
+    #[unstable(feature = "indexed_binary_heap", issue = "none")]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Checks if the heap is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(indexed_binary_heap)]
+    /// use std::collections::binary_heap::IndexedBinaryHeap;
+    ///
+    /// let mut heap = IndexedBinaryHeap::new();
+    /// assert!(heap.is_empty());
+    ///
+    /// heap.push(3);
+    /// assert!(!heap.is_empty());
+    /// ```
+    #[unstable(feature = "indexed_binary_heap", issue = "none")]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drops all items from the heap. All handles to them become invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(indexed_binary_heap)]
+    /// use std::collections::binary_heap::IndexedBinaryHeap;
+    ///
+    /// let mut heap = IndexedBinaryHeap::new();
+    /// let one = heap.push(1);
+    /// heap.push(3);
+    ///
+    /// heap.clear();
+    /// assert!(heap.is_empty());
+    /// assert!(!heap.contains(one));
+    /// ```
+    #[unstable(feature = "indexed_binary_heap", issue = "none")]
+    pub fn clear(&mut self) {
+        // Vacate the slots before dropping anything, in case a drop panics.
+        for node in &self.data {
+            let entry = &mut self.slots[node.slot];
+            entry.generation = entry.generation.wrapping_add(1);
+            entry.link = self.free;
+            self.free = node.slot;
+        }
+        self.data.clear();
+    }
+
+    /// Returns the current position of the element of `handle`, if any.
+    fn position(&self, handle: Handle) -> Option<usize> {
+        let entry = self.slots.get(handle.slot)?;
+        (entry.generation == handle.generation).then_some(entry.link)
+    }
+}
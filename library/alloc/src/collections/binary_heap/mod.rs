@@ -157,6 +157,11 @@
 use crate::vec::AsVecIntoIter;
 use crate::vec::{self, Vec};
 
+mod indexed;
+
+#[unstable(feature = "indexed_binary_heap", issue = "none")]
+pub use indexed::{Handle, IndexedBinaryHeap, PriorityMut};
+
 /// A priority queue implemented with a binary heap.
 ///
 /// This will be a max-heap.
@@ -1,11 +1,44 @@
 use core::alloc::Allocator;
+use core::iter::FusedIterator;
+use core::mem;
 
-use super::node::{self, Root};
+use super::map::MIN_LEN;
+use super::merge_iter::MergeIterInner;
+use super::node::ForceResult::*;
+use super::node::{self, Root, SplitResult, marker};
 
-impl<K, V> Root<K, V> {
-    /// Pushes all key-value pairs to the end of the tree, incrementing a
-    /// `length` variable along the way. The latter makes it easier for the
-    /// caller to avoid a leak when the iterator panicks.
+impl<K, V, C: marker::Counting> Root<K, V, C> {
+    /// Appends all key-value pairs from the union of two ascending iterators,
+    /// incrementing a `length` variable along the way. The latter makes it
+    /// easier for the caller to avoid a leak when a drop handler or `conflict`
+    /// panicks. The values of pairs whose keys occur in both iterators are
+    /// combined by `conflict`, which receives the key and value from `left`
+    /// and the value from `right`.
+    ///
+    /// If either iterator yields keys in a non-ascending order, the merged
+    /// sequence is not sorted either, and neither is the resulting tree.
+    pub(super) fn append_from_sorted_iters<I, F, A: Allocator + Clone>(
+        &mut self,
+        left: I,
+        right: I,
+        conflict: F,
+        length: &mut usize,
+        alloc: A,
+    ) where
+        K: Ord,
+        I: Iterator<Item = (K, V)> + FusedIterator,
+        F: FnMut(&K, V, V) -> V,
+    {
+        // We prepare to merge `left` and `right` into a sorted sequence in linear time.
+        let iter = MergeIter { inner: MergeIterInner::new(left, right), conflict };
+
+        // Meanwhile, we build a tree from the sorted sequence in linear time.
+        self.bulk_push(iter, length, alloc)
+    }
+
+    /// Pushes all key-value pairs to the end of an initially empty tree,
+    /// incrementing a `length` variable along the way. The latter makes it
+    /// easier for the caller to avoid a leak when the iterator panicks.
     pub(super) fn bulk_push<I, A: Allocator + Clone>(
         &mut self,
         iter: I,
@@ -13,6 +46,21 @@ pub(super) fn bulk_push<I, A: Allocator + Clone>(
         alloc: A,
     ) where
         I: Iterator<Item = (K, V)>,
+    {
+        self.push_to_right_border(iter, length, alloc);
+        self.fix_right_border_of_plentiful();
+    }
+
+    /// Pushes all key-value pairs to the end of the tree, leaving the right
+    /// border of the tree possibly underfull. Every node that is no longer
+    /// on the right border afterwards is full.
+    fn push_to_right_border<I, A: Allocator + Clone>(
+        &mut self,
+        iter: I,
+        length: &mut usize,
+        alloc: A,
+    ) where
+        I: Iterator<Item = (K, V)>,
     {
         let mut cur_node = self.borrow_mut().last_leaf_edge().into_node();
         // Iterate through all key-value pairs, pushing them into nodes at the right level.
@@ -27,13 +75,15 @@ pub(super) fn bulk_push<I, A: Allocator + Clone>(
                 loop {
                     match test_node.ascend() {
                         Ok(parent) => {
-                            let parent = parent.into_node();
+                            let mut parent = parent.into_node();
                             if parent.len() < node::CAPACITY {
                                 // Found a node with space left, push here.
                                 open_node = parent;
                                 break;
                             } else {
-                                // Go up again.
+                                // Go up again. We leave this node behind for good, so in
+                                // a counted tree, now is the time to settle its subtree length.
+                                parent.recalc_subtree_len();
                                 test_node = parent.forget_type();
                             }
                         }
@@ -61,6 +111,102 @@ pub(super) fn bulk_push<I, A: Allocator + Clone>(
             // the appended elements even if advancing the iterator panicks.
             *length += 1;
         }
-        self.fix_right_border_of_plentiful();
+        // In a counted tree, pushing into the rightmost leaf doesn't keep track of
+        // subtree lengths, but only the nodes still on the right border are left to settle.
+        cur_node.recalc_ancestors_subtree_len();
+    }
+}
+
+impl<K, V> Root<K, V, marker::Uncounted> {
+    /// Appends the key-value pair `kv`, followed by all key-value pairs of `right`, to the
+    /// end of the tree, in time logarithmic in the size of both trees. Both trees must be
+    /// non-empty and respect all `BTreeMap` tree invariants, all keys in `self` must be less
+    /// than the key of `kv`, and that must be less than all keys in `right`, for the tree to
+    /// remain ordered.
+    pub(super) fn append_tree<A: Allocator + Clone>(
+        &mut self,
+        (key, val): (K, V),
+        right: Root<K, V, marker::Uncounted>,
+        alloc: A,
+    ) {
+        debug_assert!(self.len() > 0 && right.len() > 0);
+        let (left_height, right_height) = (self.height(), right.height());
+        if left_height == right_height {
+            let mut root = self.push_internal_level(alloc.clone());
+            root.push(key, val, right);
+            // Either of the former roots may have fewer than MIN_LEN elements.
+            let mut parent_kv = root.last_kv().consider_for_balancing();
+            let left_len = parent_kv.left_child_len();
+            let right_len = parent_kv.right_child_len();
+            if parent_kv.can_merge() {
+                parent_kv.merge_tracking_parent(alloc.clone());
+                self.fix_top(alloc);
+            } else if left_len < MIN_LEN {
+                parent_kv.bulk_steal_right(MIN_LEN - left_len);
+            } else if right_len < MIN_LEN {
+                parent_kv.bulk_steal_left(MIN_LEN - right_len);
+            }
+        } else if left_height > right_height {
+            // Hang `right` from the right border of `self`, where its former root
+            // may be underfull.
+            let Internal(parent) = self.borrow_mut().last_node_at_height(right_height + 1).force()
+            else {
+                unreachable!()
+            };
+            if let Some(SplitResult { kv: (key, val), right, .. }) =
+                parent.push_recursing(key, val, right, alloc.clone())
+            {
+                self.push_internal_level(alloc.clone()).push(key, val, right);
+            }
+            self.borrow_mut()
+                .last_node_at_height(right_height)
+                .fix_node_and_affected_ancestors(alloc.clone());
+            self.fix_top(alloc);
+        } else {
+            // The symmetric clone of the above, hanging `self` from the left border of `right`.
+            let left = mem::replace(self, right);
+            let Internal(parent) = self.borrow_mut().first_node_at_height(left_height + 1).force()
+            else {
+                unreachable!()
+            };
+            if let Some(SplitResult { kv: (key, val), right, .. }) =
+                parent.push_front_recursing(key, val, left, alloc.clone())
+            {
+                self.push_internal_level(alloc.clone()).push(key, val, right);
+            }
+            self.borrow_mut()
+                .first_node_at_height(left_height)
+                .fix_node_and_affected_ancestors(alloc.clone());
+            self.fix_top(alloc);
+        }
+    }
+}
+
+// An iterator for merging two sorted sequences into one
+struct MergeIter<K, V, I: Iterator<Item = (K, V)>, F> {
+    inner: MergeIterInner<I>,
+    conflict: F,
+}
+
+impl<K: Ord, V, I, F> Iterator for MergeIter<K, V, I, F>
+where
+    I: Iterator<Item = (K, V)> + FusedIterator,
+    F: FnMut(&K, V, V) -> V,
+{
+    type Item = (K, V);
+
+    /// If two keys are equal, returns the key from the left and the value
+    /// combined by `conflict`.
+    fn next(&mut self) -> Option<(K, V)> {
+        let (a_next, b_next) = self.inner.nexts(|a: &(K, V), b: &(K, V)| K::cmp(&a.0, &b.0));
+        match (a_next, b_next) {
+            (Some((key, a_val)), Some((b_key, b_val))) => {
+                // Drop the redundant key before anything else can panic.
+                drop(b_key);
+                let val = (self.conflict)(&key, a_val, b_val);
+                Some((key, val))
+            }
+            (a_next, b_next) => a_next.or(b_next),
+        }
     }
 }
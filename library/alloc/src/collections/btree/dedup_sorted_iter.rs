@@ -1,3 +1,4 @@
+use core::cmp::Ordering;
 use core::iter::Peekable;
 
 /// An iterator for deduping the key of a sorted iterator.
@@ -47,3 +48,61 @@ fn next(&mut self) -> Option<(K, V)> {
         }
     }
 }
+
+/// An iterator for deduping the key of an iterator that is supposed to be
+/// sorted. When encountering a key that is less than the key before it,
+/// the iterator notes that and stops, instead of yielding any further pairs.
+///
+/// Used by [`BTreeMap::from_sorted_iter`][1].
+///
+/// [1]: crate::collections::BTreeMap::from_sorted_iter
+pub(super) struct CheckedDedupSortedIter<K, V, I>
+where
+    I: Iterator<Item = (K, V)>,
+{
+    iter: Peekable<I>,
+    unordered: bool,
+}
+
+impl<K, V, I> CheckedDedupSortedIter<K, V, I>
+where
+    I: Iterator<Item = (K, V)>,
+{
+    pub(super) fn new(iter: I) -> Self {
+        Self { iter: iter.peekable(), unordered: false }
+    }
+
+    /// Whether the iterator stopped because it encountered unordered keys.
+    pub(super) fn is_unordered(&self) -> bool {
+        self.unordered
+    }
+}
+
+impl<K, V, I> Iterator for CheckedDedupSortedIter<K, V, I>
+where
+    K: Ord,
+    I: Iterator<Item = (K, V)>,
+{
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<(K, V)> {
+        loop {
+            if self.unordered {
+                return None;
+            }
+
+            let next = self.iter.next()?;
+
+            let peeked = match self.iter.peek() {
+                Some(peeked) => peeked,
+                None => return Some(next),
+            };
+
+            match next.0.cmp(&peeked.0) {
+                Ordering::Less => return Some(next),
+                Ordering::Equal => {}
+                Ordering::Greater => self.unordered = true,
+            }
+        }
+    }
+}
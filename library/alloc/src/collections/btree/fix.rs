@@ -5,7 +5,9 @@
 use super::node::LeftOrRight::*;
 use super::node::{Handle, NodeRef, Root, marker};
 
-impl<'a, K: 'a, V: 'a> NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal> {
+impl<'a, K: 'a, V: 'a, C: marker::Counting>
+    NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal, C>
+{
     /// Stocks up a possibly underfull node by merging with or stealing from a
     /// sibling. If successful but at the cost of shrinking the parent node,
     /// returns that shrunk parent node. Returns an `Err` if the node is
@@ -13,7 +15,7 @@ impl<'a, K: 'a, V: 'a> NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal> {
     fn fix_node_through_parent<A: Allocator + Clone>(
         self,
         alloc: A,
-    ) -> Result<Option<NodeRef<marker::Mut<'a>, K, V, marker::Internal>>, Self> {
+    ) -> Result<Option<NodeRef<marker::Mut<'a>, K, V, marker::Internal, C>>, Self> {
         let len = self.len();
         if len >= MIN_LEN {
             Ok(None)
@@ -49,7 +51,9 @@ fn fix_node_through_parent<A: Allocator + Clone>(
     }
 }
 
-impl<'a, K: 'a, V: 'a> NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal> {
+impl<'a, K: 'a, V: 'a, C: marker::Counting>
+    NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal, C>
+{
     /// Stocks up a possibly underfull node, and if that causes its parent node
     /// to shrink, stocks up the parent, recursively.
     /// Returns `true` if it fixed the tree, `false` if it couldn't because the
@@ -71,7 +75,7 @@ pub(super) fn fix_node_and_affected_ancestors<A: Allocator + Clone>(
     }
 }
 
-impl<K, V> Root<K, V> {
+impl<K, V, C: marker::Counting> Root<K, V, C> {
     /// Removes empty levels on the top, but keeps an empty leaf if the entire tree is empty.
     pub(super) fn fix_top<A: Allocator + Clone>(&mut self, alloc: A) {
         while self.height() > 0 && self.len() == 0 {
@@ -120,7 +124,9 @@ pub(super) fn fix_right_border_of_plentiful(&mut self) {
     }
 }
 
-impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal>, marker::KV> {
+impl<'a, K: 'a, V: 'a, C: marker::Counting>
+    Handle<NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal, C>, marker::KV>
+{
     fn fix_left_border_of_left_edge<A: Allocator + Clone>(mut self, alloc: A) {
         while let Internal(internal_kv) = self.force() {
             self = internal_kv.fix_left_child(alloc.clone()).first_kv();
@@ -136,7 +142,9 @@ fn fix_right_border_of_right_edge<A: Allocator + Clone>(mut self, alloc: A) {
     }
 }
 
-impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Internal>, marker::KV> {
+impl<'a, K: 'a, V: 'a, C: marker::Counting>
+    Handle<NodeRef<marker::Mut<'a>, K, V, marker::Internal, C>, marker::KV>
+{
     /// Stocks up the left child, assuming the right child isn't underfull, and
     /// provisions an extra element to allow merging its children in turn
     /// without becoming underfull.
@@ -144,7 +152,7 @@ impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Internal>,
     fn fix_left_child<A: Allocator + Clone>(
         self,
         alloc: A,
-    ) -> NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal> {
+    ) -> NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal, C> {
         let mut internal_kv = self.consider_for_balancing();
         let left_len = internal_kv.left_child_len();
         debug_assert!(internal_kv.right_child_len() >= MIN_LEN);
@@ -167,7 +175,7 @@ fn fix_left_child<A: Allocator + Clone>(
     fn fix_right_child<A: Allocator + Clone>(
         self,
         alloc: A,
-    ) -> NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal> {
+    ) -> NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal, C> {
         let mut internal_kv = self.consider_for_balancing();
         let right_len = internal_kv.right_child_len();
         debug_assert!(internal_kv.left_child_len() >= MIN_LEN);
@@ -10,7 +10,7 @@
 use core::ptr;
 
 use super::borrow::DormantMutRef;
-use super::dedup_sorted_iter::DedupSortedIter;
+use super::dedup_sorted_iter::{CheckedDedupSortedIter, DedupSortedIter};
 use super::navigate::{LazyLeafRange, LeafRange};
 use super::node::ForceResult::*;
 use super::node::{self, Handle, NodeRef, Root, marker};
@@ -21,10 +21,13 @@
 use crate::vec::Vec;
 
 mod entry;
+mod ranked;
 
 use Entry::*;
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use entry::{Entry, OccupiedEntry, OccupiedError, VacantEntry};
+#[unstable(feature = "btree_order_statistics", issue = "none")]
+pub use ranked::RankedMap;
 
 /// Minimum number of elements in a node that is not a root.
 /// We might temporarily have fewer elements during methods.
@@ -191,7 +194,7 @@ pub struct BTreeMap<
     V,
     #[unstable(feature = "allocator_api", issue = "32838")] A: Allocator + Clone = Global,
 > {
-    root: Option<Root<K, V>>,
+    root: Option<Root<K, V, marker::Uncounted>>,
     length: usize,
     /// `ManuallyDrop` to control drop order (needs to be dropped after all the nodes).
     // Although some of the accessory types store a copy of the allocator, the nodes do not.
@@ -226,7 +229,7 @@ impl<K, V, A: Allocator + Clone> core::panic::UnwindSafe for BTreeMap<K, V, A>
 impl<K: Clone, V: Clone, A: Allocator + Clone> Clone for BTreeMap<K, V, A> {
     fn clone(&self) -> BTreeMap<K, V, A> {
         fn clone_subtree<'a, K: Clone, V: Clone, A: Allocator + Clone>(
-            node: NodeRef<marker::Immut<'a>, K, V, marker::LeafOrInternal>,
+            node: NodeRef<marker::Immut<'a>, K, V, marker::LeafOrInternal, marker::Uncounted>,
             alloc: A,
         ) -> BTreeMap<K, V, A>
         where
@@ -651,6 +654,48 @@ impl<K, V> BTreeMap<K, V> {
     pub const fn new() -> BTreeMap<K, V> {
         BTreeMap { root: None, length: 0, alloc: ManuallyDrop::new(Global), _marker: PhantomData }
     }
+
+    /// Makes a `BTreeMap` from an iterator of key-value pairs in ascending
+    /// key order, in time linear to the number of pairs.
+    ///
+    /// If the iterator yields a key more than once, the value that comes last
+    /// is kept, like [`collect`] does. Unlike [`collect`], the pairs are not
+    /// sorted, but only checked to be in order: an [`UnsortedKeysError`] is
+    /// returned as soon as a key is less than the key before it, and the pairs
+    /// taken from the iterator so far are dropped.
+    ///
+    /// [`collect`]: Iterator::collect
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(btree_from_sorted_iter)]
+    /// use std::collections::BTreeMap;
+    ///
+    /// let map = BTreeMap::from_sorted_iter([(1, "a"), (2, "b"), (2, "c"), (4, "d")]).unwrap();
+    /// assert_eq!(map.len(), 3);
+    /// assert_eq!(map[&2], "c");
+    ///
+    /// assert!(BTreeMap::from_sorted_iter([(1, "a"), (4, "b"), (2, "c")]).is_err());
+    /// ```
+    #[unstable(feature = "btree_from_sorted_iter", issue = "none")]
+    pub fn from_sorted_iter<I>(iter: I) -> Result<BTreeMap<K, V>, UnsortedKeysError>
+    where
+        K: Ord,
+        I: IntoIterator<Item = (K, V)>,
+    {
+        let mut map = BTreeMap {
+            root: Some(Root::new(Global)),
+            length: 0,
+            alloc: ManuallyDrop::new(Global),
+            _marker: PhantomData,
+        };
+        let mut iter = CheckedDedupSortedIter::new(iter.into_iter());
+        // The map drops the pairs pushed so far if the iterator panicks.
+        let root = map.root.as_mut().unwrap();
+        root.bulk_push(&mut iter, &mut map.length, Global);
+        if iter.is_unordered() { Err(UnsortedKeysError {}) } else { Ok(map) }
+    }
 }
 
 impl<K, V, A: Allocator + Clone> BTreeMap<K, V, A> {
@@ -1255,6 +1300,10 @@ pub fn append(&mut self, other: &mut Self)
     /// Similar to [`insert`], though, the key is not overwritten,
     /// which matters for types that can be `==` without being identical.
     ///
+    /// If `other` is not much smaller than `self`, both maps are merged by
+    /// rebuilding the tree in time linear to their combined length. Otherwise,
+    /// each element of `other` is inserted into place.
+    ///
     /// [`insert`]: BTreeMap::insert
     /// [`append`]: BTreeMap::append
     ///
@@ -1304,6 +1353,31 @@ pub fn merge(&mut self, mut other: Self, mut conflict: impl FnMut(&K, V, V) -> V
             return;
         }
 
+        // Inserting each element of `other` costs logarithmic time, while
+        // rebuilding costs constant time per element of either map.
+        if other.len() >= self.len() / (self.len().ilog2() as usize + 1) {
+            let alloc = (*self.alloc).clone();
+            let self_iter = mem::replace(self, Self::new_in(alloc.clone())).into_iter();
+            // Build into a separate map, such that `self` is merely empty
+            // if `conflict` or a drop handler panicks.
+            let mut merged = BTreeMap {
+                root: Some(Root::new(alloc.clone())),
+                length: 0,
+                alloc: ManuallyDrop::new(alloc.clone()),
+                _marker: PhantomData,
+            };
+            let root = merged.root.as_mut().unwrap();
+            root.append_from_sorted_iters(
+                self_iter,
+                other.into_iter(),
+                conflict,
+                &mut merged.length,
+                alloc,
+            );
+            *self = merged;
+            return;
+        }
+
         let mut other_iter = other.into_iter();
         let (first_other_key, first_other_val) = other_iter.next().unwrap();
 
@@ -1583,6 +1657,91 @@ pub fn split_off<Q: ?Sized + Ord>(&mut self, key: &Q) -> Self
         }
     }
 
+    /// Moves all elements with a key in the given range into a new map,
+    /// which is returned. The elements outside the range remain in `self`.
+    ///
+    /// Cutting out the range and joining what remains takes logarithmic time.
+    /// Like [`split_off`](Self::split_off), it also takes time to count the
+    /// elements of the smaller part on each side of a cut.
+    ///
+    /// # Panics
+    ///
+    /// Panics if range `start > end`.
+    /// Panics if range `start == end` and both bounds are `Excluded`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(btree_split_off_range)]
+    /// use std::collections::BTreeMap;
+    ///
+    /// let mut a = BTreeMap::from([(1, "a"), (2, "b"), (3, "c"), (17, "d"), (41, "e")]);
+    ///
+    /// let b = a.split_off_range(2..=17);
+    ///
+    /// assert_eq!(a.into_iter().collect::<Vec<_>>(), [(1, "a"), (41, "e")]);
+    /// assert_eq!(b.into_iter().collect::<Vec<_>>(), [(2, "b"), (3, "c"), (17, "d")]);
+    /// ```
+    #[unstable(feature = "btree_split_off_range", issue = "none")]
+    pub fn split_off_range<T: ?Sized, R>(&mut self, range: R) -> Self
+    where
+        T: Ord,
+        K: Borrow<T> + Ord,
+        R: RangeBounds<T>,
+        A: Clone,
+    {
+        let alloc = (*self.alloc).clone();
+        let Some(left_root) = self.root.as_mut() else {
+            return Self::new_in(alloc);
+        };
+        // Like `range`, this panics if the range is invalid.
+        if left_root.reborrow().search_tree_for_bifurcation(&range).is_err() {
+            // The range is empty.
+            return Self::new_in(alloc);
+        }
+
+        let start = SearchBound::from_range(range.start_bound());
+        let mut mid_root = left_root.split_off_bound(start, alloc.clone());
+        let (_, rest_len) = Root::calc_split_length(self.length, left_root, &mid_root);
+        let end = match range.end_bound() {
+            Bound::Included(key) => SearchBound::Excluded(key),
+            Bound::Excluded(key) => SearchBound::Included(key),
+            Bound::Unbounded => SearchBound::AllExcluded,
+        };
+        let right_root = mid_root.split_off_bound(end, alloc.clone());
+        let (mid_len, right_len) = Root::calc_split_length(rest_len, &mid_root, &right_root);
+
+        let mut right = BTreeMap {
+            root: Some(right_root),
+            length: right_len,
+            alloc: ManuallyDrop::new(alloc.clone()),
+            _marker: PhantomData,
+        };
+        self.length -= mid_len + right_len;
+        if self.length == 0 {
+            // Nothing precedes the range, so what follows it is all that remains.
+            mem::swap(self, &mut right);
+        } else if let Some((key, val)) = right.pop_first() {
+            // Join both parts around the first key-value pair after the range.
+            if right.is_empty() {
+                self.insert(key, val);
+            } else {
+                let right_len = mem::take(&mut right.length);
+                let right_root = right.root.take().unwrap();
+                let left_root = self.root.as_mut().unwrap();
+                left_root.append_tree((key, val), right_root, alloc.clone());
+                self.length += 1 + right_len;
+            }
+        }
+
+        BTreeMap {
+            root: Some(mid_root),
+            length: mid_len,
+            alloc: ManuallyDrop::new(alloc),
+            _marker: PhantomData,
+        }
+    }
+
     /// Creates an iterator that visits elements (key-value pairs) in the specified range in
     /// ascending key order and uses a closure to determine if an element
     /// should be removed.
@@ -1931,7 +2090,9 @@ impl<K, V, A: Allocator + Clone> IntoIter<K, V, A> {
     /// invalidated by further calls to this function and some others.
     fn dying_next(
         &mut self,
-    ) -> Option<Handle<NodeRef<marker::Dying, K, V, marker::LeafOrInternal>, marker::KV>> {
+    ) -> Option<
+        Handle<NodeRef<marker::Dying, K, V, marker::LeafOrInternal, marker::Uncounted>, marker::KV>,
+    > {
         if self.length == 0 {
             self.range.deallocating_end(self.alloc.clone());
             None
@@ -1945,7 +2106,9 @@ fn dying_next(
     /// invalidated by further calls to this function and some others.
     fn dying_next_back(
         &mut self,
-    ) -> Option<Handle<NodeRef<marker::Dying, K, V, marker::LeafOrInternal>, marker::KV>> {
+    ) -> Option<
+        Handle<NodeRef<marker::Dying, K, V, marker::LeafOrInternal, marker::Uncounted>, marker::KV>,
+    > {
         if self.length == 0 {
             self.range.deallocating_end(self.alloc.clone());
             None
@@ -2148,11 +2311,13 @@ pub(super) struct ExtractIfInner<'a, K, V, R> {
     length: &'a mut usize,
     /// Buried reference to the root field in the borrowed map.
     /// Wrapped in `Option` to allow drop handler to `take` it.
-    dormant_root: Option<DormantMutRef<'a, Root<K, V>>>,
+    dormant_root: Option<DormantMutRef<'a, Root<K, V, marker::Uncounted>>>,
     /// Contains a leaf edge preceding the next element to be returned, or the last leaf edge.
     /// Empty if the map has no root, if iteration went beyond the last leaf edge,
     /// or if a panic occurred in the predicate.
-    cur_leaf_edge: Option<Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::Edge>>,
+    cur_leaf_edge: Option<
+        Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf, marker::Uncounted>, marker::Edge>,
+    >,
     /// Range over which iteration was requested.  We don't need the left side, but we
     /// can't extract the right side without requiring K: Clone.
     range: R,
@@ -2595,7 +2760,7 @@ fn hash<H: Hasher>(&self, state: &mut H) {
 
 #[stable(feature = "rust1", since = "1.0.0")]
 #[rustc_const_unstable(feature = "const_default", issue = "143894")]
-const impl<K, V> Default for BTreeMap<K, V> {
+impl<K, V> const Default for BTreeMap<K, V> {
     /// Creates an empty `BTreeMap`.
     fn default() -> BTreeMap<K, V> {
         BTreeMap::new()
@@ -3108,8 +3273,10 @@ pub fn upper_bound_mut<Q: ?Sized>(&mut self, bound: Bound<&Q>) -> CursorMut<'_,
 #[unstable(feature = "btree_cursors", issue = "107540")]
 pub struct Cursor<'a, K: 'a, V: 'a> {
     // If current is None then it means the tree has not been allocated yet.
-    current: Option<Handle<NodeRef<marker::Immut<'a>, K, V, marker::Leaf>, marker::Edge>>,
-    root: Option<&'a node::Root<K, V>>,
+    current: Option<
+        Handle<NodeRef<marker::Immut<'a>, K, V, marker::Leaf, marker::Uncounted>, marker::Edge>,
+    >,
+    root: Option<&'a node::Root<K, V, marker::Uncounted>>,
 }
 
 #[unstable(feature = "btree_cursors", issue = "107540")]
@@ -3185,8 +3352,10 @@ pub struct CursorMutKey<
     #[unstable(feature = "allocator_api", issue = "32838")] A = Global,
 > {
     // If current is None then it means the tree has not been allocated yet.
-    current: Option<Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::Edge>>,
-    root: DormantMutRef<'a, Option<node::Root<K, V>>>,
+    current: Option<
+        Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf, marker::Uncounted>, marker::Edge>,
+    >,
+    root: DormantMutRef<'a, Option<node::Root<K, V, marker::Uncounted>>>,
     length: &'a mut usize,
     alloc: &'a mut A,
 }
@@ -3730,5 +3899,21 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 #[unstable(feature = "btree_cursors", issue = "107540")]
 impl Error for UnorderedKeyError {}
 
+/// Error type returned by [`BTreeMap::from_sorted_iter`] if the keys are
+/// not in ascending order.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[unstable(feature = "btree_from_sorted_iter", issue = "none")]
+pub struct UnsortedKeysError {}
+
+#[unstable(feature = "btree_from_sorted_iter", issue = "none")]
+impl fmt::Display for UnsortedKeysError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "keys are not in ascending order")
+    }
+}
+
+#[unstable(feature = "btree_from_sorted_iter", issue = "none")]
+impl Error for UnsortedKeysError {}
+
 #[cfg(test)]
 mod tests;
@@ -52,7 +52,9 @@ pub struct VacantEntry<
 > {
     pub(super) key: K,
     /// `None` for a (empty) map without root
-    pub(super) handle: Option<Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::Edge>>,
+    pub(super) handle: Option<
+        Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf, marker::Uncounted>, marker::Edge>,
+    >,
     pub(super) dormant_map: DormantMutRef<'a, BTreeMap<K, V, A>>,
 
     /// The BTreeMap will outlive this IntoIter so we don't care about drop order for `alloc`.
@@ -78,7 +80,10 @@ pub struct OccupiedEntry<
     V,
     #[unstable(feature = "allocator_api", issue = "32838")] A: Allocator + Clone = Global,
 > {
-    pub(super) handle: Handle<NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal>, marker::KV>,
+    pub(super) handle: Handle<
+        NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal, marker::Uncounted>,
+        marker::KV,
+    >,
     pub(super) dormant_map: DormantMutRef<'a, BTreeMap<K, V, A>>,
 
     /// The BTreeMap will outlive this IntoIter so we don't care about drop order for `alloc`.
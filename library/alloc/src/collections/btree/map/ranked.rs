@@ -0,0 +1,599 @@
+use core::borrow::Borrow;
+use core::fmt::{self, Debug};
+use core::marker::PhantomData;
+use core::mem::{self, ManuallyDrop};
+use core::ops::RangeBounds;
+
+use super::super::borrow::DormantMutRef;
+use super::super::dedup_sorted_iter::DedupSortedIter;
+use super::super::navigate::{LazyLeafRange, LeafRange};
+use super::super::node::{Handle, NodeRef, Root, marker};
+use super::super::search::SearchBound;
+use super::super::search::SearchResult::*;
+use super::{Iter, Keys, Range, Values};
+use crate::alloc::{Allocator, Global};
+use crate::vec::Vec;
+
+/// An ordered map based on a B-Tree, like [`BTreeMap`], that can also tell
+/// the position of its keys in logarithmic time.
+///
+/// Every internal node of the tree keeps track of the number of elements
+/// below it. That lets [`nth`], [`rank_of`] and [`range_len`] take logarithmic
+/// time, where `BTreeMap` has to iterate. In return, internal nodes take up
+/// slightly more memory, and inserting or removing an element updates the
+/// count of every node on the way to the root. Apart from that, a `RankedMap`
+/// behaves like a `BTreeMap`, but it offers fewer methods.
+///
+/// [`BTreeMap`]: super::BTreeMap
+/// [`nth`]: RankedMap::nth
+/// [`rank_of`]: RankedMap::rank_of
+/// [`range_len`]: RankedMap::range_len
+///
+/// # Examples
+///
+/// ```
+/// #![feature(btree_order_statistics)]
+/// use std::collections::btree_map::RankedMap;
+///
+/// let mut scores = RankedMap::new();
+/// scores.insert(78, "Alice");
+/// scores.insert(95, "Bob");
+/// scores.insert(61, "Carol");
+///
+/// // The median score.
+/// assert_eq!(scores.nth(scores.len() / 2), Some((&78, &"Alice")));
+/// // How many scored below 80.
+/// assert_eq!(scores.rank_of(&80), 2);
+/// ```
+#[unstable(feature = "btree_order_statistics", issue = "none")]
+pub struct RankedMap<
+    K,
+    V,
+    #[unstable(feature = "allocator_api", issue = "32838")] A: Allocator + Clone = Global,
+> {
+    root: Option<Root<K, V, marker::Counted>>,
+    length: usize,
+    /// `ManuallyDrop` to control drop order (needs to be dropped after all the nodes).
+    alloc: ManuallyDrop<A>,
+    // For dropck; the `Box` avoids making the `Unpin` impl more strict than `BTreeMap`'s.
+    _marker: PhantomData<crate::boxed::Box<(K, V), A>>,
+}
+
+#[unstable(feature = "btree_order_statistics", issue = "none")]
+unsafe impl<#[may_dangle] K, #[may_dangle] V, A: Allocator + Clone> Drop for RankedMap<K, V, A> {
+    fn drop(&mut self) {
+        drop(Dropper {
+            front: self.root.take().map(|root| root.into_dying().first_leaf_edge()),
+            length: self.length,
+            alloc: unsafe { ManuallyDrop::take(&mut self.alloc) },
+        });
+    }
+}
+
+/// Drops the key-value pairs of a tree in ascending order, and deallocates its nodes,
+/// like `IntoIter` does for a `BTreeMap`.
+struct Dropper<K, V, A: Allocator + Clone> {
+    front:
+        Option<Handle<NodeRef<marker::Dying, K, V, marker::Leaf, marker::Counted>, marker::Edge>>,
+    length: usize,
+    alloc: A,
+}
+
+impl<K, V, A: Allocator + Clone> Drop for Dropper<K, V, A> {
+    fn drop(&mut self) {
+        struct DropGuard<'a, K, V, A: Allocator + Clone>(&'a mut Dropper<K, V, A>);
+
+        impl<'a, K, V, A: Allocator + Clone> Drop for DropGuard<'a, K, V, A> {
+            fn drop(&mut self) {
+                // Continue the same loop we perform below. This only runs when unwinding, so we
+                // don't have to care about panics this time (they'll abort).
+                while let Some(kv) = self.0.dying_next() {
+                    // SAFETY: we consume the dying handle immediately.
+                    unsafe { kv.drop_key_val() };
+                }
+            }
+        }
+
+        while let Some(kv) = self.dying_next() {
+            let guard = DropGuard(self);
+            // SAFETY: we don't touch the tree before consuming the dying handle.
+            unsafe { kv.drop_key_val() };
+            mem::forget(guard);
+        }
+    }
+}
+
+impl<K, V, A: Allocator + Clone> Dropper<K, V, A> {
+    /// Returns a dying KV handle to the next pair, invalidated by further calls.
+    fn dying_next(
+        &mut self,
+    ) -> Option<
+        Handle<NodeRef<marker::Dying, K, V, marker::LeafOrInternal, marker::Counted>, marker::KV>,
+    > {
+        let front = self.front.as_mut()?;
+        if self.length == 0 {
+            self.front.take().unwrap().deallocating_end(self.alloc.clone());
+            None
+        } else {
+            self.length -= 1;
+            Some(unsafe { front.deallocating_next_unchecked(self.alloc.clone()) })
+        }
+    }
+}
+
+#[unstable(feature = "btree_order_statistics", issue = "none")]
+impl<K: Clone, V: Clone, A: Allocator + Clone> Clone for RankedMap<K, V, A> {
+    fn clone(&self) -> Self {
+        let mut map = RankedMap::new_in((*self.alloc).clone());
+        let root = map.root.insert(Root::new((*self.alloc).clone()));
+        // The clone drops the pairs pushed so far if cloning a key or value panics.
+        let iter = self.iter().map(|(k, v)| (k.clone(), v.clone()));
+        root.bulk_push(iter, &mut map.length, (*self.alloc).clone());
+        map
+    }
+}
+
+impl<K, V> RankedMap<K, V> {
+    /// Makes a new, empty `RankedMap`.
+    ///
+    /// Does not allocate anything on its own.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(btree_order_statistics)]
+    /// use std::collections::btree_map::RankedMap;
+    ///
+    /// let mut map = RankedMap::new();
+    /// map.insert(1, "a");
+    /// ```
+    #[unstable(feature = "btree_order_statistics", issue = "none")]
+    #[must_use]
+    pub const fn new() -> RankedMap<K, V> {
+        RankedMap::new_in(Global)
+    }
+}
+
+impl<K, V, A: Allocator + Clone> RankedMap<K, V, A> {
+    /// Makes a new, empty `RankedMap` with the given allocator.
+    #[unstable(feature = "allocator_api", issue = "32838")]
+    #[must_use]
+    pub const fn new_in(alloc: A) -> RankedMap<K, V, A> {
+        RankedMap { root: None, length: 0, alloc: ManuallyDrop::new(alloc), _marker: PhantomData }
+    }
+
+    /// Returns the number of elements in the map.
+    #[unstable(feature = "btree_order_statistics", issue = "none")]
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.length
+    }
+
+    /// Returns `true` if the map contains no elements.
+    #[unstable(feature = "btree_order_statistics", issue = "none")]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Clears the map, removing all elements.
+    #[unstable(feature = "btree_order_statistics", issue = "none")]
+    pub fn clear(&mut self) {
+        // avoid moving the allocator
+        drop(RankedMap {
+            root: mem::replace(&mut self.root, None),
+            length: mem::replace(&mut self.length, 0),
+            alloc: self.alloc.clone(),
+            _marker: PhantomData,
+        });
+    }
+
+    /// Returns a reference to the value corresponding to the key.
+    ///
+    /// The key may be any borrowed form of the map's key type, but the ordering
+    /// on the borrowed form *must* match the ordering on the key type.
+    #[unstable(feature = "btree_order_statistics", issue = "none")]
+    pub fn get<Q: ?Sized>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q> + Ord,
+        Q: Ord,
+    {
+        self.get_key_value(key).map(|(_, v)| v)
+    }
+
+    /// Returns the key-value pair corresponding to the supplied key.
+    ///
+    /// The supplied key may be any borrowed form of the map's key type, but the ordering
+    /// on the borrowed form *must* match the ordering on the key type.
+    #[unstable(feature = "btree_order_statistics", issue = "none")]
+    pub fn get_key_value<Q: ?Sized>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q> + Ord,
+        Q: Ord,
+    {
+        let root_node = self.root.as_ref()?.reborrow();
+        match root_node.search_tree(key) {
+            Found(handle) => Some(handle.into_kv()),
+            GoDown(_) => None,
+        }
+    }
+
+    /// Returns `true` if the map contains a value for the specified key.
+    ///
+    /// The key may be any borrowed form of the map's key type, but the ordering
+    /// on the borrowed form *must* match the ordering on the key type.
+    #[unstable(feature = "btree_order_statistics", issue = "none")]
+    pub fn contains_key<Q: ?Sized>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q> + Ord,
+        Q: Ord,
+    {
+        self.get(key).is_some()
+    }
+
+    /// Returns the first key-value pair in the map.
+    /// The key in this pair is the minimum key in the map.
+    #[unstable(feature = "btree_order_statistics", issue = "none")]
+    pub fn first_key_value(&self) -> Option<(&K, &V)> {
+        let root_node = self.root.as_ref()?.reborrow();
+        root_node.first_leaf_edge().right_kv().ok().map(Handle::into_kv)
+    }
+
+    /// Returns the last key-value pair in the map.
+    /// The key in this pair is the maximum key in the map.
+    #[unstable(feature = "btree_order_statistics", issue = "none")]
+    pub fn last_key_value(&self) -> Option<(&K, &V)> {
+        let root_node = self.root.as_ref()?.reborrow();
+        root_node.last_leaf_edge().left_kv().ok().map(Handle::into_kv)
+    }
+
+    /// Inserts a key-value pair into the map.
+    ///
+    /// If the map did not have this key present, `None` is returned.
+    ///
+    /// If the map did have this key present, the value is updated, and the old
+    /// value is returned. The key is not updated, though.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(btree_order_statistics)]
+    /// use std::collections::btree_map::RankedMap;
+    ///
+    /// let mut map = RankedMap::new();
+    /// assert_eq!(map.insert(37, "a"), None);
+    /// assert_eq!(map.is_empty(), false);
+    ///
+    /// map.insert(37, "b");
+    /// assert_eq!(map.insert(37, "c"), Some("b"));
+    /// assert_eq!(map.get(&37), Some(&"c"));
+    /// ```
+    #[unstable(feature = "btree_order_statistics", issue = "none")]
+    pub fn insert(&mut self, key: K, value: V) -> Option<V>
+    where
+        K: Ord,
+    {
+        let alloc = (*self.alloc).clone();
+        let (map, mut dormant_map) = DormantMutRef::new(self);
+        let Some(root) = map.root.as_mut() else {
+            let root = map.root.insert(NodeRef::new_leaf(alloc).forget_type());
+            // SAFETY: We *just* created the root as a leaf.
+            unsafe { root.borrow_mut().cast_to_leaf_unchecked() }.push(key, value);
+            map.length = 1;
+            return None;
+        };
+        match root.borrow_mut().search_tree(&key) {
+            Found(handle) => Some(mem::replace(handle.into_val_mut(), value)),
+            GoDown(handle) => {
+                handle.insert_recursing(key, value, alloc.clone(), |ins| {
+                    drop(ins.left);
+                    // SAFETY: Pushing a new root node doesn't invalidate
+                    // handles to existing nodes.
+                    let map = unsafe { dormant_map.reborrow() };
+                    let root = map.root.as_mut().unwrap(); // same as ins.left
+                    root.push_internal_level(alloc).push(ins.kv.0, ins.kv.1, ins.right)
+                });
+                // SAFETY: we are done with the handle into the tree.
+                unsafe { dormant_map.awaken() }.length += 1;
+                None
+            }
+        }
+    }
+
+    /// Removes a key from the map, returning the value at the key if the key
+    /// was previously in the map.
+    ///
+    /// The key may be any borrowed form of the map's key type, but the ordering
+    /// on the borrowed form *must* match the ordering on the key type.
+    #[unstable(feature = "btree_order_statistics", issue = "none")]
+    pub fn remove<Q: ?Sized>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q> + Ord,
+        Q: Ord,
+    {
+        self.remove_entry(key).map(|(_, v)| v)
+    }
+
+    /// Removes a key from the map, returning the stored key and value if the key
+    /// was previously in the map.
+    ///
+    /// The key may be any borrowed form of the map's key type, but the ordering
+    /// on the borrowed form *must* match the ordering on the key type.
+    #[unstable(feature = "btree_order_statistics", issue = "none")]
+    pub fn remove_entry<Q: ?Sized>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q> + Ord,
+        Q: Ord,
+    {
+        let alloc = (*self.alloc).clone();
+        let (map, dormant_map) = DormantMutRef::new(self);
+        let root_node = map.root.as_mut()?.borrow_mut();
+        match root_node.search_tree(key) {
+            Found(handle) => Some(Self::remove_kv(handle, dormant_map, alloc)),
+            GoDown(_) => None,
+        }
+    }
+
+    /// Removes and returns the first element in the map.
+    /// The key of this element is the minimum key that was in the map.
+    #[unstable(feature = "btree_order_statistics", issue = "none")]
+    pub fn pop_first(&mut self) -> Option<(K, V)> {
+        let alloc = (*self.alloc).clone();
+        let (map, dormant_map) = DormantMutRef::new(self);
+        let root_node = map.root.as_mut()?.borrow_mut();
+        let handle = root_node.first_leaf_edge().right_kv().ok()?;
+        Some(Self::remove_kv(handle.forget_node_type(), dormant_map, alloc))
+    }
+
+    /// Removes and returns the last element in the map.
+    /// The key of this element is the maximum key that was in the map.
+    #[unstable(feature = "btree_order_statistics", issue = "none")]
+    pub fn pop_last(&mut self) -> Option<(K, V)> {
+        let alloc = (*self.alloc).clone();
+        let (map, dormant_map) = DormantMutRef::new(self);
+        let root_node = map.root.as_mut()?.borrow_mut();
+        let handle = root_node.last_leaf_edge().left_kv().ok()?;
+        Some(Self::remove_kv(handle.forget_node_type(), dormant_map, alloc))
+    }
+
+    /// Takes the value of the key-value pair behind `handle` out of the map,
+    /// like `OccupiedEntry::remove_kv` does for a `BTreeMap`.
+    fn remove_kv(
+        handle: Handle<
+            NodeRef<marker::Mut<'_>, K, V, marker::LeafOrInternal, marker::Counted>,
+            marker::KV,
+        >,
+        dormant_map: DormantMutRef<'_, Self>,
+        alloc: A,
+    ) -> (K, V) {
+        let mut emptied_internal_root = false;
+        let (old_kv, _) = handle.remove_kv_tracking(|| emptied_internal_root = true, alloc.clone());
+        // SAFETY: we consumed the intermediate root borrow, `handle`.
+        let map = unsafe { dormant_map.awaken() };
+        map.length -= 1;
+        if emptied_internal_root {
+            let root = map.root.as_mut().unwrap();
+            root.pop_internal_level(alloc);
+        }
+        old_kv
+    }
+
+    /// Gets an iterator over the entries of the map, sorted by key.
+    #[unstable(feature = "btree_order_statistics", issue = "none")]
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        if let Some(root) = &self.root {
+            let full_range = root.reborrow().forget_counts().full_range();
+
+            Iter { range: full_range, length: self.length }
+        } else {
+            Iter { range: LazyLeafRange::none(), length: 0 }
+        }
+    }
+
+    /// Gets an iterator over the keys of the map, in sorted order.
+    #[unstable(feature = "btree_order_statistics", issue = "none")]
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        Keys { inner: self.iter() }
+    }
+
+    /// Gets an iterator over the values of the map, in order by key.
+    #[unstable(feature = "btree_order_statistics", issue = "none")]
+    pub fn values(&self) -> Values<'_, K, V> {
+        Values { inner: self.iter() }
+    }
+
+    /// Constructs a double-ended iterator over a sub-range of elements in the map,
+    /// like [`BTreeMap::range`] does.
+    ///
+    /// [`BTreeMap::range`]: super::BTreeMap::range
+    ///
+    /// # Panics
+    ///
+    /// Panics if range `start > end`.
+    /// Panics if range `start == end` and both bounds are `Excluded`.
+    #[unstable(feature = "btree_order_statistics", issue = "none")]
+    pub fn range<T: ?Sized, R>(&self, range: R) -> Range<'_, K, V>
+    where
+        T: Ord,
+        K: Borrow<T> + Ord,
+        R: RangeBounds<T>,
+    {
+        if let Some(root) = &self.root {
+            Range { inner: root.reborrow().forget_counts().range_search(range) }
+        } else {
+            Range { inner: LeafRange::none() }
+        }
+    }
+
+    /// Returns the key-value pair at position `n` in the map, counting from zero
+    /// in ascending key order, or `None` if the map has `n` or fewer elements.
+    ///
+    /// Unlike `map.iter().nth(n)`, this takes logarithmic rather than linear time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(btree_order_statistics)]
+    /// use std::collections::btree_map::RankedMap;
+    ///
+    /// let map = RankedMap::from([(10, "a"), (20, "b"), (30, "c")]);
+    /// assert_eq!(map.nth(0), Some((&10, &"a")));
+    /// assert_eq!(map.nth(2), Some((&30, &"c")));
+    /// assert_eq!(map.nth(3), None);
+    /// ```
+    #[unstable(feature = "btree_order_statistics", issue = "none")]
+    pub fn nth(&self, n: usize) -> Option<(&K, &V)> {
+        let root_node = self.root.as_ref()?.reborrow();
+        root_node.find_kv_at(n).map(Handle::into_kv)
+    }
+
+    /// Returns the number of keys in the map that are less than `key`, whether
+    /// or not `key` itself is in the map.
+    ///
+    /// If `key` is in the map, this is its position in ascending key order, so
+    /// that `map.nth(map.rank_of(key))` returns its entry.
+    ///
+    /// The key may be any borrowed form of the map's key type, but the ordering
+    /// on the borrowed form *must* match the ordering on the key type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(btree_order_statistics)]
+    /// use std::collections::btree_map::RankedMap;
+    ///
+    /// let map = RankedMap::from([(10, "a"), (20, "b"), (30, "c")]);
+    /// assert_eq!(map.rank_of(&20), 1);
+    /// assert_eq!(map.rank_of(&25), 2);
+    /// assert_eq!(map.rank_of(&5), 0);
+    /// assert_eq!(map.rank_of(&99), 3);
+    /// ```
+    #[unstable(feature = "btree_order_statistics", issue = "none")]
+    pub fn rank_of<Q: ?Sized>(&self, key: &Q) -> usize
+    where
+        K: Borrow<Q> + Ord,
+        Q: Ord,
+    {
+        match &self.root {
+            Some(root) => root.reborrow().len_before_lower_bound(SearchBound::Included(key)),
+            None => 0,
+        }
+    }
+
+    /// Returns the number of elements in a sub-range of the map, accepting the
+    /// same ranges as [`range`]. Unlike `map.range(range).count()`, this takes
+    /// logarithmic rather than linear time.
+    ///
+    /// [`range`]: RankedMap::range
+    ///
+    /// # Panics
+    ///
+    /// Panics if range `start > end`.
+    /// Panics if range `start == end` and both bounds are `Excluded`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(btree_order_statistics)]
+    /// use std::collections::btree_map::RankedMap;
+    ///
+    /// let map: RankedMap<i32, i32> = (0..100).map(|i| (i * 2, i)).collect();
+    /// assert_eq!(map.range_len(10..20), 5);
+    /// assert_eq!(map.range_len(..=10), 6);
+    /// assert_eq!(map.range_len(150..), 25);
+    /// assert_eq!(map.range_len(..), 100);
+    /// ```
+    #[unstable(feature = "btree_order_statistics", issue = "none")]
+    pub fn range_len<T: ?Sized, R>(&self, range: R) -> usize
+    where
+        T: Ord,
+        K: Borrow<T> + Ord,
+        R: RangeBounds<T>,
+    {
+        match &self.root {
+            Some(root) => root.reborrow().range_len(range),
+            None => 0,
+        }
+    }
+}
+
+#[unstable(feature = "btree_order_statistics", issue = "none")]
+impl<'a, K, V, A: Allocator + Clone> IntoIterator for &'a RankedMap<K, V, A> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Iter<'a, K, V> {
+        self.iter()
+    }
+}
+
+#[unstable(feature = "btree_order_statistics", issue = "none")]
+impl<K: Ord, V> FromIterator<(K, V)> for RankedMap<K, V> {
+    /// Constructs a `RankedMap<K, V>` from an iterator of key-value pairs.
+    ///
+    /// If the iterator produces any pairs with equal keys,
+    /// all but one of the corresponding values will be dropped.
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> RankedMap<K, V> {
+        let mut inputs: Vec<_> = iter.into_iter().collect();
+
+        if inputs.is_empty() {
+            return RankedMap::new();
+        }
+
+        // use stable sort to preserve the insertion order.
+        inputs.sort_by(|a, b| a.0.cmp(&b.0));
+        let mut map = RankedMap::new();
+        let root = map.root.insert(Root::new(Global));
+        root.bulk_push(DedupSortedIter::new(inputs.into_iter()), &mut map.length, Global);
+        map
+    }
+}
+
+#[unstable(feature = "btree_order_statistics", issue = "none")]
+impl<K: Ord, V, const N: usize> From<[(K, V); N]> for RankedMap<K, V> {
+    /// Converts a `[(K, V); N]` into a `RankedMap<K, V>`.
+    ///
+    /// If any entries in the array have equal keys,
+    /// all but one of the corresponding values will be dropped.
+    fn from(arr: [(K, V); N]) -> Self {
+        RankedMap::from_iter(arr)
+    }
+}
+
+#[unstable(feature = "btree_order_statistics", issue = "none")]
+impl<K: Ord, V, A: Allocator + Clone> Extend<(K, V)> for RankedMap<K, V, A> {
+    #[inline]
+    fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
+        iter.into_iter().for_each(move |(k, v)| {
+            self.insert(k, v);
+        });
+    }
+}
+
+#[unstable(feature = "btree_order_statistics", issue = "none")]
+impl<K, V> Default for RankedMap<K, V> {
+    /// Creates an empty `RankedMap`.
+    fn default() -> RankedMap<K, V> {
+        RankedMap::new()
+    }
+}
+
+#[unstable(feature = "btree_order_statistics", issue = "none")]
+impl<K: PartialEq, V: PartialEq, A: Allocator + Clone> PartialEq for RankedMap<K, V, A> {
+    fn eq(&self, other: &RankedMap<K, V, A>) -> bool {
+        self.len() == other.len() && self.iter().zip(other).all(|(a, b)| a == b)
+    }
+}
+
+#[unstable(feature = "btree_order_statistics", issue = "none")]
+impl<K: Eq, V: Eq, A: Allocator + Clone> Eq for RankedMap<K, V, A> {}
+
+#[unstable(feature = "btree_order_statistics", issue = "none")]
+impl<K: Debug, V: Debug, A: Allocator + Clone> Debug for RankedMap<K, V, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,161 @@
+use std::ops::Bound::{Excluded, Included, Unbounded};
+use std::panic::catch_unwind;
+
+use super::super::super::node::CAPACITY;
+use super::*;
+use crate::testing::crash_test::{CrashTestDummy, Panic};
+use crate::testing::rng::DeterministicRng;
+
+// Minimum number of elements to insert, to guarantee a tree with 2 levels.
+const MIN_INSERTS_HEIGHT_1: usize = CAPACITY + 1;
+
+impl<K, V> RankedMap<K, V> {
+    // Panics if the map is corrupted or if the subtree lengths are off.
+    fn check(&self) {
+        if let Some(root) = &self.root {
+            let root_node = root.reborrow();
+            root_node.assert_back_pointers();
+            assert_eq!(self.length, root_node.calc_length());
+            assert_eq!(self.length, root_node.assert_subtree_lens());
+        } else {
+            assert_eq!(self.length, 0);
+        }
+    }
+}
+
+// Checks the order statistics of a map against what iteration finds.
+fn check_order_statistics(map: &RankedMap<i32, i32>) {
+    map.check();
+    let keys: Vec<i32> = map.keys().copied().collect();
+    for (i, k) in keys.iter().enumerate() {
+        assert_eq!(map.nth(i), map.get_key_value(k));
+        assert_eq!(map.rank_of(k), i);
+        assert_eq!(map.rank_of(&(k + 1)), keys.partition_point(|&x| x < k + 1));
+    }
+    assert_eq!(map.nth(keys.len()), None);
+    assert_eq!(map.rank_of(&i32::MAX), keys.partition_point(|&x| x < i32::MAX));
+
+    let mut probes: Vec<i32> =
+        keys.iter().step_by(keys.len() / 8 + 1).flat_map(|&k| [k - 1, k, k + 1]).collect();
+    probes.extend([i32::MIN, i32::MAX]);
+    probes.sort();
+    probes.dedup();
+    assert_eq!(map.range_len::<i32, _>(..), keys.len());
+    for &a in &probes {
+        assert_eq!(map.range_len(..a), map.range(..a).count());
+        assert_eq!(map.range_len(..=a), map.range(..=a).count());
+        assert_eq!(map.range_len(a..), map.range(a..).count());
+        assert_eq!(
+            map.range_len((Excluded(a), Unbounded)),
+            map.range((Excluded(a), Unbounded)).count()
+        );
+        for &b in probes.iter().filter(|&&b| b >= a) {
+            assert_eq!(map.range_len(a..b), map.range(a..b).count());
+            assert_eq!(map.range_len(a..=b), map.range(a..=b).count());
+            assert_eq!(
+                map.range_len((Excluded(a), Included(b))),
+                map.range((Excluded(a), Included(b))).count()
+            );
+            if a != b {
+                assert_eq!(
+                    map.range_len((Excluded(a), Excluded(b))),
+                    map.range((Excluded(a), Excluded(b))).count()
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_order_statistics_small() {
+    let mut map = RankedMap::new();
+    check_order_statistics(&map);
+    map.insert(5, 50);
+    check_order_statistics(&map);
+    for i in 0..MIN_INSERTS_HEIGHT_1 as i32 {
+        map.insert(i * 10, i);
+        check_order_statistics(&map);
+    }
+}
+
+#[test]
+fn test_order_statistics_insert_remove() {
+    let mut rng = DeterministicRng::new();
+    let mut map = RankedMap::new();
+    for i in 0..600 {
+        let key = (rng.next() % 2000) as i32;
+        map.insert(key, key);
+        if i % 100 == 0 {
+            check_order_statistics(&map);
+        }
+    }
+    check_order_statistics(&map);
+    assert!(map.root.as_ref().unwrap().height() >= 2);
+
+    for i in 0..1000 {
+        map.remove(&((rng.next() % 2000) as i32));
+        if i % 100 == 0 {
+            check_order_statistics(&map);
+        }
+    }
+    check_order_statistics(&map);
+}
+
+#[test]
+fn test_order_statistics_bulk() {
+    let mut map = RankedMap::from_iter((0..300).map(|i| (i * 3, i)));
+    check_order_statistics(&map);
+
+    map.extend((0..200).map(|i| (i * 5 + 1, i)));
+    check_order_statistics(&map);
+
+    let cloned = map.clone();
+    check_order_statistics(&cloned);
+    assert_eq!(cloned, map);
+
+    for _ in 0..100 {
+        map.pop_first();
+        map.pop_last();
+    }
+    check_order_statistics(&map);
+
+    map.clear();
+    check_order_statistics(&map);
+}
+
+#[should_panic(expected = "range start is greater than range end in BTreeMap")]
+#[test]
+fn test_range_len_panic() {
+    let map = RankedMap::from_iter((0..10).map(|i| (i, i)));
+    let _ = map.range_len(8..3);
+}
+
+#[test]
+fn test_insert_remove() {
+    let mut map = RankedMap::new();
+    assert_eq!(map.insert(1, "a"), None);
+    assert_eq!(map.insert(1, "b"), Some("a"));
+    assert_eq!(map.get(&1), Some(&"b"));
+    assert_eq!(map.remove(&1), Some("b"));
+    assert_eq!(map.remove(&1), None);
+    assert!(map.is_empty());
+    map.check();
+}
+
+#[test]
+#[cfg_attr(not(panic = "unwind"), ignore = "test requires unwinding support")]
+fn test_drop_panic_leak() {
+    let a = CrashTestDummy::new(0);
+    let b = CrashTestDummy::new(1);
+    let c = CrashTestDummy::new(2);
+    let mut map = RankedMap::new();
+    map.insert(a.spawn(Panic::Never), ());
+    map.insert(b.spawn(Panic::InDrop), ());
+    map.insert(c.spawn(Panic::Never), ());
+
+    catch_unwind(move || drop(map)).unwrap_err();
+
+    assert_eq!(a.dropped(), 1);
+    assert_eq!(b.dropped(), 1);
+    assert_eq!(c.dropped(), 1);
+}
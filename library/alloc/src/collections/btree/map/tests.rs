@@ -118,7 +118,7 @@ fn compact(&mut self)
     }
 }
 
-impl<'a, K: 'a, V: 'a> NodeRef<marker::Immut<'a>, K, V, marker::LeafOrInternal> {
+impl<'a, K: 'a, V: 'a, C> NodeRef<marker::Immut<'a>, K, V, marker::LeafOrInternal, C> {
     fn assert_min_len(self, min_len: usize) {
         assert!(self.len() >= min_len, "node len {} < {}", self.len(), min_len);
         if let node::ForceResult::Internal(node) = self.force() {
@@ -2327,6 +2327,59 @@ fn test_merge_ord_chaos() {
     map1.check();
 }
 
+#[test]
+fn test_merge_vs_insert() {
+    let lens = [(0, 5), (5, 0), (1, 1), (100, 100), (1000, 50), (50, 1000), (1000, 3)];
+    for (self_len, other_len) in lens {
+        let mut map = BTreeMap::from_iter((0..self_len).map(|i| (i * 3, i)));
+        let other = BTreeMap::from_iter((0..other_len).map(|i| (i * 2, i)));
+        let mut expected = map.clone();
+        for (&k, &v) in &other {
+            expected.entry(k).and_modify(|e| *e = *e * 10000 + v).or_insert(v);
+        }
+        map.merge(other, |_, self_val, other_val| self_val * 10000 + other_val);
+        map.check();
+        assert_eq!(map, expected);
+    }
+}
+
+#[test]
+fn test_from_sorted_iter() {
+    for len in [0, 1, node::CAPACITY, MIN_INSERTS_HEIGHT_1, MIN_INSERTS_HEIGHT_2, 1000] {
+        let map = BTreeMap::from_sorted_iter((0..len).map(|i| (i, i))).unwrap();
+        map.check();
+        assert!(map.into_iter().eq((0..len).map(|i| (i, i))));
+    }
+}
+
+#[test]
+fn test_from_sorted_iter_duplicates() {
+    let pairs = (0..300).flat_map(|i| [(i, 0), (i, 1), (i, 2)]);
+    let map = BTreeMap::from_sorted_iter(pairs).unwrap();
+    map.check();
+    assert!(map.into_iter().eq((0..300).map(|i| (i, 2))));
+}
+
+#[test]
+fn test_from_sorted_iter_unordered() {
+    let pairs = (0..200).chain([100]).chain(300..400).map(|i| (i, ()));
+    assert_eq!(BTreeMap::from_sorted_iter(pairs), Err(UnsortedKeysError {}));
+
+    let a = CrashTestDummy::new(0);
+    let b = CrashTestDummy::new(1);
+    let c = CrashTestDummy::new(2);
+    let d = CrashTestDummy::new(3);
+    let pairs = [a.spawn(Panic::Never), c.spawn(Panic::Never), b.spawn(Panic::Never)]
+        .into_iter()
+        .chain([d.spawn(Panic::Never)])
+        .map(|k| (k, ()));
+    assert!(BTreeMap::from_sorted_iter(pairs).is_err());
+    assert_eq!(a.dropped(), 1);
+    assert_eq!(b.dropped(), 1);
+    assert_eq!(c.dropped(), 1);
+    assert_eq!(d.dropped(), 1);
+}
+
 fn rand_data(len: usize) -> Vec<(u32, u32)> {
     let mut rng = DeterministicRng::new();
     Vec::from_iter((0..len).map(|_| (rng.next(), rng.next())))
@@ -2428,6 +2481,70 @@ fn test_split_off_large_random_sorted() {
     assert!(right.into_iter().eq(data.into_iter().filter(|x| x.0 >= key)));
 }
 
+fn check_split_off_range<R: RangeBounds<u32> + Clone>(map: &BTreeMap<u32, u32>, range: R) {
+    let mut left = map.clone();
+    let mid = left.split_off_range(range.clone());
+    left.check();
+    mid.check();
+    assert!(mid.iter().eq(map.iter().filter(|(k, _)| range.contains(k))));
+    assert!(left.iter().eq(map.iter().filter(|(k, _)| !range.contains(k))));
+}
+
+#[test]
+fn test_split_off_range() {
+    let mut rng = DeterministicRng::new();
+    for len in [0, 1, node::CAPACITY, MIN_INSERTS_HEIGHT_1, MIN_INSERTS_HEIGHT_2, 300] {
+        // Insertion in random order creates some variation in node length.
+        let mut map = BTreeMap::new();
+        while map.len() < len {
+            map.insert(rng.next() % 4096, 0);
+        }
+        let mut probes = Vec::from_iter(map.keys().step_by(len / 5 + 1).flat_map(|&k| [k, k + 1]));
+        probes.extend([0, 5000]);
+        probes.sort();
+        for (i, &start) in probes.iter().enumerate() {
+            for &end in &probes[i..] {
+                check_split_off_range(&map, start..end);
+                check_split_off_range(&map, start..=end);
+                check_split_off_range(&map, (Excluded(start), Included(end)));
+                if start != end {
+                    check_split_off_range(&map, (Excluded(start), Excluded(end)));
+                }
+            }
+            check_split_off_range(&map, start..);
+            check_split_off_range(&map, (Excluded(start), Unbounded));
+            check_split_off_range(&map, ..start);
+            check_split_off_range(&map, ..=start);
+        }
+        check_split_off_range(&map, ..);
+    }
+}
+
+// Splitting off a range near either end of a big tree leaves parts of very
+// different heights to be joined together again.
+#[test]
+fn test_split_off_range_uneven_heights() {
+    let len = 5000;
+    let map = BTreeMap::from_iter((0..len as u32).map(|i| (i, i)));
+    assert_eq!(map.height(), Some(3));
+    for start in (0..80).chain(len as u32 - 80..len as u32).step_by(3) {
+        for width in [1, 2, 30, 300] {
+            check_split_off_range(&map, start..start + width);
+        }
+    }
+    for width in [0, 1, 2, 30, 300, 3000] {
+        check_split_off_range(&map, 100..100 + width);
+        check_split_off_range(&map, len as u32 - 100 - width..len as u32 - 100);
+    }
+}
+
+#[should_panic(expected = "range start is greater than range end in BTreeMap")]
+#[test]
+fn test_split_off_range_panic() {
+    let mut map = BTreeMap::from_iter((0..10).map(|i| (i, i)));
+    let _ = map.split_off_range(8..3);
+}
+
 #[test]
 #[cfg_attr(not(panic = "unwind"), ignore = "test requires unwinding support")]
 fn test_into_iter_drop_leak_height_0() {
@@ -8,8 +8,8 @@
 use crate::alloc::Allocator;
 // `front` and `back` are always both `None` or both `Some`.
 pub(super) struct LeafRange<BorrowType, K, V> {
-    front: Option<Handle<NodeRef<BorrowType, K, V, marker::Leaf>, marker::Edge>>,
-    back: Option<Handle<NodeRef<BorrowType, K, V, marker::Leaf>, marker::Edge>>,
+    front: Option<Handle<NodeRef<BorrowType, K, V, marker::Leaf, marker::Uncounted>, marker::Edge>>,
+    back: Option<Handle<NodeRef<BorrowType, K, V, marker::Leaf, marker::Uncounted>, marker::Edge>>,
 }
 
 impl<'a, K: 'a, V: 'a> Clone for LeafRange<marker::Immut<'a>, K, V> {
@@ -70,7 +70,12 @@ impl<BorrowType: marker::BorrowType, K, V> LeafRange<BorrowType, K, V> {
     /// If possible, extract some result from the following KV and move to the edge beyond it.
     fn perform_next_checked<F, R>(&mut self, f: F) -> Option<R>
     where
-        F: Fn(&Handle<NodeRef<BorrowType, K, V, marker::LeafOrInternal>, marker::KV>) -> R,
+        F: Fn(
+            &Handle<
+                NodeRef<BorrowType, K, V, marker::LeafOrInternal, marker::Uncounted>,
+                marker::KV,
+            >,
+        ) -> R,
     {
         if self.is_empty() {
             None
@@ -86,7 +91,12 @@ fn perform_next_checked<F, R>(&mut self, f: F) -> Option<R>
     /// If possible, extract some result from the preceding KV and move to the edge beyond it.
     fn perform_next_back_checked<F, R>(&mut self, f: F) -> Option<R>
     where
-        F: Fn(&Handle<NodeRef<BorrowType, K, V, marker::LeafOrInternal>, marker::KV>) -> R,
+        F: Fn(
+            &Handle<
+                NodeRef<BorrowType, K, V, marker::LeafOrInternal, marker::Uncounted>,
+                marker::KV,
+            >,
+        ) -> R,
     {
         if self.is_empty() {
             None
@@ -100,9 +110,12 @@ fn perform_next_back_checked<F, R>(&mut self, f: F) -> Option<R>
     }
 }
 
+type LeafEdge<BorrowType, K, V> =
+    Handle<NodeRef<BorrowType, K, V, marker::Leaf, marker::Uncounted>, marker::Edge>;
+
 enum LazyLeafHandle<BorrowType, K, V> {
-    Root(NodeRef<BorrowType, K, V, marker::LeafOrInternal>), // not yet descended
-    Edge(Handle<NodeRef<BorrowType, K, V, marker::Leaf>, marker::Edge>),
+    Root(NodeRef<BorrowType, K, V, marker::LeafOrInternal, marker::Uncounted>), // not yet descended
+    Edge(LeafEdge<BorrowType, K, V>),
 }
 
 impl<'a, K: 'a, V: 'a> Clone for LazyLeafHandle<marker::Immut<'a>, K, V> {
@@ -182,7 +195,8 @@ pub(super) unsafe fn next_back_unchecked(&mut self) -> (&'a K, &'a mut V) {
 impl<K, V> LazyLeafRange<marker::Dying, K, V> {
     fn take_front(
         &mut self,
-    ) -> Option<Handle<NodeRef<marker::Dying, K, V, marker::Leaf>, marker::Edge>> {
+    ) -> Option<Handle<NodeRef<marker::Dying, K, V, marker::Leaf, marker::Uncounted>, marker::Edge>>
+    {
         match self.front.take()? {
             LazyLeafHandle::Root(root) => Some(root.first_leaf_edge()),
             LazyLeafHandle::Edge(edge) => Some(edge),
@@ -193,7 +207,8 @@ fn take_front(
     pub(super) unsafe fn deallocating_next_unchecked<A: Allocator + Clone>(
         &mut self,
         alloc: A,
-    ) -> Handle<NodeRef<marker::Dying, K, V, marker::LeafOrInternal>, marker::KV> {
+    ) -> Handle<NodeRef<marker::Dying, K, V, marker::LeafOrInternal, marker::Uncounted>, marker::KV>
+    {
         debug_assert!(self.front.is_some());
         let front = self.init_front().unwrap();
         unsafe { front.deallocating_next_unchecked(alloc) }
@@ -203,7 +218,8 @@ pub(super) unsafe fn deallocating_next_unchecked<A: Allocator + Clone>(
     pub(super) unsafe fn deallocating_next_back_unchecked<A: Allocator + Clone>(
         &mut self,
         alloc: A,
-    ) -> Handle<NodeRef<marker::Dying, K, V, marker::LeafOrInternal>, marker::KV> {
+    ) -> Handle<NodeRef<marker::Dying, K, V, marker::LeafOrInternal, marker::Uncounted>, marker::KV>
+    {
         debug_assert!(self.back.is_some());
         let back = self.init_back().unwrap();
         unsafe { back.deallocating_next_back_unchecked(alloc) }
@@ -218,9 +234,7 @@ pub(super) fn deallocating_end<A: Allocator + Clone>(&mut self, alloc: A) {
 }
 
 impl<BorrowType: marker::BorrowType, K, V> LazyLeafRange<BorrowType, K, V> {
-    fn init_front(
-        &mut self,
-    ) -> Option<&mut Handle<NodeRef<BorrowType, K, V, marker::Leaf>, marker::Edge>> {
+    fn init_front(&mut self) -> Option<&mut LeafEdge<BorrowType, K, V>> {
         if let Some(LazyLeafHandle::Root(root)) = &self.front {
             self.front = Some(LazyLeafHandle::Edge(unsafe { ptr::read(root) }.first_leaf_edge()));
         }
@@ -232,9 +246,7 @@ fn init_front(
         }
     }
 
-    fn init_back(
-        &mut self,
-    ) -> Option<&mut Handle<NodeRef<BorrowType, K, V, marker::Leaf>, marker::Edge>> {
+    fn init_back(&mut self) -> Option<&mut LeafEdge<BorrowType, K, V>> {
         if let Some(LazyLeafHandle::Root(root)) = &self.back {
             self.back = Some(LazyLeafHandle::Edge(unsafe { ptr::read(root) }.last_leaf_edge()));
         }
@@ -247,7 +259,9 @@ fn init_back(
     }
 }
 
-impl<BorrowType: marker::BorrowType, K, V> NodeRef<BorrowType, K, V, marker::LeafOrInternal> {
+impl<BorrowType: marker::BorrowType, K, V>
+    NodeRef<BorrowType, K, V, marker::LeafOrInternal, marker::Uncounted>
+{
     /// Finds the distinct leaf edges delimiting a specified range in a tree.
     ///
     /// If such distinct edges exist, returns them in ascending order, meaning
@@ -299,8 +313,8 @@ unsafe fn find_leaf_edges_spanning_range<Q: ?Sized, R>(
 }
 
 fn full_range<BorrowType: marker::BorrowType, K, V>(
-    root1: NodeRef<BorrowType, K, V, marker::LeafOrInternal>,
-    root2: NodeRef<BorrowType, K, V, marker::LeafOrInternal>,
+    root1: NodeRef<BorrowType, K, V, marker::LeafOrInternal, marker::Uncounted>,
+    root2: NodeRef<BorrowType, K, V, marker::LeafOrInternal, marker::Uncounted>,
 ) -> LazyLeafRange<BorrowType, K, V> {
     LazyLeafRange {
         front: Some(LazyLeafHandle::Root(root1)),
@@ -308,7 +322,7 @@ fn full_range<BorrowType: marker::BorrowType, K, V>(
     }
 }
 
-impl<'a, K: 'a, V: 'a> NodeRef<marker::Immut<'a>, K, V, marker::LeafOrInternal> {
+impl<'a, K: 'a, V: 'a> NodeRef<marker::Immut<'a>, K, V, marker::LeafOrInternal, marker::Uncounted> {
     /// Finds the pair of leaf edges delimiting a specific range in a tree.
     ///
     /// The result is meaningful only if the tree is ordered by key, like the tree
@@ -329,7 +343,9 @@ pub(super) fn full_range(self) -> LazyLeafRange<marker::Immut<'a>, K, V> {
     }
 }
 
-impl<'a, K: 'a, V: 'a> NodeRef<marker::ValMut<'a>, K, V, marker::LeafOrInternal> {
+impl<'a, K: 'a, V: 'a>
+    NodeRef<marker::ValMut<'a>, K, V, marker::LeafOrInternal, marker::Uncounted>
+{
     /// Splits a unique reference into a pair of leaf edges delimiting a specified range.
     /// The result are non-unique references allowing (some) mutation, which must be used
     /// carefully.
@@ -359,7 +375,7 @@ pub(super) fn full_range(self) -> LazyLeafRange<marker::ValMut<'a>, K, V> {
     }
 }
 
-impl<K, V> NodeRef<marker::Dying, K, V, marker::LeafOrInternal> {
+impl<K, V> NodeRef<marker::Dying, K, V, marker::LeafOrInternal, marker::Uncounted> {
     /// Splits a unique reference into a pair of leaf edges delimiting the full range of the tree.
     /// The results are non-unique references allowing massively destructive mutation, so must be
     /// used with the utmost care.
@@ -371,8 +387,8 @@ pub(super) fn full_range(self) -> LazyLeafRange<marker::Dying, K, V> {
     }
 }
 
-impl<BorrowType: marker::BorrowType, K, V>
-    Handle<NodeRef<BorrowType, K, V, marker::Leaf>, marker::Edge>
+impl<BorrowType: marker::BorrowType, K, V, C>
+    Handle<NodeRef<BorrowType, K, V, marker::Leaf, C>, marker::Edge>
 {
     /// Given a leaf edge handle, returns [`Result::Ok`] with a handle to the neighboring KV
     /// on the right side, which is either in the same leaf node or in an ancestor node.
@@ -380,8 +396,8 @@ impl<BorrowType: marker::BorrowType, K, V>
     pub(super) fn next_kv(
         self,
     ) -> Result<
-        Handle<NodeRef<BorrowType, K, V, marker::LeafOrInternal>, marker::KV>,
-        NodeRef<BorrowType, K, V, marker::LeafOrInternal>,
+        Handle<NodeRef<BorrowType, K, V, marker::LeafOrInternal, C>, marker::KV>,
+        NodeRef<BorrowType, K, V, marker::LeafOrInternal, C>,
     > {
         let mut edge = self.forget_node_type();
         loop {
@@ -401,8 +417,8 @@ pub(super) fn next_kv(
     pub(super) fn next_back_kv(
         self,
     ) -> Result<
-        Handle<NodeRef<BorrowType, K, V, marker::LeafOrInternal>, marker::KV>,
-        NodeRef<BorrowType, K, V, marker::LeafOrInternal>,
+        Handle<NodeRef<BorrowType, K, V, marker::LeafOrInternal, C>, marker::KV>,
+        NodeRef<BorrowType, K, V, marker::LeafOrInternal, C>,
     > {
         let mut edge = self.forget_node_type();
         loop {
@@ -417,8 +433,8 @@ pub(super) fn next_back_kv(
     }
 }
 
-impl<BorrowType: marker::BorrowType, K, V>
-    Handle<NodeRef<BorrowType, K, V, marker::Internal>, marker::Edge>
+impl<BorrowType: marker::BorrowType, K, V, C>
+    Handle<NodeRef<BorrowType, K, V, marker::Internal, C>, marker::Edge>
 {
     /// Given an internal edge handle, returns [`Result::Ok`] with a handle to the neighboring KV
     /// on the right side, which is either in the same internal node or in an ancestor node.
@@ -426,8 +442,8 @@ impl<BorrowType: marker::BorrowType, K, V>
     fn next_kv(
         self,
     ) -> Result<
-        Handle<NodeRef<BorrowType, K, V, marker::Internal>, marker::KV>,
-        NodeRef<BorrowType, K, V, marker::Internal>,
+        Handle<NodeRef<BorrowType, K, V, marker::Internal, C>, marker::KV>,
+        NodeRef<BorrowType, K, V, marker::Internal, C>,
     > {
         let mut edge = self;
         loop {
@@ -442,7 +458,9 @@ fn next_kv(
     }
 }
 
-impl<K, V> Handle<NodeRef<marker::Dying, K, V, marker::Leaf>, marker::Edge> {
+impl<K, V, C: marker::Counting>
+    Handle<NodeRef<marker::Dying, K, V, marker::Leaf, C>, marker::Edge>
+{
     /// Given a leaf edge handle into a dying tree, returns the next leaf edge
     /// on the right side, and the key-value pair in between, if they exist.
     ///
@@ -459,7 +477,7 @@ impl<K, V> Handle<NodeRef<marker::Dying, K, V, marker::Leaf>, marker::Edge> {
     unsafe fn deallocating_next<A: Allocator + Clone>(
         self,
         alloc: A,
-    ) -> Option<(Self, Handle<NodeRef<marker::Dying, K, V, marker::LeafOrInternal>, marker::KV>)>
+    ) -> Option<(Self, Handle<NodeRef<marker::Dying, K, V, marker::LeafOrInternal, C>, marker::KV>)>
     {
         let mut edge = self.forget_node_type();
         loop {
@@ -491,7 +509,7 @@ unsafe fn deallocating_next<A: Allocator + Clone>(
     unsafe fn deallocating_next_back<A: Allocator + Clone>(
         self,
         alloc: A,
-    ) -> Option<(Self, Handle<NodeRef<marker::Dying, K, V, marker::LeafOrInternal>, marker::KV>)>
+    ) -> Option<(Self, Handle<NodeRef<marker::Dying, K, V, marker::LeafOrInternal, C>, marker::KV>)>
     {
         let mut edge = self.forget_node_type();
         loop {
@@ -513,7 +531,7 @@ unsafe fn deallocating_next_back<A: Allocator + Clone>(
     /// both sides of the tree, and have hit the same edge. As it is intended
     /// only to be called when all keys and values have been returned,
     /// no cleanup is done on any of the keys or values.
-    fn deallocating_end<A: Allocator + Clone>(self, alloc: A) {
+    pub(super) fn deallocating_end<A: Allocator + Clone>(self, alloc: A) {
         let mut edge = self.forget_node_type();
         while let Some(parent_edge) =
             unsafe { edge.into_node().deallocate_and_ascend(alloc.clone()) }
@@ -523,7 +541,7 @@ fn deallocating_end<A: Allocator + Clone>(self, alloc: A) {
     }
 }
 
-impl<'a, K, V> Handle<NodeRef<marker::Immut<'a>, K, V, marker::Leaf>, marker::Edge> {
+impl<'a, K, V, C> Handle<NodeRef<marker::Immut<'a>, K, V, marker::Leaf, C>, marker::Edge> {
     /// Moves the leaf edge handle to the next leaf edge and returns references to the
     /// key and value in between.
     ///
@@ -549,7 +567,7 @@ unsafe fn next_back_unchecked(&mut self) -> (&'a K, &'a V) {
     }
 }
 
-impl<'a, K, V> Handle<NodeRef<marker::ValMut<'a>, K, V, marker::Leaf>, marker::Edge> {
+impl<'a, K, V, C> Handle<NodeRef<marker::ValMut<'a>, K, V, marker::Leaf, C>, marker::Edge> {
     /// Moves the leaf edge handle to the next leaf edge and returns references to the
     /// key and value in between.
     ///
@@ -579,7 +597,9 @@ unsafe fn next_back_unchecked(&mut self) -> (&'a K, &'a mut V) {
     }
 }
 
-impl<K, V> Handle<NodeRef<marker::Dying, K, V, marker::Leaf>, marker::Edge> {
+impl<K, V, C: marker::Counting>
+    Handle<NodeRef<marker::Dying, K, V, marker::Leaf, C>, marker::Edge>
+{
     /// Moves the leaf edge handle to the next leaf edge and returns the key and value
     /// in between, deallocating any node left behind while leaving the corresponding
     /// edge in its parent node dangling.
@@ -592,10 +612,10 @@ impl<K, V> Handle<NodeRef<marker::Dying, K, V, marker::Leaf>, marker::Edge> {
     ///
     /// The only safe way to proceed with the updated handle is to compare it, drop it,
     /// or call this method or counterpart `deallocating_next_back_unchecked` again.
-    unsafe fn deallocating_next_unchecked<A: Allocator + Clone>(
+    pub(super) unsafe fn deallocating_next_unchecked<A: Allocator + Clone>(
         &mut self,
         alloc: A,
-    ) -> Handle<NodeRef<marker::Dying, K, V, marker::LeafOrInternal>, marker::KV> {
+    ) -> Handle<NodeRef<marker::Dying, K, V, marker::LeafOrInternal, C>, marker::KV> {
         super::mem::replace(self, |leaf_edge| unsafe {
             leaf_edge.deallocating_next(alloc).unwrap()
         })
@@ -616,20 +636,20 @@ unsafe fn deallocating_next_unchecked<A: Allocator + Clone>(
     unsafe fn deallocating_next_back_unchecked<A: Allocator + Clone>(
         &mut self,
         alloc: A,
-    ) -> Handle<NodeRef<marker::Dying, K, V, marker::LeafOrInternal>, marker::KV> {
+    ) -> Handle<NodeRef<marker::Dying, K, V, marker::LeafOrInternal, C>, marker::KV> {
         super::mem::replace(self, |leaf_edge| unsafe {
             leaf_edge.deallocating_next_back(alloc).unwrap()
         })
     }
 }
 
-impl<BorrowType: marker::BorrowType, K, V> NodeRef<BorrowType, K, V, marker::LeafOrInternal> {
+impl<BorrowType: marker::BorrowType, K, V, C> NodeRef<BorrowType, K, V, marker::LeafOrInternal, C> {
     /// Returns the leftmost leaf edge in or underneath a node - in other words, the edge
     /// you need first when navigating forward (or last when navigating backward).
     #[inline]
     pub(super) fn first_leaf_edge(
         self,
-    ) -> Handle<NodeRef<BorrowType, K, V, marker::Leaf>, marker::Edge> {
+    ) -> Handle<NodeRef<BorrowType, K, V, marker::Leaf, C>, marker::Edge> {
         let mut node = self;
         loop {
             match node.force() {
@@ -644,7 +664,7 @@ pub(super) fn first_leaf_edge(
     #[inline]
     pub(super) fn last_leaf_edge(
         self,
-    ) -> Handle<NodeRef<BorrowType, K, V, marker::Leaf>, marker::Edge> {
+    ) -> Handle<NodeRef<BorrowType, K, V, marker::Leaf, C>, marker::Edge> {
         let mut node = self;
         loop {
             match node.force() {
@@ -653,21 +673,45 @@ pub(super) fn last_leaf_edge(
             }
         }
     }
+
+    /// Returns the leftmost node at the given height in or underneath a node.
+    pub(super) fn first_node_at_height(self, height: usize) -> Self {
+        let mut node = self;
+        while node.height() > height {
+            match node.force() {
+                Leaf(_) => unreachable!(),
+                Internal(internal) => node = internal.first_edge().descend(),
+            }
+        }
+        node
+    }
+
+    /// Returns the rightmost node at the given height in or underneath a node.
+    pub(super) fn last_node_at_height(self, height: usize) -> Self {
+        let mut node = self;
+        while node.height() > height {
+            match node.force() {
+                Leaf(_) => unreachable!(),
+                Internal(internal) => node = internal.last_edge().descend(),
+            }
+        }
+        node
+    }
 }
 
-pub(super) enum Position<BorrowType, K, V> {
-    Leaf(NodeRef<BorrowType, K, V, marker::Leaf>),
-    Internal(NodeRef<BorrowType, K, V, marker::Internal>),
+pub(super) enum Position<BorrowType, K, V, C> {
+    Leaf(NodeRef<BorrowType, K, V, marker::Leaf, C>),
+    Internal(NodeRef<BorrowType, K, V, marker::Internal, C>),
     InternalKV,
 }
 
-impl<'a, K: 'a, V: 'a> NodeRef<marker::Immut<'a>, K, V, marker::LeafOrInternal> {
+impl<'a, K: 'a, V: 'a, C> NodeRef<marker::Immut<'a>, K, V, marker::LeafOrInternal, C> {
     /// Visits leaf nodes and internal KVs in order of ascending keys, and also
     /// visits internal nodes as a whole in a depth first order, meaning that
     /// internal nodes precede their individual KVs and their child nodes.
     pub(super) fn visit_nodes_in_order<F>(self, mut visit: F)
     where
-        F: FnMut(Position<marker::Immut<'a>, K, V>),
+        F: FnMut(Position<marker::Immut<'a>, K, V, C>),
     {
         match self.force() {
             Leaf(leaf) => visit(Position::Leaf(leaf)),
@@ -708,13 +752,13 @@ pub(super) fn calc_length(self) -> usize {
     }
 }
 
-impl<BorrowType: marker::BorrowType, K, V>
-    Handle<NodeRef<BorrowType, K, V, marker::LeafOrInternal>, marker::KV>
+impl<BorrowType: marker::BorrowType, K, V, C>
+    Handle<NodeRef<BorrowType, K, V, marker::LeafOrInternal, C>, marker::KV>
 {
     /// Returns the leaf edge closest to a KV for forward navigation.
     pub(super) fn next_leaf_edge(
         self,
-    ) -> Handle<NodeRef<BorrowType, K, V, marker::Leaf>, marker::Edge> {
+    ) -> Handle<NodeRef<BorrowType, K, V, marker::Leaf, C>, marker::Edge> {
         match self.force() {
             Leaf(leaf_kv) => leaf_kv.right_edge(),
             Internal(internal_kv) => {
@@ -727,7 +771,7 @@ pub(super) fn next_leaf_edge(
     /// Returns the leaf edge closest to a KV for backward navigation.
     pub(super) fn next_back_leaf_edge(
         self,
-    ) -> Handle<NodeRef<BorrowType, K, V, marker::Leaf>, marker::Edge> {
+    ) -> Handle<NodeRef<BorrowType, K, V, marker::Leaf, C>, marker::Edge> {
         match self.force() {
             Leaf(leaf_kv) => leaf_kv.left_edge(),
             Internal(internal_kv) => {
@@ -738,13 +782,13 @@ pub(super) fn next_back_leaf_edge(
     }
 }
 
-impl<BorrowType: marker::BorrowType, K, V> NodeRef<BorrowType, K, V, marker::LeafOrInternal> {
+impl<BorrowType: marker::BorrowType, K, V, C> NodeRef<BorrowType, K, V, marker::LeafOrInternal, C> {
     /// Returns the leaf edge corresponding to the first point at which the
     /// given bound is true.
     pub(super) fn lower_bound<Q: ?Sized>(
         self,
         mut bound: SearchBound<&Q>,
-    ) -> Handle<NodeRef<BorrowType, K, V, marker::Leaf>, marker::Edge>
+    ) -> Handle<NodeRef<BorrowType, K, V, marker::Leaf, C>, marker::Edge>
     where
         Q: Ord,
         K: Borrow<Q>,
@@ -767,7 +811,7 @@ pub(super) fn lower_bound<Q: ?Sized>(
     pub(super) fn upper_bound<Q: ?Sized>(
         self,
         mut bound: SearchBound<&Q>,
-    ) -> Handle<NodeRef<BorrowType, K, V, marker::Leaf>, marker::Edge>
+    ) -> Handle<NodeRef<BorrowType, K, V, marker::Leaf, C>, marker::Edge>
     where
         Q: Ord,
         K: Borrow<Q>,
@@ -30,6 +30,8 @@
 //   For a leaf node, "having an edge" only means we can identify a position in the node,
 //   since leaf edges are empty and need no data representation. In an internal node,
 //   an edge both identifies a position and contains a pointer to a child node.
+// - In a counted tree, an internal node knows the number of key-value pairs in the subtree
+//   it roots. Every operation available on counted trees keeps these counts up to date.
 
 use core::marker::PhantomData;
 use core::mem::{self, MaybeUninit};
@@ -126,6 +128,48 @@ unsafe fn new<A: Allocator + Clone>(alloc: A) -> Box<Self, A> {
             node.assume_init()
         }
     }
+
+    /// Allocates a new internal node for a tree of counting type `C`, and returns a
+    /// pointer to its `InternalNode` portion. The node counts no key-value pairs yet.
+    ///
+    /// # Safety
+    /// Same as `InternalNode::new`.
+    unsafe fn new_counting<C: marker::Counting, A: Allocator + Clone>(alloc: A) -> NonNull<Self> {
+        // The allocator must be dropped, not leaked.  See also `BTreeMap::alloc`.
+        if C::COUNTED {
+            let mut node = Box::<CountedInternalNode<K, V>, _>::new_uninit_in(alloc);
+            unsafe {
+                // SAFETY: argument points to the `node.internal.data` `LeafNode`
+                LeafNode::init(&raw mut (*node.as_mut_ptr()).internal.data);
+                (&raw mut (*node.as_mut_ptr()).subtree_len).write(0);
+                // SAFETY: `node.internal.data` and `node.subtree_len` were just initialized
+                // and `node.internal.edges` is MaybeUninit.
+                let (node, _alloc) = Box::into_non_null_with_allocator(node.assume_init());
+                node.cast()
+            }
+        } else {
+            let (node, _alloc) = Box::into_non_null_with_allocator(unsafe { Self::new(alloc) });
+            node
+        }
+    }
+
+    /// The layout of the allocation of internal nodes in a tree of counting type `C`.
+    fn layout<C: marker::Counting>() -> Layout {
+        if C::COUNTED { Layout::new::<CountedInternalNode<K, V>>() } else { Layout::new::<Self>() }
+    }
+}
+
+/// The underlying representation of internal nodes in a counted tree. Any pointer to a
+/// `CountedInternalNode` can be cast to a pointer to the `InternalNode` portion, and from
+/// there to the `LeafNode` portion, so the code only needs to know about this type when
+/// allocating, deallocating, or dealing with `subtree_len`.
+#[repr(C)]
+struct CountedInternalNode<K, V> {
+    internal: InternalNode<K, V>,
+
+    /// The number of key-value pairs stored in this node and all of its descendants.
+    /// For a leaf node, this is simply `len`, so leaves don't store it.
+    subtree_len: usize,
 }
 
 /// A managed, non-null pointer to a node. This is either an owned pointer to
@@ -165,6 +209,9 @@ unsafe fn new<A: Allocator + Clone>(alloc: A) -> Box<Self, A> {
 ///   `NodeRef` points to an internal node, and when this is `LeafOrInternal` the
 ///   `NodeRef` could be pointing to either type of node.
 ///   `Type` is named `NodeType` when used outside `NodeRef`.
+/// - `C`: This can be `Uncounted` or `Counted`. When this is `Counted`, internal
+///   nodes are `CountedInternalNode`s, which keep track of the number of key-value
+///   pairs in their subtree. Like `BorrowType`, this applies to the entire tree.
 ///
 /// Both `BorrowType` and `NodeType` restrict what methods we implement, to
 /// exploit static type safety. There are limitations in the way we can apply
@@ -187,7 +234,7 @@ unsafe fn new<A: Allocator + Clone>(alloc: A) -> Box<Self, A> {
 ///   as the returned reference is used.
 ///   The methods supporting insert bend this rule by returning a raw pointer,
 ///   i.e., a reference without any lifetime.
-pub(super) struct NodeRef<BorrowType, K, V, Type> {
+pub(super) struct NodeRef<BorrowType, K, V, Type, C> {
     /// The number of levels that the node and the level of leaves are apart, a
     /// constant of the node that cannot be entirely described by `Type`, and that
     /// the node itself does not store. We only need to store the height of the root
@@ -197,30 +244,30 @@ pub(super) struct NodeRef<BorrowType, K, V, Type> {
     /// The pointer to the leaf or internal node. The definition of `InternalNode`
     /// ensures that the pointer is valid either way.
     node: NonNull<LeafNode<K, V>>,
-    _marker: PhantomData<(BorrowType, Type)>,
+    _marker: PhantomData<(BorrowType, Type, C)>,
 }
 
 /// The root node of an owned tree.
 ///
 /// Note that this does not have a destructor, and must be cleaned up manually.
-pub(super) type Root<K, V> = NodeRef<marker::Owned, K, V, marker::LeafOrInternal>;
+pub(super) type Root<K, V, C> = NodeRef<marker::Owned, K, V, marker::LeafOrInternal, C>;
 
-impl<'a, K: 'a, V: 'a, Type> Copy for NodeRef<marker::Immut<'a>, K, V, Type> {}
-impl<'a, K: 'a, V: 'a, Type> Clone for NodeRef<marker::Immut<'a>, K, V, Type> {
+impl<'a, K: 'a, V: 'a, Type, C> Copy for NodeRef<marker::Immut<'a>, K, V, Type, C> {}
+impl<'a, K: 'a, V: 'a, Type, C> Clone for NodeRef<marker::Immut<'a>, K, V, Type, C> {
     fn clone(&self) -> Self {
         *self
     }
 }
 
-unsafe impl<BorrowType, K: Sync, V: Sync, Type> Sync for NodeRef<BorrowType, K, V, Type> {}
+unsafe impl<BorrowType, K: Sync, V: Sync, Type, C> Sync for NodeRef<BorrowType, K, V, Type, C> {}
 
-unsafe impl<K: Sync, V: Sync, Type> Send for NodeRef<marker::Immut<'_>, K, V, Type> {}
-unsafe impl<K: Send, V: Send, Type> Send for NodeRef<marker::Mut<'_>, K, V, Type> {}
-unsafe impl<K: Send, V: Send, Type> Send for NodeRef<marker::ValMut<'_>, K, V, Type> {}
-unsafe impl<K: Send, V: Send, Type> Send for NodeRef<marker::Owned, K, V, Type> {}
-unsafe impl<K: Send, V: Send, Type> Send for NodeRef<marker::Dying, K, V, Type> {}
+unsafe impl<K: Sync, V: Sync, Type, C> Send for NodeRef<marker::Immut<'_>, K, V, Type, C> {}
+unsafe impl<K: Send, V: Send, Type, C> Send for NodeRef<marker::Mut<'_>, K, V, Type, C> {}
+unsafe impl<K: Send, V: Send, Type, C> Send for NodeRef<marker::ValMut<'_>, K, V, Type, C> {}
+unsafe impl<K: Send, V: Send, Type, C> Send for NodeRef<marker::Owned, K, V, Type, C> {}
+unsafe impl<K: Send, V: Send, Type, C> Send for NodeRef<marker::Dying, K, V, Type, C> {}
 
-impl<K, V> NodeRef<marker::Owned, K, V, marker::Leaf> {
+impl<K, V, C> NodeRef<marker::Owned, K, V, marker::Leaf, C> {
     pub(super) fn new_leaf<A: Allocator + Clone>(alloc: A) -> Self {
         Self::from_new_leaf(LeafNode::new(alloc))
     }
@@ -232,28 +279,34 @@ fn from_new_leaf<A: Allocator + Clone>(leaf: Box<LeafNode<K, V>, A>) -> Self {
     }
 }
 
-impl<K, V> NodeRef<marker::Owned, K, V, marker::Internal> {
+impl<K, V, C: marker::Counting> NodeRef<marker::Owned, K, V, marker::Internal, C> {
     /// Creates a new internal (height > 0) `NodeRef`
-    fn new_internal<A: Allocator + Clone>(child: Root<K, V>, alloc: A) -> Self {
-        let mut new_node = unsafe { InternalNode::new(alloc) };
-        new_node.edges[0].write(child.node);
-        NodeRef::from_new_internal(new_node, NonZero::new(child.height + 1).unwrap())
+    fn new_internal<A: Allocator + Clone>(child: Root<K, V, C>, alloc: A) -> Self {
+        let mut new_node = unsafe { InternalNode::new_counting::<C, A>(alloc) };
+        unsafe { new_node.as_mut().edges[0].write(child.node) };
+        let mut this = unsafe {
+            NodeRef::from_new_internal(new_node, NonZero::new(child.height + 1).unwrap())
+        };
+        this.borrow_mut().recalc_subtree_len();
+        this
     }
 
     /// Creates a new internal (height > 0) `NodeRef` from an existing internal node
-    fn from_new_internal<A: Allocator + Clone>(
-        internal: Box<InternalNode<K, V>, A>,
+    ///
+    /// # Safety
+    /// `internal` was allocated by `InternalNode::new_counting::<C, _>`.
+    unsafe fn from_new_internal(
+        internal: NonNull<InternalNode<K, V>>,
         height: NonZero<usize>,
     ) -> Self {
-        // The allocator must be dropped, not leaked.  See also `BTreeMap::alloc`.
-        let (node, _alloc) = Box::into_non_null_with_allocator(internal);
-        let mut this = NodeRef { height: height.into(), node: node.cast(), _marker: PhantomData };
+        let mut this =
+            NodeRef { height: height.into(), node: internal.cast(), _marker: PhantomData };
         this.borrow_mut().correct_all_childrens_parent_links();
         this
     }
 }
 
-impl<BorrowType, K, V> NodeRef<BorrowType, K, V, marker::Internal> {
+impl<BorrowType, K, V, C> NodeRef<BorrowType, K, V, marker::Internal, C> {
     /// Unpack a node reference that was packed as `NodeRef::parent`.
     fn from_internal(node: NonNull<InternalNode<K, V>>, height: usize) -> Self {
         debug_assert!(height > 0);
@@ -261,7 +314,7 @@ fn from_internal(node: NonNull<InternalNode<K, V>>, height: usize) -> Self {
     }
 }
 
-impl<BorrowType, K, V> NodeRef<BorrowType, K, V, marker::Internal> {
+impl<BorrowType, K, V, C> NodeRef<BorrowType, K, V, marker::Internal, C> {
     /// Exposes the data of an internal node.
     ///
     /// Returns a raw ptr to avoid invalidating other references to this node.
@@ -271,7 +324,7 @@ fn as_internal_ptr(this: &Self) -> *mut InternalNode<K, V> {
     }
 }
 
-impl<'a, K, V> NodeRef<marker::Mut<'a>, K, V, marker::Internal> {
+impl<'a, K, V, C> NodeRef<marker::Mut<'a>, K, V, marker::Internal, C> {
     /// Borrows exclusive access to the data of an internal node.
     fn as_internal_mut(&mut self) -> &mut InternalNode<K, V> {
         let ptr = Self::as_internal_ptr(self);
@@ -279,7 +332,7 @@ fn as_internal_mut(&mut self) -> &mut InternalNode<K, V> {
     }
 }
 
-impl<BorrowType, K, V, Type> NodeRef<BorrowType, K, V, Type> {
+impl<BorrowType, K, V, Type, C> NodeRef<BorrowType, K, V, Type, C> {
     /// Finds the length of the node. This is the number of keys or values.
     /// The number of edges is `len() + 1`.
     /// Note that, despite being safe, calling this function can have the side effect
@@ -300,7 +353,7 @@ pub(super) fn height(&self) -> usize {
     }
 
     /// Temporarily takes out another, immutable reference to the same node.
-    pub(super) fn reborrow(&self) -> NodeRef<marker::Immut<'_>, K, V, Type> {
+    pub(super) fn reborrow(&self) -> NodeRef<marker::Immut<'_>, K, V, Type, C> {
         NodeRef { height: self.height, node: self.node, _marker: PhantomData }
     }
 
@@ -315,7 +368,40 @@ fn as_leaf_ptr(this: &Self) -> *mut LeafNode<K, V> {
     }
 }
 
-impl<BorrowType: marker::BorrowType, K, V, Type> NodeRef<BorrowType, K, V, Type> {
+impl<BorrowType, K, V, Type, C: marker::Counting> NodeRef<BorrowType, K, V, Type, C> {
+    /// Temporarily takes out another, immutable reference to the same node,
+    /// as a node of a counted tree, if the tree is one.
+    fn counted(&self) -> Option<NodeRef<marker::Immut<'_>, K, V, Type, marker::Counted>> {
+        if C::COUNTED {
+            Some(NodeRef { height: self.height, node: self.node, _marker: PhantomData })
+        } else {
+            None
+        }
+    }
+}
+
+impl<BorrowType, K, V, Type> NodeRef<BorrowType, K, V, Type, marker::Counted> {
+    /// Returns the number of key-value pairs in the subtree rooted at this node,
+    /// i.e. in this node and all of its descendants.
+    pub(super) fn subtree_len(&self) -> usize {
+        if self.height == 0 {
+            self.len()
+        } else {
+            // Like `len`, we only access the `subtree_len` field here.
+            unsafe { (*(self.node.as_ptr() as *const CountedInternalNode<K, V>)).subtree_len }
+        }
+    }
+}
+
+impl<'a, K, V, Type> NodeRef<marker::Immut<'a>, K, V, Type, marker::Counted> {
+    /// Views a counted tree as an uncounted one, which is fine for anything
+    /// that doesn't change the tree.
+    pub(super) fn forget_counts(self) -> NodeRef<marker::Immut<'a>, K, V, Type, marker::Uncounted> {
+        NodeRef { height: self.height, node: self.node, _marker: PhantomData }
+    }
+}
+
+impl<BorrowType: marker::BorrowType, K, V, Type, C> NodeRef<BorrowType, K, V, Type, C> {
     /// Finds the parent of the current node. Returns `Ok(handle)` if the current
     /// node actually has a parent, where `handle` points to the edge of the parent
     /// that points to the current node. Returns `Err(self)` if the current node has
@@ -327,7 +413,7 @@ impl<BorrowType: marker::BorrowType, K, V, Type> NodeRef<BorrowType, K, V, Type>
     /// both, upon success, do nothing.
     pub(super) fn ascend(
         self,
-    ) -> Result<Handle<NodeRef<BorrowType, K, V, marker::Internal>, marker::Edge>, Self> {
+    ) -> Result<Handle<NodeRef<BorrowType, K, V, marker::Internal, C>, marker::Edge>, Self> {
         const {
             assert!(BorrowType::TRAVERSAL_PERMIT);
         }
@@ -369,7 +455,7 @@ pub(super) fn last_kv(self) -> Handle<Self, marker::KV> {
     }
 }
 
-impl<BorrowType, K, V, Type> NodeRef<BorrowType, K, V, Type> {
+impl<BorrowType, K, V, Type, C> NodeRef<BorrowType, K, V, Type, C> {
     /// Could be a public implementation of PartialEq, but only used in this module.
     fn eq(&self, other: &Self) -> bool {
         let Self { node, height, _marker } = self;
@@ -382,7 +468,7 @@ fn eq(&self, other: &Self) -> bool {
     }
 }
 
-impl<'a, K: 'a, V: 'a, Type> NodeRef<marker::Immut<'a>, K, V, Type> {
+impl<'a, K: 'a, V: 'a, Type, C> NodeRef<marker::Immut<'a>, K, V, Type, C> {
     /// Exposes the leaf portion of any leaf or internal node in an immutable tree.
     fn into_leaf(self) -> &'a LeafNode<K, V> {
         let ptr = Self::as_leaf_ptr(&self);
@@ -397,14 +483,14 @@ pub(super) fn keys(&self) -> &[K] {
     }
 }
 
-impl<K, V> NodeRef<marker::Dying, K, V, marker::LeafOrInternal> {
+impl<K, V, C: marker::Counting> NodeRef<marker::Dying, K, V, marker::LeafOrInternal, C> {
     /// Similar to `ascend`, gets a reference to a node's parent node, but also
     /// deallocates the current node in the process. This is unsafe because the
     /// current node will still be accessible despite being deallocated.
     pub(super) unsafe fn deallocate_and_ascend<A: Allocator + Clone>(
         self,
         alloc: A,
-    ) -> Option<Handle<NodeRef<marker::Dying, K, V, marker::Internal>, marker::Edge>> {
+    ) -> Option<Handle<NodeRef<marker::Dying, K, V, marker::Internal, C>, marker::Edge>> {
         let height = self.height;
         let node = self.node;
         let ret = self.ascend().ok();
@@ -412,7 +498,7 @@ pub(super) unsafe fn deallocate_and_ascend<A: Allocator + Clone>(
             alloc.deallocate(
                 node.cast(),
                 if height > 0 {
-                    Layout::new::<InternalNode<K, V>>()
+                    InternalNode::<K, V>::layout::<C>()
                 } else {
                     Layout::new::<LeafNode<K, V>>()
                 },
@@ -422,7 +508,7 @@ pub(super) unsafe fn deallocate_and_ascend<A: Allocator + Clone>(
     }
 }
 
-impl<'a, K, V, Type> NodeRef<marker::Mut<'a>, K, V, Type> {
+impl<'a, K, V, Type, C> NodeRef<marker::Mut<'a>, K, V, Type, C> {
     /// Temporarily takes out another mutable reference to the same node. Beware, as
     /// this method is very dangerous, doubly so since it might not immediately appear
     /// dangerous.
@@ -433,7 +519,7 @@ impl<'a, K, V, Type> NodeRef<marker::Mut<'a>, K, V, Type> {
     // FIXME(@gereeter) consider adding yet another type parameter to `NodeRef`
     // that restricts the use of navigation methods on reborrowed pointers,
     // preventing this unsafety.
-    unsafe fn reborrow_mut(&mut self) -> NodeRef<marker::Mut<'_>, K, V, Type> {
+    unsafe fn reborrow_mut(&mut self) -> NodeRef<marker::Mut<'_>, K, V, Type, C> {
         NodeRef { height: self.height, node: self.node, _marker: PhantomData }
     }
 
@@ -453,24 +539,36 @@ fn into_leaf_mut(mut self) -> &'a mut LeafNode<K, V> {
 
     /// Returns a dormant copy of this node with its lifetime erased which can
     /// be reawakened later.
-    pub(super) fn dormant(&self) -> NodeRef<marker::DormantMut, K, V, Type> {
+    pub(super) fn dormant(&self) -> NodeRef<marker::DormantMut, K, V, Type, C> {
         NodeRef { height: self.height, node: self.node, _marker: PhantomData }
     }
 }
 
-impl<K, V, Type> NodeRef<marker::DormantMut, K, V, Type> {
+impl<'a, K, V, Type, C: marker::Counting> NodeRef<marker::Mut<'a>, K, V, Type, C> {
+    /// Temporarily takes out another mutable reference to the same node,
+    /// as a node of a counted tree, if the tree is one.
+    fn counted_mut(&mut self) -> Option<NodeRef<marker::Mut<'_>, K, V, Type, marker::Counted>> {
+        if C::COUNTED {
+            Some(NodeRef { height: self.height, node: self.node, _marker: PhantomData })
+        } else {
+            None
+        }
+    }
+}
+
+impl<K, V, Type, C> NodeRef<marker::DormantMut, K, V, Type, C> {
     /// Revert to the unique borrow initially captured.
     ///
     /// # Safety
     ///
     /// The reborrow must have ended, i.e., the reference returned by `new` and
     /// all pointers and references derived from it, must not be used anymore.
-    pub(super) unsafe fn awaken<'a>(self) -> NodeRef<marker::Mut<'a>, K, V, Type> {
+    pub(super) unsafe fn awaken<'a>(self) -> NodeRef<marker::Mut<'a>, K, V, Type, C> {
         NodeRef { height: self.height, node: self.node, _marker: PhantomData }
     }
 }
 
-impl<K, V, Type> NodeRef<marker::Dying, K, V, Type> {
+impl<K, V, Type, C> NodeRef<marker::Dying, K, V, Type, C> {
     /// Borrows exclusive access to the leaf portion of a dying leaf or internal node.
     fn as_leaf_dying(&mut self) -> &mut LeafNode<K, V> {
         let ptr = Self::as_leaf_ptr(self);
@@ -479,7 +577,7 @@ fn as_leaf_dying(&mut self) -> &mut LeafNode<K, V> {
     }
 }
 
-impl<'a, K: 'a, V: 'a, Type> NodeRef<marker::Mut<'a>, K, V, Type> {
+impl<'a, K: 'a, V: 'a, Type, C> NodeRef<marker::Mut<'a>, K, V, Type, C> {
     /// Borrows exclusive access to an element of the key storage area.
     ///
     /// # Safety
@@ -509,7 +607,7 @@ unsafe fn val_area_mut<I, Output: ?Sized>(&mut self, index: I) -> &mut Output
     }
 }
 
-impl<'a, K: 'a, V: 'a> NodeRef<marker::Mut<'a>, K, V, marker::Internal> {
+impl<'a, K: 'a, V: 'a, C> NodeRef<marker::Mut<'a>, K, V, marker::Internal, C> {
     /// Borrows exclusive access to an element or slice of the node's storage area for edge contents.
     ///
     /// # Safety
@@ -525,7 +623,7 @@ unsafe fn edge_area_mut<I, Output: ?Sized>(&mut self, index: I) -> &mut Output
     }
 }
 
-impl<'a, K, V, Type> NodeRef<marker::ValMut<'a>, K, V, Type> {
+impl<'a, K, V, Type, C> NodeRef<marker::ValMut<'a>, K, V, Type, C> {
     /// # Safety
     /// - The node has more than `idx` initialized elements.
     unsafe fn into_key_val_mut_at(mut self, idx: usize) -> (&'a K, &'a mut V) {
@@ -544,14 +642,14 @@ unsafe fn into_key_val_mut_at(mut self, idx: usize) -> (&'a K, &'a mut V) {
     }
 }
 
-impl<'a, K: 'a, V: 'a, Type> NodeRef<marker::Mut<'a>, K, V, Type> {
+impl<'a, K: 'a, V: 'a, Type, C> NodeRef<marker::Mut<'a>, K, V, Type, C> {
     /// Borrows exclusive access to the length of the node.
     pub(super) fn len_mut(&mut self) -> &mut u16 {
         &mut self.as_leaf_mut().len
     }
 }
 
-impl<'a, K, V> NodeRef<marker::Mut<'a>, K, V, marker::Internal> {
+impl<'a, K, V, C> NodeRef<marker::Mut<'a>, K, V, marker::Internal, C> {
     /// # Safety
     /// Every item returned by `range` is a valid edge index for the node.
     unsafe fn correct_childrens_parent_links<R: Iterator<Item = usize>>(&mut self, range: R) {
@@ -567,7 +665,86 @@ fn correct_all_childrens_parent_links(&mut self) {
     }
 }
 
-impl<'a, K: 'a, V: 'a> NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal> {
+impl<'a, K: 'a, V: 'a> NodeRef<marker::Immut<'a>, K, V, marker::Internal, marker::Counted> {
+    /// Returns the total subtree length of the children behind the edges in `range`.
+    ///
+    /// # Safety
+    /// Every item returned by `range` is a valid edge index for the node.
+    unsafe fn childrens_subtree_len<R: Iterator<Item = usize>>(self, range: R) -> usize {
+        let mut total = 0;
+        for i in range {
+            debug_assert!(i <= self.len());
+            total += unsafe { Handle::new_edge(self, i) }.descend().subtree_len();
+        }
+        total
+    }
+}
+
+impl<'a, K, V> NodeRef<marker::Mut<'a>, K, V, marker::Internal, marker::Counted> {
+    /// Borrows exclusive access to the subtree length of an internal node.
+    fn subtree_len_mut(&mut self) -> &mut usize {
+        let ptr = Self::as_internal_ptr(self) as *mut CountedInternalNode<K, V>;
+        // SAFETY: the static node type is `Internal` and the tree is counted.
+        unsafe { &mut (*ptr).subtree_len }
+    }
+}
+
+impl<'a, K, V, C: marker::Counting> NodeRef<marker::Mut<'a>, K, V, marker::Internal, C> {
+    /// In a counted tree, sets the node's subtree length from its own length and
+    /// its children's subtree lengths, assuming the latter are up to date.
+    /// Does nothing in an uncounted tree.
+    pub(super) fn recalc_subtree_len(&mut self) {
+        if let Some(mut node) = self.counted_mut() {
+            let len = node.len();
+            let children_len = unsafe { node.reborrow().childrens_subtree_len(0..=len) };
+            *node.subtree_len_mut() = len + children_len;
+        }
+    }
+}
+
+impl<'a, K: 'a, V: 'a, Type, C: marker::Counting> NodeRef<marker::Mut<'a>, K, V, Type, C> {
+    /// In a counted tree, applies `f` to the subtree length of every ancestor of this node.
+    fn update_ancestors_subtree_len(&mut self, f: impl Fn(&mut usize)) {
+        let Some(node) = self.counted_mut() else { return };
+        let mut node: NodeRef<marker::Mut<'_>, K, V, marker::LeafOrInternal, marker::Counted> =
+            NodeRef { height: node.height, node: node.node, _marker: PhantomData };
+        while let Ok(parent) = node.ascend() {
+            let mut parent = parent.into_node();
+            f(parent.subtree_len_mut());
+            node = parent.forget_type();
+        }
+    }
+
+    /// In a counted tree, accounts for a key-value pair that is about to be inserted
+    /// into this node, or into one of its descendants, in the subtree length of every
+    /// ancestor. Does nothing in an uncounted tree.
+    pub(super) fn increment_ancestors_subtree_len(&mut self) {
+        self.update_ancestors_subtree_len(|len| *len += 1);
+    }
+
+    /// In a counted tree, accounts for a key-value pair that was removed from this
+    /// node, or from one of its descendants, in the subtree length of every ancestor.
+    /// Does nothing in an uncounted tree.
+    pub(super) fn decrement_ancestors_subtree_len(&mut self) {
+        self.update_ancestors_subtree_len(|len| *len -= 1);
+    }
+
+    /// In a counted tree, recalculates the subtree length of every ancestor of this
+    /// node, from the bottom up. Useful after pairs and edges have been moved around
+    /// along the path from the root down to this node. Does nothing in an uncounted tree.
+    pub(super) fn recalc_ancestors_subtree_len(&mut self) {
+        let Some(node) = self.counted_mut() else { return };
+        let mut node: NodeRef<marker::Mut<'_>, K, V, marker::LeafOrInternal, marker::Counted> =
+            NodeRef { height: node.height, node: node.node, _marker: PhantomData };
+        while let Ok(parent) = node.ascend() {
+            let mut parent = parent.into_node();
+            parent.recalc_subtree_len();
+            node = parent.forget_type();
+        }
+    }
+}
+
+impl<'a, K: 'a, V: 'a, C> NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal, C> {
     /// Sets the node's link to its parent edge,
     /// without invalidating other references to the node.
     fn set_parent_link(&mut self, parent: NonNull<InternalNode<K, V>>, parent_idx: usize) {
@@ -577,7 +754,7 @@ fn set_parent_link(&mut self, parent: NonNull<InternalNode<K, V>>, parent_idx: u
     }
 }
 
-impl<K, V> NodeRef<marker::Owned, K, V, marker::LeafOrInternal> {
+impl<K, V, C> NodeRef<marker::Owned, K, V, marker::LeafOrInternal, C> {
     /// Clears the root's link to its parent edge.
     fn clear_parent_link(&mut self) {
         let mut root_node = self.borrow_mut();
@@ -586,7 +763,7 @@ fn clear_parent_link(&mut self) {
     }
 }
 
-impl<K, V> NodeRef<marker::Owned, K, V, marker::LeafOrInternal> {
+impl<K, V, C: marker::Counting> NodeRef<marker::Owned, K, V, marker::LeafOrInternal, C> {
     /// Returns a new owned tree, with its own root node that is initially empty.
     pub(super) fn new<A: Allocator + Clone>(alloc: A) -> Self {
         NodeRef::new_leaf(alloc).forget_type()
@@ -598,7 +775,7 @@ pub(super) fn new<A: Allocator + Clone>(alloc: A) -> Self {
     pub(super) fn push_internal_level<A: Allocator + Clone>(
         &mut self,
         alloc: A,
-    ) -> NodeRef<marker::Mut<'_>, K, V, marker::Internal> {
+    ) -> NodeRef<marker::Mut<'_>, K, V, marker::Internal, C> {
         super::mem::take_mut(self, |old_root| NodeRef::new_internal(old_root, alloc).forget_type());
 
         // `self.borrow_mut()`, except that we just forgot we're internal now:
@@ -628,34 +805,35 @@ pub(super) fn pop_internal_level<A: Allocator + Clone>(&mut self, alloc: A) {
         self.clear_parent_link();
 
         unsafe {
-            alloc.deallocate(top.cast(), Layout::new::<InternalNode<K, V>>());
+            alloc.deallocate(top.cast(), InternalNode::<K, V>::layout::<C>());
         }
     }
 }
 
-impl<K, V, Type> NodeRef<marker::Owned, K, V, Type> {
+impl<K, V, Type, C> NodeRef<marker::Owned, K, V, Type, C> {
     /// Mutably borrows the owned root node. Unlike `reborrow_mut`, this is safe
     /// because the return value cannot be used to destroy the root, and there
     /// cannot be other references to the tree.
-    pub(super) fn borrow_mut(&mut self) -> NodeRef<marker::Mut<'_>, K, V, Type> {
+    pub(super) fn borrow_mut(&mut self) -> NodeRef<marker::Mut<'_>, K, V, Type, C> {
         NodeRef { height: self.height, node: self.node, _marker: PhantomData }
     }
 
     /// Slightly mutably borrows the owned root node.
-    pub(super) fn borrow_valmut(&mut self) -> NodeRef<marker::ValMut<'_>, K, V, Type> {
+    pub(super) fn borrow_valmut(&mut self) -> NodeRef<marker::ValMut<'_>, K, V, Type, C> {
         NodeRef { height: self.height, node: self.node, _marker: PhantomData }
     }
 
     /// Irreversibly transitions to a reference that permits traversal and offers
     /// destructive methods and little else.
-    pub(super) fn into_dying(self) -> NodeRef<marker::Dying, K, V, Type> {
+    pub(super) fn into_dying(self) -> NodeRef<marker::Dying, K, V, Type, C> {
         NodeRef { height: self.height, node: self.node, _marker: PhantomData }
     }
 }
 
-impl<'a, K: 'a, V: 'a> NodeRef<marker::Mut<'a>, K, V, marker::Leaf> {
+impl<'a, K: 'a, V: 'a, C> NodeRef<marker::Mut<'a>, K, V, marker::Leaf, C> {
     /// Adds a key-value pair to the end of the node, and returns
-    /// a handle to the inserted value.
+    /// a handle to the inserted value. Does not update the subtree lengths
+    /// of ancestors.
     ///
     /// # Safety
     ///
@@ -664,7 +842,7 @@ pub(super) unsafe fn push_with_handle<'b>(
         &mut self,
         key: K,
         val: V,
-    ) -> Handle<NodeRef<marker::Mut<'b>, K, V, marker::Leaf>, marker::KV> {
+    ) -> Handle<NodeRef<marker::Mut<'b>, K, V, marker::Leaf, C>, marker::KV> {
         let len = self.len_mut();
         let idx = usize::from(*len);
         assert!(idx < CAPACITY);
@@ -680,17 +858,19 @@ pub(super) unsafe fn push_with_handle<'b>(
     }
 
     /// Adds a key-value pair to the end of the node, and returns
-    /// the mutable reference of the inserted value.
+    /// the mutable reference of the inserted value. Does not update the
+    /// subtree lengths of ancestors.
     pub(super) fn push(&mut self, key: K, val: V) -> *mut V {
         // SAFETY: The unbound handle is no longer accessible.
         unsafe { self.push_with_handle(key, val).into_val_mut() }
     }
 }
 
-impl<'a, K: 'a, V: 'a> NodeRef<marker::Mut<'a>, K, V, marker::Internal> {
+impl<'a, K: 'a, V: 'a, C: marker::Counting> NodeRef<marker::Mut<'a>, K, V, marker::Internal, C> {
     /// Adds a key-value pair, and an edge to go to the right of that pair,
-    /// to the end of the node.
-    pub(super) fn push(&mut self, key: K, val: V, edge: Root<K, V>) {
+    /// to the end of the node. In a counted tree, updates the subtree length
+    /// of the node, but not of its ancestors.
+    pub(super) fn push(&mut self, key: K, val: V, edge: Root<K, V, C>) {
         assert!(edge.height == self.height - 1);
 
         let len = self.len_mut();
@@ -700,33 +880,36 @@ pub(super) fn push(&mut self, key: K, val: V, edge: Root<K, V>) {
         unsafe {
             self.key_area_mut(idx).write(key);
             self.val_area_mut(idx).write(val);
+            if let Some(mut node) = self.counted_mut() {
+                *node.subtree_len_mut() += 1 + edge.reborrow().counted().unwrap().subtree_len();
+            }
             self.edge_area_mut(idx + 1).write(edge.node);
             Handle::new_edge(self.reborrow_mut(), idx + 1).correct_parent_link();
         }
     }
 }
 
-impl<BorrowType, K, V> NodeRef<BorrowType, K, V, marker::Leaf> {
+impl<BorrowType, K, V, C> NodeRef<BorrowType, K, V, marker::Leaf, C> {
     /// Removes any static information asserting that this node is a `Leaf` node.
-    pub(super) fn forget_type(self) -> NodeRef<BorrowType, K, V, marker::LeafOrInternal> {
+    pub(super) fn forget_type(self) -> NodeRef<BorrowType, K, V, marker::LeafOrInternal, C> {
         NodeRef { height: self.height, node: self.node, _marker: PhantomData }
     }
 }
 
-impl<BorrowType, K, V> NodeRef<BorrowType, K, V, marker::Internal> {
+impl<BorrowType, K, V, C> NodeRef<BorrowType, K, V, marker::Internal, C> {
     /// Removes any static information asserting that this node is an `Internal` node.
-    pub(super) fn forget_type(self) -> NodeRef<BorrowType, K, V, marker::LeafOrInternal> {
+    pub(super) fn forget_type(self) -> NodeRef<BorrowType, K, V, marker::LeafOrInternal, C> {
         NodeRef { height: self.height, node: self.node, _marker: PhantomData }
     }
 }
 
-impl<BorrowType, K, V> NodeRef<BorrowType, K, V, marker::LeafOrInternal> {
+impl<BorrowType, K, V, C> NodeRef<BorrowType, K, V, marker::LeafOrInternal, C> {
     /// Checks whether a node is an `Internal` node or a `Leaf` node.
     pub(super) fn force(
         self,
     ) -> ForceResult<
-        NodeRef<BorrowType, K, V, marker::Leaf>,
-        NodeRef<BorrowType, K, V, marker::Internal>,
+        NodeRef<BorrowType, K, V, marker::Leaf, C>,
+        NodeRef<BorrowType, K, V, marker::Internal, C>,
     > {
         if self.height == 0 {
             ForceResult::Leaf(NodeRef {
@@ -744,17 +927,19 @@ pub(super) fn force(
     }
 }
 
-impl<'a, K, V> NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal> {
+impl<'a, K, V, C> NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal, C> {
     /// Unsafely asserts to the compiler the static information that this node is a `Leaf`.
     pub(super) unsafe fn cast_to_leaf_unchecked(
         self,
-    ) -> NodeRef<marker::Mut<'a>, K, V, marker::Leaf> {
+    ) -> NodeRef<marker::Mut<'a>, K, V, marker::Leaf, C> {
         debug_assert!(self.height == 0);
         NodeRef { height: self.height, node: self.node, _marker: PhantomData }
     }
 
     /// Unsafely asserts to the compiler the static information that this node is an `Internal`.
-    unsafe fn cast_to_internal_unchecked(self) -> NodeRef<marker::Mut<'a>, K, V, marker::Internal> {
+    unsafe fn cast_to_internal_unchecked(
+        self,
+    ) -> NodeRef<marker::Mut<'a>, K, V, marker::Internal, C> {
         debug_assert!(self.height > 0);
         NodeRef { height: self.height, node: self.node, _marker: PhantomData }
     }
@@ -795,26 +980,26 @@ pub(super) fn idx(&self) -> usize {
     }
 }
 
-impl<BorrowType, K, V, NodeType> Handle<NodeRef<BorrowType, K, V, NodeType>, marker::KV> {
+impl<BorrowType, K, V, NodeType, C> Handle<NodeRef<BorrowType, K, V, NodeType, C>, marker::KV> {
     /// Creates a new handle to a key-value pair in `node`.
     /// Unsafe because the caller must ensure that `idx < node.len()`.
-    pub(super) unsafe fn new_kv(node: NodeRef<BorrowType, K, V, NodeType>, idx: usize) -> Self {
+    pub(super) unsafe fn new_kv(node: NodeRef<BorrowType, K, V, NodeType, C>, idx: usize) -> Self {
         debug_assert!(idx < node.len());
 
         Handle { node, idx, _marker: PhantomData }
     }
 
-    pub(super) fn left_edge(self) -> Handle<NodeRef<BorrowType, K, V, NodeType>, marker::Edge> {
+    pub(super) fn left_edge(self) -> Handle<NodeRef<BorrowType, K, V, NodeType, C>, marker::Edge> {
         unsafe { Handle::new_edge(self.node, self.idx) }
     }
 
-    pub(super) fn right_edge(self) -> Handle<NodeRef<BorrowType, K, V, NodeType>, marker::Edge> {
+    pub(super) fn right_edge(self) -> Handle<NodeRef<BorrowType, K, V, NodeType, C>, marker::Edge> {
         unsafe { Handle::new_edge(self.node, self.idx + 1) }
     }
 }
 
-impl<BorrowType, K, V, NodeType, HandleType> PartialEq
-    for Handle<NodeRef<BorrowType, K, V, NodeType>, HandleType>
+impl<BorrowType, K, V, NodeType, HandleType, C> PartialEq
+    for Handle<NodeRef<BorrowType, K, V, NodeType, C>, HandleType>
 {
     fn eq(&self, other: &Self) -> bool {
         let Self { node, idx, _marker } = self;
@@ -822,19 +1007,21 @@ fn eq(&self, other: &Self) -> bool {
     }
 }
 
-impl<BorrowType, K, V, NodeType, HandleType>
-    Handle<NodeRef<BorrowType, K, V, NodeType>, HandleType>
+impl<BorrowType, K, V, NodeType, HandleType, C>
+    Handle<NodeRef<BorrowType, K, V, NodeType, C>, HandleType>
 {
     /// Temporarily takes out another immutable handle on the same location.
     pub(super) fn reborrow(
         &self,
-    ) -> Handle<NodeRef<marker::Immut<'_>, K, V, NodeType>, HandleType> {
+    ) -> Handle<NodeRef<marker::Immut<'_>, K, V, NodeType, C>, HandleType> {
         // We can't use Handle::new_kv or Handle::new_edge because we don't know our type
         Handle { node: self.node.reborrow(), idx: self.idx, _marker: PhantomData }
     }
 }
 
-impl<'a, K, V, NodeType, HandleType> Handle<NodeRef<marker::Mut<'a>, K, V, NodeType>, HandleType> {
+impl<'a, K, V, NodeType, HandleType, C>
+    Handle<NodeRef<marker::Mut<'a>, K, V, NodeType, C>, HandleType>
+{
     /// Temporarily takes out another mutable handle on the same location. Beware, as
     /// this method is very dangerous, doubly so since it might not immediately appear
     /// dangerous.
@@ -842,7 +1029,7 @@ impl<'a, K, V, NodeType, HandleType> Handle<NodeRef<marker::Mut<'a>, K, V, NodeT
     /// For details, see `NodeRef::reborrow_mut`.
     pub(super) unsafe fn reborrow_mut(
         &mut self,
-    ) -> Handle<NodeRef<marker::Mut<'_>, K, V, NodeType>, HandleType> {
+    ) -> Handle<NodeRef<marker::Mut<'_>, K, V, NodeType, C>, HandleType> {
         // We can't use Handle::new_kv or Handle::new_edge because we don't know our type
         Handle { node: unsafe { self.node.reborrow_mut() }, idx: self.idx, _marker: PhantomData }
     }
@@ -852,12 +1039,14 @@ pub(super) unsafe fn reborrow_mut(
     /// See `DormantMutRef` for more details.
     pub(super) fn dormant(
         &self,
-    ) -> Handle<NodeRef<marker::DormantMut, K, V, NodeType>, HandleType> {
+    ) -> Handle<NodeRef<marker::DormantMut, K, V, NodeType, C>, HandleType> {
         Handle { node: self.node.dormant(), idx: self.idx, _marker: PhantomData }
     }
 }
 
-impl<K, V, NodeType, HandleType> Handle<NodeRef<marker::DormantMut, K, V, NodeType>, HandleType> {
+impl<K, V, NodeType, HandleType, C>
+    Handle<NodeRef<marker::DormantMut, K, V, NodeType, C>, HandleType>
+{
     /// Revert to the unique borrow initially captured.
     ///
     /// # Safety
@@ -866,15 +1055,18 @@ impl<K, V, NodeType, HandleType> Handle<NodeRef<marker::DormantMut, K, V, NodeTy
     /// all pointers and references derived from it, must not be used anymore.
     pub(super) unsafe fn awaken<'a>(
         self,
-    ) -> Handle<NodeRef<marker::Mut<'a>, K, V, NodeType>, HandleType> {
+    ) -> Handle<NodeRef<marker::Mut<'a>, K, V, NodeType, C>, HandleType> {
         Handle { node: unsafe { self.node.awaken() }, idx: self.idx, _marker: PhantomData }
     }
 }
 
-impl<BorrowType, K, V, NodeType> Handle<NodeRef<BorrowType, K, V, NodeType>, marker::Edge> {
+impl<BorrowType, K, V, NodeType, C> Handle<NodeRef<BorrowType, K, V, NodeType, C>, marker::Edge> {
     /// Creates a new handle to an edge in `node`.
     /// Unsafe because the caller must ensure that `idx <= node.len()`.
-    pub(super) unsafe fn new_edge(node: NodeRef<BorrowType, K, V, NodeType>, idx: usize) -> Self {
+    pub(super) unsafe fn new_edge(
+        node: NodeRef<BorrowType, K, V, NodeType, C>,
+        idx: usize,
+    ) -> Self {
         debug_assert!(idx <= node.len());
 
         Handle { node, idx, _marker: PhantomData }
@@ -882,7 +1074,7 @@ pub(super) unsafe fn new_edge(node: NodeRef<BorrowType, K, V, NodeType>, idx: us
 
     pub(super) fn left_kv(
         self,
-    ) -> Result<Handle<NodeRef<BorrowType, K, V, NodeType>, marker::KV>, Self> {
+    ) -> Result<Handle<NodeRef<BorrowType, K, V, NodeType, C>, marker::KV>, Self> {
         if self.idx > 0 {
             Ok(unsafe { Handle::new_kv(self.node, self.idx - 1) })
         } else {
@@ -892,7 +1084,7 @@ pub(super) fn left_kv(
 
     pub(super) fn right_kv(
         self,
-    ) -> Result<Handle<NodeRef<BorrowType, K, V, NodeType>, marker::KV>, Self> {
+    ) -> Result<Handle<NodeRef<BorrowType, K, V, NodeType, C>, marker::KV>, Self> {
         if self.idx < self.node.len() {
             Ok(unsafe { Handle::new_kv(self.node, self.idx) })
         } else {
@@ -922,7 +1114,7 @@ fn splitpoint(edge_idx: usize) -> (usize, LeftOrRight<usize>) {
     }
 }
 
-impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::Edge> {
+impl<'a, K: 'a, V: 'a, C> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf, C>, marker::Edge> {
     /// Inserts a new key-value pair between the key-value pairs to the right and left of
     /// this edge. This method assumes that there is enough space in the node for the new
     /// pair to fit.
@@ -930,7 +1122,7 @@ unsafe fn insert_fit(
         mut self,
         key: K,
         val: V,
-    ) -> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::KV> {
+    ) -> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf, C>, marker::KV> {
         debug_assert!(self.node.len() < CAPACITY);
         let new_len = self.node.len() + 1;
 
@@ -944,7 +1136,7 @@ unsafe fn insert_fit(
     }
 }
 
-impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::Edge> {
+impl<'a, K: 'a, V: 'a, C> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf, C>, marker::Edge> {
     /// Inserts a new key-value pair between the key-value pairs to the right and left of
     /// this edge. This method splits the node if there isn't enough room.
     ///
@@ -956,8 +1148,8 @@ fn insert<A: Allocator + Clone>(
         val: V,
         alloc: A,
     ) -> (
-        Option<SplitResult<'a, K, V, marker::Leaf>>,
-        Handle<NodeRef<marker::DormantMut, K, V, marker::Leaf>, marker::KV>,
+        Option<SplitResult<'a, K, V, marker::Leaf, C>>,
+        Handle<NodeRef<marker::DormantMut, K, V, marker::Leaf, C>, marker::KV>,
     ) {
         if self.node.len() < CAPACITY {
             // SAFETY: There is enough space in the node for insertion.
@@ -983,7 +1175,7 @@ fn insert<A: Allocator + Clone>(
     }
 }
 
-impl<'a, K, V> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Internal>, marker::Edge> {
+impl<'a, K, V, C> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Internal, C>, marker::Edge> {
     /// Fixes the parent pointer and index in the child node that this edge
     /// links to. This is useful when the ordering of edges has been changed,
     fn correct_parent_link(self) {
@@ -995,11 +1187,13 @@ fn correct_parent_link(self) {
     }
 }
 
-impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Internal>, marker::Edge> {
+impl<'a, K: 'a, V: 'a, C: marker::Counting>
+    Handle<NodeRef<marker::Mut<'a>, K, V, marker::Internal, C>, marker::Edge>
+{
     /// Inserts a new key-value pair and an edge that will go to the right of that new pair
     /// between this edge and the key-value pair to the right of this edge. This method assumes
     /// that there is enough space in the node for the new pair to fit.
-    fn insert_fit(&mut self, key: K, val: V, edge: Root<K, V>) {
+    fn insert_fit(&mut self, key: K, val: V, edge: Root<K, V, C>) {
         debug_assert!(self.node.len() < CAPACITY);
         debug_assert!(edge.height == self.node.height - 1);
         let new_len = self.node.len() + 1;
@@ -1021,9 +1215,9 @@ fn insert<A: Allocator + Clone>(
         mut self,
         key: K,
         val: V,
-        edge: Root<K, V>,
+        edge: Root<K, V, C>,
         alloc: A,
-    ) -> Option<SplitResult<'a, K, V, marker::Internal>> {
+    ) -> Option<SplitResult<'a, K, V, marker::Internal, C>> {
         assert!(edge.height == self.node.height - 1);
 
         if self.node.len() < CAPACITY {
@@ -1042,12 +1236,97 @@ fn insert<A: Allocator + Clone>(
                 },
             };
             insertion_edge.insert_fit(key, val, edge);
+            // The subtree lengths of the ancestors already account for the inserted pair,
+            // but both halves have to be recounted.
+            result.left.recalc_subtree_len();
+            result.right.borrow_mut().recalc_subtree_len();
             Some(result)
         }
     }
 }
 
-impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::Edge> {
+impl<'a, K: 'a, V: 'a> NodeRef<marker::Mut<'a>, K, V, marker::Internal, marker::Uncounted> {
+    /// Adds a key-value pair, and an edge to go to the left of that pair,
+    /// to the beginning of the node. This method assumes that there is enough
+    /// space in the node.
+    fn push_front_fit(&mut self, key: K, val: V, edge: Root<K, V, marker::Uncounted>) {
+        debug_assert!(self.len() < CAPACITY);
+        debug_assert!(edge.height == self.height - 1);
+        let new_len = self.len() + 1;
+
+        unsafe {
+            slice_insert(self.key_area_mut(..new_len), 0, key);
+            slice_insert(self.val_area_mut(..new_len), 0, val);
+            slice_insert(self.edge_area_mut(..new_len + 1), 0, edge.node);
+            *self.len_mut() = new_len as u16;
+
+            self.correct_childrens_parent_links(0..new_len + 1);
+        }
+    }
+
+    /// Adds a key-value pair, and an edge to go to the right of that pair, to the end
+    /// of the node. This method splits the node if there isn't enough room, and inserts
+    /// the split off portion into the parent node recursively, until the root is reached.
+    ///
+    /// If the returned result is some `SplitResult`, the `left` field will be the root node.
+    pub(super) fn push_recursing<A: Allocator + Clone>(
+        self,
+        key: K,
+        val: V,
+        edge: Root<K, V, marker::Uncounted>,
+        alloc: A,
+    ) -> Option<SplitResult<'a, K, V, marker::LeafOrInternal, marker::Uncounted>> {
+        assert!(edge.height == self.height - 1);
+
+        let len = self.len();
+        let split = unsafe { Handle::new_edge(self, len) }.insert(key, val, edge, alloc.clone())?;
+        Self::insert_split_recursing(split.forget_node_type(), alloc)
+    }
+
+    /// Adds a key-value pair, and an edge to go to the left of that pair, to the beginning
+    /// of the node. This method splits the node if there isn't enough room, and inserts
+    /// the split off portion into the parent node recursively, until the root is reached.
+    ///
+    /// If the returned result is some `SplitResult`, the `left` field will be the root node.
+    pub(super) fn push_front_recursing<A: Allocator + Clone>(
+        mut self,
+        key: K,
+        val: V,
+        edge: Root<K, V, marker::Uncounted>,
+        alloc: A,
+    ) -> Option<SplitResult<'a, K, V, marker::LeafOrInternal, marker::Uncounted>> {
+        assert!(edge.height == self.height - 1);
+
+        if self.len() < CAPACITY {
+            self.push_front_fit(key, val, edge);
+            return None;
+        }
+        let middle = unsafe { Handle::new_kv(self, KV_IDX_CENTER) };
+        let mut result = middle.split(alloc.clone());
+        result.left.push_front_fit(key, val, edge);
+        Self::insert_split_recursing(result.forget_node_type(), alloc)
+    }
+
+    /// Inserts the split off portion of a node into its parent node, splitting
+    /// that one too if there isn't enough room, until the root is reached.
+    fn insert_split_recursing<A: Allocator + Clone>(
+        mut split: SplitResult<'a, K, V, marker::LeafOrInternal, marker::Uncounted>,
+        alloc: A,
+    ) -> Option<SplitResult<'a, K, V, marker::LeafOrInternal, marker::Uncounted>> {
+        loop {
+            split = match split.left.ascend() {
+                Ok(parent) => parent
+                    .insert(split.kv.0, split.kv.1, split.right, alloc.clone())?
+                    .forget_node_type(),
+                Err(root) => return Some(SplitResult { left: root, ..split }),
+            };
+        }
+    }
+}
+
+impl<'a, K: 'a, V: 'a, C: marker::Counting>
+    Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf, C>, marker::Edge>
+{
     /// Inserts a new key-value pair between the key-value pairs to the right and left of
     /// this edge. This method splits the node if there isn't enough room, and tries to
     /// insert the split off portion into the parent node recursively, until the root is reached.
@@ -1056,12 +1335,15 @@ impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, mark
     /// The returned pointer points to the inserted value, which in the case of `SplitResult`
     /// is in the `left` or `right` tree.
     pub(super) fn insert_recursing<A: Allocator + Clone>(
-        self,
+        mut self,
         key: K,
         value: V,
         alloc: A,
-        split_root: impl FnOnce(SplitResult<'a, K, V, marker::LeafOrInternal>),
-    ) -> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::KV> {
+        split_root: impl FnOnce(SplitResult<'a, K, V, marker::LeafOrInternal, C>),
+    ) -> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf, C>, marker::KV> {
+        // Account for the new pair up front, while the leaf is still attached to
+        // its ancestors. Splitting only moves pairs around within their subtree.
+        self.node.increment_ancestors_subtree_len();
         let (mut split, handle) = match self.insert(key, value, alloc.clone()) {
             // SAFETY: we have finished splitting and can now re-awaken the
             // handle to the inserted element.
@@ -1090,8 +1372,8 @@ pub(super) fn insert_recursing<A: Allocator + Clone>(
     }
 }
 
-impl<BorrowType: marker::BorrowType, K, V>
-    Handle<NodeRef<BorrowType, K, V, marker::Internal>, marker::Edge>
+impl<BorrowType: marker::BorrowType, K, V, C>
+    Handle<NodeRef<BorrowType, K, V, marker::Internal, C>, marker::Edge>
 {
     /// Finds the node pointed to by this edge.
     ///
@@ -1099,7 +1381,7 @@ impl<BorrowType: marker::BorrowType, K, V>
     ///
     /// `edge.descend().ascend().unwrap()` and `node.ascend().unwrap().descend()` should
     /// both, upon success, do nothing.
-    pub(super) fn descend(self) -> NodeRef<BorrowType, K, V, marker::LeafOrInternal> {
+    pub(super) fn descend(self) -> NodeRef<BorrowType, K, V, marker::LeafOrInternal, C> {
         const {
             assert!(BorrowType::TRAVERSAL_PERMIT);
         }
@@ -1117,7 +1399,9 @@ pub(super) fn descend(self) -> NodeRef<BorrowType, K, V, marker::LeafOrInternal>
     }
 }
 
-impl<'a, K: 'a, V: 'a, NodeType> Handle<NodeRef<marker::Immut<'a>, K, V, NodeType>, marker::KV> {
+impl<'a, K: 'a, V: 'a, NodeType, C>
+    Handle<NodeRef<marker::Immut<'a>, K, V, NodeType, C>, marker::KV>
+{
     pub(super) fn into_kv(self) -> (&'a K, &'a V) {
         debug_assert!(self.idx < self.node.len());
         let leaf = self.node.into_leaf();
@@ -1127,7 +1411,9 @@ pub(super) fn into_kv(self) -> (&'a K, &'a V) {
     }
 }
 
-impl<'a, K: 'a, V: 'a, NodeType> Handle<NodeRef<marker::Mut<'a>, K, V, NodeType>, marker::KV> {
+impl<'a, K: 'a, V: 'a, NodeType, C>
+    Handle<NodeRef<marker::Mut<'a>, K, V, NodeType, C>, marker::KV>
+{
     pub(super) fn key_mut(&mut self) -> &mut K {
         unsafe { self.node.key_area_mut(self.idx).assume_init_mut() }
     }
@@ -1147,13 +1433,15 @@ pub(super) fn into_kv_mut(self) -> (&'a mut K, &'a mut V) {
     }
 }
 
-impl<'a, K, V, NodeType> Handle<NodeRef<marker::ValMut<'a>, K, V, NodeType>, marker::KV> {
+impl<'a, K, V, NodeType, C> Handle<NodeRef<marker::ValMut<'a>, K, V, NodeType, C>, marker::KV> {
     pub(super) fn into_kv_valmut(self) -> (&'a K, &'a mut V) {
         unsafe { self.node.into_key_val_mut_at(self.idx) }
     }
 }
 
-impl<'a, K: 'a, V: 'a, NodeType> Handle<NodeRef<marker::Mut<'a>, K, V, NodeType>, marker::KV> {
+impl<'a, K: 'a, V: 'a, NodeType, C>
+    Handle<NodeRef<marker::Mut<'a>, K, V, NodeType, C>, marker::KV>
+{
     pub(super) fn kv_mut(&mut self) -> (&mut K, &mut V) {
         debug_assert!(self.idx < self.node.len());
         // We cannot call separate key and value methods, because calling the second one
@@ -1173,7 +1461,7 @@ pub(super) fn replace_kv(&mut self, k: K, v: V) -> (K, V) {
     }
 }
 
-impl<K, V, NodeType> Handle<NodeRef<marker::Dying, K, V, NodeType>, marker::KV> {
+impl<K, V, NodeType, C> Handle<NodeRef<marker::Dying, K, V, NodeType, C>, marker::KV> {
     /// Extracts the key and value that the KV handle refers to.
     /// # Safety
     /// The node that the handle refers to must not yet have been deallocated.
@@ -1215,7 +1503,9 @@ fn drop(&mut self) {
     }
 }
 
-impl<'a, K: 'a, V: 'a, NodeType> Handle<NodeRef<marker::Mut<'a>, K, V, NodeType>, marker::KV> {
+impl<'a, K: 'a, V: 'a, NodeType, C>
+    Handle<NodeRef<marker::Mut<'a>, K, V, NodeType, C>, marker::KV>
+{
     /// Helps implementations of `split` for a particular `NodeType`,
     /// by taking care of leaf data.
     fn split_leaf_data(&mut self, new_node: &mut LeafNode<K, V>) -> (K, V) {
@@ -1242,7 +1532,7 @@ fn split_leaf_data(&mut self, new_node: &mut LeafNode<K, V>) -> (K, V) {
     }
 }
 
-impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::KV> {
+impl<'a, K: 'a, V: 'a, C> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf, C>, marker::KV> {
     /// Splits the underlying node into three parts:
     ///
     /// - The node is truncated to only contain the key-value pairs to the left of
@@ -1253,7 +1543,7 @@ impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, mark
     pub(super) fn split<A: Allocator + Clone>(
         mut self,
         alloc: A,
-    ) -> SplitResult<'a, K, V, marker::Leaf> {
+    ) -> SplitResult<'a, K, V, marker::Leaf, C> {
         let mut new_node = LeafNode::new(alloc);
 
         let kv = self.split_leaf_data(&mut new_node);
@@ -1266,7 +1556,7 @@ pub(super) fn split<A: Allocator + Clone>(
     /// that the key-value pair collapsed into.
     pub(super) fn remove(
         mut self,
-    ) -> ((K, V), Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::Edge>) {
+    ) -> ((K, V), Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf, C>, marker::Edge>) {
         let old_len = self.node.len();
         unsafe {
             let k = slice_remove(self.node.key_area_mut(..old_len), self.idx);
@@ -1277,7 +1567,9 @@ pub(super) fn remove(
     }
 }
 
-impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Internal>, marker::KV> {
+impl<'a, K: 'a, V: 'a, C: marker::Counting>
+    Handle<NodeRef<marker::Mut<'a>, K, V, marker::Internal, C>, marker::KV>
+{
     /// Splits the underlying node into three parts:
     ///
     /// - The node is truncated to only contain the edges and key-value pairs to the
@@ -1288,15 +1580,15 @@ impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Internal>,
     pub(super) fn split<A: Allocator + Clone>(
         mut self,
         alloc: A,
-    ) -> SplitResult<'a, K, V, marker::Internal> {
+    ) -> SplitResult<'a, K, V, marker::Internal, C> {
         let old_len = self.node.len();
         unsafe {
-            let mut new_node = InternalNode::new(alloc);
-            let kv = self.split_leaf_data(&mut new_node.data);
-            let new_len = usize::from(new_node.data.len);
+            let mut new_node = InternalNode::new_counting::<C, A>(alloc);
+            let kv = self.split_leaf_data(&mut new_node.as_mut().data);
+            let new_len = usize::from(new_node.as_ref().data.len);
             move_to_slice(
                 self.node.edge_area_mut(self.idx + 1..old_len + 1),
-                &mut new_node.edges[..new_len + 1],
+                &mut new_node.as_mut().edges[..new_len + 1],
             );
 
             // SAFETY: self is `marker::Internal`, so `self.node.height` is positive
@@ -1310,14 +1602,14 @@ pub(super) fn split<A: Allocator + Clone>(
 
 /// Represents a session for evaluating and performing a balancing operation
 /// around an internal key-value pair.
-pub(super) struct BalancingContext<'a, K, V> {
-    parent: Handle<NodeRef<marker::Mut<'a>, K, V, marker::Internal>, marker::KV>,
-    left_child: NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal>,
-    right_child: NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal>,
+pub(super) struct BalancingContext<'a, K, V, C> {
+    parent: Handle<NodeRef<marker::Mut<'a>, K, V, marker::Internal, C>, marker::KV>,
+    left_child: NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal, C>,
+    right_child: NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal, C>,
 }
 
-impl<'a, K, V> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Internal>, marker::KV> {
-    pub(super) fn consider_for_balancing(self) -> BalancingContext<'a, K, V> {
+impl<'a, K, V, C> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Internal, C>, marker::KV> {
+    pub(super) fn consider_for_balancing(self) -> BalancingContext<'a, K, V, C> {
         let self1 = unsafe { ptr::read(&self) };
         let self2 = unsafe { ptr::read(&self) };
         BalancingContext {
@@ -1328,7 +1620,7 @@ pub(super) fn consider_for_balancing(self) -> BalancingContext<'a, K, V> {
     }
 }
 
-impl<'a, K, V> NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal> {
+impl<'a, K, V, C> NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal, C> {
     /// Chooses a balancing context involving the node as a child, thus between
     /// the KV immediately to the left or to the right in the parent node.
     /// Returns an `Err` if there is no parent.
@@ -1343,7 +1635,9 @@ impl<'a, K, V> NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal> {
     /// typically faster, since we only need to shift the node's N elements to
     /// the right, instead of shifting at least N of the sibling's elements to
     /// the left.
-    pub(super) fn choose_parent_kv(self) -> Result<LeftOrRight<BalancingContext<'a, K, V>>, Self> {
+    pub(super) fn choose_parent_kv(
+        self,
+    ) -> Result<LeftOrRight<BalancingContext<'a, K, V, C>>, Self> {
         match unsafe { ptr::read(&self) }.ascend() {
             Ok(parent_edge) => match parent_edge.left_kv() {
                 Ok(left_parent_kv) => Ok(LeftOrRight::Left(BalancingContext {
@@ -1365,7 +1659,7 @@ pub(super) fn choose_parent_kv(self) -> Result<LeftOrRight<BalancingContext<'a,
     }
 }
 
-impl<'a, K, V> BalancingContext<'a, K, V> {
+impl<'a, K, V, C> BalancingContext<'a, K, V, C> {
     pub(super) fn left_child_len(&self) -> usize {
         self.left_child.len()
     }
@@ -1374,11 +1668,15 @@ pub(super) fn right_child_len(&self) -> usize {
         self.right_child.len()
     }
 
-    pub(super) fn into_left_child(self) -> NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal> {
+    pub(super) fn into_left_child(
+        self,
+    ) -> NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal, C> {
         self.left_child
     }
 
-    pub(super) fn into_right_child(self) -> NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal> {
+    pub(super) fn into_right_child(
+        self,
+    ) -> NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal, C> {
         self.right_child
     }
 
@@ -1389,12 +1687,12 @@ pub(super) fn can_merge(&self) -> bool {
     }
 }
 
-impl<'a, K: 'a, V: 'a> BalancingContext<'a, K, V> {
+impl<'a, K: 'a, V: 'a, C: marker::Counting> BalancingContext<'a, K, V, C> {
     /// Performs a merge and lets a closure decide what to return.
     fn do_merge<
         F: FnOnce(
-            NodeRef<marker::Mut<'a>, K, V, marker::Internal>,
-            NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal>,
+            NodeRef<marker::Mut<'a>, K, V, marker::Internal, C>,
+            NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal, C>,
         ) -> R,
         R,
         A: Allocator,
@@ -1445,8 +1743,12 @@ fn do_merge<
                 );
 
                 left_node.correct_childrens_parent_links(old_left_len + 1..new_left_len + 1);
+                if let Some(mut left_node) = left_node.counted_mut() {
+                    let right_len = right_node.counted().unwrap().subtree_len();
+                    *left_node.subtree_len_mut() += 1 + right_len;
+                }
 
-                alloc.deallocate(right_node.node.cast(), Layout::new::<InternalNode<K, V>>());
+                alloc.deallocate(right_node.node.cast(), InternalNode::<K, V>::layout::<C>());
             } else {
                 alloc.deallocate(right_node.node.cast(), Layout::new::<LeafNode<K, V>>());
             }
@@ -1461,7 +1763,7 @@ fn do_merge<
     pub(super) fn merge_tracking_parent<A: Allocator + Clone>(
         self,
         alloc: A,
-    ) -> NodeRef<marker::Mut<'a>, K, V, marker::Internal> {
+    ) -> NodeRef<marker::Mut<'a>, K, V, marker::Internal, C> {
         self.do_merge(|parent, _child| parent, alloc)
     }
 
@@ -1472,7 +1774,7 @@ pub(super) fn merge_tracking_parent<A: Allocator + Clone>(
     pub(super) fn merge_tracking_child<A: Allocator + Clone>(
         self,
         alloc: A,
-    ) -> NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal> {
+    ) -> NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal, C> {
         self.do_merge(|_parent, child| child, alloc)
     }
 
@@ -1485,7 +1787,7 @@ pub(super) fn merge_tracking_child_edge<A: Allocator + Clone>(
         self,
         track_edge_idx: LeftOrRight<usize>,
         alloc: A,
-    ) -> Handle<NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal>, marker::Edge> {
+    ) -> Handle<NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal, C>, marker::Edge> {
         let old_left_len = self.left_child.len();
         let right_len = self.right_child.len();
         assert!(match track_edge_idx {
@@ -1507,7 +1809,7 @@ pub(super) fn merge_tracking_child_edge<A: Allocator + Clone>(
     pub(super) fn steal_left(
         mut self,
         track_right_edge_idx: usize,
-    ) -> Handle<NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal>, marker::Edge> {
+    ) -> Handle<NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal, C>, marker::Edge> {
         self.bulk_steal_left(1);
         unsafe { Handle::new_edge(self.right_child, 1 + track_right_edge_idx) }
     }
@@ -1519,7 +1821,7 @@ pub(super) fn steal_left(
     pub(super) fn steal_right(
         mut self,
         track_left_edge_idx: usize,
-    ) -> Handle<NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal>, marker::Edge> {
+    ) -> Handle<NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal, C>, marker::Edge> {
         self.bulk_steal_right(1);
         unsafe { Handle::new_edge(self.left_child, track_left_edge_idx) }
     }
@@ -1580,6 +1882,14 @@ pub(super) fn bulk_steal_left(&mut self, count: usize) {
                     );
 
                     right.correct_childrens_parent_links(0..new_right_len + 1);
+
+                    if let (Some(mut left), Some(mut right)) =
+                        (left.counted_mut(), right.counted_mut())
+                    {
+                        let moved_len = count + right.reborrow().childrens_subtree_len(0..count);
+                        *left.subtree_len_mut() -= moved_len;
+                        *right.subtree_len_mut() += moved_len;
+                    }
                 }
                 (ForceResult::Leaf(_), ForceResult::Leaf(_)) => {}
                 _ => unreachable!(),
@@ -1644,6 +1954,15 @@ pub(super) fn bulk_steal_right(&mut self, count: usize) {
 
                     left.correct_childrens_parent_links(old_left_len + 1..new_left_len + 1);
                     right.correct_childrens_parent_links(0..new_right_len + 1);
+
+                    if let (Some(mut left), Some(mut right)) =
+                        (left.counted_mut(), right.counted_mut())
+                    {
+                        let moved_range = old_left_len + 1..new_left_len + 1;
+                        let moved_len = count + left.reborrow().childrens_subtree_len(moved_range);
+                        *left.subtree_len_mut() += moved_len;
+                        *right.subtree_len_mut() -= moved_len;
+                    }
                 }
                 (ForceResult::Leaf(_), ForceResult::Leaf(_)) => {}
                 _ => unreachable!(),
@@ -1652,37 +1971,45 @@ pub(super) fn bulk_steal_right(&mut self, count: usize) {
     }
 }
 
-impl<BorrowType, K, V> Handle<NodeRef<BorrowType, K, V, marker::Leaf>, marker::Edge> {
+impl<BorrowType, K, V, C> Handle<NodeRef<BorrowType, K, V, marker::Leaf, C>, marker::Edge> {
     pub(super) fn forget_node_type(
         self,
-    ) -> Handle<NodeRef<BorrowType, K, V, marker::LeafOrInternal>, marker::Edge> {
+    ) -> Handle<NodeRef<BorrowType, K, V, marker::LeafOrInternal, C>, marker::Edge> {
         unsafe { Handle::new_edge(self.node.forget_type(), self.idx) }
     }
 }
 
-impl<BorrowType, K, V> Handle<NodeRef<BorrowType, K, V, marker::Internal>, marker::Edge> {
+impl<BorrowType, K, V, C> Handle<NodeRef<BorrowType, K, V, marker::Internal, C>, marker::Edge> {
     pub(super) fn forget_node_type(
         self,
-    ) -> Handle<NodeRef<BorrowType, K, V, marker::LeafOrInternal>, marker::Edge> {
+    ) -> Handle<NodeRef<BorrowType, K, V, marker::LeafOrInternal, C>, marker::Edge> {
         unsafe { Handle::new_edge(self.node.forget_type(), self.idx) }
     }
 }
 
-impl<BorrowType, K, V> Handle<NodeRef<BorrowType, K, V, marker::Leaf>, marker::KV> {
+impl<BorrowType, K, V, C> Handle<NodeRef<BorrowType, K, V, marker::Leaf, C>, marker::KV> {
     pub(super) fn forget_node_type(
         self,
-    ) -> Handle<NodeRef<BorrowType, K, V, marker::LeafOrInternal>, marker::KV> {
+    ) -> Handle<NodeRef<BorrowType, K, V, marker::LeafOrInternal, C>, marker::KV> {
         unsafe { Handle::new_kv(self.node.forget_type(), self.idx) }
     }
 }
 
-impl<BorrowType, K, V, Type> Handle<NodeRef<BorrowType, K, V, marker::LeafOrInternal>, Type> {
+impl<BorrowType, K, V, C> Handle<NodeRef<BorrowType, K, V, marker::Internal, C>, marker::KV> {
+    pub(super) fn forget_node_type(
+        self,
+    ) -> Handle<NodeRef<BorrowType, K, V, marker::LeafOrInternal, C>, marker::KV> {
+        unsafe { Handle::new_kv(self.node.forget_type(), self.idx) }
+    }
+}
+
+impl<BorrowType, K, V, Type, C> Handle<NodeRef<BorrowType, K, V, marker::LeafOrInternal, C>, Type> {
     /// Checks whether the underlying node is an `Internal` node or a `Leaf` node.
     pub(super) fn force(
         self,
     ) -> ForceResult<
-        Handle<NodeRef<BorrowType, K, V, marker::Leaf>, Type>,
-        Handle<NodeRef<BorrowType, K, V, marker::Internal>, Type>,
+        Handle<NodeRef<BorrowType, K, V, marker::Leaf, C>, Type>,
+        Handle<NodeRef<BorrowType, K, V, marker::Internal, C>, Type>,
     > {
         match self.node.force() {
             ForceResult::Leaf(node) => {
@@ -1695,22 +2022,24 @@ pub(super) fn force(
     }
 }
 
-impl<'a, K, V, Type> Handle<NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal>, Type> {
+impl<'a, K, V, Type, C> Handle<NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal, C>, Type> {
     /// Unsafely asserts to the compiler the static information that the handle's node is a `Leaf`.
     pub(super) unsafe fn cast_to_leaf_unchecked(
         self,
-    ) -> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, Type> {
+    ) -> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf, C>, Type> {
         let node = unsafe { self.node.cast_to_leaf_unchecked() };
         Handle { node, idx: self.idx, _marker: PhantomData }
     }
 }
 
-impl<'a, K, V> Handle<NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal>, marker::Edge> {
+impl<'a, K, V>
+    Handle<NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal, marker::Uncounted>, marker::Edge>
+{
     /// Move the suffix after `self` from one node to another one. `right` must be empty.
     /// The first edge of `right` remains unchanged.
     pub(super) fn move_suffix(
         &mut self,
-        right: &mut NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal>,
+        right: &mut NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal, marker::Uncounted>,
     ) {
         unsafe {
             let new_left_len = self.idx;
@@ -1757,23 +2086,23 @@ pub(super) enum ForceResult<Leaf, Internal> {
 }
 
 /// Result of insertion, when a node needed to expand beyond its capacity.
-pub(super) struct SplitResult<'a, K, V, NodeType> {
+pub(super) struct SplitResult<'a, K, V, NodeType, C> {
     // Altered node in existing tree with elements and edges that belong to the left of `kv`.
-    pub left: NodeRef<marker::Mut<'a>, K, V, NodeType>,
+    pub left: NodeRef<marker::Mut<'a>, K, V, NodeType, C>,
     // Some key and value that existed before and were split off, to be inserted elsewhere.
     pub kv: (K, V),
     // Owned, unattached, new node with elements and edges that belong to the right of `kv`.
-    pub right: NodeRef<marker::Owned, K, V, NodeType>,
+    pub right: NodeRef<marker::Owned, K, V, NodeType, C>,
 }
 
-impl<'a, K, V> SplitResult<'a, K, V, marker::Leaf> {
-    pub(super) fn forget_node_type(self) -> SplitResult<'a, K, V, marker::LeafOrInternal> {
+impl<'a, K, V, C> SplitResult<'a, K, V, marker::Leaf, C> {
+    pub(super) fn forget_node_type(self) -> SplitResult<'a, K, V, marker::LeafOrInternal, C> {
         SplitResult { left: self.left.forget_type(), kv: self.kv, right: self.right.forget_type() }
     }
 }
 
-impl<'a, K, V> SplitResult<'a, K, V, marker::Internal> {
-    pub(super) fn forget_node_type(self) -> SplitResult<'a, K, V, marker::LeafOrInternal> {
+impl<'a, K, V, C> SplitResult<'a, K, V, marker::Internal, C> {
+    pub(super) fn forget_node_type(self) -> SplitResult<'a, K, V, marker::LeafOrInternal, C> {
         SplitResult { left: self.left.forget_type(), kv: self.kv, right: self.right.forget_type() }
     }
 }
@@ -1813,6 +2142,21 @@ impl BorrowType for DormantMut {}
 
     pub(crate) enum KV {}
     pub(crate) enum Edge {}
+
+    pub(crate) enum Uncounted {}
+    pub(crate) enum Counted {}
+
+    pub(crate) trait Counting {
+        /// Whether internal nodes keep track of the number of key-value pairs
+        /// in their subtree, i.e. whether they are `CountedInternalNode`s.
+        const COUNTED: bool;
+    }
+    impl Counting for Uncounted {
+        const COUNTED: bool = false;
+    }
+    impl Counting for Counted {
+        const COUNTED: bool = true;
+    }
 }
 
 /// Inserts a value into a slice of initialized elements followed by one uninitialized element.
@@ -4,7 +4,7 @@
 use crate::fmt::Debug;
 use crate::string::String;
 
-impl<'a, K: 'a, V: 'a> NodeRef<marker::Immut<'a>, K, V, marker::LeafOrInternal> {
+impl<'a, K: 'a, V: 'a, C> NodeRef<marker::Immut<'a>, K, V, marker::LeafOrInternal, C> {
     // Asserts that the back pointer in each reachable node points to its parent.
     pub(crate) fn assert_back_pointers(self) {
         if let ForceResult::Internal(node) = self.force() {
@@ -38,6 +38,27 @@ pub(crate) fn dump_keys(self) -> String
     }
 }
 
+impl<'a, K: 'a, V: 'a> NodeRef<marker::Immut<'a>, K, V, marker::LeafOrInternal, marker::Counted> {
+    // Asserts that the subtree length of each reachable internal node equals
+    // the number of key-value pairs actually in its subtree, and returns that
+    // number for this node.
+    pub(crate) fn assert_subtree_lens(self) -> usize {
+        let len = match self.force() {
+            ForceResult::Leaf(leaf) => leaf.len(),
+            ForceResult::Internal(node) => {
+                let mut len = node.len();
+                for idx in 0..=node.len() {
+                    let edge = unsafe { Handle::new_edge(node, idx) };
+                    len += edge.descend().assert_subtree_lens();
+                }
+                len
+            }
+        };
+        assert_eq!(self.subtree_len(), len);
+        len
+    }
+}
+
 #[test]
 fn test_splitpoint() {
     for idx in 0..=CAPACITY {
@@ -64,7 +85,7 @@ fn test_splitpoint() {
 
 #[test]
 fn test_partial_eq() {
-    let mut root1 = NodeRef::new_leaf(Global);
+    let mut root1 = NodeRef::<_, _, _, _, marker::Uncounted>::new_leaf(Global);
     root1.borrow_mut().push(1, ());
     let mut root1 = NodeRef::new_internal(root1.forget_type(), Global).forget_type();
     let root2 = Root::new(Global);
@@ -96,4 +117,5 @@ fn test_sizes() {
     assert_eq!(size_of::<LeafNode<i64, i64>>(), 16 + CAPACITY * 2 * 8);
     assert_eq!(size_of::<InternalNode<(), ()>>(), 16 + (CAPACITY + 1) * 8);
     assert_eq!(size_of::<InternalNode<i64, i64>>(), 16 + (CAPACITY * 3 + 1) * 8);
+    assert_eq!(size_of::<CountedInternalNode<(), ()>>(), 16 + (CAPACITY + 2) * 8);
 }
@@ -0,0 +1,143 @@
+use core::borrow::Borrow;
+use core::ops::RangeBounds;
+
+use super::node::ForceResult::*;
+use super::node::{Handle, NodeRef, marker};
+use super::search::SearchBound;
+
+impl<BorrowType: marker::BorrowType, K, V>
+    NodeRef<BorrowType, K, V, marker::LeafOrInternal, marker::Counted>
+{
+    /// Finds the key-value pair at position `index` in the (sub)tree headed by
+    /// the node, counting from zero in ascending key order. Returns `None` if
+    /// the subtree doesn't have more than `index` pairs.
+    pub(super) fn find_kv_at(
+        self,
+        mut index: usize,
+    ) -> Option<
+        Handle<NodeRef<BorrowType, K, V, marker::LeafOrInternal, marker::Counted>, marker::KV>,
+    > {
+        if index >= self.subtree_len() {
+            return None;
+        }
+        let mut node = self;
+        loop {
+            match node.force() {
+                Leaf(leaf) => {
+                    return Some(unsafe { Handle::new_kv(leaf, index) }.forget_node_type());
+                }
+                Internal(internal) => {
+                    let mut idx = 0;
+                    loop {
+                        debug_assert!(idx <= internal.len());
+                        let edge = unsafe { Handle::new_edge(internal.reborrow(), idx) };
+                        let child_len = edge.descend().subtree_len();
+                        if index < child_len {
+                            break;
+                        }
+                        index -= child_len;
+                        if index == 0 {
+                            let kv = unsafe { Handle::new_kv(internal, idx) };
+                            return Some(kv.forget_node_type());
+                        }
+                        index -= 1;
+                        idx += 1;
+                    }
+                    node = unsafe { Handle::new_edge(internal, idx) }.descend();
+                }
+            }
+        }
+    }
+
+    /// Counts the key-value pairs in the (sub)tree headed by the node that lie
+    /// before the lower bound `bound`.
+    ///
+    /// The result is meaningful only if the tree is ordered by key.
+    pub(super) fn len_before_lower_bound<'r, Q>(self, bound: SearchBound<&'r Q>) -> usize
+    where
+        Q: ?Sized + Ord,
+        K: Borrow<Q>,
+    {
+        self.len_before_bound(bound, true)
+    }
+
+    /// Counts the key-value pairs in the (sub)tree headed by the node that lie
+    /// before the upper bound `bound`, i.e. that `bound` doesn't exclude.
+    ///
+    /// The result is meaningful only if the tree is ordered by key.
+    pub(super) fn len_before_upper_bound<'r, Q>(self, bound: SearchBound<&'r Q>) -> usize
+    where
+        Q: ?Sized + Ord,
+        K: Borrow<Q>,
+    {
+        self.len_before_bound(bound, false)
+    }
+
+    /// Counts the key-value pairs in the range in the (sub)tree headed by the node.
+    ///
+    /// The result is meaningful only if the tree is ordered by key, and if
+    /// the ordering of `Q` corresponds to that of `K`.
+    ///
+    /// # Panics
+    /// Panics if `range` is `start > end`.
+    /// Panics if `range` is `start == end` and both bounds are `Excluded`.
+    pub(super) fn range_len<Q, R>(self, range: R) -> usize
+    where
+        Q: ?Sized + Ord,
+        K: Borrow<Q>,
+        R: RangeBounds<Q>,
+    {
+        match self.search_tree_for_bifurcation(&range) {
+            Ok((node, lower_edge_idx, upper_edge_idx, lower_child_bound, upper_child_bound)) => {
+                // All key-value pairs between the two edges are in range.
+                let mut len = upper_edge_idx - lower_edge_idx;
+                if let Internal(internal) = node.force() {
+                    let child =
+                        |idx| unsafe { Handle::new_edge(internal.reborrow(), idx) }.descend();
+                    // So are the children between the two edges.
+                    for idx in lower_edge_idx + 1..upper_edge_idx {
+                        len += child(idx).subtree_len();
+                    }
+                    // And part of the children behind the edges themselves.
+                    let lower_child = child(lower_edge_idx);
+                    len += lower_child.subtree_len();
+                    len -= lower_child.len_before_lower_bound(lower_child_bound);
+                    len += child(upper_edge_idx).len_before_upper_bound(upper_child_bound);
+                }
+                len
+            }
+            Err(_) => 0,
+        }
+    }
+
+    /// Counts the key-value pairs before the edge delimiting the lower bound
+    /// (if `is_lower`) or the upper bound in each node on the way down from
+    /// this node to a leaf.
+    fn len_before_bound<'r, Q>(self, mut bound: SearchBound<&'r Q>, is_lower: bool) -> usize
+    where
+        Q: ?Sized + Ord,
+        K: Borrow<Q>,
+    {
+        let mut len = 0;
+        let mut node = self;
+        loop {
+            let (edge, child_bound) = if is_lower {
+                node.find_lower_bound_edge(bound)
+            } else {
+                node.find_upper_bound_edge(bound)
+            };
+            len += edge.idx();
+            match edge.force() {
+                Leaf(_) => return len,
+                Internal(edge) => {
+                    let parent = edge.reborrow().into_node();
+                    for idx in 0..edge.idx() {
+                        len += unsafe { Handle::new_edge(parent, idx) }.descend().subtree_len();
+                    }
+                    node = edge.descend();
+                    bound = child_bound;
+                }
+            }
+        }
+    }
+}
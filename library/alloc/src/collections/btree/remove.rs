@@ -5,7 +5,9 @@
 use super::node::LeftOrRight::*;
 use super::node::{Handle, NodeRef, marker};
 
-impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal>, marker::KV> {
+impl<'a, K: 'a, V: 'a, C: marker::Counting>
+    Handle<NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal, C>, marker::KV>
+{
     /// Removes a key-value pair from the tree, and returns that pair, as well as
     /// the leaf edge corresponding to that former pair. It's possible this empties
     /// a root node that is internal, which the caller should pop from the map
@@ -14,7 +16,7 @@ pub(super) fn remove_kv_tracking<F: FnOnce(), A: Allocator + Clone>(
         self,
         handle_emptied_internal_root: F,
         alloc: A,
-    ) -> ((K, V), Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::Edge>) {
+    ) -> ((K, V), Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf, C>, marker::Edge>) {
         match self.force() {
             Leaf(node) => node.remove_leaf_kv(handle_emptied_internal_root, alloc),
             Internal(node) => node.remove_internal_kv(handle_emptied_internal_root, alloc),
@@ -22,13 +24,16 @@ pub(super) fn remove_kv_tracking<F: FnOnce(), A: Allocator + Clone>(
     }
 }
 
-impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::KV> {
+impl<'a, K: 'a, V: 'a, C: marker::Counting>
+    Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf, C>, marker::KV>
+{
     fn remove_leaf_kv<F: FnOnce(), A: Allocator + Clone>(
         self,
         handle_emptied_internal_root: F,
         alloc: A,
-    ) -> ((K, V), Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::Edge>) {
+    ) -> ((K, V), Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf, C>, marker::Edge>) {
         let (old_kv, mut pos) = self.remove();
+        unsafe { pos.reborrow_mut() }.into_node().decrement_ancestors_subtree_len();
         let len = pos.reborrow().into_node().len();
         if len < MIN_LEN {
             let idx = pos.idx();
@@ -75,12 +80,14 @@ fn remove_leaf_kv<F: FnOnce(), A: Allocator + Clone>(
     }
 }
 
-impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Internal>, marker::KV> {
+impl<'a, K: 'a, V: 'a, C: marker::Counting>
+    Handle<NodeRef<marker::Mut<'a>, K, V, marker::Internal, C>, marker::KV>
+{
     fn remove_internal_kv<F: FnOnce(), A: Allocator + Clone>(
         self,
         handle_emptied_internal_root: F,
         alloc: A,
-    ) -> ((K, V), Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::Edge>) {
+    ) -> ((K, V), Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf, C>, marker::Edge>) {
         // Remove an adjacent KV from its leaf and then put it back in place of
         // the element we were asked to remove. Prefer the left adjacent KV,
         // for the reasons listed in `choose_parent_kv`.
@@ -29,9 +29,9 @@ pub(super) fn from_range(range_bound: Bound<T>) -> Self {
     }
 }
 
-pub(super) enum SearchResult<BorrowType, K, V, FoundType, GoDownType> {
-    Found(Handle<NodeRef<BorrowType, K, V, FoundType>, marker::KV>),
-    GoDown(Handle<NodeRef<BorrowType, K, V, GoDownType>, marker::Edge>),
+pub(super) enum SearchResult<BorrowType, K, V, FoundType, GoDownType, C> {
+    Found(Handle<NodeRef<BorrowType, K, V, FoundType, C>, marker::KV>),
+    GoDown(Handle<NodeRef<BorrowType, K, V, GoDownType, C>, marker::Edge>),
 }
 
 pub(super) enum IndexResult {
@@ -39,7 +39,7 @@ pub(super) enum IndexResult {
     Edge(usize),
 }
 
-impl<BorrowType: marker::BorrowType, K, V> NodeRef<BorrowType, K, V, marker::LeafOrInternal> {
+impl<BorrowType: marker::BorrowType, K, V, C> NodeRef<BorrowType, K, V, marker::LeafOrInternal, C> {
     /// Looks up a given key in a (sub)tree headed by the node, recursively.
     /// Returns a `Found` with the handle of the matching KV, if any. Otherwise,
     /// returns a `GoDown` with the handle of the leaf edge where the key belongs.
@@ -49,7 +49,7 @@ impl<BorrowType: marker::BorrowType, K, V> NodeRef<BorrowType, K, V, marker::Lea
     pub(super) fn search_tree<Q: ?Sized>(
         mut self,
         key: &Q,
-    ) -> SearchResult<BorrowType, K, V, marker::LeafOrInternal, marker::Leaf>
+    ) -> SearchResult<BorrowType, K, V, marker::LeafOrInternal, marker::Leaf, C>
     where
         Q: Ord,
         K: Borrow<Q>,
@@ -85,13 +85,13 @@ pub(super) fn search_tree_for_bifurcation<'r, Q: ?Sized, R>(
         range: &'r R,
     ) -> Result<
         (
-            NodeRef<BorrowType, K, V, marker::LeafOrInternal>,
+            NodeRef<BorrowType, K, V, marker::LeafOrInternal, C>,
             usize,
             usize,
             SearchBound<&'r Q>,
             SearchBound<&'r Q>,
         ),
-        Handle<NodeRef<BorrowType, K, V, marker::Leaf>, marker::Edge>,
+        Handle<NodeRef<BorrowType, K, V, marker::Leaf, C>, marker::Edge>,
     >
     where
         Q: Ord,
@@ -184,7 +184,7 @@ pub(super) fn find_upper_bound_edge<'r, Q>(
     }
 }
 
-impl<BorrowType, K, V, Type> NodeRef<BorrowType, K, V, Type> {
+impl<BorrowType, K, V, Type, C> NodeRef<BorrowType, K, V, Type, C> {
     /// Looks up a given key in the node, without recursion.
     /// Returns a `Found` with the handle of the matching KV, if any. Otherwise,
     /// returns a `GoDown` with the handle of the edge where the key might be found
@@ -195,7 +195,7 @@ impl<BorrowType, K, V, Type> NodeRef<BorrowType, K, V, Type> {
     pub(super) fn search_node<Q: ?Sized>(
         self,
         key: &Q,
-    ) -> SearchResult<BorrowType, K, V, Type, Type>
+    ) -> SearchResult<BorrowType, K, V, Type, Type, C>
     where
         Q: Ord,
         K: Borrow<Q>,
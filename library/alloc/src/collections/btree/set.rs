@@ -14,9 +14,12 @@
 use crate::vec::Vec;
 
 mod entry;
+mod ranked;
 
 #[unstable(feature = "btree_set_entry", issue = "133549")]
 pub use self::entry::{Entry, OccupiedEntry, VacantEntry};
+#[unstable(feature = "btree_order_statistics", issue = "none")]
+pub use self::ranked::RankedSet;
 
 /// An ordered set based on a B-Tree.
 ///
@@ -0,0 +1,356 @@
+use core::borrow::Borrow;
+use core::fmt::{self, Debug};
+use core::ops::RangeBounds;
+
+use super::super::map::RankedMap;
+use super::super::set_val::SetValZST;
+use super::{Iter, Range};
+use crate::alloc::{Allocator, Global};
+
+/// An ordered set based on a B-Tree, like [`BTreeSet`], that can also tell
+/// the position of its elements in logarithmic time.
+///
+/// See [`RankedMap`]'s documentation for what that costs, compared to
+/// a `BTreeSet`.
+///
+/// [`BTreeSet`]: super::BTreeSet
+///
+/// # Examples
+///
+/// ```
+/// #![feature(btree_order_statistics)]
+/// use std::collections::btree_set::RankedSet;
+///
+/// let set = RankedSet::from([10, 20, 30, 40]);
+/// assert_eq!(set.nth(1), Some(&20));
+/// assert_eq!(set.rank_of(&35), 3);
+/// assert_eq!(set.range_len(15..=30), 2);
+/// ```
+#[unstable(feature = "btree_order_statistics", issue = "none")]
+pub struct RankedSet<
+    T,
+    #[unstable(feature = "allocator_api", issue = "32838")] A: Allocator + Clone = Global,
+> {
+    map: RankedMap<T, SetValZST, A>,
+}
+
+#[unstable(feature = "btree_order_statistics", issue = "none")]
+impl<T: Clone, A: Allocator + Clone> Clone for RankedSet<T, A> {
+    fn clone(&self) -> Self {
+        RankedSet { map: self.map.clone() }
+    }
+}
+
+impl<T> RankedSet<T> {
+    /// Makes a new, empty `RankedSet`.
+    ///
+    /// Does not allocate anything on its own.
+    #[unstable(feature = "btree_order_statistics", issue = "none")]
+    #[must_use]
+    pub const fn new() -> RankedSet<T> {
+        RankedSet { map: RankedMap::new() }
+    }
+}
+
+impl<T, A: Allocator + Clone> RankedSet<T, A> {
+    /// Makes a new, empty `RankedSet` with the given allocator.
+    #[unstable(feature = "allocator_api", issue = "32838")]
+    #[must_use]
+    pub const fn new_in(alloc: A) -> RankedSet<T, A> {
+        RankedSet { map: RankedMap::new_in(alloc) }
+    }
+
+    /// Returns the number of elements in the set.
+    #[unstable(feature = "btree_order_statistics", issue = "none")]
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns `true` if the set contains no elements.
+    #[unstable(feature = "btree_order_statistics", issue = "none")]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Clears the set, removing all elements.
+    #[unstable(feature = "btree_order_statistics", issue = "none")]
+    pub fn clear(&mut self) {
+        self.map.clear()
+    }
+
+    /// Returns `true` if the set contains an element equal to the value.
+    ///
+    /// The value may be any borrowed form of the set's element type,
+    /// but the ordering on the borrowed form *must* match the
+    /// ordering on the element type.
+    #[unstable(feature = "btree_order_statistics", issue = "none")]
+    pub fn contains<Q: ?Sized>(&self, value: &Q) -> bool
+    where
+        T: Borrow<Q> + Ord,
+        Q: Ord,
+    {
+        self.map.contains_key(value)
+    }
+
+    /// Returns a reference to the element in the set, if any, that is equal to
+    /// the value.
+    ///
+    /// The value may be any borrowed form of the set's element type,
+    /// but the ordering on the borrowed form *must* match the
+    /// ordering on the element type.
+    #[unstable(feature = "btree_order_statistics", issue = "none")]
+    pub fn get<Q: ?Sized>(&self, value: &Q) -> Option<&T>
+    where
+        T: Borrow<Q> + Ord,
+        Q: Ord,
+    {
+        self.map.get_key_value(value).map(|(k, _)| k)
+    }
+
+    /// Returns a reference to the first element in the set, if any.
+    /// This element is always the minimum of all elements in the set.
+    #[unstable(feature = "btree_order_statistics", issue = "none")]
+    pub fn first(&self) -> Option<&T> {
+        self.map.first_key_value().map(|(k, _)| k)
+    }
+
+    /// Returns a reference to the last element in the set, if any.
+    /// This element is always the maximum of all elements in the set.
+    #[unstable(feature = "btree_order_statistics", issue = "none")]
+    pub fn last(&self) -> Option<&T> {
+        self.map.last_key_value().map(|(k, _)| k)
+    }
+
+    /// Removes the first element from the set and returns it, if any.
+    /// The first element is always the minimum element in the set.
+    #[unstable(feature = "btree_order_statistics", issue = "none")]
+    pub fn pop_first(&mut self) -> Option<T> {
+        self.map.pop_first().map(|kv| kv.0)
+    }
+
+    /// Removes the last element from the set and returns it, if any.
+    /// The last element is always the maximum element in the set.
+    #[unstable(feature = "btree_order_statistics", issue = "none")]
+    pub fn pop_last(&mut self) -> Option<T> {
+        self.map.pop_last().map(|kv| kv.0)
+    }
+
+    /// Adds a value to the set.
+    ///
+    /// Returns whether the value was newly inserted. That is:
+    ///
+    /// - If the set did not previously contain an equal value, `true` is
+    ///   returned.
+    /// - If the set already contained an equal value, `false` is returned, and
+    ///   the entry is not updated.
+    #[unstable(feature = "btree_order_statistics", issue = "none")]
+    pub fn insert(&mut self, value: T) -> bool
+    where
+        T: Ord,
+    {
+        self.map.insert(value, SetValZST::default()).is_none()
+    }
+
+    /// If the set contains an element equal to the value, removes it from the
+    /// set and drops it. Returns whether such an element was present.
+    ///
+    /// The value may be any borrowed form of the set's element type,
+    /// but the ordering on the borrowed form *must* match the
+    /// ordering on the element type.
+    #[unstable(feature = "btree_order_statistics", issue = "none")]
+    pub fn remove<Q: ?Sized>(&mut self, value: &Q) -> bool
+    where
+        T: Borrow<Q> + Ord,
+        Q: Ord,
+    {
+        self.map.remove(value).is_some()
+    }
+
+    /// Removes and returns the element in the set, if any, that is equal to
+    /// the value.
+    ///
+    /// The value may be any borrowed form of the set's element type,
+    /// but the ordering on the borrowed form *must* match the
+    /// ordering on the element type.
+    #[unstable(feature = "btree_order_statistics", issue = "none")]
+    pub fn take<Q: ?Sized>(&mut self, value: &Q) -> Option<T>
+    where
+        T: Borrow<Q> + Ord,
+        Q: Ord,
+    {
+        self.map.remove_entry(value).map(|(k, _)| k)
+    }
+
+    /// Gets an iterator that visits the elements in the set in ascending order.
+    #[unstable(feature = "btree_order_statistics", issue = "none")]
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { iter: self.map.keys() }
+    }
+
+    /// Constructs a double-ended iterator over a sub-range of elements in the set,
+    /// like [`BTreeSet::range`] does.
+    ///
+    /// [`BTreeSet::range`]: super::BTreeSet::range
+    ///
+    /// # Panics
+    ///
+    /// Panics if range `start > end`.
+    /// Panics if range `start == end` and both bounds are `Excluded`.
+    #[unstable(feature = "btree_order_statistics", issue = "none")]
+    pub fn range<K: ?Sized, R>(&self, range: R) -> Range<'_, T>
+    where
+        K: Ord,
+        T: Borrow<K> + Ord,
+        R: RangeBounds<K>,
+    {
+        Range { iter: self.map.range(range) }
+    }
+
+    /// Returns the element at position `n` in the set, counting from zero in
+    /// ascending order, or `None` if the set has `n` or fewer elements.
+    ///
+    /// Unlike `set.iter().nth(n)`, this takes logarithmic rather than linear time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(btree_order_statistics)]
+    /// use std::collections::btree_set::RankedSet;
+    ///
+    /// let set = RankedSet::from([10, 20, 30]);
+    /// assert_eq!(set.nth(1), Some(&20));
+    /// assert_eq!(set.nth(3), None);
+    /// ```
+    #[unstable(feature = "btree_order_statistics", issue = "none")]
+    pub fn nth(&self, n: usize) -> Option<&T> {
+        self.map.nth(n).map(|(k, _)| k)
+    }
+
+    /// Returns the number of elements in the set that are less than `value`,
+    /// whether or not `value` itself is in the set.
+    ///
+    /// If `value` is in the set, this is its position in ascending order, so
+    /// that `set.nth(set.rank_of(value))` returns it.
+    ///
+    /// The value may be any borrowed form of the set's element type,
+    /// but the ordering on the borrowed form *must* match the
+    /// ordering on the element type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(btree_order_statistics)]
+    /// use std::collections::btree_set::RankedSet;
+    ///
+    /// let set = RankedSet::from([10, 20, 30]);
+    /// assert_eq!(set.rank_of(&20), 1);
+    /// assert_eq!(set.rank_of(&25), 2);
+    /// ```
+    #[unstable(feature = "btree_order_statistics", issue = "none")]
+    pub fn rank_of<Q: ?Sized>(&self, value: &Q) -> usize
+    where
+        T: Borrow<Q> + Ord,
+        Q: Ord,
+    {
+        self.map.rank_of(value)
+    }
+
+    /// Returns the number of elements in a sub-range of the set, accepting the
+    /// same ranges as [`range`]. Unlike `set.range(range).count()`, this takes
+    /// logarithmic rather than linear time.
+    ///
+    /// [`range`]: RankedSet::range
+    ///
+    /// # Panics
+    ///
+    /// Panics if range `start > end`.
+    /// Panics if range `start == end` and both bounds are `Excluded`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(btree_order_statistics)]
+    /// use std::collections::btree_set::RankedSet;
+    ///
+    /// let set: RankedSet<i32> = (0..100).collect();
+    /// assert_eq!(set.range_len(10..20), 10);
+    /// assert_eq!(set.range_len(90..=200), 10);
+    /// ```
+    #[unstable(feature = "btree_order_statistics", issue = "none")]
+    pub fn range_len<K: ?Sized, R>(&self, range: R) -> usize
+    where
+        K: Ord,
+        T: Borrow<K> + Ord,
+        R: RangeBounds<K>,
+    {
+        self.map.range_len(range)
+    }
+}
+
+#[unstable(feature = "btree_order_statistics", issue = "none")]
+impl<'a, T, A: Allocator + Clone> IntoIterator for &'a RankedSet<T, A> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+#[unstable(feature = "btree_order_statistics", issue = "none")]
+impl<T: Ord> FromIterator<T> for RankedSet<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> RankedSet<T> {
+        RankedSet { map: iter.into_iter().map(|k| (k, SetValZST::default())).collect() }
+    }
+}
+
+#[unstable(feature = "btree_order_statistics", issue = "none")]
+impl<T: Ord, const N: usize> From<[T; N]> for RankedSet<T> {
+    /// Converts a `[T; N]` into a `RankedSet<T>`.
+    ///
+    /// If the array contains any equal values,
+    /// all but one will be dropped.
+    fn from(arr: [T; N]) -> Self {
+        RankedSet::from_iter(arr)
+    }
+}
+
+#[unstable(feature = "btree_order_statistics", issue = "none")]
+impl<T: Ord, A: Allocator + Clone> Extend<T> for RankedSet<T, A> {
+    #[inline]
+    fn extend<Iter: IntoIterator<Item = T>>(&mut self, iter: Iter) {
+        iter.into_iter().for_each(move |elem| {
+            self.insert(elem);
+        });
+    }
+}
+
+#[unstable(feature = "btree_order_statistics", issue = "none")]
+impl<T> Default for RankedSet<T> {
+    /// Creates an empty `RankedSet`.
+    fn default() -> RankedSet<T> {
+        RankedSet::new()
+    }
+}
+
+#[unstable(feature = "btree_order_statistics", issue = "none")]
+impl<T: PartialEq, A: Allocator + Clone> PartialEq for RankedSet<T, A> {
+    fn eq(&self, other: &RankedSet<T, A>) -> bool {
+        self.map == other.map
+    }
+}
+
+#[unstable(feature = "btree_order_statistics", issue = "none")]
+impl<T: Eq, A: Allocator + Clone> Eq for RankedSet<T, A> {}
+
+#[unstable(feature = "btree_order_statistics", issue = "none")]
+impl<T: Debug, A: Allocator + Clone> Debug for RankedSet<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests;
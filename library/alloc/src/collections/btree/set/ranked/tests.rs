@@ -0,0 +1,40 @@
+use super::*;
+use crate::vec::Vec;
+
+#[test]
+fn test_order_statistics() {
+    let mut a: RankedSet<i32> = (0..100).map(|i| i * 2).collect();
+    assert_eq!(a.nth(0), Some(&0));
+    assert_eq!(a.nth(42), Some(&84));
+    assert_eq!(a.nth(100), None);
+    assert_eq!(a.rank_of(&84), 42);
+    assert_eq!(a.rank_of(&85), 43);
+    assert_eq!(a.range_len(10..=20), 6);
+    assert_eq!(a.range_len(11..11), 0);
+
+    let multiples_of_3: Vec<i32> = a.iter().copied().filter(|x| x % 3 == 0).collect();
+    for x in multiples_of_3 {
+        assert!(a.remove(&x));
+    }
+    assert_eq!(a.len(), 66);
+    for (i, x) in a.iter().enumerate() {
+        assert_eq!(a.nth(i), Some(x));
+        assert_eq!(a.rank_of(x), i);
+    }
+    assert_eq!(a.range_len(100..), a.range(100..).count());
+}
+
+#[test]
+fn test_basic() {
+    let mut a = RankedSet::new();
+    assert!(a.insert(2));
+    assert!(a.insert(1));
+    assert!(!a.insert(2));
+    assert_eq!(a.first(), Some(&1));
+    assert_eq!(a.last(), Some(&2));
+    assert_eq!(a.clone(), a);
+    assert_eq!(format!("{a:?}"), "{1, 2}");
+    assert_eq!(a.pop_last(), Some(2));
+    assert_eq!(a.take(&1), Some(1));
+    assert!(a.is_empty());
+}
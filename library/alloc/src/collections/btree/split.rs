@@ -2,16 +2,16 @@
 use core::borrow::Borrow;
 
 use super::node::ForceResult::*;
-use super::node::Root;
-use super::search::SearchResult::*;
+use super::node::{Root, marker};
+use super::search::SearchBound;
 
-impl<K, V> Root<K, V> {
+impl<K, V> Root<K, V, marker::Uncounted> {
     /// Calculates the length of both trees that result from splitting up
     /// a given number of distinct key-value pairs.
     pub(super) fn calc_split_length(
         total_num: usize,
-        root_a: &Root<K, V>,
-        root_b: &Root<K, V>,
+        root_a: &Root<K, V, marker::Uncounted>,
+        root_b: &Root<K, V, marker::Uncounted>,
     ) -> (usize, usize) {
         let (length_a, length_b);
         if root_a.height() < root_b.height() {
@@ -36,6 +36,20 @@ pub(super) fn split_off<Q: ?Sized + Ord, A: Allocator + Clone>(
         key: &Q,
         alloc: A,
     ) -> Self
+    where
+        K: Borrow<Q>,
+    {
+        self.split_off_bound(SearchBound::Included(key), alloc)
+    }
+
+    /// Split off a tree with the key-value pairs that the lower bound `bound`
+    /// of a range doesn't exclude, i.e. those that lie in the range ending at
+    /// the root's last key. Same requirements and guarantees as `split_off`.
+    pub(super) fn split_off_bound<Q: ?Sized + Ord, A: Allocator + Clone>(
+        &mut self,
+        mut bound: SearchBound<&Q>,
+        alloc: A,
+    ) -> Self
     where
         K: Borrow<Q>,
     {
@@ -45,11 +59,8 @@ pub(super) fn split_off<Q: ?Sized + Ord, A: Allocator + Clone>(
         let mut right_node = right_root.borrow_mut();
 
         loop {
-            let mut split_edge = match left_node.search_node(key) {
-                // key is going to the right tree
-                Found(kv) => kv.left_edge(),
-                GoDown(edge) => edge,
-            };
+            let (mut split_edge, child_bound) = left_node.find_lower_bound_edge(bound);
+            bound = child_bound;
 
             split_edge.move_suffix(&mut right_node);
 
@@ -49,6 +49,7 @@
 use core::unicode::conversions;
 use core::{mem, ptr};
 
+use crate::alloc::Allocator;
 use crate::borrow::ToOwned;
 use crate::boxed::Box;
 use crate::slice::{Concat, Join, SliceIndex};
@@ -226,7 +227,7 @@ fn case_ignorable_then_cased<I: Iterator<Item = char>>(iter: I) -> bool {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl Borrow<str> for String {
+impl<A: Allocator> Borrow<str> for String<A> {
     #[inline]
     fn borrow(&self) -> &str {
         &self[..]
@@ -234,7 +235,7 @@ fn borrow(&self) -> &str {
 }
 
 #[stable(feature = "string_borrow_mut", since = "1.36.0")]
-impl BorrowMut<str> for String {
+impl<A: Allocator> BorrowMut<str> for String<A> {
     #[inline]
     fn borrow_mut(&mut self) -> &mut str {
         &mut self[..]
@@ -42,6 +42,7 @@
 
 #![stable(feature = "rust1", since = "1.0.0")]
 
+use core::cmp::Ordering;
 use core::error::Error;
 use core::iter::FusedIterator;
 #[cfg(not(no_global_oom_handling))]
@@ -56,15 +57,14 @@
 use core::str::pattern::{Pattern, Utf8Pattern};
 use core::{fmt, hash, ptr, slice};
 
-#[cfg(not(no_global_oom_handling))]
-use crate::alloc::Allocator;
+use crate::alloc::{Allocator, Global};
 #[cfg(not(no_global_oom_handling))]
 use crate::borrow::{Cow, ToOwned};
 use crate::boxed::Box;
 use crate::collections::TryReserveError;
-use crate::str::{self, CharIndices, Chars, Utf8Error, from_utf8_unchecked_mut};
 #[cfg(not(no_global_oom_handling))]
-use crate::str::{FromStr, from_boxed_utf8_unchecked};
+use crate::str::FromStr;
+use crate::str::{self, CharIndices, Chars, Utf8Error, from_utf8_unchecked_mut};
 use crate::vec::{self, Vec};
 
 /// A UTF-8–encoded, growable string.
@@ -347,11 +347,10 @@
 /// [Deref]: core::ops::Deref "ops::Deref"
 /// [`Deref`]: core::ops::Deref "ops::Deref"
 /// [`as_str()`]: String::as_str
-#[derive(PartialEq, PartialOrd, Eq, Ord)]
 #[stable(feature = "rust1", since = "1.0.0")]
 #[lang = "String"]
-pub struct String {
-    vec: Vec<u8>,
+pub struct String<#[unstable(feature = "allocator_api", issue = "32838")] A: Allocator = Global> {
+    vec: Vec<u8, A>,
 }
 
 /// A possible error value when converting a `String` from a UTF-8 byte vector.
@@ -985,6 +984,75 @@ pub fn into_raw_parts(self) -> (*mut u8, usize, usize) {
     pub unsafe fn from_raw_parts(buf: *mut u8, length: usize, capacity: usize) -> String {
         unsafe { String { vec: Vec::from_raw_parts(buf, length, capacity) } }
     }
+}
+
+impl<A: Allocator> String<A> {
+    /// Creates a new empty `String` using the provided allocator.
+    ///
+    /// Like [`String::new`], this does not allocate any initial buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(allocator_api)]
+    ///
+    /// use std::alloc::System;
+    ///
+    /// let mut s = String::new_in(System);
+    /// s.push_str("hello");
+    /// assert_eq!(s, "hello");
+    /// ```
+    #[inline]
+    #[unstable(feature = "allocator_api", issue = "32838")]
+    pub const fn new_in(alloc: A) -> String<A> {
+        String { vec: Vec::new_in(alloc) }
+    }
+
+    /// Creates a new empty `String` with at least the specified capacity,
+    /// using the provided allocator.
+    ///
+    /// See [`String::with_capacity`] for details on the capacity.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(allocator_api)]
+    ///
+    /// use std::alloc::System;
+    ///
+    /// let mut s = String::with_capacity_in(10, System);
+    /// assert!(s.capacity() >= 10);
+    ///
+    /// // This is done without reallocating...
+    /// s.push_str("0123456789");
+    /// assert!(s.capacity() >= 10);
+    /// ```
+    #[cfg(not(no_global_oom_handling))]
+    #[inline]
+    #[unstable(feature = "allocator_api", issue = "32838")]
+    pub fn with_capacity_in(capacity: usize, alloc: A) -> String<A> {
+        String { vec: Vec::with_capacity_in(capacity, alloc) }
+    }
+
+    /// Creates a new empty `String` with at least the specified capacity,
+    /// using the provided allocator.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Err`] if the capacity exceeds `isize::MAX` bytes,
+    /// or if the allocator reports allocation failure.
+    #[inline]
+    #[unstable(feature = "allocator_api", issue = "32838")]
+    pub fn try_with_capacity_in(capacity: usize, alloc: A) -> Result<String<A>, TryReserveError> {
+        Ok(String { vec: Vec::try_with_capacity_in(capacity, alloc)? })
+    }
+
+    /// Returns a reference to the underlying allocator.
+    #[inline]
+    #[unstable(feature = "allocator_api", issue = "32838")]
+    pub fn allocator(&self) -> &A {
+        self.vec.allocator()
+    }
 
     /// Converts a vector of bytes to a `String` without checking that the
     /// string contains valid UTF-8.
@@ -1015,7 +1083,7 @@ pub unsafe fn from_raw_parts(buf: *mut u8, length: usize, capacity: usize) -> St
     #[inline]
     #[must_use]
     #[stable(feature = "rust1", since = "1.0.0")]
-    pub unsafe fn from_utf8_unchecked(bytes: Vec<u8>) -> String {
+    pub unsafe fn from_utf8_unchecked(bytes: Vec<u8, A>) -> String<A> {
         String { vec: bytes }
     }
 
@@ -1036,7 +1104,7 @@ pub unsafe fn from_utf8_unchecked(bytes: Vec<u8>) -> String {
     #[stable(feature = "rust1", since = "1.0.0")]
     #[rustc_const_stable(feature = "const_vec_string_slice", since = "1.87.0")]
     #[rustc_allow_const_fn_unstable(const_precise_live_drops)]
-    pub const fn into_bytes(self) -> Vec<u8> {
+    pub const fn into_bytes(self) -> Vec<u8, A> {
         self.vec
     }
 
@@ -1113,14 +1181,14 @@ fn push_str_slice(&mut self, slice: &[&str]) {
         // use saturating arithmetic to ensure that in the case of an overflow, reserve() throws OOM
         let additional: Saturating<usize> = slice.iter().map(|x| Saturating(x.len())).sum();
         self.reserve(additional.0);
-        let (ptr, len, cap) = core::mem::take(self).into_raw_parts();
+        let len = self.vec.len();
         unsafe {
-            let mut dst = ptr.add(len);
+            let mut dst = self.vec.as_mut_ptr().add(len);
             for new in slice {
                 core::ptr::copy_nonoverlapping(new.as_ptr(), dst, new.len());
                 dst = dst.add(new.len());
             }
-            *self = String::from_raw_parts(ptr, len + additional.0, cap);
+            self.vec.set_len(len + additional.0);
         }
     }
 
@@ -1650,13 +1718,13 @@ pub fn retain<F>(&mut self, mut f: F)
     where
         F: FnMut(char) -> bool,
     {
-        struct SetLenOnDrop<'a> {
-            s: &'a mut String,
+        struct SetLenOnDrop<'a, A: Allocator> {
+            s: &'a mut String<A>,
             idx: usize,
             del_bytes: usize,
         }
 
-        impl<'a> Drop for SetLenOnDrop<'a> {
+        impl<'a, A: Allocator> Drop for SetLenOnDrop<'a, A> {
             fn drop(&mut self) {
                 let new_len = self.idx - self.del_bytes;
                 debug_assert!(new_len <= self.s.len());
@@ -1838,7 +1906,7 @@ pub fn insert_str(&mut self, idx: usize, string: &str) {
     #[inline]
     #[stable(feature = "rust1", since = "1.0.0")]
     #[rustc_const_stable(feature = "const_vec_string_slice", since = "1.87.0")]
-    pub const unsafe fn as_mut_vec(&mut self) -> &mut Vec<u8> {
+    pub const unsafe fn as_mut_vec(&mut self) -> &mut Vec<u8, A> {
         &mut self.vec
     }
 
@@ -1914,7 +1982,10 @@ pub const fn is_empty(&self) -> bool {
     #[track_caller]
     #[stable(feature = "string_split_off", since = "1.16.0")]
     #[must_use = "use `.truncate()` if you don't need the other half"]
-    pub fn split_off(&mut self, at: usize) -> String {
+    pub fn split_off(&mut self, at: usize) -> String<A>
+    where
+        A: Clone,
+    {
         assert!(self.is_char_boundary(at));
         let other = self.vec.split_off(at);
         unsafe { String::from_utf8_unchecked(other) }
@@ -1977,7 +2048,7 @@ pub fn clear(&mut self) {
     /// ```
     #[stable(feature = "drain", since = "1.6.0")]
     #[track_caller]
-    pub fn drain<R>(&mut self, range: R) -> Drain<'_>
+    pub fn drain<R>(&mut self, range: R) -> Drain<'_, A>
     where
         R: RangeBounds<usize>,
     {
@@ -1999,7 +2070,9 @@ pub fn drain<R>(&mut self, range: R) -> Drain<'_>
 
         Drain { start, end, iter: chars_iter, string: self_ptr }
     }
+}
 
+impl String {
     /// Converts a `String` into an iterator over the [`char`]s of the string.
     ///
     /// As a string consists of valid UTF-8, we can iterate through a string
@@ -2054,7 +2127,9 @@ pub fn drain<R>(&mut self, range: R) -> Drain<'_>
     pub fn into_chars(self) -> IntoChars {
         IntoChars { bytes: self.into_bytes().into_iter() }
     }
+}
 
+impl<A: Allocator> String<A> {
     /// Removes the specified range in the string,
     /// and replaces it with the given string.
     /// The given string doesn't need to be the same length as the range.
@@ -2176,9 +2251,10 @@ pub fn replace_last<P: Pattern>(&mut self, from: P, to: &str)
     #[stable(feature = "box_str", since = "1.4.0")]
     #[must_use = "`self` will be dropped if the result is not used"]
     #[inline]
-    pub fn into_boxed_str(self) -> Box<str> {
+    pub fn into_boxed_str(self) -> Box<str, A> {
         let slice = self.vec.into_boxed_slice();
-        unsafe { from_boxed_utf8_unchecked(slice) }
+        let (raw, alloc) = Box::into_raw_with_allocator(slice);
+        unsafe { Box::from_raw_in(raw as *mut str, alloc) }
     }
 
     /// Consumes and leaks the `String`, returning a mutable reference to the contents,
@@ -2207,7 +2283,10 @@ pub fn into_boxed_str(self) -> Box<str> {
     /// ```
     #[stable(feature = "string_leak", since = "1.72.0")]
     #[inline]
-    pub fn leak<'a>(self) -> &'a mut str {
+    pub fn leak<'a>(self) -> &'a mut str
+    where
+        A: 'a,
+    {
         let slice = self.vec.leak();
         unsafe { from_utf8_unchecked_mut(slice) }
     }
@@ -2374,7 +2453,7 @@ impl Error for FromUtf16Error {}
 
 #[cfg(not(no_global_oom_handling))]
 #[stable(feature = "rust1", since = "1.0.0")]
-impl Clone for String {
+impl<A: Allocator + Clone> Clone for String<A> {
     fn clone(&self) -> Self {
         String { vec: self.vec.clone() }
     }
@@ -2491,7 +2570,7 @@ fn from_iter<T: IntoIterator<Item = &'a core::ascii::Char>>(iter: T) -> Self {
 
 #[cfg(not(no_global_oom_handling))]
 #[stable(feature = "rust1", since = "1.0.0")]
-impl Extend<char> for String {
+impl<A: Allocator> Extend<char> for String<A> {
     fn extend<I: IntoIterator<Item = char>>(&mut self, iter: I) {
         let iterator = iter.into_iter();
         let (lower_bound, _) = iterator.size_hint();
@@ -2512,7 +2591,7 @@ fn extend_reserve(&mut self, additional: usize) {
 
 #[cfg(not(no_global_oom_handling))]
 #[stable(feature = "extend_ref", since = "1.2.0")]
-impl<'a> Extend<&'a char> for String {
+impl<'a, A: Allocator> Extend<&'a char> for String<A> {
     fn extend<I: IntoIterator<Item = &'a char>>(&mut self, iter: I) {
         self.extend(iter.into_iter().cloned());
     }
@@ -2530,7 +2609,7 @@ fn extend_reserve(&mut self, additional: usize) {
 
 #[cfg(not(no_global_oom_handling))]
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<'a> Extend<&'a str> for String {
+impl<'a, A: Allocator> Extend<&'a str> for String<A> {
     fn extend<I: IntoIterator<Item = &'a str>>(&mut self, iter: I) {
         <I as SpecExtendStr>::spec_extend_into(iter, self)
     }
@@ -2543,41 +2622,41 @@ fn extend_one(&mut self, s: &'a str) {
 
 #[cfg(not(no_global_oom_handling))]
 trait SpecExtendStr {
-    fn spec_extend_into(self, s: &mut String);
+    fn spec_extend_into<A: Allocator>(self, s: &mut String<A>);
 }
 
 #[cfg(not(no_global_oom_handling))]
 impl<'a, T: IntoIterator<Item = &'a str>> SpecExtendStr for T {
-    default fn spec_extend_into(self, target: &mut String) {
+    default fn spec_extend_into<A: Allocator>(self, target: &mut String<A>) {
         self.into_iter().for_each(move |s| target.push_str(s));
     }
 }
 
 #[cfg(not(no_global_oom_handling))]
 impl SpecExtendStr for [&str] {
-    fn spec_extend_into(self, target: &mut String) {
+    fn spec_extend_into<A: Allocator>(self, target: &mut String<A>) {
         target.push_str_slice(&self);
     }
 }
 
 #[cfg(not(no_global_oom_handling))]
 impl<const N: usize> SpecExtendStr for [&str; N] {
-    fn spec_extend_into(self, target: &mut String) {
+    fn spec_extend_into<A: Allocator>(self, target: &mut String<A>) {
         target.push_str_slice(&self[..]);
     }
 }
 
 #[cfg(not(no_global_oom_handling))]
 #[stable(feature = "box_str2", since = "1.45.0")]
-impl<A: Allocator> Extend<Box<str, A>> for String {
-    fn extend<I: IntoIterator<Item = Box<str, A>>>(&mut self, iter: I) {
+impl<A: Allocator, B: Allocator> Extend<Box<str, B>> for String<A> {
+    fn extend<I: IntoIterator<Item = Box<str, B>>>(&mut self, iter: I) {
         iter.into_iter().for_each(move |s| self.push_str(&s));
     }
 }
 
 #[cfg(not(no_global_oom_handling))]
 #[stable(feature = "extend_string", since = "1.4.0")]
-impl Extend<String> for String {
+impl<A: Allocator> Extend<String> for String<A> {
     fn extend<I: IntoIterator<Item = String>>(&mut self, iter: I) {
         iter.into_iter().for_each(move |s| self.push_str(&s));
     }
@@ -2590,7 +2669,7 @@ fn extend_one(&mut self, s: String) {
 
 #[cfg(not(no_global_oom_handling))]
 #[stable(feature = "herd_cows", since = "1.19.0")]
-impl<'a> Extend<Cow<'a, str>> for String {
+impl<'a, A: Allocator> Extend<Cow<'a, str>> for String<A> {
     fn extend<I: IntoIterator<Item = Cow<'a, str>>>(&mut self, iter: I) {
         iter.into_iter().for_each(move |s| self.push_str(&s));
     }
@@ -2603,7 +2682,7 @@ fn extend_one(&mut self, s: Cow<'a, str>) {
 
 #[cfg(not(no_global_oom_handling))]
 #[unstable(feature = "ascii_char", issue = "110998")]
-impl Extend<core::ascii::Char> for String {
+impl<A: Allocator> Extend<core::ascii::Char> for String<A> {
     #[inline]
     fn extend<I: IntoIterator<Item = core::ascii::Char>>(&mut self, iter: I) {
         self.vec.extend(iter.into_iter().map(|c| c.to_u8()));
@@ -2617,7 +2696,7 @@ fn extend_one(&mut self, c: core::ascii::Char) {
 
 #[cfg(not(no_global_oom_handling))]
 #[unstable(feature = "ascii_char", issue = "110998")]
-impl<'a> Extend<&'a core::ascii::Char> for String {
+impl<'a, A: Allocator> Extend<&'a core::ascii::Char> for String<A> {
     #[inline]
     fn extend<I: IntoIterator<Item = &'a core::ascii::Char>>(&mut self, iter: I) {
         self.extend(iter.into_iter().cloned());
@@ -2641,7 +2720,7 @@ fn extend_one(&mut self, c: &'a core::ascii::Char) {
     reason = "API not fully fleshed out and ready to be stabilized",
     issue = "27721"
 )]
-impl<'b> Pattern for &'b String {
+impl<'b, A: Allocator> Pattern for &'b String<A> {
     type Searcher<'a> = <&'b str as Pattern>::Searcher<'a>;
 
     fn into_searcher(self, haystack: &str) -> <&'b str as Pattern>::Searcher<'_> {
@@ -2686,9 +2765,9 @@ fn as_utf8_pattern(&self) -> Option<Utf8Pattern<'_>> {
 }
 
 macro_rules! impl_eq {
-    ($lhs:ty, $rhs: ty) => {
+    ([$($vars:tt)*] $lhs:ty, $rhs: ty) => {
         #[stable(feature = "rust1", since = "1.0.0")]
-        impl PartialEq<$rhs> for $lhs {
+        impl<$($vars)*> PartialEq<$rhs> for $lhs {
             #[inline]
             fn eq(&self, other: &$rhs) -> bool {
                 PartialEq::eq(&self[..], &other[..])
@@ -2700,7 +2779,7 @@ fn ne(&self, other: &$rhs) -> bool {
         }
 
         #[stable(feature = "rust1", since = "1.0.0")]
-        impl PartialEq<$lhs> for $rhs {
+        impl<$($vars)*> PartialEq<$lhs> for $rhs {
             #[inline]
             fn eq(&self, other: &$lhs) -> bool {
                 PartialEq::eq(&self[..], &other[..])
@@ -2711,10 +2790,13 @@ fn ne(&self, other: &$lhs) -> bool {
             }
         }
     };
+    ($lhs:ty, $rhs: ty) => {
+        impl_eq! { [] $lhs, $rhs }
+    };
 }
 
-impl_eq! { String, str }
-impl_eq! { String, &str }
+impl_eq! { [A: Allocator] String<A>, str }
+impl_eq! { [A: Allocator] String<A>, &str }
 #[cfg(not(no_global_oom_handling))]
 impl_eq! { Cow<'_, str>, str }
 #[cfg(not(no_global_oom_handling))]
@@ -2733,7 +2815,7 @@ fn default() -> String {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl fmt::Display for String {
+impl<A: Allocator> fmt::Display for String<A> {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         fmt::Display::fmt(&**self, f)
@@ -2741,7 +2823,7 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl fmt::Debug for String {
+impl<A: Allocator> fmt::Debug for String<A> {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         fmt::Debug::fmt(&**self, f)
@@ -2749,7 +2831,34 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl hash::Hash for String {
+impl<A: Allocator> PartialEq for String<A> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.vec == other.vec
+    }
+}
+
+#[stable(feature = "rust1", since = "1.0.0")]
+impl<A: Allocator> Eq for String<A> {}
+
+#[stable(feature = "rust1", since = "1.0.0")]
+impl<A: Allocator> PartialOrd for String<A> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.vec.partial_cmp(&other.vec)
+    }
+}
+
+#[stable(feature = "rust1", since = "1.0.0")]
+impl<A: Allocator> Ord for String<A> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.vec.cmp(&other.vec)
+    }
+}
+
+#[stable(feature = "rust1", since = "1.0.0")]
+impl<A: Allocator> hash::Hash for String<A> {
     #[inline]
     fn hash<H: hash::Hasher>(&self, hasher: &mut H) {
         (**self).hash(hasher)
@@ -2795,11 +2904,11 @@ fn hash<H: hash::Hasher>(&self, hasher: &mut H) {
 /// ```
 #[cfg(not(no_global_oom_handling))]
 #[stable(feature = "rust1", since = "1.0.0")]
-impl Add<&str> for String {
-    type Output = String;
+impl<A: Allocator> Add<&str> for String<A> {
+    type Output = String<A>;
 
     #[inline]
-    fn add(mut self, other: &str) -> String {
+    fn add(mut self, other: &str) -> String<A> {
         self.push_str(other);
         self
     }
@@ -2810,7 +2919,7 @@ fn add(mut self, other: &str) -> String {
 /// This has the same behavior as the [`push_str`][String::push_str] method.
 #[cfg(not(no_global_oom_handling))]
 #[stable(feature = "stringaddassign", since = "1.12.0")]
-impl AddAssign<&str> for String {
+impl<A: Allocator> AddAssign<&str> for String<A> {
     #[inline]
     fn add_assign(&mut self, other: &str) {
         self.push_str(other);
@@ -2818,7 +2927,7 @@ fn add_assign(&mut self, other: &str) {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<I> ops::Index<I> for String
+impl<I, A: Allocator> ops::Index<I> for String<A>
 where
     I: slice::SliceIndex<str>,
 {
@@ -2831,7 +2940,7 @@ fn index(&self, index: I) -> &I::Output {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<I> ops::IndexMut<I> for String
+impl<I, A: Allocator> ops::IndexMut<I> for String<A>
 where
     I: slice::SliceIndex<str>,
 {
@@ -2842,7 +2951,7 @@ fn index_mut(&mut self, index: I) -> &mut I::Output {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl ops::Deref for String {
+impl<A: Allocator> ops::Deref for String<A> {
     type Target = str;
 
     #[inline]
@@ -2852,10 +2961,10 @@ fn deref(&self) -> &str {
 }
 
 #[unstable(feature = "deref_pure_trait", issue = "87121")]
-unsafe impl ops::DerefPure for String {}
+unsafe impl<A: Allocator> ops::DerefPure for String<A> {}
 
 #[stable(feature = "derefmut_for_string", since = "1.3.0")]
-impl ops::DerefMut for String {
+impl<A: Allocator> ops::DerefMut for String<A> {
     #[inline]
     fn deref_mut(&mut self) -> &mut str {
         self.as_mut_str()
@@ -3107,7 +3216,7 @@ fn spec_to_string(&self) -> String {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl AsRef<str> for String {
+impl<A: Allocator> AsRef<str> for String<A> {
     #[inline]
     fn as_ref(&self) -> &str {
         self
@@ -3115,7 +3224,7 @@ fn as_ref(&self) -> &str {
 }
 
 #[stable(feature = "string_as_mut", since = "1.43.0")]
-impl AsMut<str> for String {
+impl<A: Allocator> AsMut<str> for String<A> {
     #[inline]
     fn as_mut(&mut self) -> &mut str {
         self
@@ -3123,7 +3232,7 @@ fn as_mut(&mut self) -> &mut str {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl AsRef<[u8]> for String {
+impl<A: Allocator> AsRef<[u8]> for String<A> {
     #[inline]
     fn as_ref(&self) -> &[u8] {
         self.as_bytes()
@@ -3188,7 +3297,7 @@ fn from(s: Box<str>) -> String {
 
 #[cfg(not(no_global_oom_handling))]
 #[stable(feature = "box_from_str", since = "1.20.0")]
-impl From<String> for Box<str> {
+impl<A: Allocator> From<String<A>> for Box<str, A> {
     /// Converts the given [`String`] to a boxed `str` slice that is owned.
     ///
     /// # Examples
@@ -3200,7 +3309,7 @@ impl From<String> for Box<str> {
     ///
     /// assert_eq!("hello world", s3)
     /// ```
-    fn from(s: String) -> Box<str> {
+    fn from(s: String<A>) -> Box<str, A> {
         s.into_boxed_str()
     }
 }
@@ -3328,7 +3437,7 @@ fn from_iter<T: IntoIterator<Item = core::ascii::Char>>(it: T) -> Self {
 }
 
 #[stable(feature = "from_string_for_vec_u8", since = "1.14.0")]
-impl From<String> for Vec<u8> {
+impl<A: Allocator> From<String<A>> for Vec<u8, A> {
     /// Converts the given [`String`] to a vector [`Vec`] that holds values of type [`u8`].
     ///
     /// # Examples
@@ -3341,7 +3450,7 @@ impl From<String> for Vec<u8> {
     ///     println!("{b}");
     /// }
     /// ```
-    fn from(string: String) -> Vec<u8> {
+    fn from(string: String<A>) -> Vec<u8, A> {
         string.into_bytes()
     }
 }
@@ -3366,7 +3475,7 @@ fn try_from(bytes: Vec<u8>) -> Result<Self, Self::Error> {
 
 #[cfg(not(no_global_oom_handling))]
 #[stable(feature = "rust1", since = "1.0.0")]
-impl fmt::Write for String {
+impl<A: Allocator> fmt::Write for String<A> {
     #[inline]
     fn write_str(&mut self, s: &str) -> fmt::Result {
         self.push_str(s);
@@ -3515,9 +3624,10 @@ impl FusedIterator for IntoChars {}
 ///
 /// [`drain`]: String::drain
 #[stable(feature = "drain", since = "1.6.0")]
-pub struct Drain<'a> {
+pub struct Drain<'a, #[unstable(feature = "allocator_api", issue = "32838")] A: Allocator = Global>
+{
     /// Will be used as &'a mut String in the destructor
-    string: *mut String,
+    string: *mut String<A>,
     /// Start of part to remove
     start: usize,
     /// End of part to remove
@@ -3527,19 +3637,19 @@ pub struct Drain<'a> {
 }
 
 #[stable(feature = "collection_debug", since = "1.17.0")]
-impl fmt::Debug for Drain<'_> {
+impl<A: Allocator> fmt::Debug for Drain<'_, A> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_tuple("Drain").field(&self.as_str()).finish()
     }
 }
 
 #[stable(feature = "drain", since = "1.6.0")]
-unsafe impl Sync for Drain<'_> {}
+unsafe impl<A: Allocator + Sync> Sync for Drain<'_, A> {}
 #[stable(feature = "drain", since = "1.6.0")]
-unsafe impl Send for Drain<'_> {}
+unsafe impl<A: Allocator + Send> Send for Drain<'_, A> {}
 
 #[stable(feature = "drain", since = "1.6.0")]
-impl Drop for Drain<'_> {
+impl<A: Allocator> Drop for Drain<'_, A> {
     fn drop(&mut self) {
         unsafe {
             // Use Vec::drain. "Reaffirm" the bounds checks to avoid
@@ -3552,7 +3662,7 @@ fn drop(&mut self) {
     }
 }
 
-impl<'a> Drain<'a> {
+impl<'a, A: Allocator> Drain<'a, A> {
     /// Returns the remaining (sub)string of this iterator as a slice.
     ///
     /// # Examples
@@ -3572,21 +3682,21 @@ pub fn as_str(&self) -> &str {
 }
 
 #[stable(feature = "string_drain_as_str", since = "1.55.0")]
-impl<'a> AsRef<str> for Drain<'a> {
+impl<'a, A: Allocator> AsRef<str> for Drain<'a, A> {
     fn as_ref(&self) -> &str {
         self.as_str()
     }
 }
 
 #[stable(feature = "string_drain_as_str", since = "1.55.0")]
-impl<'a> AsRef<[u8]> for Drain<'a> {
+impl<'a, A: Allocator> AsRef<[u8]> for Drain<'a, A> {
     fn as_ref(&self) -> &[u8] {
         self.as_str().as_bytes()
     }
 }
 
 #[stable(feature = "drain", since = "1.6.0")]
-impl Iterator for Drain<'_> {
+impl<A: Allocator> Iterator for Drain<'_, A> {
     type Item = char;
 
     #[inline]
@@ -3605,7 +3715,7 @@ fn last(mut self) -> Option<char> {
 }
 
 #[stable(feature = "drain", since = "1.6.0")]
-impl DoubleEndedIterator for Drain<'_> {
+impl<A: Allocator> DoubleEndedIterator for Drain<'_, A> {
     #[inline]
     fn next_back(&mut self) -> Option<char> {
         self.iter.next_back()
@@ -3613,7 +3723,7 @@ fn next_back(&mut self) -> Option<char> {
 }
 
 #[stable(feature = "fused", since = "1.26.0")]
-impl FusedIterator for Drain<'_> {}
+impl<A: Allocator> FusedIterator for Drain<'_, A> {}
 
 #[cfg(not(no_global_oom_handling))]
 #[stable(feature = "from_char_for_string", since = "1.46.0")]
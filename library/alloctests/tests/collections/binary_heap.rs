@@ -590,3 +590,134 @@ fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
         }
     }
 }
+
+#[test]
+fn test_indexed_push_pop() {
+    let mut heap = IndexedBinaryHeap::new();
+    let handles: Vec<_> = [5, 9, 3, 7, 1].into_iter().map(|x| heap.push(x)).collect();
+    assert_eq!(heap.len(), 5);
+    assert_eq!(heap.peek(), Some(&9));
+    for (&h, x) in handles.iter().zip([5, 9, 3, 7, 1]) {
+        assert_eq!(heap.get(h), Some(&x));
+    }
+
+    let mut popped = Vec::new();
+    while let Some(x) = heap.pop() {
+        popped.push(x);
+    }
+    assert_eq!(popped, [9, 7, 5, 3, 1]);
+    assert!(handles.iter().all(|&h| !heap.contains(h)));
+}
+
+#[test]
+fn test_indexed_stale_handles() {
+    let mut heap = IndexedBinaryHeap::new();
+    let a = heap.push(1);
+    assert_eq!(heap.remove(a), Some(1));
+
+    // The new element takes over the vacant slot, but not the old handle.
+    let b = heap.push(2);
+    assert_ne!(a, b);
+    assert_eq!(heap.get(a), None);
+    assert!(heap.change_priority(a).is_none());
+    assert_eq!(heap.remove(a), None);
+    assert_eq!(heap.get(b), Some(&2));
+
+    heap.clear();
+    assert!(!heap.contains(b));
+    let c = heap.push(3);
+    assert_eq!(heap.get(b), None);
+    assert_eq!(heap.get(c), Some(&3));
+}
+
+#[test]
+fn test_indexed_random_ops() {
+    use rand::Rng;
+
+    let mut rng = crate::test_rng();
+    let mut heap = IndexedBinaryHeap::new();
+    // The elements that should be in the heap, along with their handles.
+    let mut model: Vec<(Handle, u32)> = Vec::new();
+    let steps = if cfg!(miri) { 200 } else { 5000 };
+
+    for _ in 0..steps {
+        match rng.random_range(0..5) {
+            0 | 1 => {
+                let x = rng.random_range(0..100);
+                model.push((heap.push(x), x));
+            }
+            2 if !model.is_empty() => {
+                let i = rng.random_range(0..model.len());
+                let x = rng.random_range(0..100);
+                *heap.change_priority(model[i].0).unwrap() = x;
+                model[i].1 = x;
+            }
+            3 if !model.is_empty() => {
+                let (h, x) = model.swap_remove(rng.random_range(0..model.len()));
+                assert_eq!(heap.remove(h), Some(x));
+            }
+            _ => {
+                let max = model.iter().map(|&(_, x)| x).max();
+                assert_eq!(heap.pop(), max);
+                if let Some(max) = max {
+                    let i = model.iter().position(|&(h, x)| x == max && !heap.contains(h));
+                    model.swap_remove(i.unwrap());
+                }
+            }
+        }
+        assert_eq!(heap.len(), model.len());
+        assert_eq!(heap.peek().copied(), model.iter().map(|&(_, x)| x).max());
+        for &(h, x) in &model {
+            assert_eq!(heap.get(h), Some(&x));
+        }
+    }
+}
+
+#[test]
+#[cfg_attr(not(panic = "unwind"), ignore = "test requires unwinding support")]
+fn test_indexed_panic_safe() {
+    use std::cmp;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    static PANIC: AtomicBool = AtomicBool::new(false);
+
+    #[derive(Eq, PartialEq, Debug)]
+    struct PanicOrd(u32);
+
+    impl PartialOrd for PanicOrd {
+        fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for PanicOrd {
+        fn cmp(&self, other: &Self) -> cmp::Ordering {
+            if PANIC.load(Ordering::SeqCst) {
+                panic!("Panicking comparison");
+            }
+            self.0.cmp(&other.0)
+        }
+    }
+
+    let mut heap = IndexedBinaryHeap::new();
+    let handles: Vec<_> = (1..=32).map(|x| heap.push(PanicOrd(x))).collect();
+
+    // Raise the smallest element, which has to move up through the heap,
+    // and make the first comparison of that move panic.
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        let mut smallest = heap.change_priority(handles[0]).unwrap();
+        smallest.0 = 100;
+        PANIC.store(true, Ordering::SeqCst);
+    }));
+    PANIC.store(false, Ordering::SeqCst);
+    assert!(result.is_err());
+
+    // Every handle still leads to its own element.
+    assert_eq!(heap.len(), 32);
+    assert_eq!(heap.get(handles[0]), Some(&PanicOrd(100)));
+    for (&h, x) in handles.iter().zip(1..).skip(1) {
+        assert_eq!(heap.get(h), Some(&PanicOrd(x)));
+    }
+    assert_eq!(heap.remove(handles[0]), Some(PanicOrd(100)));
+    assert_eq!(heap.pop(), Some(PanicOrd(32)));
+}
@@ -1,3 +1,4 @@
+use std::alloc::System;
 use std::borrow::Cow;
 use std::cell::Cell;
 use std::collections::TryReserveErrorKind::*;
@@ -956,3 +957,47 @@ fn test_str_concat() {
     let s: String = format!("{a}{b}");
     assert_eq!(s.as_bytes()[9], 'd' as u8);
 }
+
+#[test]
+fn test_new_in() {
+    let mut s = String::new_in(System);
+    assert_eq!(s.capacity(), 0);
+    s.push_str("hello");
+    s.push(' ');
+    s += "world";
+    assert_eq!(s, "hello world");
+    assert_eq!("hello world", s);
+    assert_eq!(format!("{s:?}"), "\"hello world\"");
+}
+
+#[test]
+fn test_with_capacity_in() {
+    let mut s = String::with_capacity_in(10, System);
+    assert!(s.capacity() >= 10);
+    s.extend(['a', 'b', 'c']);
+    s.extend(["de", "f"]);
+    assert_eq!(s, "abcdef");
+
+    let t = String::try_with_capacity_in(4, System).unwrap();
+    assert!(t.capacity() >= 4);
+    assert_matches!(
+        String::try_with_capacity_in(usize::MAX, System).map_err(|e| e.kind()),
+        Err(CapacityOverflow)
+    );
+}
+
+#[test]
+fn test_allocator_conversions() {
+    let mut s = String::new_in(System);
+    s.push_str("αβγ");
+    assert_eq!(s.drain(2..4).collect::<String>(), "β");
+    let t = s.split_off(2);
+    assert_eq!((s.as_str(), t.as_str()), ("α", "γ"));
+    assert_eq!(s.clone(), s);
+    assert!(s < t);
+
+    let bytes: Vec<u8, System> = t.into_bytes();
+    let t = unsafe { String::from_utf8_unchecked(bytes) };
+    let b: Box<str, System> = t.into_boxed_str();
+    assert_eq!(&*b, "γ");
+}
@@ -0,0 +1,1062 @@
+use super::display_buffer::DisplayBuffer;
+use crate::error::Error;
+use crate::fmt::{self, Write};
+use crate::iter::FusedIterator;
+use crate::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use crate::ops::RangeInclusive;
+
+/// An IP network, either IPv4 or IPv6.
+///
+/// This enum can contain either an [`Ipv4Net`] or an [`Ipv6Net`], see their respective
+/// documentation for more details.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ip_net)]
+///
+/// use std::net::{IpAddr, IpNet, Ipv4Addr, Ipv4Net, Ipv6Addr};
+///
+/// let net: IpNet = "10.0.0.0/8".parse().unwrap();
+///
+/// assert_eq!(net, IpNet::V4(Ipv4Net::new(Ipv4Addr::new(10, 0, 0, 0), 8).unwrap()));
+/// assert!(net.contains(IpAddr::V4(Ipv4Addr::new(10, 1, 2, 3))));
+/// assert!(!net.contains(IpAddr::V6(Ipv6Addr::LOCALHOST)));
+/// ```
+#[unstable(feature = "ip_net", issue = "none")]
+#[derive(Copy, Clone, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub enum IpNet {
+    /// An IPv4 network.
+    #[unstable(feature = "ip_net", issue = "none")]
+    V4(Ipv4Net),
+    /// An IPv6 network.
+    #[unstable(feature = "ip_net", issue = "none")]
+    V6(Ipv6Net),
+}
+
+/// An IPv4 network, made of an [`Ipv4Addr`] and a prefix length, as in `192.168.0.0/16`.
+///
+/// The prefix length is the number of leading bits of the address that identify the network;
+/// the remaining bits identify a host inside of it. The address does not have to be the network
+/// address: `192.168.1.7/16` is a valid `Ipv4Net`, holding both an address and the network it is
+/// part of. Use [`trunc`] to clear the host bits.
+///
+/// See [`IpNet`] for a type encompassing both IPv4 and IPv6 networks.
+///
+/// [`trunc`]: Ipv4Net::trunc
+///
+/// # Textual representation
+///
+/// `Ipv4Net` provides a [`FromStr`] implementation. The address is parsed as an [`Ipv4Addr`],
+/// followed by a `/` and the prefix length in decimal notation, without leading zeros. The
+/// prefix length must not be more than 32. This is also the format used by [`Display`].
+///
+/// [`FromStr`]: crate::str::FromStr
+/// [`Display`]: fmt::Display
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ip_net)]
+///
+/// use std::net::{Ipv4Addr, Ipv4Net};
+///
+/// let net: Ipv4Net = "192.168.1.7/16".parse().unwrap();
+///
+/// assert_eq!(net.addr(), Ipv4Addr::new(192, 168, 1, 7));
+/// assert_eq!(net.prefix_len(), 16);
+/// assert_eq!(net.network(), Ipv4Addr::new(192, 168, 0, 0));
+/// assert_eq!(net.to_string(), "192.168.1.7/16");
+/// ```
+#[unstable(feature = "ip_net", issue = "none")]
+#[derive(Copy, Clone, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub struct Ipv4Net {
+    addr: Ipv4Addr,
+    prefix_len: u8,
+}
+
+/// An IPv6 network, made of an [`Ipv6Addr`] and a prefix length, as in `2001:db8::/32`.
+///
+/// The prefix length is the number of leading bits of the address that identify the network;
+/// the remaining bits identify a host inside of it. The address does not have to be the network
+/// address: `2001:db8::1/32` is a valid `Ipv6Net`, holding both an address and the network it is
+/// part of. Use [`trunc`] to clear the host bits.
+///
+/// See [`IpNet`] for a type encompassing both IPv4 and IPv6 networks.
+///
+/// [`trunc`]: Ipv6Net::trunc
+///
+/// # Textual representation
+///
+/// `Ipv6Net` provides a [`FromStr`] implementation. The address is parsed as an [`Ipv6Addr`],
+/// followed by a `/` and the prefix length in decimal notation, without leading zeros. The
+/// prefix length must not be more than 128. This is also the format used by [`Display`].
+///
+/// [`FromStr`]: crate::str::FromStr
+/// [`Display`]: fmt::Display
+///
+/// # Examples
+///
+/// ```
+/// #![feature(ip_net)]
+///
+/// use std::net::{Ipv6Addr, Ipv6Net};
+///
+/// let net: Ipv6Net = "2001:db8::1/32".parse().unwrap();
+///
+/// assert_eq!(net.addr(), Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1));
+/// assert_eq!(net.prefix_len(), 32);
+/// assert_eq!(net.network(), Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0));
+/// assert_eq!(net.to_string(), "2001:db8::1/32");
+/// ```
+#[unstable(feature = "ip_net", issue = "none")]
+#[derive(Copy, Clone, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub struct Ipv6Net {
+    addr: Ipv6Addr,
+    prefix_len: u8,
+}
+
+/// An error returned when a prefix length is out of range for an IP network.
+///
+/// This error is returned by [`Ipv4Net::new`], [`Ipv6Net::new`], [`IpNet::new`] and the
+/// `subnets` methods.
+#[unstable(feature = "ip_net", issue = "none")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrefixLenError(());
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl fmt::Display for PrefixLenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        "invalid IP prefix length".fmt(f)
+    }
+}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl Error for PrefixLenError {}
+
+impl IpNet {
+    /// Creates a new IP network from an address and a prefix length.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PrefixLenError`] if `prefix_len` is more than 32 for an IPv4 address, or more
+    /// than 128 for an IPv6 address.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    ///
+    /// use std::net::{IpAddr, IpNet, Ipv4Addr};
+    ///
+    /// let addr = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 0));
+    /// assert!(IpNet::new(addr, 8).is_ok());
+    /// assert!(IpNet::new(addr, 33).is_err());
+    /// ```
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[inline]
+    pub const fn new(addr: IpAddr, prefix_len: u8) -> Result<IpNet, PrefixLenError> {
+        match addr {
+            IpAddr::V4(a) => match Ipv4Net::new(a, prefix_len) {
+                Ok(net) => Ok(IpNet::V4(net)),
+                Err(err) => Err(err),
+            },
+            IpAddr::V6(a) => match Ipv6Net::new(a, prefix_len) {
+                Ok(net) => Ok(IpNet::V6(net)),
+                Err(err) => Err(err),
+            },
+        }
+    }
+
+    /// Returns the address of this network, as given when creating it.
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn addr(&self) -> IpAddr {
+        match self {
+            IpNet::V4(net) => IpAddr::V4(net.addr()),
+            IpNet::V6(net) => IpAddr::V6(net.addr()),
+        }
+    }
+
+    /// Returns the prefix length of this network.
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn prefix_len(&self) -> u8 {
+        match self {
+            IpNet::V4(net) => net.prefix_len(),
+            IpNet::V6(net) => net.prefix_len(),
+        }
+    }
+
+    /// Returns the network mask of this network.
+    ///
+    /// See [`Ipv4Net::netmask`] and [`Ipv6Net::netmask`].
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn netmask(&self) -> IpAddr {
+        match self {
+            IpNet::V4(net) => IpAddr::V4(net.netmask()),
+            IpNet::V6(net) => IpAddr::V6(net.netmask()),
+        }
+    }
+
+    /// Returns the host mask of this network.
+    ///
+    /// See [`Ipv4Net::hostmask`] and [`Ipv6Net::hostmask`].
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn hostmask(&self) -> IpAddr {
+        match self {
+            IpNet::V4(net) => IpAddr::V4(net.hostmask()),
+            IpNet::V6(net) => IpAddr::V6(net.hostmask()),
+        }
+    }
+
+    /// Returns the network address of this network, which is its address with all host bits
+    /// cleared.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    ///
+    /// use std::net::IpNet;
+    ///
+    /// let net: IpNet = "10.1.2.3/8".parse().unwrap();
+    /// assert_eq!(net.network(), "10.0.0.0".parse::<std::net::IpAddr>().unwrap());
+    /// ```
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn network(&self) -> IpAddr {
+        match self {
+            IpNet::V4(net) => IpAddr::V4(net.network()),
+            IpNet::V6(net) => IpAddr::V6(net.network()),
+        }
+    }
+
+    /// Returns a copy of this network with the host bits of its address cleared.
+    ///
+    /// See [`Ipv4Net::trunc`] and [`Ipv6Net::trunc`].
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn trunc(&self) -> IpNet {
+        match self {
+            IpNet::V4(net) => IpNet::V4(net.trunc()),
+            IpNet::V6(net) => IpNet::V6(net.trunc()),
+        }
+    }
+
+    /// Returns the smallest network containing this one, which has a prefix length one less
+    /// than this one, or [`None`] if the prefix length is 0.
+    ///
+    /// See [`Ipv4Net::supernet`] and [`Ipv6Net::supernet`].
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn supernet(&self) -> Option<IpNet> {
+        match self {
+            IpNet::V4(net) => match net.supernet() {
+                Some(net) => Some(IpNet::V4(net)),
+                None => None,
+            },
+            IpNet::V6(net) => match net.supernet() {
+                Some(net) => Some(IpNet::V6(net)),
+                None => None,
+            },
+        }
+    }
+
+    /// Returns `true` if `addr` is part of this network.
+    ///
+    /// An IPv4 network never contains an IPv6 address, and vice versa.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    ///
+    /// use std::net::{IpAddr, IpNet};
+    ///
+    /// let net: IpNet = "2001:db8::/32".parse().unwrap();
+    /// assert!(net.contains("2001:db8::1".parse::<IpAddr>().unwrap()));
+    /// assert!(!net.contains("2001:db9::1".parse::<IpAddr>().unwrap()));
+    /// assert!(!net.contains("32.1.13.184".parse::<IpAddr>().unwrap()));
+    /// ```
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn contains(&self, addr: IpAddr) -> bool {
+        match (self, addr) {
+            (IpNet::V4(net), IpAddr::V4(addr)) => net.contains(addr),
+            (IpNet::V6(net), IpAddr::V6(addr)) => net.contains(addr),
+            _ => false,
+        }
+    }
+
+    /// Returns `true` if every address of `other` is part of this network.
+    ///
+    /// An IPv4 network never contains an IPv6 network, and vice versa.
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn contains_net(&self, other: IpNet) -> bool {
+        match (self, other) {
+            (IpNet::V4(net), IpNet::V4(other)) => net.contains_net(other),
+            (IpNet::V6(net), IpNet::V6(other)) => net.contains_net(other),
+            _ => false,
+        }
+    }
+
+    /// Returns [`true`] if this is an IPv4 network, and [`false`] otherwise.
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn is_ipv4(&self) -> bool {
+        matches!(self, IpNet::V4(_))
+    }
+
+    /// Returns [`true`] if this is an IPv6 network, and [`false`] otherwise.
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn is_ipv6(&self) -> bool {
+        matches!(self, IpNet::V6(_))
+    }
+}
+
+impl Ipv4Net {
+    /// The largest prefix length of an IPv4 network, 32.
+    #[unstable(feature = "ip_net", issue = "none")]
+    pub const MAX_PREFIX_LEN: u8 = 32;
+
+    /// Creates a new IPv4 network from an address and a prefix length.
+    ///
+    /// The host bits of `addr` are kept as is; see [`trunc`](Ipv4Net::trunc).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PrefixLenError`] if `prefix_len` is more than 32.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    ///
+    /// use std::net::{Ipv4Addr, Ipv4Net};
+    ///
+    /// assert!(Ipv4Net::new(Ipv4Addr::new(10, 0, 0, 0), 8).is_ok());
+    /// assert!(Ipv4Net::new(Ipv4Addr::new(10, 0, 0, 0), 33).is_err());
+    /// ```
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[inline]
+    pub const fn new(addr: Ipv4Addr, prefix_len: u8) -> Result<Ipv4Net, PrefixLenError> {
+        if prefix_len <= Self::MAX_PREFIX_LEN {
+            Ok(Ipv4Net { addr, prefix_len })
+        } else {
+            Err(PrefixLenError(()))
+        }
+    }
+
+    /// Returns the address of this network, as given when creating it.
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn addr(&self) -> Ipv4Addr {
+        self.addr
+    }
+
+    /// Returns the prefix length of this network.
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn prefix_len(&self) -> u8 {
+        self.prefix_len
+    }
+
+    /// Returns the network mask of this network, which has the first `prefix_len` bits set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    ///
+    /// use std::net::{Ipv4Addr, Ipv4Net};
+    ///
+    /// let net: Ipv4Net = "10.0.0.0/12".parse().unwrap();
+    /// assert_eq!(net.netmask(), Ipv4Addr::new(255, 240, 0, 0));
+    /// ```
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn netmask(&self) -> Ipv4Addr {
+        Ipv4Addr::from_bits(!self.hostmask_bits())
+    }
+
+    /// Returns the host mask of this network, which has the last `32 - prefix_len` bits set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    ///
+    /// use std::net::{Ipv4Addr, Ipv4Net};
+    ///
+    /// let net: Ipv4Net = "10.0.0.0/12".parse().unwrap();
+    /// assert_eq!(net.hostmask(), Ipv4Addr::new(0, 15, 255, 255));
+    /// ```
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn hostmask(&self) -> Ipv4Addr {
+        Ipv4Addr::from_bits(self.hostmask_bits())
+    }
+
+    const fn hostmask_bits(&self) -> u32 {
+        u32::MAX.unbounded_shr(self.prefix_len as u32)
+    }
+
+    /// Returns the network address of this network, which is its first address.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    ///
+    /// use std::net::{Ipv4Addr, Ipv4Net};
+    ///
+    /// let net: Ipv4Net = "172.16.123.45/12".parse().unwrap();
+    /// assert_eq!(net.network(), Ipv4Addr::new(172, 16, 0, 0));
+    /// ```
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn network(&self) -> Ipv4Addr {
+        Ipv4Addr::from_bits(self.addr.to_bits() & !self.hostmask_bits())
+    }
+
+    /// Returns the broadcast address of this network, which is its last address.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    ///
+    /// use std::net::{Ipv4Addr, Ipv4Net};
+    ///
+    /// let net: Ipv4Net = "172.16.123.45/12".parse().unwrap();
+    /// assert_eq!(net.broadcast(), Ipv4Addr::new(172, 31, 255, 255));
+    /// ```
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn broadcast(&self) -> Ipv4Addr {
+        Ipv4Addr::from_bits(self.addr.to_bits() | self.hostmask_bits())
+    }
+
+    /// Returns a copy of this network with the host bits of its address cleared, so that its
+    /// address is the [network address](Ipv4Net::network).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    ///
+    /// use std::net::Ipv4Net;
+    ///
+    /// let net: Ipv4Net = "192.168.1.7/24".parse().unwrap();
+    /// assert_eq!(net.trunc().to_string(), "192.168.1.0/24");
+    /// ```
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn trunc(&self) -> Ipv4Net {
+        Ipv4Net { addr: self.network(), prefix_len: self.prefix_len }
+    }
+
+    /// Returns the smallest network containing this one, which has a prefix length one less
+    /// than this one, or [`None`] if the prefix length is 0.
+    ///
+    /// The returned network is truncated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    ///
+    /// use std::net::Ipv4Net;
+    ///
+    /// let net: Ipv4Net = "10.1.0.0/16".parse().unwrap();
+    /// assert_eq!(net.supernet().unwrap().to_string(), "10.0.0.0/15");
+    ///
+    /// let all: Ipv4Net = "0.0.0.0/0".parse().unwrap();
+    /// assert_eq!(all.supernet(), None);
+    /// ```
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn supernet(&self) -> Option<Ipv4Net> {
+        if self.prefix_len == 0 {
+            None
+        } else {
+            Some(Ipv4Net { addr: self.addr, prefix_len: self.prefix_len - 1 }.trunc())
+        }
+    }
+
+    /// Returns `true` if `addr` is part of this network.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    ///
+    /// use std::net::{Ipv4Addr, Ipv4Net};
+    ///
+    /// let net: Ipv4Net = "10.0.0.0/8".parse().unwrap();
+    /// assert!(net.contains(Ipv4Addr::new(10, 20, 30, 40)));
+    /// assert!(!net.contains(Ipv4Addr::new(11, 0, 0, 0)));
+    /// ```
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn contains(&self, addr: Ipv4Addr) -> bool {
+        (addr.to_bits() ^ self.addr.to_bits()) & !self.hostmask_bits() == 0
+    }
+
+    /// Returns `true` if every address of `other` is part of this network.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    ///
+    /// use std::net::Ipv4Net;
+    ///
+    /// let net: Ipv4Net = "10.0.0.0/8".parse().unwrap();
+    /// assert!(net.contains_net("10.1.0.0/16".parse().unwrap()));
+    /// assert!(!net.contains_net("10.0.0.0/7".parse().unwrap()));
+    /// ```
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn contains_net(&self, other: Ipv4Net) -> bool {
+        other.prefix_len >= self.prefix_len && self.contains(other.addr)
+    }
+
+    /// Returns an iterator over the usable host addresses of this network.
+    ///
+    /// This excludes the network and broadcast addresses, except for `/31` and `/32` networks
+    /// which don't have any (see [IETF RFC 3021]).
+    ///
+    /// [IETF RFC 3021]: https://tools.ietf.org/html/rfc3021
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    ///
+    /// use std::net::{Ipv4Addr, Ipv4Net};
+    ///
+    /// let net: Ipv4Net = "192.168.0.0/30".parse().unwrap();
+    /// assert!(net.hosts().eq([Ipv4Addr::new(192, 168, 0, 1), Ipv4Addr::new(192, 168, 0, 2)]));
+    ///
+    /// let net: Ipv4Net = "192.168.0.0/31".parse().unwrap();
+    /// assert_eq!(net.hosts().count(), 2);
+    /// ```
+    #[unstable(feature = "ip_net", issue = "none")]
+    pub fn hosts(&self) -> RangeInclusive<Ipv4Addr> {
+        let (network, broadcast) = (self.network(), self.broadcast());
+        if self.prefix_len >= 31 {
+            network..=broadcast
+        } else {
+            Ipv4Addr::from_bits(network.to_bits() + 1)
+                ..=Ipv4Addr::from_bits(broadcast.to_bits() - 1)
+        }
+    }
+
+    /// Returns an iterator over the subnets of this network with the given prefix length, in
+    /// increasing order.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PrefixLenError`] if `new_prefix_len` is less than the prefix length of this
+    /// network, or more than 32.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    ///
+    /// use std::net::Ipv4Net;
+    ///
+    /// let net: Ipv4Net = "10.0.0.0/23".parse().unwrap();
+    /// let subnets: Vec<String> = net.subnets(25).unwrap().map(|n| n.to_string()).collect();
+    ///
+    /// assert_eq!(subnets, ["10.0.0.0/25", "10.0.0.128/25", "10.0.1.0/25", "10.0.1.128/25"]);
+    /// assert!(net.subnets(22).is_err());
+    /// ```
+    #[unstable(feature = "ip_net", issue = "none")]
+    pub fn subnets(&self, new_prefix_len: u8) -> Result<Ipv4Subnets, PrefixLenError> {
+        if new_prefix_len < self.prefix_len || new_prefix_len > Self::MAX_PREFIX_LEN {
+            return Err(PrefixLenError(()));
+        }
+        let last = Ipv4Net { addr: self.broadcast(), prefix_len: new_prefix_len }.network();
+        Ok(Ipv4Subnets { next: Some(self.network()), last, prefix_len: new_prefix_len })
+    }
+}
+
+impl Ipv6Net {
+    /// The largest prefix length of an IPv6 network, 128.
+    #[unstable(feature = "ip_net", issue = "none")]
+    pub const MAX_PREFIX_LEN: u8 = 128;
+
+    /// Creates a new IPv6 network from an address and a prefix length.
+    ///
+    /// The host bits of `addr` are kept as is; see [`trunc`](Ipv6Net::trunc).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PrefixLenError`] if `prefix_len` is more than 128.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    ///
+    /// use std::net::{Ipv6Addr, Ipv6Net};
+    ///
+    /// assert!(Ipv6Net::new(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0), 32).is_ok());
+    /// assert!(Ipv6Net::new(Ipv6Addr::UNSPECIFIED, 129).is_err());
+    /// ```
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[inline]
+    pub const fn new(addr: Ipv6Addr, prefix_len: u8) -> Result<Ipv6Net, PrefixLenError> {
+        if prefix_len <= Self::MAX_PREFIX_LEN {
+            Ok(Ipv6Net { addr, prefix_len })
+        } else {
+            Err(PrefixLenError(()))
+        }
+    }
+
+    /// Returns the address of this network, as given when creating it.
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn addr(&self) -> Ipv6Addr {
+        self.addr
+    }
+
+    /// Returns the prefix length of this network.
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn prefix_len(&self) -> u8 {
+        self.prefix_len
+    }
+
+    /// Returns the network mask of this network, which has the first `prefix_len` bits set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    ///
+    /// use std::net::{Ipv6Addr, Ipv6Net};
+    ///
+    /// let net: Ipv6Net = "2001:db8::/36".parse().unwrap();
+    /// assert_eq!(net.netmask(), Ipv6Addr::new(0xffff, 0xffff, 0xf000, 0, 0, 0, 0, 0));
+    /// ```
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn netmask(&self) -> Ipv6Addr {
+        Ipv6Addr::from_bits(!self.hostmask_bits())
+    }
+
+    /// Returns the host mask of this network, which has the last `128 - prefix_len` bits set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    ///
+    /// use std::net::{Ipv6Addr, Ipv6Net};
+    ///
+    /// let net: Ipv6Net = "2001:db8::/112".parse().unwrap();
+    /// assert_eq!(net.hostmask(), Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 0xffff));
+    /// ```
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn hostmask(&self) -> Ipv6Addr {
+        Ipv6Addr::from_bits(self.hostmask_bits())
+    }
+
+    const fn hostmask_bits(&self) -> u128 {
+        u128::MAX.unbounded_shr(self.prefix_len as u32)
+    }
+
+    /// Returns the network address of this network, which is its first address.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    ///
+    /// use std::net::{Ipv6Addr, Ipv6Net};
+    ///
+    /// let net: Ipv6Net = "2001:db8:1234::1/32".parse().unwrap();
+    /// assert_eq!(net.network(), Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0));
+    /// ```
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn network(&self) -> Ipv6Addr {
+        Ipv6Addr::from_bits(self.addr.to_bits() & !self.hostmask_bits())
+    }
+
+    /// Returns the last address of this network, which has all host bits set.
+    ///
+    /// IPv6 has no broadcast addresses, so this is an ordinary address.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    ///
+    /// use std::net::{Ipv6Addr, Ipv6Net};
+    ///
+    /// let net: Ipv6Net = "2001:db8::/112".parse().unwrap();
+    /// assert_eq!(net.last_addr(), Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0xffff));
+    /// ```
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn last_addr(&self) -> Ipv6Addr {
+        Ipv6Addr::from_bits(self.addr.to_bits() | self.hostmask_bits())
+    }
+
+    /// Returns a copy of this network with the host bits of its address cleared, so that its
+    /// address is the [network address](Ipv6Net::network).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    ///
+    /// use std::net::Ipv6Net;
+    ///
+    /// let net: Ipv6Net = "2001:db8::1/64".parse().unwrap();
+    /// assert_eq!(net.trunc().to_string(), "2001:db8::/64");
+    /// ```
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn trunc(&self) -> Ipv6Net {
+        Ipv6Net { addr: self.network(), prefix_len: self.prefix_len }
+    }
+
+    /// Returns the smallest network containing this one, which has a prefix length one less
+    /// than this one, or [`None`] if the prefix length is 0.
+    ///
+    /// The returned network is truncated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    ///
+    /// use std::net::Ipv6Net;
+    ///
+    /// let net: Ipv6Net = "2001:db9::/32".parse().unwrap();
+    /// assert_eq!(net.supernet().unwrap().to_string(), "2001:db8::/31");
+    /// ```
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn supernet(&self) -> Option<Ipv6Net> {
+        if self.prefix_len == 0 {
+            None
+        } else {
+            Some(Ipv6Net { addr: self.addr, prefix_len: self.prefix_len - 1 }.trunc())
+        }
+    }
+
+    /// Returns `true` if `addr` is part of this network.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    ///
+    /// use std::net::{Ipv6Addr, Ipv6Net};
+    ///
+    /// let net: Ipv6Net = "fe80::/10".parse().unwrap();
+    /// assert!(net.contains(Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 1)));
+    /// assert!(!net.contains(Ipv6Addr::LOCALHOST));
+    /// ```
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn contains(&self, addr: Ipv6Addr) -> bool {
+        (addr.to_bits() ^ self.addr.to_bits()) & !self.hostmask_bits() == 0
+    }
+
+    /// Returns `true` if every address of `other` is part of this network.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    ///
+    /// use std::net::Ipv6Net;
+    ///
+    /// let net: Ipv6Net = "2001:db8::/32".parse().unwrap();
+    /// assert!(net.contains_net("2001:db8:1::/48".parse().unwrap()));
+    /// assert!(!net.contains_net("2001:db8::/31".parse().unwrap()));
+    /// ```
+    #[unstable(feature = "ip_net", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn contains_net(&self, other: Ipv6Net) -> bool {
+        other.prefix_len >= self.prefix_len && self.contains(other.addr)
+    }
+
+    /// Returns an iterator over all addresses of this network.
+    ///
+    /// Unlike IPv4, IPv6 has no broadcast addresses, so no addresses are excluded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    ///
+    /// use std::net::Ipv6Net;
+    ///
+    /// let net: Ipv6Net = "2001:db8::/126".parse().unwrap();
+    /// assert_eq!(net.hosts().count(), 4);
+    /// ```
+    #[unstable(feature = "ip_net", issue = "none")]
+    pub fn hosts(&self) -> RangeInclusive<Ipv6Addr> {
+        self.network()..=self.last_addr()
+    }
+
+    /// Returns an iterator over the subnets of this network with the given prefix length, in
+    /// increasing order.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PrefixLenError`] if `new_prefix_len` is less than the prefix length of this
+    /// network, or more than 128.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    ///
+    /// use std::net::Ipv6Net;
+    ///
+    /// let net: Ipv6Net = "2001:db8::/32".parse().unwrap();
+    /// let subnets: Vec<String> = net.subnets(33).unwrap().map(|n| n.to_string()).collect();
+    ///
+    /// assert_eq!(subnets, ["2001:db8::/33", "2001:db8:8000::/33"]);
+    /// ```
+    #[unstable(feature = "ip_net", issue = "none")]
+    pub fn subnets(&self, new_prefix_len: u8) -> Result<Ipv6Subnets, PrefixLenError> {
+        if new_prefix_len < self.prefix_len || new_prefix_len > Self::MAX_PREFIX_LEN {
+            return Err(PrefixLenError(()));
+        }
+        let last = Ipv6Net { addr: self.last_addr(), prefix_len: new_prefix_len }.network();
+        Ok(Ipv6Subnets { next: Some(self.network()), last, prefix_len: new_prefix_len })
+    }
+}
+
+/// An iterator over the subnets of an [`Ipv4Net`].
+///
+/// This `struct` is created by the [`Ipv4Net::subnets`] method. See its documentation for more.
+#[unstable(feature = "ip_net", issue = "none")]
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Ipv4Subnets {
+    /// The network address of the next subnet, or `None` once exhausted.
+    next: Option<Ipv4Addr>,
+    /// The network address of the last subnet.
+    last: Ipv4Addr,
+    prefix_len: u8,
+}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl Iterator for Ipv4Subnets {
+    type Item = Ipv4Net;
+
+    fn next(&mut self) -> Option<Ipv4Net> {
+        let addr = self.next?;
+        self.next = if addr == self.last {
+            None
+        } else {
+            // Can't overflow: there is at least one subnet after this one, so the prefix length
+            // isn't 0.
+            Some(Ipv4Addr::from_bits(addr.to_bits() + (1 << (32 - self.prefix_len))))
+        };
+        Some(Ipv4Net { addr, prefix_len: self.prefix_len })
+    }
+}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl FusedIterator for Ipv4Subnets {}
+
+/// An iterator over the subnets of an [`Ipv6Net`].
+///
+/// This `struct` is created by the [`Ipv6Net::subnets`] method. See its documentation for more.
+#[unstable(feature = "ip_net", issue = "none")]
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Ipv6Subnets {
+    /// The network address of the next subnet, or `None` once exhausted.
+    next: Option<Ipv6Addr>,
+    /// The network address of the last subnet.
+    last: Ipv6Addr,
+    prefix_len: u8,
+}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl Iterator for Ipv6Subnets {
+    type Item = Ipv6Net;
+
+    fn next(&mut self) -> Option<Ipv6Net> {
+        let addr = self.next?;
+        self.next = if addr == self.last {
+            None
+        } else {
+            // Can't overflow: there is at least one subnet after this one, so the prefix length
+            // isn't 0.
+            Some(Ipv6Addr::from_bits(addr.to_bits() + (1 << (128 - self.prefix_len))))
+        };
+        Some(Ipv6Net { addr, prefix_len: self.prefix_len })
+    }
+}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl FusedIterator for Ipv6Subnets {}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl From<Ipv4Net> for IpNet {
+    /// Copies this network to a new `IpNet::V4`.
+    #[inline]
+    fn from(net: Ipv4Net) -> IpNet {
+        IpNet::V4(net)
+    }
+}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl From<Ipv6Net> for IpNet {
+    /// Copies this network to a new `IpNet::V6`.
+    #[inline]
+    fn from(net: Ipv6Net) -> IpNet {
+        IpNet::V6(net)
+    }
+}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl From<Ipv4Addr> for Ipv4Net {
+    /// Creates a network holding only `addr`, with a prefix length of 32.
+    #[inline]
+    fn from(addr: Ipv4Addr) -> Ipv4Net {
+        Ipv4Net { addr, prefix_len: Ipv4Net::MAX_PREFIX_LEN }
+    }
+}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl From<Ipv6Addr> for Ipv6Net {
+    /// Creates a network holding only `addr`, with a prefix length of 128.
+    #[inline]
+    fn from(addr: Ipv6Addr) -> Ipv6Net {
+        Ipv6Net { addr, prefix_len: Ipv6Net::MAX_PREFIX_LEN }
+    }
+}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl From<IpAddr> for IpNet {
+    /// Creates a network holding only `addr`, with the largest prefix length of its family.
+    #[inline]
+    fn from(addr: IpAddr) -> IpNet {
+        match addr {
+            IpAddr::V4(addr) => IpNet::V4(addr.into()),
+            IpAddr::V6(addr) => IpNet::V6(addr.into()),
+        }
+    }
+}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl fmt::Display for IpNet {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IpNet::V4(net) => net.fmt(fmt),
+            IpNet::V6(net) => net.fmt(fmt),
+        }
+    }
+}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl fmt::Debug for IpNet {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, fmt)
+    }
+}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl fmt::Display for Ipv4Net {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // If there are no alignment requirements, write the network directly to `f`.
+        // Otherwise, write it to a local buffer and then use `f.pad`.
+        if fmt.precision().is_none() && fmt.width().is_none() {
+            write!(fmt, "{}/{}", self.addr, self.prefix_len)
+        } else {
+            const LONGEST_IPV4_NET: &str = "255.255.255.255/32";
+
+            let mut buf = DisplayBuffer::<{ LONGEST_IPV4_NET.len() }>::new();
+            // Buffer is long enough for the longest possible IPv4 network, so this should never fail.
+            write!(buf, "{}/{}", self.addr, self.prefix_len).unwrap();
+
+            fmt.pad(buf.as_str())
+        }
+    }
+}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl fmt::Debug for Ipv4Net {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, fmt)
+    }
+}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl fmt::Display for Ipv6Net {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // If there are no alignment requirements, write the network directly to `f`.
+        // Otherwise, write it to a local buffer and then use `f.pad`.
+        if fmt.precision().is_none() && fmt.width().is_none() {
+            write!(fmt, "{}/{}", self.addr, self.prefix_len)
+        } else {
+            const LONGEST_IPV6_NET: &str = "ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff/128";
+
+            let mut buf = DisplayBuffer::<{ LONGEST_IPV6_NET.len() }>::new();
+            // Buffer is long enough for the longest possible IPv6 network, so this should never fail.
+            write!(buf, "{}/{}", self.addr, self.prefix_len).unwrap();
+
+            fmt.pad(buf.as_str())
+        }
+    }
+}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl fmt::Debug for Ipv6Net {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, fmt)
+    }
+}
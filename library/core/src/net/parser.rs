@@ -1,11 +1,13 @@
-//! A private parser implementation of IPv4, IPv6, and socket addresses.
+//! A private parser implementation of IPv4, IPv6, socket addresses, and IP networks.
 //!
 //! This module is "publicly exported" through the `FromStr` implementations
 //! below.
 
 use crate::error::Error;
 use crate::fmt;
-use crate::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+use crate::net::{
+    IpAddr, IpNet, Ipv4Addr, Ipv4Net, Ipv6Addr, Ipv6Net, SocketAddr, SocketAddrV4, SocketAddrV6,
+};
 use crate::str::FromStr;
 
 trait ReadNumberHelper: Sized {
@@ -281,6 +283,38 @@ fn read_socket_addr(&mut self) -> Option<SocketAddr> {
             .map(SocketAddr::V4)
             .or_else(|| self.read_socket_addr_v6().map(SocketAddr::V6))
     }
+
+    /// Reads a `/` followed by a prefix length in base 10, without leading zeros, that is at
+    /// most `max`.
+    fn read_prefix_len(&mut self, max: u8) -> Option<u8> {
+        self.read_atomically(|p| {
+            p.read_given_char('/')?;
+            p.read_radix_max_digits::<u8>(10, 3, false).filter(|&len| len <= max)
+        })
+    }
+
+    /// Reads an IPv4 address with a prefix length.
+    fn read_ipv4_net(&mut self) -> Option<Ipv4Net> {
+        self.read_atomically(|p| {
+            let ip = p.read_ipv4_addr()?;
+            let prefix_len = p.read_prefix_len(Ipv4Net::MAX_PREFIX_LEN)?;
+            Ipv4Net::new(ip, prefix_len).ok()
+        })
+    }
+
+    /// Reads an IPv6 address with a prefix length.
+    fn read_ipv6_net(&mut self) -> Option<Ipv6Net> {
+        self.read_atomically(|p| {
+            let ip = p.read_ipv6_addr()?;
+            let prefix_len = p.read_prefix_len(Ipv6Net::MAX_PREFIX_LEN)?;
+            Ipv6Net::new(ip, prefix_len).ok()
+        })
+    }
+
+    /// Reads an IP address with a prefix length.
+    fn read_ip_net(&mut self) -> Option<IpNet> {
+        self.read_ipv4_net().map(IpNet::V4).or_else(|| self.read_ipv6_net().map(IpNet::V6))
+    }
 }
 
 impl IpAddr {
@@ -448,6 +482,89 @@ fn from_str(s: &str) -> Result<SocketAddr, AddrParseError> {
     }
 }
 
+impl IpNet {
+    /// Parse an IP network from a slice of bytes.
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    ///
+    /// use std::net::{IpAddr, IpNet, Ipv4Addr};
+    ///
+    /// let net = IpNet::new(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 0)), 8).unwrap();
+    ///
+    /// assert_eq!(IpNet::parse_ascii(b"10.0.0.0/8"), Ok(net));
+    /// ```
+    #[unstable(feature = "ip_net", issue = "none")]
+    pub fn parse_ascii(b: &[u8]) -> Result<Self, AddrParseError> {
+        Parser::new(b).parse_with(|p| p.read_ip_net(), AddrKind::IpNet)
+    }
+}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl FromStr for IpNet {
+    type Err = AddrParseError;
+    fn from_str(s: &str) -> Result<IpNet, AddrParseError> {
+        Self::parse_ascii(s.as_bytes())
+    }
+}
+
+impl Ipv4Net {
+    /// Parse an IPv4 network from a slice of bytes.
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    ///
+    /// use std::net::{Ipv4Addr, Ipv4Net};
+    ///
+    /// let net = Ipv4Net::new(Ipv4Addr::new(192, 168, 0, 0), 16).unwrap();
+    ///
+    /// assert_eq!(Ipv4Net::parse_ascii(b"192.168.0.0/16"), Ok(net));
+    /// ```
+    #[unstable(feature = "ip_net", issue = "none")]
+    pub fn parse_ascii(b: &[u8]) -> Result<Self, AddrParseError> {
+        // don't try to parse if too long
+        if b.len() > 18 {
+            Err(AddrParseError(AddrKind::Ipv4Net))
+        } else {
+            Parser::new(b).parse_with(|p| p.read_ipv4_net(), AddrKind::Ipv4Net)
+        }
+    }
+}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl FromStr for Ipv4Net {
+    type Err = AddrParseError;
+    fn from_str(s: &str) -> Result<Ipv4Net, AddrParseError> {
+        Self::parse_ascii(s.as_bytes())
+    }
+}
+
+impl Ipv6Net {
+    /// Parse an IPv6 network from a slice of bytes.
+    ///
+    /// ```
+    /// #![feature(ip_net)]
+    ///
+    /// use std::net::{Ipv6Addr, Ipv6Net};
+    ///
+    /// let net = Ipv6Net::new(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0), 32).unwrap();
+    ///
+    /// assert_eq!(Ipv6Net::parse_ascii(b"2001:db8::/32"), Ok(net));
+    /// ```
+    #[unstable(feature = "ip_net", issue = "none")]
+    pub fn parse_ascii(b: &[u8]) -> Result<Self, AddrParseError> {
+        Parser::new(b).parse_with(|p| p.read_ipv6_net(), AddrKind::Ipv6Net)
+    }
+}
+
+#[unstable(feature = "ip_net", issue = "none")]
+impl FromStr for Ipv6Net {
+    type Err = AddrParseError;
+    fn from_str(s: &str) -> Result<Ipv6Net, AddrParseError> {
+        Self::parse_ascii(s.as_bytes())
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 enum AddrKind {
     Ip,
@@ -456,13 +573,16 @@ enum AddrKind {
     Socket,
     SocketV4,
     SocketV6,
+    IpNet,
+    Ipv4Net,
+    Ipv6Net,
 }
 
 /// An error which can be returned when parsing an IP address or a socket address.
 ///
 /// This error is used as the error type for the [`FromStr`] implementation for
 /// [`IpAddr`], [`Ipv4Addr`], [`Ipv6Addr`], [`SocketAddr`], [`SocketAddrV4`], and
-/// [`SocketAddrV6`].
+/// [`SocketAddrV6`], as well as for the IP network types such as [`IpNet`].
 ///
 /// # Potential causes
 ///
@@ -496,6 +616,9 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
             AddrKind::Socket => "invalid socket address syntax",
             AddrKind::SocketV4 => "invalid IPv4 socket address syntax",
             AddrKind::SocketV6 => "invalid IPv6 socket address syntax",
+            AddrKind::IpNet => "invalid IP network syntax",
+            AddrKind::Ipv4Net => "invalid IPv4 network syntax",
+            AddrKind::Ipv6Net => "invalid IPv6 network syntax",
         }
         .fmt(f)
     }
@@ -1,6 +1,7 @@
 //! Random value generation.
 
-use crate::ops::RangeFull;
+use crate::fmt;
+use crate::ops::{Range, RangeFull, RangeInclusive};
 
 /// A source of randomness.
 #[unstable(feature = "random", issue = "130703")]
@@ -38,9 +39,11 @@ macro_rules! impl_primitive {
     ($t:ty) => {
         impl Distribution<$t> for RangeFull {
             fn sample(&self, source: &mut (impl Rng + ?Sized)) -> $t {
-                let mut bytes = (0 as $t).to_ne_bytes();
+                let mut bytes = (0 as $t).to_le_bytes();
                 source.fill_bytes(&mut bytes);
-                <$t>::from_ne_bytes(bytes)
+                // Little endian, so that deterministic sources like `SeededRng` produce the same
+                // values on all platforms.
+                <$t>::from_le_bytes(bytes)
             }
         }
     };
@@ -58,3 +61,320 @@ fn sample(&self, source: &mut (impl Rng + ?Sized)) -> $t {
 impl_primitive!(i128);
 impl_primitive!(usize);
 impl_primitive!(isize);
+
+/// Returns a uniformly distributed value in `0..range`, which must not be empty.
+///
+/// This uses Lemire's widening multiplication method, rejecting the few samples that would
+/// introduce a bias.
+fn sample_below_u32(source: &mut (impl Rng + ?Sized), range: u32) -> u32 {
+    let threshold = range.wrapping_neg() % range;
+    loop {
+        let x: u32 = RangeFull.sample(source);
+        let m = u64::from(x) * u64::from(range);
+        if m as u32 >= threshold {
+            return (m >> 32) as u32;
+        }
+    }
+}
+
+/// Returns a uniformly distributed value in `0..range`, which must not be empty.
+///
+/// See `sample_below_u32`.
+fn sample_below_u64(source: &mut (impl Rng + ?Sized), range: u64) -> u64 {
+    let threshold = range.wrapping_neg() % range;
+    loop {
+        let x: u64 = RangeFull.sample(source);
+        let m = u128::from(x) * u128::from(range);
+        if m as u64 >= threshold {
+            return (m >> 64) as u64;
+        }
+    }
+}
+
+/// Returns a uniformly distributed value in `0..range`, which must not be empty.
+///
+/// There is no wider integer type to multiply into, so this masks samples down to the smallest
+/// power of two containing `range`, and rejects those out of range.
+fn sample_below_u128(source: &mut (impl Rng + ?Sized), range: u128) -> u128 {
+    let mask = u128::MAX.unbounded_shr((range - 1).leading_zeros());
+    loop {
+        let x: u128 = RangeFull.sample(source);
+        if x & mask < range {
+            return x & mask;
+        }
+    }
+}
+
+macro_rules! impl_range {
+    ($t:ty, $u:ty, $sample_below:ident, $w:ty) => {
+        /// Samples uniformly from the integers in the range.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the range is empty.
+        impl Distribution<$t> for Range<$t> {
+            fn sample(&self, source: &mut (impl Rng + ?Sized)) -> $t {
+                assert!(!self.is_empty(), "cannot sample from an empty range");
+                let range = self.end.wrapping_sub(self.start) as $u;
+                let offset = $sample_below(source, range as $w) as $u;
+                self.start.wrapping_add(offset as $t)
+            }
+        }
+
+        /// Samples uniformly from the integers in the range.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the range is empty.
+        impl Distribution<$t> for RangeInclusive<$t> {
+            fn sample(&self, source: &mut (impl Rng + ?Sized)) -> $t {
+                assert!(!self.is_empty(), "cannot sample from an empty range");
+                let (start, end) = (*self.start(), *self.end());
+                // Wraps around to 0 if the range covers every value of the type.
+                let range = (end.wrapping_sub(start) as $u).wrapping_add(1);
+                let offset: $u = if range == 0 {
+                    RangeFull.sample(source)
+                } else {
+                    $sample_below(source, range as $w) as $u
+                };
+                start.wrapping_add(offset as $t)
+            }
+        }
+    };
+}
+
+impl_range!(u8, u8, sample_below_u32, u32);
+impl_range!(i8, u8, sample_below_u32, u32);
+impl_range!(u16, u16, sample_below_u32, u32);
+impl_range!(i16, u16, sample_below_u32, u32);
+impl_range!(u32, u32, sample_below_u32, u32);
+impl_range!(i32, u32, sample_below_u32, u32);
+impl_range!(u64, u64, sample_below_u64, u64);
+impl_range!(i64, u64, sample_below_u64, u64);
+impl_range!(u128, u128, sample_below_u128, u128);
+impl_range!(i128, u128, sample_below_u128, u128);
+impl_range!(usize, usize, sample_below_u64, u64);
+impl_range!(isize, usize, sample_below_u64, u64);
+
+macro_rules! impl_float_range {
+    ($t:ty, $bits:ty, $mantissa_digits:expr) => {
+        /// Samples uniformly from the floating-point numbers in the range, in the sense that
+        /// the probability of a value lying in some part of the range is proportional to the
+        /// size of that part.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the range is empty, or if its size is not finite (which includes ranges
+        /// with an infinite or NaN bound).
+        impl Distribution<$t> for Range<$t> {
+            fn sample(&self, source: &mut (impl Rng + ?Sized)) -> $t {
+                assert!(!self.is_empty(), "cannot sample from an empty range");
+                let scale = self.end - self.start;
+                assert!(scale.is_finite(), "cannot sample from an infinite range");
+                loop {
+                    // A uniformly distributed number in `0.0..1.0`, with every representable
+                    // multiple of the precision being equally likely.
+                    let bits: $bits = RangeFull.sample(source);
+                    let unit = (bits >> (<$bits>::BITS - $mantissa_digits)) as $t
+                        / ((1 as $bits) << $mantissa_digits) as $t;
+                    let value = self.start + scale * unit;
+                    // Rounding can produce the (excluded) end of the range; try again then.
+                    if value < self.end {
+                        return value;
+                    }
+                }
+            }
+        }
+
+        /// Samples uniformly from the floating-point numbers in the range, in the sense that
+        /// the probability of a value lying in some part of the range is proportional to the
+        /// size of that part.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the range is empty, or if its size is not finite (which includes ranges
+        /// with an infinite or NaN bound).
+        impl Distribution<$t> for RangeInclusive<$t> {
+            fn sample(&self, source: &mut (impl Rng + ?Sized)) -> $t {
+                assert!(!self.is_empty(), "cannot sample from an empty range");
+                let (start, end) = (*self.start(), *self.end());
+                let scale = end - start;
+                assert!(scale.is_finite(), "cannot sample from an infinite range");
+                // A uniformly distributed number in `0.0..=1.0`.
+                let bits: $bits = RangeFull.sample(source);
+                let unit = (bits >> (<$bits>::BITS - $mantissa_digits)) as $t
+                    / (((1 as $bits) << $mantissa_digits) - 1) as $t;
+                (start + scale * unit).min(end)
+            }
+        }
+    };
+}
+
+impl_float_range!(f32, u32, f32::MANTISSA_DIGITS);
+impl_float_range!(f64, u64, f64::MANTISSA_DIGITS);
+
+/// A fast, deterministic random number generator, seeded by the user.
+///
+/// Given the same seed, a `SeededRng` always produces the same sequence of random values, on
+/// every platform and in every version of Rust. This makes it suitable for simulations, tests
+/// and other uses where results must be reproducible. Sampling a [`Distribution`] implemented by
+/// the standard library with the same generator state also gives the same value everywhere.
+///
+/// This is **not** a cryptographically secure generator: its output is predictable from a small
+/// number of samples, so it must never be used to generate keys, tokens or anything else that
+/// needs to stay secret. Use `std::random::SystemRng` for those.
+///
+/// # Algorithm
+///
+/// The generator is [xoshiro256++], which has 256 bits of state and a period of 2<sup>256</sup>
+/// − 1. [`SeededRng::new`] expands its 64-bit seed to the full state using [SplitMix64]. The
+/// bytes written by [`fill_bytes`] are the generated 64-bit words in little-endian order, with
+/// the unused bytes of the last word discarded.
+///
+/// [xoshiro256++]: https://prng.di.unimi.it/
+/// [SplitMix64]: https://prng.di.unimi.it/splitmix64.c
+/// [`fill_bytes`]: Rng::fill_bytes
+///
+/// # Examples
+///
+/// ```
+/// #![feature(random)]
+///
+/// use std::random::{Distribution, SeededRng};
+///
+/// let mut rng = SeededRng::new(42);
+/// let rolls: Vec<u8> = (0..5).map(|_| (1..=6).sample(&mut rng)).collect();
+///
+/// // The same seed gives the same rolls.
+/// let mut rng = SeededRng::new(42);
+/// assert!(rolls.iter().all(|&roll| roll == (1..=6).sample(&mut rng)));
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+#[unstable(feature = "random", issue = "130703")]
+pub struct SeededRng {
+    state: [u64; 4],
+}
+
+impl SeededRng {
+    /// Creates a new generator from a 64-bit seed.
+    ///
+    /// Every seed, including 0, gives a generator of the same quality.
+    #[must_use]
+    #[unstable(feature = "random", issue = "130703")]
+    pub const fn new(seed: u64) -> SeededRng {
+        let mut x = seed;
+        let mut state = [0; 4];
+        let mut i = 0;
+        while i < state.len() {
+            // SplitMix64.
+            x = x.wrapping_add(0x9e3779b97f4a7c15);
+            let mut z = x;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+            state[i] = z ^ (z >> 31);
+            i += 1;
+        }
+        SeededRng { state }
+    }
+
+    /// Creates a new generator from its full 256-bit state, made of four little-endian 64-bit
+    /// words.
+    ///
+    /// The xoshiro256++ state must not be all zeros, so a zero `seed` is replaced by the state
+    /// that [`SeededRng::new(0)`](SeededRng::new) would use.
+    #[must_use]
+    #[unstable(feature = "random", issue = "130703")]
+    pub const fn from_seed(seed: [u8; 32]) -> SeededRng {
+        let mut state = [0; 4];
+        let mut i = 0;
+        while i < state.len() {
+            let mut word = [0; 8];
+            let mut j = 0;
+            while j < word.len() {
+                word[j] = seed[i * 8 + j];
+                j += 1;
+            }
+            state[i] = u64::from_le_bytes(word);
+            i += 1;
+        }
+        if state[0] | state[1] | state[2] | state[3] == 0 {
+            return SeededRng::new(0);
+        }
+        SeededRng { state }
+    }
+
+    /// Creates a new generator seeded from another random source.
+    ///
+    /// This is typically used to seed a fast generator from a slow, high-quality one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(random)]
+    ///
+    /// use std::random::{SeededRng, SystemRng};
+    ///
+    /// let mut rng = SeededRng::from_rng(&mut SystemRng);
+    /// let _ = rng.next_u64();
+    /// ```
+    #[must_use]
+    #[unstable(feature = "random", issue = "130703")]
+    pub fn from_rng(source: &mut (impl Rng + ?Sized)) -> SeededRng {
+        let mut seed = [0; 32];
+        source.fill_bytes(&mut seed);
+        SeededRng::from_seed(seed)
+    }
+
+    /// Generates a random `u64`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(random)]
+    ///
+    /// use std::random::SeededRng;
+    ///
+    /// let mut rng = SeededRng::new(0);
+    /// assert_eq!(rng.next_u64(), 0x53175d61490b23df);
+    /// ```
+    #[unstable(feature = "random", issue = "130703")]
+    pub fn next_u64(&mut self) -> u64 {
+        let s = &mut self.state;
+        let result = s[0].wrapping_add(s[3]).rotate_left(23).wrapping_add(s[0]);
+        let t = s[1] << 17;
+        s[2] ^= s[0];
+        s[3] ^= s[1];
+        s[1] ^= s[2];
+        s[0] ^= s[3];
+        s[2] ^= t;
+        s[3] = s[3].rotate_left(45);
+        result
+    }
+
+    /// Generates a random `u32`, made of the upper half of a random `u64`.
+    #[unstable(feature = "random", issue = "130703")]
+    pub fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+}
+
+#[unstable(feature = "random", issue = "130703")]
+impl Rng for SeededRng {
+    fn fill_bytes(&mut self, bytes: &mut [u8]) {
+        let mut chunks = bytes.chunks_exact_mut(8);
+        for chunk in &mut chunks {
+            chunk.copy_from_slice(&self.next_u64().to_le_bytes());
+        }
+        let rest = chunks.into_remainder();
+        if !rest.is_empty() {
+            rest.copy_from_slice(&self.next_u64().to_le_bytes()[..rest.len()]);
+        }
+    }
+}
+
+#[unstable(feature = "random", issue = "130703")]
+impl fmt::Debug for SeededRng {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SeededRng").finish_non_exhaustive()
+    }
+}
@@ -70,6 +70,7 @@
 #![feature(integer_casts)]
 #![feature(io_slice_as_bytes)]
 #![feature(ip)]
+#![feature(ip_net)]
 #![feature(is_ascii_octdigit)]
 #![feature(iter_advance_by)]
 #![feature(iter_array_chunks)]
@@ -98,6 +99,7 @@
 #![feature(pointer_is_aligned_to)]
 #![feature(portable_simd)]
 #![feature(ptr_metadata)]
+#![feature(random)]
 #![feature(rustc_attrs)]
 #![feature(signed_bigint_helpers)]
 #![feature(slice_from_ptr_range)]
@@ -205,6 +207,7 @@ fn $test() $block
 mod pin;
 mod pin_macro;
 mod ptr;
+mod random;
 mod result;
 mod simd;
 mod slice;
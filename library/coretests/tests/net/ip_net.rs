@@ -0,0 +1,185 @@
+use core::net::{IpAddr, IpNet, Ipv4Addr, Ipv4Net, Ipv6Addr, Ipv6Net};
+use core::str::FromStr;
+
+fn v4(s: &str) -> Ipv4Net {
+    s.parse().unwrap()
+}
+
+fn v6(s: &str) -> Ipv6Net {
+    s.parse().unwrap()
+}
+
+#[test]
+fn parse_ipv4_net() {
+    let net = v4("192.168.1.7/16");
+    assert_eq!(net.addr(), Ipv4Addr::new(192, 168, 1, 7));
+    assert_eq!(net.prefix_len(), 16);
+
+    assert_eq!(v4("0.0.0.0/0").prefix_len(), 0);
+    assert_eq!(v4("255.255.255.255/32").prefix_len(), 32);
+
+    assert!(Ipv4Net::from_str("10.0.0.0").is_err());
+    assert!(Ipv4Net::from_str("10.0.0.0/").is_err());
+    assert!(Ipv4Net::from_str("10.0.0.0/33").is_err());
+    assert!(Ipv4Net::from_str("10.0.0.0/08").is_err());
+    assert!(Ipv4Net::from_str("10.0.0.0/8/8").is_err());
+    assert!(Ipv4Net::from_str("10.0.0.0/-1").is_err());
+    assert!(Ipv4Net::from_str("10.0.0/8").is_err());
+    assert!(Ipv4Net::from_str("2001:db8::/32").is_err());
+}
+
+#[test]
+fn parse_ipv6_net() {
+    let net = v6("2001:db8::1/32");
+    assert_eq!(net.addr(), Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1));
+    assert_eq!(net.prefix_len(), 32);
+
+    assert_eq!(v6("::/0").prefix_len(), 0);
+    assert_eq!(v6("::ffff:192.168.0.1/128").addr(), Ipv4Addr::new(192, 168, 0, 1).to_ipv6_mapped());
+
+    assert!(Ipv6Net::from_str("2001:db8::").is_err());
+    assert!(Ipv6Net::from_str("2001:db8::/129").is_err());
+    assert!(Ipv6Net::from_str("2001:db8::/032").is_err());
+    assert!(Ipv6Net::from_str("[2001:db8::]/32").is_err());
+    assert!(Ipv6Net::from_str("10.0.0.0/8").is_err());
+}
+
+#[test]
+fn parse_ip_net() {
+    assert_eq!(IpNet::from_str("10.0.0.0/8"), Ok(IpNet::V4(v4("10.0.0.0/8"))));
+    assert_eq!(IpNet::from_str("2001:db8::/32"), Ok(IpNet::V6(v6("2001:db8::/32"))));
+    assert!(IpNet::from_str("10.0.0.0/64").is_err());
+    assert!(IpNet::from_str("10.0.0.0:80").is_err());
+}
+
+#[test]
+fn new() {
+    assert_eq!(Ipv4Net::new(Ipv4Addr::new(10, 0, 0, 0), 8), Ok(v4("10.0.0.0/8")));
+    assert!(Ipv4Net::new(Ipv4Addr::new(10, 0, 0, 0), 33).is_err());
+    assert_eq!(Ipv6Net::new(Ipv6Addr::LOCALHOST, 128), Ok(v6("::1/128")));
+    assert!(Ipv6Net::new(Ipv6Addr::LOCALHOST, 129).is_err());
+
+    let addr = IpAddr::V4(Ipv4Addr::LOCALHOST);
+    assert_eq!(IpNet::new(addr, 8), Ok(IpNet::V4(v4("127.0.0.1/8"))));
+    assert!(IpNet::new(addr, 33).is_err());
+    assert!(IpNet::new(IpAddr::V6(Ipv6Addr::LOCALHOST), 33).is_ok());
+
+    assert_eq!(Ipv4Net::from(Ipv4Addr::LOCALHOST), v4("127.0.0.1/32"));
+    assert_eq!(IpNet::from(IpAddr::V6(Ipv6Addr::LOCALHOST)), IpNet::V6(v6("::1/128")));
+}
+
+#[test]
+fn display() {
+    assert_eq!(v4("192.168.1.7/16").to_string(), "192.168.1.7/16");
+    assert_eq!(v6("2001:db8:0:0::1/64").to_string(), "2001:db8::1/64");
+    assert_eq!(IpNet::V4(v4("10.0.0.0/8")).to_string(), "10.0.0.0/8");
+
+    assert_eq!(format!("{:>12}", v4("10.0.0.0/8")), "  10.0.0.0/8");
+    assert_eq!(format!("{:<12}|", v6("::/0")), "::/0        |");
+    assert_eq!(format!("{:?}", v4("10.0.0.0/8")), "10.0.0.0/8");
+}
+
+#[test]
+fn masks() {
+    let net = v4("172.16.123.45/12");
+    assert_eq!(net.netmask(), Ipv4Addr::new(255, 240, 0, 0));
+    assert_eq!(net.hostmask(), Ipv4Addr::new(0, 15, 255, 255));
+    assert_eq!(net.network(), Ipv4Addr::new(172, 16, 0, 0));
+    assert_eq!(net.broadcast(), Ipv4Addr::new(172, 31, 255, 255));
+    assert_eq!(net.trunc(), v4("172.16.0.0/12"));
+
+    assert_eq!(v4("1.2.3.4/0").netmask(), Ipv4Addr::UNSPECIFIED);
+    assert_eq!(v4("1.2.3.4/0").network(), Ipv4Addr::UNSPECIFIED);
+    assert_eq!(v4("1.2.3.4/32").netmask(), Ipv4Addr::BROADCAST);
+    assert_eq!(v4("1.2.3.4/32").broadcast(), Ipv4Addr::new(1, 2, 3, 4));
+
+    let net = v6("2001:db8:1234::1/36");
+    assert_eq!(net.netmask(), Ipv6Addr::new(0xffff, 0xffff, 0xf000, 0, 0, 0, 0, 0));
+    assert_eq!(net.network(), Ipv6Addr::new(0x2001, 0xdb8, 0x1000, 0, 0, 0, 0, 0));
+    assert_eq!(
+        net.last_addr().segments(),
+        [0x2001, 0xdb8, 0x1fff, 0xffff, 0xffff, 0xffff, 0xffff, 0xffff]
+    );
+    assert_eq!(v6("::1/0").hostmask(), Ipv6Addr::from_bits(u128::MAX));
+    assert_eq!(v6("::1/128").hostmask(), Ipv6Addr::UNSPECIFIED);
+}
+
+#[test]
+fn contains() {
+    let net = v4("10.0.0.0/8");
+    assert!(net.contains(Ipv4Addr::new(10, 0, 0, 0)));
+    assert!(net.contains(Ipv4Addr::new(10, 255, 255, 255)));
+    assert!(!net.contains(Ipv4Addr::new(11, 0, 0, 0)));
+    assert!(v4("0.0.0.0/0").contains(Ipv4Addr::BROADCAST));
+
+    assert!(net.contains_net(net));
+    assert!(net.contains_net(v4("10.1.0.0/16")));
+    assert!(!net.contains_net(v4("10.0.0.0/7")));
+    assert!(!net.contains_net(v4("11.0.0.0/16")));
+
+    let net = v6("fe80::/10");
+    assert!(net.contains(Ipv6Addr::new(0xfebf, 0, 0, 0, 0, 0, 0, 1)));
+    assert!(!net.contains(Ipv6Addr::new(0xfec0, 0, 0, 0, 0, 0, 0, 0)));
+    assert!(net.contains_net(v6("fe80::/64")));
+
+    let net = IpNet::V4(v4("127.0.0.0/8"));
+    assert!(net.contains(IpAddr::V4(Ipv4Addr::LOCALHOST)));
+    assert!(!net.contains(IpAddr::V6(Ipv6Addr::LOCALHOST)));
+    assert!(!net.contains_net(IpNet::V6(v6("::/0"))));
+}
+
+#[test]
+fn supernet() {
+    assert_eq!(v4("10.1.0.0/16").supernet(), Some(v4("10.0.0.0/15")));
+    assert_eq!(v4("10.1.2.3/1").supernet(), Some(v4("0.0.0.0/0")));
+    assert_eq!(v4("10.1.2.3/0").supernet(), None);
+    assert_eq!(v6("2001:db9::/32").supernet(), Some(v6("2001:db8::/31")));
+    assert_eq!(v6("::/0").supernet(), None);
+}
+
+#[test]
+fn hosts() {
+    assert!(
+        v4("192.168.0.0/30")
+            .hosts()
+            .eq([Ipv4Addr::new(192, 168, 0, 1), Ipv4Addr::new(192, 168, 0, 2)])
+    );
+    assert!(
+        v4("192.168.0.0/31")
+            .hosts()
+            .eq([Ipv4Addr::new(192, 168, 0, 0), Ipv4Addr::new(192, 168, 0, 1)])
+    );
+    assert!(v4("192.168.0.5/32").hosts().eq([Ipv4Addr::new(192, 168, 0, 5)]));
+    assert_eq!(v4("10.0.0.0/24").hosts().count(), 254);
+    assert_eq!(v4("0.0.0.0/0").hosts().last(), Some(Ipv4Addr::new(255, 255, 255, 254)));
+
+    assert_eq!(v6("2001:db8::/126").hosts().count(), 4);
+    assert!(v6("::1/128").hosts().eq([Ipv6Addr::LOCALHOST]));
+}
+
+#[test]
+fn subnets() {
+    let net = v4("10.0.0.0/23");
+    assert!(net.subnets(25).unwrap().eq([
+        v4("10.0.0.0/25"),
+        v4("10.0.0.128/25"),
+        v4("10.0.1.0/25"),
+        v4("10.0.1.128/25"),
+    ]));
+    assert!(net.subnets(23).unwrap().eq([net]));
+    assert!(net.subnets(22).is_err());
+    assert!(net.subnets(33).is_err());
+
+    // The subnets start at the network address, regardless of the host bits.
+    assert!(v4("10.0.0.1/31").subnets(32).unwrap().eq([v4("10.0.0.0/32"), v4("10.0.0.1/32")]));
+
+    assert_eq!(v4("0.0.0.0/0").subnets(8).unwrap().count(), 256);
+    assert_eq!(v4("255.255.255.0/24").subnets(32).unwrap().last(), Some(v4("255.255.255.255/32")));
+
+    let mut iter = v6("::/0").subnets(1).unwrap();
+    assert_eq!(iter.next(), Some(v6("::/1")));
+    assert_eq!(iter.next(), Some(v6("8000::/1")));
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next(), None);
+    assert!(v6("2001:db8::/32").subnets(129).is_err());
+}
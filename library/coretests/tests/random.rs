@@ -0,0 +1,98 @@
+use core::random::{Distribution, Rng, SeededRng};
+
+#[test]
+fn seeded_rng_is_reproducible() {
+    // Reference values of xoshiro256++ seeded with SplitMix64.
+    let mut rng = SeededRng::new(0);
+    assert_eq!(rng.next_u64(), 0x53175d61490b23df);
+    assert_eq!(rng.next_u64(), 0x61da6f3dc380d507);
+    assert_eq!(rng.next_u64(), 0x5c0fdf91ec9a7bfc);
+
+    let mut rng = SeededRng::new(42);
+    assert_eq!(rng.next_u64(), 0xd0764d4f4476689f);
+    assert_eq!(rng.next_u32(), 0x519e4174);
+}
+
+#[test]
+fn seeded_rng_fill_bytes() {
+    let mut bytes = [0; 13];
+    SeededRng::new(0).fill_bytes(&mut bytes);
+    assert_eq!(
+        bytes,
+        [0xdf, 0x23, 0x0b, 0x49, 0x61, 0x5d, 0x17, 0x53, 0x07, 0xd5, 0x80, 0xc3, 0x3d]
+    );
+
+    let x: u64 = (..).sample(&mut SeededRng::new(0));
+    assert_eq!(x, 0x53175d61490b23df);
+}
+
+#[test]
+fn seeded_rng_from_seed() {
+    assert!(SeededRng::from_seed([0; 32]) == SeededRng::new(0));
+
+    let mut seed = [0; 32];
+    seed[0] = 1;
+    let mut a = SeededRng::from_seed(seed);
+    let mut b = SeededRng::from_rng(&mut a.clone());
+    assert!(a != SeededRng::new(0));
+    assert_ne!(a.next_u64(), b.next_u64());
+}
+
+#[test]
+fn int_ranges() {
+    let mut rng = SeededRng::new(1);
+    let mut counts = [0; 6];
+    for _ in 0..6000 {
+        let roll: u8 = (1..=6).sample(&mut rng);
+        counts[usize::from(roll - 1)] += 1;
+    }
+    assert!(counts.iter().all(|&count| (900..1100).contains(&count)), "{counts:?}");
+
+    for _ in 0..100 {
+        let x: i32 = (-3..3).sample(&mut rng);
+        assert!((-3..3).contains(&x));
+        let x: i128 = (i128::MIN..i128::MIN + 2).sample(&mut rng);
+        assert!(x == i128::MIN || x == i128::MIN + 1);
+        let x: isize = (isize::MIN..=isize::MAX).sample(&mut rng);
+        let _ = x;
+        let x: u64 = (7..=7).sample(&mut rng);
+        assert_eq!(x, 7);
+        let x: u16 = (0..u16::MAX).sample(&mut rng);
+        assert_ne!(x, u16::MAX);
+    }
+}
+
+#[test]
+fn float_ranges() {
+    let mut rng = SeededRng::new(2);
+    let mut sum = 0.0;
+    for _ in 0..1000 {
+        let x: f64 = (-1.0..1.0).sample(&mut rng);
+        assert!((-1.0..1.0).contains(&x));
+        sum += x;
+        let x: f32 = (10.0..=20.0).sample(&mut rng);
+        assert!((10.0..=20.0).contains(&x));
+    }
+    assert!(sum.abs() < 100.0);
+
+    let x: f32 = (0.5..=0.5).sample(&mut rng);
+    assert_eq!(x, 0.5);
+}
+
+#[test]
+#[should_panic = "cannot sample from an empty range"]
+fn empty_int_range() {
+    let _: u32 = (5..5).sample(&mut SeededRng::new(0));
+}
+
+#[test]
+#[should_panic = "cannot sample from an empty range"]
+fn empty_float_range() {
+    let _: f64 = (f64::NAN..=1.0).sample(&mut SeededRng::new(0));
+}
+
+#[test]
+#[should_panic = "cannot sample from an infinite range"]
+fn infinite_float_range() {
+    let _: f64 = (f64::MIN..f64::MAX).sample(&mut SeededRng::new(0));
+}
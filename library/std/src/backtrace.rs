@@ -89,12 +89,14 @@
 // a backtrace or actually symbolizing it.
 
 use crate::backtrace_rs::{self, BytesOrWideString};
+use crate::borrow::Cow;
 use crate::ffi::c_void;
 use crate::panic::UnwindSafe;
+use crate::path::Path;
 use crate::sync::LazyLock;
 use crate::sync::atomic::Ordering::Relaxed;
 use crate::sync::atomic::{Atomic, AtomicU8};
-use crate::sys::backtrace::{lock, output_filename, set_image_base};
+use crate::sys::backtrace::{lock, output_filename, path_from_bytes_or_wide, set_image_base};
 use crate::{env, fmt};
 
 /// A captured OS thread stack backtrace.
@@ -159,11 +161,21 @@ enum RawFrame {
     Fake,
 }
 
-struct BacktraceSymbol {
+/// A resolved symbol of a [`BacktraceFrame`].
+///
+/// A frame usually resolves to a single symbol: the function that was executing. When functions
+/// were inlined into that function, the frame resolves to several symbols, starting with the
+/// innermost inlined function.
+///
+/// Every piece of information about a symbol is optional, as it depends on the debug
+/// information available at runtime.
+#[unstable(feature = "backtrace_frames", issue = "79676")]
+pub struct BacktraceSymbol {
     name: Option<Vec<u8>>,
     filename: Option<BytesOrWide>,
     lineno: Option<u32>,
     colno: Option<u32>,
+    inlined: bool,
 }
 
 enum BytesOrWide {
@@ -207,6 +219,7 @@ fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
     }
 }
 
+#[unstable(feature = "backtrace_frames", issue = "79676")]
 impl fmt::Debug for BacktraceSymbol {
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
         // FIXME: improve formatting: https://github.com/rust-lang/rust/issues/65280
@@ -378,6 +391,129 @@ pub fn frames(&'a self) -> &'a [BacktraceFrame] {
     }
 }
 
+impl BacktraceFrame {
+    /// Returns the instruction pointer of this frame.
+    ///
+    /// This is normally the next instruction to execute in the frame, but not all
+    /// implementations list this with 100% accuracy (though it's generally pretty close).
+    #[must_use]
+    #[unstable(feature = "backtrace_frames", issue = "79676")]
+    pub fn ip(&self) -> *mut c_void {
+        self.frame.ip()
+    }
+
+    /// Returns the starting address of the function of this frame.
+    ///
+    /// This attempts to rewind the instruction pointer returned by [`ip`](Self::ip) to the start
+    /// of the function, but it may just return the instruction pointer on platforms where this
+    /// isn't possible.
+    #[must_use]
+    #[unstable(feature = "backtrace_frames", issue = "79676")]
+    pub fn symbol_address(&self) -> *mut c_void {
+        self.frame.symbol_address()
+    }
+
+    /// Returns the base address at which the module (executable or shared library) containing
+    /// this frame is loaded, if known.
+    ///
+    /// Subtracting this from the [instruction pointer](Self::ip) gives an address that doesn't
+    /// depend on where the module was loaded, which can be symbolized offline.
+    #[must_use]
+    #[unstable(feature = "backtrace_frames", issue = "79676")]
+    pub fn module_base_address(&self) -> Option<*mut c_void> {
+        self.frame.module_base_address()
+    }
+
+    /// Returns the symbols this frame resolved to.
+    ///
+    /// This is empty if the frame couldn't be resolved, for instance because no debug
+    /// information is available. If functions were inlined into the function of this frame,
+    /// they come first, and the function itself comes last.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(backtrace_frames)]
+    ///
+    /// use std::backtrace::Backtrace;
+    ///
+    /// let backtrace = Backtrace::force_capture();
+    /// for frame in backtrace.frames() {
+    ///     for symbol in frame.symbols() {
+    ///         if let (Some(name), Some(line)) = (symbol.name(), symbol.lineno()) {
+    ///             println!("{name} at line {line}");
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    #[must_use]
+    #[unstable(feature = "backtrace_frames", issue = "79676")]
+    pub fn symbols(&self) -> &[BacktraceSymbol] {
+        &self.symbols
+    }
+}
+
+impl BacktraceSymbol {
+    /// Returns the demangled name of this symbol, without the hash Rust appends to symbol names.
+    ///
+    /// Use [`name_bytes`](Self::name_bytes) to get the name as it appears in the binary.
+    #[must_use]
+    #[unstable(feature = "backtrace_frames", issue = "79676")]
+    pub fn name(&self) -> Option<String> {
+        self.name.as_ref().map(|b| format!("{:#}", backtrace_rs::SymbolName::new(b)))
+    }
+
+    /// Returns the raw name of this symbol, as it appears in the binary.
+    ///
+    /// This is typically a mangled name.
+    #[must_use]
+    #[unstable(feature = "backtrace_frames", issue = "79676")]
+    pub fn name_bytes(&self) -> Option<&[u8]> {
+        self.name.as_deref()
+    }
+
+    /// Returns the path of the source file this symbol is defined in.
+    ///
+    /// This is the path recorded in the debug information, which may be relative, or refer to the
+    /// machine the program was built on.
+    #[must_use]
+    #[unstable(feature = "backtrace_frames", issue = "79676")]
+    pub fn filename(&self) -> Option<Cow<'_, Path>> {
+        self.filename.as_ref().and_then(|b| {
+            path_from_bytes_or_wide(match b {
+                BytesOrWide::Bytes(w) => BytesOrWideString::Bytes(w),
+                BytesOrWide::Wide(w) => BytesOrWideString::Wide(w),
+            })
+        })
+    }
+
+    /// Returns the line number in the source file, starting at 1.
+    #[must_use]
+    #[unstable(feature = "backtrace_frames", issue = "79676")]
+    pub fn lineno(&self) -> Option<u32> {
+        self.lineno
+    }
+
+    /// Returns the column number in the source file, starting at 1.
+    #[must_use]
+    #[unstable(feature = "backtrace_frames", issue = "79676")]
+    pub fn colno(&self) -> Option<u32> {
+        self.colno
+    }
+
+    /// Returns `true` if this symbol is a function that was inlined into the function of its
+    /// frame, rather than the function itself.
+    ///
+    /// The source location of an inlined symbol is where the frame was executing inside of the
+    /// inlined function, while the source location of the following symbol is where the
+    /// inlined function was called.
+    #[must_use]
+    #[unstable(feature = "backtrace_frames", issue = "79676")]
+    pub fn is_inlined(&self) -> bool {
+        self.inlined
+    }
+}
+
 #[stable(feature = "backtrace", since = "1.65.0")]
 impl fmt::Display for Backtrace {
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -456,9 +592,15 @@ pub(super) fn lazy_resolve(mut capture: Capture) -> LazyResolve {
                             }),
                             lineno: symbol.lineno(),
                             colno: symbol.colno(),
+                            inlined: true,
                         });
                     });
                 }
+                // Symbols are resolved from the innermost inlined function to the function
+                // that was actually called, which comes last.
+                if let Some(symbol) = symbols.last_mut() {
+                    symbol.inlined = false;
+                }
             }
 
             capture
@@ -475,4 +617,20 @@ fn ip(&self) -> *mut c_void {
             RawFrame::Fake => crate::ptr::without_provenance_mut(1),
         }
     }
+
+    fn symbol_address(&self) -> *mut c_void {
+        match self {
+            RawFrame::Actual(frame) => frame.symbol_address(),
+            #[cfg(test)]
+            RawFrame::Fake => crate::ptr::without_provenance_mut(1),
+        }
+    }
+
+    fn module_base_address(&self) -> Option<*mut c_void> {
+        match self {
+            RawFrame::Actual(frame) => frame.module_base_address(),
+            #[cfg(test)]
+            RawFrame::Fake => None,
+        }
+    }
 }
@@ -10,6 +10,7 @@ fn generate_fake_frames() -> Vec<BacktraceFrame> {
                 filename: Some(BytesOrWide::Bytes(b"rust/backtrace.rs".to_vec())),
                 lineno: Some(100),
                 colno: None,
+                inlined: false,
             }],
         },
         BacktraceFrame {
@@ -19,6 +20,7 @@ fn generate_fake_frames() -> Vec<BacktraceFrame> {
                 filename: None,
                 lineno: None,
                 colno: None,
+                inlined: false,
             }],
         },
         BacktraceFrame {
@@ -29,12 +31,14 @@ fn generate_fake_frames() -> Vec<BacktraceFrame> {
                     filename: Some(BytesOrWide::Bytes(b"rust/rt.rs".to_vec())),
                     lineno: Some(300),
                     colno: Some(5),
+                    inlined: true,
                 },
                 BacktraceSymbol {
                     name: Some(b"std::rt::lang_start".to_vec()),
                     filename: Some(BytesOrWide::Bytes(b"rust/rt.rs".to_vec())),
                     lineno: Some(400),
                     colno: None,
+                    inlined: false,
                 },
             ],
         },
@@ -91,6 +95,34 @@ fn test_frames() {
     assert!(iter.all(|(f, e)| format!("{f:#?}") == *e));
 }
 
+#[test]
+fn test_frame_accessors() {
+    let frames = generate_fake_frames();
+
+    assert!(frames.iter().all(|frame| !frame.ip().is_null()));
+    assert!(frames.iter().all(|frame| frame.module_base_address().is_none()));
+
+    let symbols = frames[0].symbols();
+    assert_eq!(symbols.len(), 1);
+    assert_eq!(symbols[0].name().as_deref(), Some("std::backtrace::Backtrace::create"));
+    assert_eq!(symbols[0].name_bytes(), Some(&b"std::backtrace::Backtrace::create"[..]));
+    assert_eq!(symbols[0].filename().as_deref(), Some(Path::new("rust/backtrace.rs")));
+    assert_eq!(symbols[0].lineno(), Some(100));
+    assert_eq!(symbols[0].colno(), None);
+    assert!(!symbols[0].is_inlined());
+
+    let symbols = frames[1].symbols();
+    assert_eq!(symbols[0].filename(), None);
+    assert_eq!(symbols[0].lineno(), None);
+
+    let symbols = frames[2].symbols();
+    assert_eq!(symbols.len(), 2);
+    assert_eq!(symbols[0].name().as_deref(), Some("std::rt::lang_start_internal"));
+    assert_eq!(symbols[0].colno(), Some(5));
+    assert!(symbols[0].is_inlined());
+    assert!(!symbols[1].is_inlined());
+}
+
 #[test]
 fn backtrace_unwind_safe() {
     fn assert_unwind_safe<T: UnwindSafe + RefUnwindSafe>() {}
@@ -885,6 +885,24 @@ pub fn clear(&mut self) {
     pub fn hasher(&self) -> &S {
         self.base.hasher()
     }
+
+    /// Returns a reference to the underlying allocator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(allocator_api)]
+    /// use std::alloc::System;
+    /// use std::collections::HashMap;
+    ///
+    /// let map: HashMap<i32, i32, _, _> = HashMap::new_in(System);
+    /// let _alloc: &System = map.allocator();
+    /// ```
+    #[inline]
+    #[unstable(feature = "allocator_api", issue = "32838")]
+    pub fn allocator(&self) -> &A {
+        self.base.allocator()
+    }
 }
 
 impl<K, V, S, A> HashMap<K, V, S, A>
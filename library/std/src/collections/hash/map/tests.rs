@@ -4,8 +4,9 @@
 use super::Entry::{Occupied, Vacant};
 use super::HashMap;
 use crate::assert_matches;
-use crate::cell::RefCell;
+use crate::cell::{Cell, RefCell};
 use crate::hash::{BuildHasher, BuildHasherDefault, DefaultHasher, RandomState};
+use crate::rc::Rc;
 use crate::test_helpers::test_rng;
 
 // https://github.com/rust-lang/rust/issues/62301
@@ -1057,3 +1058,36 @@ fn build_hasher(&self) -> Self::Hasher {
     const Z: HashMap<(), (), BuildHasherDefault<DefaultHasher>> = Default::default();
     assert_eq!(X, Z);
 }
+
+#[test]
+fn test_allocator() {
+    use crate::alloc::{AllocError, Allocator, Layout, System};
+    use crate::ptr::NonNull;
+
+    #[derive(Clone, Default)]
+    struct CountingAllocator(Rc<Cell<usize>>);
+
+    unsafe impl Allocator for CountingAllocator {
+        fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+            self.0.set(self.0.get() + 1);
+            System.allocate(layout)
+        }
+
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+            unsafe { System.deallocate(ptr, layout) }
+        }
+    }
+
+    let alloc = CountingAllocator::default();
+    let mut m = HashMap::new_in(alloc.clone());
+    assert_eq!(alloc.0.get(), 0);
+    m.insert(1, 2);
+    assert!(alloc.0.get() > 0);
+    assert_eq!(m[&1], 2);
+    assert!(Rc::ptr_eq(&m.allocator().0, &alloc.0));
+
+    let allocations = alloc.0.get();
+    let m: HashMap<i32, i32, _, _> = HashMap::with_capacity_in(16, alloc.clone());
+    assert!(m.capacity() >= 16);
+    assert_eq!(alloc.0.get(), allocations + 1);
+}
@@ -557,6 +557,24 @@ pub fn clear(&mut self) {
     pub fn hasher(&self) -> &S {
         self.base.hasher()
     }
+
+    /// Returns a reference to the underlying allocator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(allocator_api)]
+    /// use std::alloc::System;
+    /// use std::collections::HashSet;
+    ///
+    /// let set: HashSet<i32, _, _> = HashSet::new_in(System);
+    /// let _alloc: &System = set.allocator();
+    /// ```
+    #[inline]
+    #[unstable(feature = "allocator_api", issue = "32838")]
+    pub fn allocator(&self) -> &A {
+        self.base.allocator()
+    }
 }
 
 impl<T, S, A> HashSet<T, S, A>
@@ -527,3 +527,19 @@ fn test_insert_does_not_overwrite_the_value() {
             must be the same as first value pointer we inserted"
     );
 }
+
+#[test]
+fn test_allocator() {
+    use crate::alloc::System;
+
+    let mut s = HashSet::new_in(System);
+    assert_eq!(s.capacity(), 0);
+    assert!(s.insert(1));
+    assert!(!s.insert(1));
+    assert!(s.contains(&1));
+    let _: &System = s.allocator();
+
+    let s: HashSet<i32, _, _> = HashSet::with_capacity_in(16, System);
+    assert!(s.capacity() >= 16);
+    assert!(s.is_empty());
+}
@@ -41,12 +41,14 @@
 ))]
 mod tests;
 
+use crate::collections::VecDeque;
 use crate::ffi::OsString;
 use crate::io::{self, BorrowedCursor, IoSlice, IoSliceMut, Read, Seek, SeekFrom, Write};
 use crate::path::{Path, PathBuf};
+use crate::sync::Arc;
 use crate::sys::{AsInner, AsInnerMut, FromInner, IntoInner, fs as fs_imp};
 use crate::time::SystemTime;
-use crate::{error, fmt};
+use crate::{error, fmt, mem};
 
 /// An object providing access to an open file on the filesystem.
 ///
@@ -233,6 +235,162 @@ pub struct Dir {
 #[stable(feature = "rust1", since = "1.0.0")]
 pub struct DirEntry(fs_imp::DirEntry);
 
+/// A file that atomically replaces another one once it is complete.
+///
+/// An `AtomicFile` is created with [`AtomicFile::create`], in the same
+/// directory as the file it replaces. It can be written like any [`File`],
+/// and other processes never see it under its final name until [`commit`] is
+/// called: they either see the whole previous contents of that file, or the
+/// whole new contents, even if the system crashes in the meantime.
+///
+/// Dropping an `AtomicFile` without committing it discards it, and leaves the
+/// file it would have replaced untouched.
+///
+/// [`commit`]: AtomicFile::commit
+///
+/// # Platform-specific behavior
+///
+/// On Linux, the file is created without a name using `O_TMPFILE` where the
+/// filesystem supports it, so that nothing is left behind if the process dies
+/// before committing it. Elsewhere, it is created under a hidden, temporary
+/// name in the same directory, which is removed when it is discarded.
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(fs_atomic_file)]
+///
+/// use std::fs::AtomicFile;
+/// use std::io::Write;
+///
+/// fn main() -> std::io::Result<()> {
+///     let mut file = AtomicFile::create("config.toml")?;
+///     writeln!(file, "verbose = true")?;
+///     file.commit()
+/// }
+/// ```
+#[unstable(feature = "fs_atomic_file", issue = "none")]
+#[derive(Debug)]
+pub struct AtomicFile {
+    file: File,
+    path: PathBuf,
+    // The name of the file until it is committed, if it has one.
+    temp_path: Option<PathBuf>,
+}
+
+/// Options for copying a directory and all of its contents.
+///
+/// This builder configures how [`copy_dir_all`] copies a directory tree. Like
+/// [`OpenOptions`], it is created with [`CopyDirOptions::new`], configured by
+/// chaining methods, and used by calling [`copy`] at the end.
+///
+/// [`copy`]: CopyDirOptions::copy
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(fs_copy_dir)]
+///
+/// use std::fs::CopyDirOptions;
+///
+/// // Copies as much as possible, and reports what couldn't be copied.
+/// let result = CopyDirOptions::new().preserve_times(true).continue_on_error(true).copy("a", "b");
+/// if let Err(e) = result {
+///     for (path, error) in e.errors() {
+///         eprintln!("failed to copy {}: {error}", path.display());
+///     }
+/// }
+/// ```
+#[unstable(feature = "fs_copy_dir", issue = "none")]
+#[derive(Clone, Debug)]
+pub struct CopyDirOptions {
+    preserve_permissions: bool,
+    preserve_times: bool,
+    continue_on_error: bool,
+}
+
+/// The errors that occurred while copying a directory with [`CopyDirOptions`].
+///
+/// Unless [`CopyDirOptions::continue_on_error`] is set, there is exactly one
+/// error: the one that stopped the copy.
+#[unstable(feature = "fs_copy_dir", issue = "none")]
+#[derive(Debug)]
+pub struct CopyDirError {
+    errors: Vec<(PathBuf, io::Error)>,
+    bytes_copied: u64,
+}
+
+/// Options for removing a directory and all of its contents.
+///
+/// This builder configures how a directory tree is removed, like
+/// [`remove_dir_all`] does. It is created with [`RemoveDirOptions::new`],
+/// configured by chaining methods, and used by calling [`remove`] at the end.
+///
+/// [`remove`]: RemoveDirOptions::remove
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(fs_copy_dir)]
+///
+/// use std::fs::RemoveDirOptions;
+///
+/// // Removes as much as possible, and reports what couldn't be removed.
+/// if let Err(e) = RemoveDirOptions::new().continue_on_error(true).remove("a") {
+///     for (path, error) in e.errors() {
+///         eprintln!("failed to remove {}: {error}", path.display());
+///     }
+/// }
+/// ```
+#[unstable(feature = "fs_copy_dir", issue = "none")]
+#[derive(Clone, Debug)]
+pub struct RemoveDirOptions {
+    continue_on_error: bool,
+}
+
+/// The errors that occurred while removing a directory with
+/// [`RemoveDirOptions`].
+///
+/// Unless [`RemoveDirOptions::continue_on_error`] is set, there is exactly one
+/// error: the one that stopped the removal.
+#[unstable(feature = "fs_copy_dir", issue = "none")]
+#[derive(Debug)]
+pub struct RemoveDirError {
+    errors: Vec<(PathBuf, io::Error)>,
+}
+
+/// Iterator over the entries in a directory and, recursively, in all of its
+/// subdirectories.
+///
+/// This iterator is returned from the [`walk_dir`] function of this module and
+/// will yield instances of <code>[io::Result]<[DirEntry]></code>. It can be
+/// configured before iterating over it, with [`max_depth`], [`follow_symlinks`]
+/// and [`breadth_first`].
+///
+/// The order in which the entries of each directory are returned is platform
+/// and filesystem dependent, as with [`ReadDir`].
+///
+/// # Errors
+///
+/// This [`io::Result`] will be an [`Err`] if an error occurred while fetching
+/// the next entry from the OS, or while opening a directory to walk into it.
+/// The walk goes on after an error, so the remaining entries can still be
+/// reached.
+///
+/// [`max_depth`]: WalkDir::max_depth
+/// [`follow_symlinks`]: WalkDir::follow_symlinks
+/// [`breadth_first`]: WalkDir::breadth_first
+#[unstable(feature = "fs_walk_dir", issue = "none")]
+#[derive(Debug)]
+pub struct WalkDir {
+    max_depth: usize,
+    follow_symlinks: bool,
+    breadth_first: bool,
+    // The directories left to read. The one being read is the last one when
+    // walking depth-first, and the first one when walking breadth-first.
+    dirs: VecDeque<WalkDirNode>,
+}
+
 /// Options and flags which can be used to configure how a file is opened.
 ///
 /// This builder exposes the ability to configure how a [`File`] is opened and
@@ -1544,6 +1702,183 @@ fn stream_position(&mut self) -> io::Result<u64> {
 #[unstable(feature = "core_io_internals", reason = "exposed only for libstd", issue = "none")]
 impl crate::io::IoHandle for File {}
 
+impl AtomicFile {
+    /// Creates a new file, to replace the file at `path` once it is committed.
+    ///
+    /// The new file is empty, open for reading and writing, and gets the
+    /// default permissions of new files rather than those of the file it
+    /// replaces. Nothing happens to `path` until [`commit`] is called, and
+    /// there doesn't need to be a file there yet.
+    ///
+    /// [`commit`]: AtomicFile::commit
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if `path` doesn't end in a file
+    /// name, or if the file can't be created in the directory of `path`.
+    #[unstable(feature = "fs_atomic_file", issue = "none")]
+    pub fn create<P: AsRef<Path>>(path: P) -> io::Result<AtomicFile> {
+        let path = path.as_ref();
+        let dir = atomic_file_dir(path)?;
+        if let Some(inner) = fs_imp::create_tmpfile(dir)? {
+            return Ok(AtomicFile { file: File { inner }, path: path.to_owned(), temp_path: None });
+        }
+        let (file, temp_path) = with_temp_name(path, |temp_path| {
+            OpenOptions::new().read(true).write(true).create_new(true).open(temp_path)
+        })?;
+        Ok(AtomicFile { file, path: path.to_owned(), temp_path: Some(temp_path) })
+    }
+
+    /// Returns a reference to the file being written.
+    #[unstable(feature = "fs_atomic_file", issue = "none")]
+    pub fn get_ref(&self) -> &File {
+        &self.file
+    }
+
+    /// Returns a mutable reference to the file being written.
+    ///
+    /// The file must not be renamed or removed through other means than this
+    /// `AtomicFile`.
+    #[unstable(feature = "fs_atomic_file", issue = "none")]
+    pub fn get_mut(&mut self) -> &mut File {
+        &mut self.file
+    }
+
+    /// Replaces the file at the path given to [`AtomicFile::create`] with this
+    /// one.
+    ///
+    /// The contents of the file are flushed to disk first, and the directory
+    /// they were moved into afterwards, so that the new contents are durable
+    /// once this function returns. If there already was a file at that path,
+    /// it is replaced in a single step. If it was a symbolic link, the link
+    /// itself is replaced rather than the file it points to.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the file can't be flushed or
+    /// moved into place, in which case it is discarded, and the file that it
+    /// should have replaced is left untouched. An error may also be returned
+    /// if the directory can't be flushed, after the file has been replaced.
+    #[unstable(feature = "fs_atomic_file", issue = "none")]
+    pub fn commit(mut self) -> io::Result<()> {
+        self.file.sync_all()?;
+        let temp_path = match self.temp_path.take() {
+            Some(temp_path) => temp_path,
+            None => self.link()?,
+        };
+        if let Err(e) = rename(&temp_path, &self.path) {
+            let _ = remove_file(&temp_path);
+            return Err(e);
+        }
+        fs_imp::sync_dir(atomic_file_dir(&self.path)?)
+    }
+
+    /// Discards this file, leaving the file it would have replaced untouched.
+    ///
+    /// This is the same as dropping it, except that errors are reported.
+    #[unstable(feature = "fs_atomic_file", issue = "none")]
+    pub fn discard(mut self) -> io::Result<()> {
+        match self.temp_path.take() {
+            Some(temp_path) => remove_file(temp_path),
+            None => Ok(()),
+        }
+    }
+
+    /// Gives a temporary name to a file created without one.
+    #[cfg(target_os = "linux")]
+    fn link(&self) -> io::Result<PathBuf> {
+        with_temp_name(&self.path, |temp_path| fs_imp::link_tmpfile(&self.file.inner, temp_path))
+            .map(|((), temp_path)| temp_path)
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn link(&self) -> io::Result<PathBuf> {
+        unreachable!("files are only created without a name on Linux")
+    }
+}
+
+/// Returns the directory that an [`AtomicFile`] for `path` is created in.
+fn atomic_file_dir(path: &Path) -> io::Result<&Path> {
+    if path.file_name().is_none() {
+        return Err(io::const_error!(
+            io::ErrorKind::InvalidInput,
+            "path doesn't end in a file name"
+        ));
+    }
+    match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => Ok(dir),
+        _ => Ok(Path::new(".")),
+    }
+}
+
+/// Calls `f` with a hidden, random path next to `path`, trying new ones for as
+/// long as it fails because the path is taken.
+fn with_temp_name<T>(
+    path: &Path,
+    mut f: impl FnMut(&Path) -> io::Result<T>,
+) -> io::Result<(T, PathBuf)> {
+    const ATTEMPTS: usize = 32;
+    let file_name = path.file_name().unwrap_or_default();
+    for _ in 0..ATTEMPTS {
+        let mut temp_name = OsString::from(".");
+        temp_name.push(file_name);
+        temp_name.push(format!(".{:016x}.tmp", crate::sys::random::hashmap_random_keys().0));
+        let temp_path = path.with_file_name(temp_name);
+        match f(&temp_path) {
+            Ok(t) => return Ok((t, temp_path)),
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Err(io::const_error!(io::ErrorKind::AlreadyExists, "too many temporary files already exist"))
+}
+
+#[unstable(feature = "fs_atomic_file", issue = "none")]
+impl Drop for AtomicFile {
+    fn drop(&mut self) {
+        if let Some(temp_path) = self.temp_path.take() {
+            let _ = remove_file(temp_path);
+        }
+    }
+}
+
+#[unstable(feature = "fs_atomic_file", issue = "none")]
+impl Read for AtomicFile {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.file.read(buf)
+    }
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        self.file.read_vectored(bufs)
+    }
+    #[inline]
+    fn is_read_vectored(&self) -> bool {
+        self.file.is_read_vectored()
+    }
+}
+#[unstable(feature = "fs_atomic_file", issue = "none")]
+impl Write for AtomicFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.file.write(buf)
+    }
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        self.file.write_vectored(bufs)
+    }
+    #[inline]
+    fn is_write_vectored(&self) -> bool {
+        self.file.is_write_vectored()
+    }
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+#[unstable(feature = "fs_atomic_file", issue = "none")]
+impl Seek for AtomicFile {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.file.seek(pos)
+    }
+}
+
 impl Dir {
     /// Attempts to open a directory at `path` in read-only mode.
     ///
@@ -2628,6 +2963,233 @@ fn as_inner(&self) -> &fs_imp::DirEntry {
     }
 }
 
+impl WalkDir {
+    /// Sets the maximum depth of the entries to return.
+    ///
+    /// The entries directly inside the directory given to [`walk_dir`] have a
+    /// depth of 1, so a maximum depth of 1 returns the same entries as
+    /// [`read_dir`], and a maximum depth of 0 returns no entries at all. There
+    /// is no limit by default.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(fs_walk_dir)]
+    ///
+    /// use std::fs;
+    ///
+    /// // Lists `src` and its direct subdirectories.
+    /// for entry in fs::walk_dir("src").max_depth(2) {
+    ///     println!("{}", entry?.path().display());
+    /// }
+    /// # std::io::Result::Ok(())
+    /// ```
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Sets whether to walk into symbolic links to directories.
+    ///
+    /// By default, symbolic links are returned like any other entry, but not
+    /// walked into. The directory given to [`walk_dir`] is always followed.
+    ///
+    /// When following symbolic links, every link to a directory is checked
+    /// against the directories that lead to it, so that the walk doesn't run
+    /// in circles: a link to one of them results in an error of kind
+    /// [`io::ErrorKind::FilesystemLoop`], right after the link itself is
+    /// returned.
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    pub fn follow_symlinks(mut self, follow_symlinks: bool) -> Self {
+        self.follow_symlinks = follow_symlinks;
+        self
+    }
+
+    /// Sets whether to walk the directory tree breadth-first.
+    ///
+    /// By default, the walk is depth-first: every directory is immediately
+    /// followed by its contents. Breadth-first, all the entries at a given
+    /// depth are returned before any entry deeper than that.
+    ///
+    /// Walking depth-first keeps a directory open for every level between the
+    /// root and the current directory, while walking breadth-first only keeps
+    /// one open, but remembers the path of every directory left to walk.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// When walking depth-first on Linux, Android, FreeBSD and Apple platforms,
+    /// directories are opened relative to the directory they were found in
+    /// rather than through their path, so that replacing a directory with a
+    /// symbolic link while it is being walked can't redirect the walk. When
+    /// walking breadth-first, directories are always opened through their path.
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    pub fn breadth_first(mut self, breadth_first: bool) -> Self {
+        self.breadth_first = breadth_first;
+        self
+    }
+
+    fn current(&mut self) -> Option<&mut WalkDirNode> {
+        if self.breadth_first { self.dirs.front_mut() } else { self.dirs.back_mut() }
+    }
+
+    fn pop_current(&mut self) {
+        if self.breadth_first {
+            self.dirs.pop_front()
+        } else {
+            self.dirs.pop_back()
+        };
+    }
+}
+
+#[unstable(feature = "fs_walk_dir", issue = "none")]
+impl Iterator for WalkDir {
+    type Item = io::Result<DirEntry>;
+
+    fn next(&mut self) -> Option<io::Result<DirEntry>> {
+        let (max_depth, follow_symlinks, breadth_first) =
+            (self.max_depth, self.follow_symlinks, self.breadth_first);
+        loop {
+            let dir = self.current()?;
+            // Only the root can be deeper than the limit, when the limit is 0.
+            if dir.depth > max_depth {
+                self.pop_current();
+                continue;
+            }
+            let entry = match dir.open(follow_symlinks).map(|entries| entries.next()) {
+                Ok(Some(Ok(entry))) => entry,
+                Ok(Some(Err(error))) => return Some(Err(error)),
+                Ok(None) => {
+                    self.pop_current();
+                    continue;
+                }
+                Err(error) => {
+                    self.pop_current();
+                    return Some(Err(error));
+                }
+            };
+            if dir.depth < max_depth {
+                if let Some(subdir) = dir.subdir(&entry, follow_symlinks, breadth_first) {
+                    self.dirs.push_back(subdir);
+                }
+            }
+            return Some(Ok(entry));
+        }
+    }
+}
+
+#[derive(Debug)]
+struct WalkDirNode {
+    contents: WalkDirContents,
+    // The depth of the entries in this directory.
+    depth: usize,
+    // This directory and the ones leading to it, when following symlinks.
+    ancestors: Option<Arc<WalkDirAncestor>>,
+}
+
+#[derive(Debug)]
+enum WalkDirContents {
+    // A directory to open through its path once it is reached.
+    Path(PathBuf),
+    // A directory that couldn't be walked into, with the error to report once it is reached.
+    Failed(io::Error),
+    Open(ReadDir),
+}
+
+#[derive(Debug)]
+struct WalkDirAncestor {
+    canonical_path: PathBuf,
+    parent: Option<Arc<WalkDirAncestor>>,
+}
+
+impl WalkDirNode {
+    fn open(&mut self, follow_symlinks: bool) -> io::Result<&mut ReadDir> {
+        if let WalkDirContents::Path(path) = &self.contents {
+            // Only the root has no ancestors to start with.
+            if follow_symlinks && self.ancestors.is_none() {
+                let canonical_path = canonicalize(path)?;
+                self.ancestors = Some(Arc::new(WalkDirAncestor { canonical_path, parent: None }));
+            }
+            self.contents = WalkDirContents::Open(read_dir(path)?);
+        }
+        if let WalkDirContents::Failed(_) = self.contents {
+            // The placeholder is never looked at: the caller drops this directory.
+            let failed = mem::replace(&mut self.contents, WalkDirContents::Path(PathBuf::new()));
+            let WalkDirContents::Failed(error) = failed else { unreachable!() };
+            return Err(error);
+        }
+        let WalkDirContents::Open(entries) = &mut self.contents else { unreachable!() };
+        Ok(entries)
+    }
+
+    /// Returns the directory to walk into for `entry`, if it is one.
+    fn subdir(
+        &self,
+        entry: &DirEntry,
+        follow_symlinks: bool,
+        breadth_first: bool,
+    ) -> Option<WalkDirNode> {
+        let depth = self.depth + 1;
+        let failed = |error| WalkDirNode {
+            contents: WalkDirContents::Failed(error),
+            depth,
+            ancestors: None,
+        };
+        let is_symlink = match entry.file_type() {
+            Ok(file_type) if file_type.is_dir() => false,
+            Ok(file_type) if file_type.is_symlink() && follow_symlinks => {
+                if !entry.path().is_dir() {
+                    return None;
+                }
+                true
+            }
+            Ok(_) => return None,
+            Err(error) => return Some(failed(error)),
+        };
+
+        let ancestors = match &self.ancestors {
+            Some(parent) => {
+                let canonical_path = if is_symlink {
+                    match canonicalize(entry.path()) {
+                        Ok(path) => path,
+                        Err(error) => return Some(failed(error)),
+                    }
+                } else {
+                    parent.canonical_path.join(entry.file_name())
+                };
+                let mut ancestor = Some(parent);
+                while let Some(dir) = ancestor {
+                    if dir.canonical_path == canonical_path {
+                        return Some(failed(io::Error::new(
+                            io::ErrorKind::FilesystemLoop,
+                            format!(
+                                "`{}` links to `{}`, which contains it",
+                                entry.path().display(),
+                                canonical_path.display(),
+                            ),
+                        )));
+                    }
+                    ancestor = dir.parent.as_ref();
+                }
+                Some(Arc::new(WalkDirAncestor { canonical_path, parent: Some(parent.clone()) }))
+            }
+            None => None,
+        };
+
+        let contents = if breadth_first {
+            // Keeping the directory open until it is reached could exhaust file descriptors,
+            // since every directory of a level is queued before any is read.
+            WalkDirContents::Path(entry.path())
+        } else {
+            match fs_imp::read_dir_entry(&entry.0, follow_symlinks) {
+                Ok(entries) => WalkDirContents::Open(ReadDir(entries)),
+                Err(error) => WalkDirContents::Failed(error),
+            }
+        };
+        Some(WalkDirNode { contents, depth, ancestors })
+    }
+}
+
 /// Removes a file from the filesystem.
 ///
 /// Note that there is no
@@ -2857,6 +3419,579 @@ pub fn copy<P: AsRef<Path>, Q: AsRef<Path>>(from: P, to: Q) -> io::Result<u64> {
     fs_imp::copy(from.as_ref(), to.as_ref())
 }
 
+/// Copies a directory and all of its contents to another path.
+///
+/// Files are copied like with [`copy`], including their permissions, and
+/// symbolic links are copied as symbolic links rather than followed. The
+/// permissions of directories are copied too, once their contents have been.
+/// The copy stops at the first error. To copy timestamps or to go on after an
+/// error, use [`CopyDirOptions`] instead.
+///
+/// `to` is created if it doesn't exist yet. If it does, the contents of `from`
+/// are merged into it, replacing files and symbolic links with the same names.
+///
+/// On success, the total number of bytes copied is returned.
+///
+/// # Platform-specific behavior
+///
+/// On Linux (including Android), file contents are copied with
+/// copy_file_range(2), sendfile(2), or splice(2) where possible, like [`copy`],
+/// so filesystems that support it share the data between both copies instead
+/// of duplicating it.
+///
+/// Note that platform-specific behavior [may change in the future][changes].
+///
+/// [changes]: io#platform-specific-behavior
+///
+/// # Errors
+///
+/// This function will return an error in the following situations, but is not
+/// limited to just these cases:
+///
+/// * `from` is not a directory, or doesn't exist.
+/// * `to` is inside of `from`.
+/// * `from` contains something other than files, directories and symbolic
+///   links, such as a named pipe.
+/// * The current process does not have the permission rights to read
+///   something in `from`, or write something in `to`.
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(fs_copy_dir)]
+///
+/// use std::fs;
+///
+/// fn main() -> std::io::Result<()> {
+///     fs::copy_dir_all("assets", "target/assets")?;
+///     Ok(())
+/// }
+/// ```
+#[unstable(feature = "fs_copy_dir", issue = "none")]
+pub fn copy_dir_all<P: AsRef<Path>, Q: AsRef<Path>>(from: P, to: Q) -> io::Result<u64> {
+    CopyDirOptions::new().copy(from, to).map_err(io::Error::from)
+}
+
+impl CopyDirOptions {
+    /// Creates a blank new set of options.
+    ///
+    /// By default, permissions are copied, timestamps are not, and the copy
+    /// stops at the first error, like with [`copy_dir_all`].
+    #[unstable(feature = "fs_copy_dir", issue = "none")]
+    #[must_use]
+    pub fn new() -> Self {
+        CopyDirOptions {
+            preserve_permissions: true,
+            preserve_times: false,
+            continue_on_error: false,
+        }
+    }
+
+    /// Sets whether to copy the permissions of files and directories.
+    ///
+    /// When not copying them, copies get the default permissions of new files
+    /// and directories.
+    #[unstable(feature = "fs_copy_dir", issue = "none")]
+    pub fn preserve_permissions(&mut self, preserve: bool) -> &mut Self {
+        self.preserve_permissions = preserve;
+        self
+    }
+
+    /// Sets whether to copy the last access and modification times of files
+    /// and directories.
+    ///
+    /// The timestamps of symbolic links themselves are not copied.
+    #[unstable(feature = "fs_copy_dir", issue = "none")]
+    pub fn preserve_times(&mut self, preserve: bool) -> &mut Self {
+        self.preserve_times = preserve;
+        self
+    }
+
+    /// Sets whether to go on copying the rest of the directory after an error.
+    ///
+    /// When set, everything that can be copied is, and all the errors are
+    /// returned together at the end. Nothing inside of a directory that can't
+    /// be created or read is copied.
+    #[unstable(feature = "fs_copy_dir", issue = "none")]
+    pub fn continue_on_error(&mut self, continue_on_error: bool) -> &mut Self {
+        self.continue_on_error = continue_on_error;
+        self
+    }
+
+    /// Copies the directory `from` and all of its contents to `to`, with the
+    /// options specified by `self`.
+    ///
+    /// On success, the total number of bytes copied is returned. See
+    /// [`copy_dir_all`] for more details.
+    ///
+    /// # Errors
+    ///
+    /// Returns every error that occurred, along with the path that couldn't be
+    /// copied. If `from` itself can't be copied, nothing is.
+    #[unstable(feature = "fs_copy_dir", issue = "none")]
+    pub fn copy<P: AsRef<Path>, Q: AsRef<Path>>(
+        &self,
+        from: P,
+        to: Q,
+    ) -> Result<u64, CopyDirError> {
+        self.copy_dir(from.as_ref(), to.as_ref(), &mut |_, _| {})
+    }
+
+    /// Copies the directory `from` and all of its contents to `to`, calling
+    /// `progress` every time something has been copied.
+    ///
+    /// `progress` is called with the path of every file, directory and
+    /// symbolic link created in `to`, including `to` itself, and the number of
+    /// bytes copied into it. Otherwise, this is the same as [`copy`].
+    ///
+    /// [`copy`]: CopyDirOptions::copy
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(fs_copy_dir)]
+    ///
+    /// use std::fs::CopyDirOptions;
+    ///
+    /// let mut total = 0;
+    /// CopyDirOptions::new().copy_with_progress("a", "b", |path, bytes| {
+    ///     total += bytes;
+    ///     println!("copied {} ({total} bytes so far)", path.display());
+    /// })?;
+    /// # std::io::Result::Ok(())
+    /// ```
+    #[unstable(feature = "fs_copy_dir", issue = "none")]
+    pub fn copy_with_progress<P: AsRef<Path>, Q: AsRef<Path>>(
+        &self,
+        from: P,
+        to: Q,
+        mut progress: impl FnMut(&Path, u64),
+    ) -> Result<u64, CopyDirError> {
+        self.copy_dir(from.as_ref(), to.as_ref(), &mut progress)
+    }
+
+    fn copy_dir(
+        &self,
+        from: &Path,
+        to: &Path,
+        progress: &mut dyn FnMut(&Path, u64),
+    ) -> Result<u64, CopyDirError> {
+        let fail = |path: &Path, error| CopyDirError {
+            errors: vec![(path.to_owned(), error)],
+            bytes_copied: 0,
+        };
+        match metadata(from) {
+            Ok(m) if m.is_dir() => {}
+            Ok(_) => {
+                return Err(fail(
+                    from,
+                    io::const_error!(io::ErrorKind::NotADirectory, "the source is not a directory"),
+                ));
+            }
+            Err(e) => return Err(fail(from, e)),
+        }
+        match is_inside(to, from) {
+            Ok(false) => {}
+            Ok(true) => {
+                return Err(fail(
+                    to,
+                    io::const_error!(
+                        io::ErrorKind::InvalidInput,
+                        "cannot copy a directory into itself",
+                    ),
+                ));
+            }
+            Err(e) => return Err(fail(to, e)),
+        }
+
+        let mut errors = Vec::new();
+        let mut bytes_copied = 0;
+        // Records an error, and returns whether to go on.
+        let record = |errors: &mut Vec<_>, path: &Path, error| {
+            errors.push((path.to_owned(), error));
+            self.continue_on_error
+        };
+        // The directories created so far, parents first, to copy the metadata of at the end.
+        let mut dirs = Vec::new();
+        let mut pending = vec![(from.to_owned(), to.to_owned())];
+        'dirs: while let Some((from, to)) = pending.pop() {
+            if let Err(e) = create_dir_if_missing(&to) {
+                if record(&mut errors, &from, e) {
+                    continue;
+                } else {
+                    break;
+                }
+            }
+            progress(&to, 0);
+            let entries = match read_dir(&from) {
+                Ok(entries) => entries,
+                Err(e) => {
+                    if record(&mut errors, &from, e) {
+                        continue;
+                    } else {
+                        break;
+                    }
+                }
+            };
+            for entry in entries {
+                let entry = match entry {
+                    Ok(entry) => entry,
+                    Err(e) => {
+                        if record(&mut errors, &from, e) {
+                            continue;
+                        } else {
+                            break 'dirs;
+                        }
+                    }
+                };
+                let (from, to) = (entry.path(), to.join(entry.file_name()));
+                let copied = entry.file_type().and_then(|file_type| {
+                    if file_type.is_dir() {
+                        pending.push((from.clone(), to.clone()));
+                        return Ok(None);
+                    }
+                    self.copy_entry(&from, &to, file_type).map(Some)
+                });
+                match copied {
+                    Ok(Some(bytes)) => {
+                        bytes_copied += bytes;
+                        progress(&to, bytes);
+                    }
+                    Ok(None) => {}
+                    Err(e) => {
+                        if !record(&mut errors, &from, e) {
+                            break 'dirs;
+                        }
+                    }
+                }
+            }
+            dirs.push((from, to));
+        }
+
+        if errors.is_empty() || self.continue_on_error {
+            // Children first, in case the permissions of their parents forbid changing them.
+            for (from, to) in dirs.iter().rev() {
+                if let Err(e) = self.copy_metadata(from, to) {
+                    if !record(&mut errors, from, e) {
+                        break;
+                    }
+                }
+            }
+        }
+        if errors.is_empty() {
+            Ok(bytes_copied)
+        } else {
+            Err(CopyDirError { errors, bytes_copied })
+        }
+    }
+
+    /// Copies anything but a directory.
+    fn copy_entry(&self, from: &Path, to: &Path, file_type: FileType) -> io::Result<u64> {
+        if file_type.is_symlink() {
+            copy_symlink(from, to)?;
+            return Ok(0);
+        }
+        if !file_type.is_file() {
+            return Err(io::const_error!(
+                io::ErrorKind::InvalidInput,
+                "the source path is neither a regular file, a directory nor a symlink",
+            ));
+        }
+        // Don't write through a symbolic link that is in the way.
+        remove_non_dir(to)?;
+        let bytes = if self.preserve_permissions {
+            copy(from, to)?
+        } else {
+            io::copy(&mut File::open(from)?, &mut File::create(to)?)?
+        };
+        if self.preserve_times {
+            set_times(to, file_times(&metadata(from)?)?)?;
+        }
+        Ok(bytes)
+    }
+
+    fn copy_metadata(&self, from: &Path, to: &Path) -> io::Result<()> {
+        if !self.preserve_permissions && !self.preserve_times {
+            return Ok(());
+        }
+        let metadata = metadata(from)?;
+        if self.preserve_times {
+            set_times(to, file_times(&metadata)?)?;
+        }
+        if self.preserve_permissions {
+            set_permissions(to, metadata.permissions())?;
+        }
+        Ok(())
+    }
+}
+
+#[unstable(feature = "fs_copy_dir", issue = "none")]
+impl Default for CopyDirOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns whether `path`, which may not exist yet, is `dir` or inside of it.
+fn is_inside(path: &Path, dir: &Path) -> io::Result<bool> {
+    let path = match canonicalize(path) {
+        Ok(path) => path,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => match (path.parent(), path.file_name()) {
+            (Some(parent), Some(name)) if !parent.as_os_str().is_empty() => {
+                canonicalize(parent)?.join(name)
+            }
+            (_, Some(name)) => canonicalize(".")?.join(name),
+            _ => return Err(e),
+        },
+        Err(e) => return Err(e),
+    };
+    Ok(path.starts_with(canonicalize(dir)?))
+}
+
+fn create_dir_if_missing(path: &Path) -> io::Result<()> {
+    match create_dir(path) {
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+            if symlink_metadata(path)?.is_dir() {
+                Ok(())
+            } else {
+                Err(e)
+            }
+        }
+        result => result,
+    }
+}
+
+fn copy_symlink(from: &Path, to: &Path) -> io::Result<()> {
+    let target = read_link(from)?;
+    remove_non_dir(to)?;
+    #[cfg(windows)]
+    {
+        use crate::os::windows::fs::FileTypeExt;
+        let dir = symlink_metadata(from)?.file_type().is_symlink_dir();
+        fs_imp::symlink_inner(&target, to, dir)
+    }
+    #[cfg(not(windows))]
+    fs_imp::symlink(&target, to)
+}
+
+/// Removes anything at `path` that isn't a directory, so that it can be replaced.
+fn remove_non_dir(path: &Path) -> io::Result<()> {
+    #[cfg(windows)]
+    use crate::os::windows::fs::FileTypeExt;
+
+    match symlink_metadata(path) {
+        Ok(m) if m.is_dir() => Ok(()),
+        #[cfg(windows)]
+        Ok(m) if m.file_type().is_symlink_dir() => remove_dir(path),
+        Ok(_) => remove_file(path),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e),
+    }
+}
+
+fn file_times(metadata: &Metadata) -> io::Result<FileTimes> {
+    Ok(FileTimes::new().set_accessed(metadata.accessed()?).set_modified(metadata.modified()?))
+}
+
+impl CopyDirError {
+    /// Returns the errors that occurred, along with the path in the source
+    /// directory that couldn't be copied, in the order they occurred.
+    #[unstable(feature = "fs_copy_dir", issue = "none")]
+    pub fn errors(&self) -> &[(PathBuf, io::Error)] {
+        &self.errors
+    }
+
+    /// Consumes this error, returning the errors that occurred.
+    #[unstable(feature = "fs_copy_dir", issue = "none")]
+    pub fn into_errors(self) -> Vec<(PathBuf, io::Error)> {
+        self.errors
+    }
+
+    /// Returns the number of bytes copied despite the errors.
+    #[unstable(feature = "fs_copy_dir", issue = "none")]
+    pub fn bytes_copied(&self) -> u64 {
+        self.bytes_copied
+    }
+}
+
+#[unstable(feature = "fs_copy_dir", issue = "none")]
+impl fmt::Display for CopyDirError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (path, error) = &self.errors[0];
+        write!(f, "failed to copy `{}`: {error}", path.display())?;
+        match self.errors.len() {
+            1 => Ok(()),
+            2 => write!(f, " (and 1 other error)"),
+            n => write!(f, " (and {} other errors)", n - 1),
+        }
+    }
+}
+
+#[unstable(feature = "fs_copy_dir", issue = "none")]
+impl error::Error for CopyDirError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        Some(&self.errors[0].1)
+    }
+}
+
+#[unstable(feature = "fs_copy_dir", issue = "none")]
+impl From<CopyDirError> for io::Error {
+    /// Converts the first error, adding the path it occurred at.
+    fn from(err: CopyDirError) -> io::Error {
+        io::Error::new(err.errors[0].1.kind(), err)
+    }
+}
+
+impl RemoveDirOptions {
+    /// Creates a blank new set of options.
+    ///
+    /// By default, the removal stops at the first error, like with
+    /// [`remove_dir_all`].
+    #[unstable(feature = "fs_copy_dir", issue = "none")]
+    #[must_use]
+    pub fn new() -> Self {
+        RemoveDirOptions { continue_on_error: false }
+    }
+
+    /// Sets whether to go on removing the rest of the directory after an
+    /// error.
+    ///
+    /// When set, everything that can be removed is, and all the errors are
+    /// returned together at the end. A directory is only removed once all of
+    /// its contents are, so an error also leaves every directory above it in
+    /// place.
+    #[unstable(feature = "fs_copy_dir", issue = "none")]
+    pub fn continue_on_error(&mut self, continue_on_error: bool) -> &mut Self {
+        self.continue_on_error = continue_on_error;
+        self
+    }
+
+    /// Removes the directory at `path` and all of its contents, with the
+    /// options specified by `self`.
+    ///
+    /// Symbolic links are removed rather than followed, as with
+    /// [`remove_dir_all`], which has more details.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// On platforms other than Unix (including WASI) and UEFI, this calls
+    /// [`remove_dir_all`], so it stops at the first error whether or not
+    /// [`continue_on_error`] is set, and the error is reported for `path`.
+    ///
+    /// Note that platform-specific behavior [may change in the future][changes].
+    ///
+    /// [changes]: io#platform-specific-behavior
+    /// [`continue_on_error`]: RemoveDirOptions::continue_on_error
+    ///
+    /// # Errors
+    ///
+    /// Returns every error that occurred, along with the path that couldn't be
+    /// removed.
+    #[unstable(feature = "fs_copy_dir", issue = "none")]
+    pub fn remove<P: AsRef<Path>>(&self, path: P) -> Result<(), RemoveDirError> {
+        self.remove_dir(path.as_ref(), &mut |_| {})
+    }
+
+    /// Removes the directory at `path` and all of its contents, calling
+    /// `progress` every time something has been removed.
+    ///
+    /// `progress` is called with the path of every file, directory and
+    /// symbolic link removed, including `path` itself, after its contents.
+    /// Otherwise, this is the same as [`remove`].
+    ///
+    /// [`remove`]: RemoveDirOptions::remove
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(fs_copy_dir)]
+    ///
+    /// use std::fs::RemoveDirOptions;
+    ///
+    /// RemoveDirOptions::new().remove_with_progress("a", |path| {
+    ///     println!("removed {}", path.display());
+    /// })?;
+    /// # std::io::Result::Ok(())
+    /// ```
+    #[unstable(feature = "fs_copy_dir", issue = "none")]
+    pub fn remove_with_progress<P: AsRef<Path>>(
+        &self,
+        path: P,
+        mut progress: impl FnMut(&Path),
+    ) -> Result<(), RemoveDirError> {
+        self.remove_dir(path.as_ref(), &mut progress)
+    }
+
+    fn remove_dir(
+        &self,
+        path: &Path,
+        progress: &mut dyn FnMut(&Path),
+    ) -> Result<(), RemoveDirError> {
+        let mut errors = Vec::new();
+        fs_imp::remove_dir_all_with(path, &mut |path, result| match result {
+            Ok(()) => {
+                progress(path);
+                true
+            }
+            Err(e) => {
+                errors.push((path.to_owned(), e));
+                self.continue_on_error
+            }
+        });
+        if errors.is_empty() { Ok(()) } else { Err(RemoveDirError { errors }) }
+    }
+}
+
+#[unstable(feature = "fs_copy_dir", issue = "none")]
+impl Default for RemoveDirOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RemoveDirError {
+    /// Returns the errors that occurred, along with the path that couldn't be
+    /// removed, in the order they occurred.
+    #[unstable(feature = "fs_copy_dir", issue = "none")]
+    pub fn errors(&self) -> &[(PathBuf, io::Error)] {
+        &self.errors
+    }
+
+    /// Consumes this error, returning the errors that occurred.
+    #[unstable(feature = "fs_copy_dir", issue = "none")]
+    pub fn into_errors(self) -> Vec<(PathBuf, io::Error)> {
+        self.errors
+    }
+}
+
+#[unstable(feature = "fs_copy_dir", issue = "none")]
+impl fmt::Display for RemoveDirError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (path, error) = &self.errors[0];
+        write!(f, "failed to remove `{}`: {error}", path.display())?;
+        match self.errors.len() {
+            1 => Ok(()),
+            2 => write!(f, " (and 1 other error)"),
+            n => write!(f, " (and {} other errors)", n - 1),
+        }
+    }
+}
+
+#[unstable(feature = "fs_copy_dir", issue = "none")]
+impl error::Error for RemoveDirError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        Some(&self.errors[0].1)
+    }
+}
+
+#[unstable(feature = "fs_copy_dir", issue = "none")]
+impl From<RemoveDirError> for io::Error {
+    /// Converts the first error, adding the path it occurred at.
+    fn from(err: RemoveDirError) -> io::Error {
+        io::Error::new(err.errors[0].1.kind(), err)
+    }
+}
+
 /// Creates a new hard link on the filesystem.
 ///
 /// The `link` path will be a link pointing to the `original` path. Note that
@@ -3187,6 +4322,9 @@ pub fn remove_dir<P: AsRef<Path>>(path: P) -> io::Result<()> {
 ///
 /// Consider ignoring the error if validating the removal is not required for your use case.
 ///
+/// To go on removing the rest of the directory after an error, and collect all the errors, use
+/// [`RemoveDirOptions`].
+///
 /// This function may return [`io::ErrorKind::DirectoryNotEmpty`] if the directory is concurrently
 /// written into, which typically indicates some contents were removed but not all.
 /// [`io::ErrorKind::NotFound`] is only returned if no removal occurs.
@@ -3288,6 +4426,50 @@ pub fn read_dir<P: AsRef<Path>>(path: P) -> io::Result<ReadDir> {
     fs_imp::read_dir(path.as_ref()).map(ReadDir)
 }
 
+/// Returns an iterator over the entries within a directory and, recursively,
+/// within all of its subdirectories.
+///
+/// The iterator will yield instances of <code>[io::Result]<[DirEntry]></code>,
+/// like [`read_dir`], but doesn't yield an entry for `path` itself. By default
+/// the walk is depth-first, has no maximum depth, and doesn't walk into
+/// symbolic links; see [`WalkDir`] for how to change that.
+///
+/// Nothing is read until the first call to [`next`]. An error opening `path`
+/// is returned as the first item of the iterator.
+///
+/// [`next`]: Iterator::next
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(fs_walk_dir)]
+///
+/// use std::fs;
+///
+/// // Finds all the Rust source files in a crate.
+/// for entry in fs::walk_dir("src") {
+///     let path = entry?.path();
+///     if path.extension().is_some_and(|ext| ext == "rs") {
+///         println!("{}", path.display());
+///     }
+/// }
+/// # std::io::Result::Ok(())
+/// ```
+#[unstable(feature = "fs_walk_dir", issue = "none")]
+pub fn walk_dir<P: AsRef<Path>>(path: P) -> WalkDir {
+    let root = WalkDirNode {
+        contents: WalkDirContents::Path(path.as_ref().to_path_buf()),
+        depth: 1,
+        ancestors: None,
+    };
+    WalkDir {
+        max_depth: usize::MAX,
+        follow_symlinks: false,
+        breadth_first: false,
+        dirs: VecDeque::from([root]),
+    }
+}
+
 /// Changes the permissions found on a file or a directory.
 ///
 /// # Platform-specific behavior
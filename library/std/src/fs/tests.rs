@@ -1684,6 +1684,389 @@ fn read_dir_not_found() {
     assert_eq!(res.err().unwrap().kind(), ErrorKind::NotFound);
 }
 
+fn walk_dir_tree(tmpdir: &TempDir) {
+    // root/
+    //   a/
+    //     b/
+    //       f3
+    //     f2
+    //   f1
+    check!(fs::create_dir_all(tmpdir.join("a").join("b")));
+    check!(File::create(tmpdir.join("f1")));
+    check!(File::create(tmpdir.join("a").join("f2")));
+    check!(File::create(tmpdir.join("a").join("b").join("f3")));
+}
+
+fn walk_dir_paths(walk: fs::WalkDir, root: &Path) -> Vec<String> {
+    walk.map(|entry| {
+        let path = check!(entry).path();
+        path.strip_prefix(root).unwrap().to_str().unwrap().replace('\\', "/")
+    })
+    .collect()
+}
+
+#[test]
+fn walk_dir_depth_first() {
+    let tmpdir = tmpdir();
+    walk_dir_tree(&tmpdir);
+    let paths = walk_dir_paths(fs::walk_dir(tmpdir.path()), tmpdir.path());
+
+    let mut sorted = paths.clone();
+    sorted.sort();
+    assert_eq!(sorted, ["a", "a/b", "a/b/f3", "a/f2", "f1"]);
+    // Every directory is immediately followed by its contents.
+    let a = paths.iter().position(|p| p == "a").unwrap();
+    let mut contents = paths[a + 1..a + 4].to_vec();
+    contents.sort();
+    assert_eq!(contents, ["a/b", "a/b/f3", "a/f2"]);
+    let b = paths.iter().position(|p| p == "a/b").unwrap();
+    assert_eq!(paths[b + 1], "a/b/f3");
+}
+
+#[test]
+fn walk_dir_breadth_first() {
+    let tmpdir = tmpdir();
+    walk_dir_tree(&tmpdir);
+    let paths = walk_dir_paths(fs::walk_dir(tmpdir.path()).breadth_first(true), tmpdir.path());
+
+    let depths: Vec<_> = paths.iter().map(|p| p.matches('/').count()).collect();
+    assert!(depths.is_sorted(), "{paths:?} is not breadth-first");
+    let mut sorted = paths.clone();
+    sorted.sort();
+    assert_eq!(sorted, ["a", "a/b", "a/b/f3", "a/f2", "f1"]);
+}
+
+#[test]
+fn walk_dir_max_depth() {
+    let tmpdir = tmpdir();
+    walk_dir_tree(&tmpdir);
+    for breadth_first in [false, true] {
+        let walk = fs::walk_dir(tmpdir.path()).breadth_first(breadth_first);
+        let mut paths = walk_dir_paths(walk.max_depth(2), tmpdir.path());
+        paths.sort();
+        assert_eq!(paths, ["a", "a/b", "a/f2", "f1"]);
+
+        let walk = fs::walk_dir(tmpdir.path()).breadth_first(breadth_first);
+        assert_eq!(walk.max_depth(0).count(), 0);
+    }
+}
+
+#[test]
+#[cfg_attr(
+    all(windows, target_arch = "aarch64"),
+    ignore = "SymLinks not enabled on Arm64 Windows runners https://github.com/actions/partner-runner-images/issues/94"
+)]
+fn walk_dir_symlinks() {
+    let tmpdir = tmpdir();
+    if !got_symlink_permission(&tmpdir) {
+        return;
+    };
+    walk_dir_tree(&tmpdir);
+    check!(fs::create_dir(tmpdir.join("c")));
+    check!(File::create(tmpdir.join("c").join("f4")));
+    check!(symlink_dir(tmpdir.join("c"), tmpdir.join("a").join("link")));
+
+    // Symlinks are returned but not followed by default.
+    let mut paths = walk_dir_paths(fs::walk_dir(tmpdir.path()), tmpdir.path());
+    paths.sort();
+    assert_eq!(paths, ["a", "a/b", "a/b/f3", "a/f2", "a/link", "c", "c/f4", "f1"]);
+
+    for breadth_first in [false, true] {
+        let walk = fs::walk_dir(tmpdir.path()).follow_symlinks(true).breadth_first(breadth_first);
+        let mut paths = walk_dir_paths(walk, tmpdir.path());
+        paths.sort();
+        assert_eq!(paths, ["a", "a/b", "a/b/f3", "a/f2", "a/link", "a/link/f4", "c", "c/f4", "f1"]);
+    }
+}
+
+#[test]
+#[cfg_attr(
+    all(windows, target_arch = "aarch64"),
+    ignore = "SymLinks not enabled on Arm64 Windows runners https://github.com/actions/partner-runner-images/issues/94"
+)]
+fn walk_dir_symlink_loop() {
+    let tmpdir = tmpdir();
+    if !got_symlink_permission(&tmpdir) {
+        return;
+    };
+    walk_dir_tree(&tmpdir);
+    let link = tmpdir.join("a").join("b").join("loop");
+    check!(symlink_dir(tmpdir.join("a"), &link));
+
+    for breadth_first in [false, true] {
+        let walk = fs::walk_dir(tmpdir.path()).follow_symlinks(true).breadth_first(breadth_first);
+        let mut entries = 0;
+        let mut loops = 0;
+        for entry in walk {
+            match entry {
+                Ok(_) => entries += 1,
+                Err(e) => {
+                    assert_eq!(e.kind(), ErrorKind::FilesystemLoop);
+                    loops += 1;
+                }
+            }
+        }
+        assert_eq!((entries, loops), (6, 1));
+    }
+}
+
+#[test]
+fn atomic_file_commit() {
+    let tmpdir = tmpdir();
+    let path = tmpdir.join("file");
+
+    let mut file = check!(fs::AtomicFile::create(&path));
+    check!(file.write_all(b"first"));
+    assert!(!path.exists());
+    check!(file.commit());
+    assert_eq!(check!(fs::read(&path)), b"first");
+
+    // Committing replaces the existing file.
+    let mut file = check!(fs::AtomicFile::create(&path));
+    check!(file.write_all(b"second"));
+    assert_eq!(check!(fs::read(&path)), b"first");
+    check!(file.commit());
+    assert_eq!(check!(fs::read(&path)), b"second");
+
+    // No temporary file is left behind.
+    assert_eq!(check!(fs::read_dir(tmpdir.path())).count(), 1);
+}
+
+#[test]
+fn atomic_file_discard() {
+    let tmpdir = tmpdir();
+    let path = tmpdir.join("file");
+    check!(fs::write(&path, b"original"));
+
+    let mut file = check!(fs::AtomicFile::create(&path));
+    check!(file.write_all(b"dropped"));
+    drop(file);
+    let mut file = check!(fs::AtomicFile::create(&path));
+    check!(file.write_all(b"discarded"));
+    check!(file.discard());
+
+    assert_eq!(check!(fs::read(&path)), b"original");
+    assert_eq!(check!(fs::read_dir(tmpdir.path())).count(), 1);
+}
+
+#[test]
+fn atomic_file_read_back() {
+    let tmpdir = tmpdir();
+    let mut file = check!(fs::AtomicFile::create(tmpdir.join("file")));
+    check!(file.write_all(b"contents"));
+    check!(file.seek(SeekFrom::Start(0)));
+    let mut contents = String::new();
+    check!(file.read_to_string(&mut contents));
+    assert_eq!(contents, "contents");
+}
+
+#[test]
+fn atomic_file_invalid_path() {
+    let tmpdir = tmpdir();
+    let res = fs::AtomicFile::create(tmpdir.join(".."));
+    assert_eq!(res.err().unwrap().kind(), ErrorKind::InvalidInput);
+    let res = fs::AtomicFile::create(tmpdir.join("missing").join("file"));
+    assert_eq!(res.err().unwrap().kind(), ErrorKind::NotFound);
+}
+
+#[test]
+fn copy_dir_all() {
+    let tmpdir = tmpdir();
+    let src = tmpdir.join("src");
+    let dst = tmpdir.join("dst");
+    check!(fs::create_dir(&src));
+    check!(fs::create_dir_all(src.join("a").join("b")));
+    check!(fs::write(src.join("f1"), b"hello"));
+    check!(fs::write(src.join("a").join("b").join("f2"), b"world!"));
+
+    assert_eq!(check!(fs::copy_dir_all(&src, &dst)), 11);
+    assert_eq!(check!(fs::read(dst.join("f1"))), b"hello");
+    assert_eq!(check!(fs::read(dst.join("a").join("b").join("f2"))), b"world!");
+
+    // Copying again merges into the existing directory.
+    check!(fs::write(src.join("f1"), b"bye"));
+    check!(fs::write(dst.join("f3"), b"kept"));
+    check!(fs::copy_dir_all(&src, &dst));
+    assert_eq!(check!(fs::read(dst.join("f1"))), b"bye");
+    assert_eq!(check!(fs::read(dst.join("f3"))), b"kept");
+}
+
+#[test]
+#[cfg_attr(
+    all(windows, target_arch = "aarch64"),
+    ignore = "SymLinks not enabled on Arm64 Windows runners https://github.com/actions/partner-runner-images/issues/94"
+)]
+fn copy_dir_all_symlinks() {
+    let tmpdir = tmpdir();
+    if !got_symlink_permission(&tmpdir) {
+        return;
+    };
+    let src = tmpdir.join("src");
+    let dst = tmpdir.join("dst");
+    check!(fs::create_dir_all(src.join("dir")));
+    check!(fs::write(src.join("file"), b"contents"));
+    check!(symlink_file("file", src.join("link")));
+    check!(symlink_dir("dir", src.join("dir_link")));
+
+    check!(fs::copy_dir_all(&src, &dst));
+    assert!(check!(fs::symlink_metadata(dst.join("link"))).file_type().is_symlink());
+    assert_eq!(check!(fs::read_link(dst.join("link"))), Path::new("file"));
+    assert!(check!(fs::symlink_metadata(dst.join("dir_link"))).file_type().is_symlink());
+    assert_eq!(check!(fs::read_to_string(dst.join("link"))), "contents");
+}
+
+#[test]
+fn copy_dir_all_preserve_times() {
+    let tmpdir = tmpdir();
+    let src = tmpdir.join("src");
+    let dst = tmpdir.join("dst");
+    check!(fs::create_dir(&src));
+    check!(fs::write(src.join("file"), b""));
+    let time = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000_000);
+    let times = fs::FileTimes::new().set_accessed(time).set_modified(time);
+    check!(fs::set_times(src.join("file"), times));
+    check!(fs::set_times(&src, times));
+
+    check!(fs::CopyDirOptions::new().preserve_times(true).copy(&src, &dst));
+    assert_eq!(check!(check!(fs::metadata(dst.join("file"))).modified()), time);
+    assert_eq!(check!(check!(fs::metadata(&dst)).modified()), time);
+}
+
+#[test]
+fn copy_dir_all_continue_on_error() {
+    let tmpdir = tmpdir();
+    let src = tmpdir.join("src");
+    let dst = tmpdir.join("dst");
+    check!(fs::create_dir_all(src.join("dir")));
+    check!(fs::write(src.join("dir").join("f1"), b"lost"));
+    check!(fs::write(src.join("f2"), b"copied"));
+    // A file in the way of a directory can't be replaced.
+    check!(fs::create_dir(&dst));
+    check!(fs::write(dst.join("dir"), b""));
+
+    let err = fs::CopyDirOptions::new().continue_on_error(true).copy(&src, &dst).unwrap_err();
+    assert_eq!(err.errors().len(), 1);
+    assert_eq!(err.errors()[0].0, src.join("dir"));
+    assert_eq!(err.errors()[0].1.kind(), ErrorKind::AlreadyExists);
+    assert_eq!(err.bytes_copied(), 6);
+    assert_eq!(check!(fs::read(dst.join("f2"))), b"copied");
+
+    check!(fs::remove_file(dst.join("f2")));
+    let mut copied = Vec::new();
+    let res = fs::CopyDirOptions::new().copy_with_progress(&src, &dst, |path, _| {
+        copied.push(path.to_owned());
+    });
+    assert_eq!(io::Error::from(res.unwrap_err()).kind(), ErrorKind::AlreadyExists);
+    assert!(copied.len() <= 2, "{copied:?}");
+}
+
+#[test]
+#[cfg_attr(
+    all(windows, target_arch = "aarch64"),
+    ignore = "SymLinks not enabled on Arm64 Windows runners https://github.com/actions/partner-runner-images/issues/94"
+)]
+fn copy_dir_all_replaces_symlinks() {
+    let tmpdir = tmpdir();
+    if !got_symlink_permission(&tmpdir) {
+        return;
+    };
+    let src = tmpdir.join("src");
+    let dst = tmpdir.join("dst");
+    let outside = tmpdir.join("outside");
+    check!(fs::create_dir(&src));
+    check!(fs::create_dir(&dst));
+    check!(fs::write(src.join("file"), b"copied"));
+    check!(fs::write(&outside, b"untouched"));
+    check!(symlink_file(&outside, dst.join("file")));
+
+    check!(fs::copy_dir_all(&src, &dst));
+    assert!(!check!(fs::symlink_metadata(dst.join("file"))).file_type().is_symlink());
+    assert_eq!(check!(fs::read(dst.join("file"))), b"copied");
+    assert_eq!(check!(fs::read(&outside)), b"untouched");
+
+    check!(fs::remove_file(dst.join("file")));
+    check!(symlink_file(&outside, dst.join("file")));
+    check!(fs::CopyDirOptions::new().preserve_permissions(false).copy(&src, &dst));
+    assert_eq!(check!(fs::read(dst.join("file"))), b"copied");
+    assert_eq!(check!(fs::read(&outside)), b"untouched");
+}
+
+#[test]
+fn copy_dir_all_into_itself() {
+    let tmpdir = tmpdir();
+    let src = tmpdir.join("src");
+    check!(fs::create_dir(&src));
+    let res = fs::copy_dir_all(&src, src.join("copy"));
+    assert_eq!(res.unwrap_err().kind(), ErrorKind::InvalidInput);
+    assert!(!src.join("copy").exists());
+    let res = fs::copy_dir_all(tmpdir.join("missing"), tmpdir.join("copy"));
+    assert_eq!(res.unwrap_err().kind(), ErrorKind::NotFound);
+}
+
+#[test]
+fn remove_dir_options() {
+    let tmpdir = tmpdir();
+    let dir = tmpdir.join("dir");
+    check!(fs::create_dir_all(dir.join("a").join("b")));
+    check!(fs::write(dir.join("f1"), b""));
+    check!(fs::write(dir.join("a").join("b").join("f2"), b""));
+
+    let mut removed = Vec::new();
+    check!(fs::RemoveDirOptions::new().remove_with_progress(&dir, |path| {
+        removed.push(path.to_owned());
+    }));
+    assert!(!dir.exists());
+    if cfg!(any(unix, target_os = "wasi")) {
+        assert_eq!(removed.len(), 5, "{removed:?}");
+        let pos = |path: &Path| removed.iter().position(|p| p == path).unwrap();
+        assert!(pos(&dir.join("a").join("b").join("f2")) < pos(&dir.join("a").join("b")));
+        assert!(pos(&dir.join("a")) < pos(&dir));
+    }
+    assert_eq!(removed.last(), Some(&dir));
+
+    let err = fs::RemoveDirOptions::new().continue_on_error(true).remove(&dir).unwrap_err();
+    assert_eq!(err.errors().len(), 1);
+    assert_eq!(err.errors()[0].0, dir);
+    assert_eq!(io::Error::from(err).kind(), ErrorKind::NotFound);
+}
+
+#[test]
+#[cfg(unix)]
+fn remove_dir_options_continue_on_error() {
+    use crate::os::unix::fs::PermissionsExt;
+
+    let tmpdir = tmpdir();
+    let dir = tmpdir.join("dir");
+    let locked = dir.join("locked");
+    check!(fs::create_dir_all(&locked));
+    check!(fs::write(locked.join("kept"), b""));
+    check!(fs::write(dir.join("removed"), b""));
+    check!(fs::set_permissions(&locked, fs::Permissions::from_mode(0o500)));
+    // Permissions don't stop root.
+    if fs::remove_file(locked.join("kept")).is_ok() {
+        check!(fs::set_permissions(&locked, fs::Permissions::from_mode(0o700)));
+        return;
+    }
+
+    let err = fs::RemoveDirOptions::new().continue_on_error(true).remove(&dir).unwrap_err();
+    let paths: Vec<_> = err.errors().iter().map(|(path, _)| path.clone()).collect();
+    assert_eq!(paths, [locked.join("kept"), locked.clone(), dir.clone()]);
+    assert_eq!(err.errors()[0].1.kind(), ErrorKind::PermissionDenied);
+    assert!(!dir.join("removed").exists());
+    assert!(locked.join("kept").exists());
+
+    check!(fs::set_permissions(&locked, fs::Permissions::from_mode(0o700)));
+    check!(fs::RemoveDirOptions::new().remove(&dir));
+    assert!(!dir.exists());
+}
+
+#[test]
+fn walk_dir_not_found() {
+    let mut walk = fs::walk_dir("/path/that/does/not/exist");
+    assert_eq!(walk.next().unwrap().err().unwrap().kind(), ErrorKind::NotFound);
+    assert!(walk.next().is_none());
+}
+
 #[test]
 fn file_open_not_found() {
     let res = File::open("/path/that/does/not/exist");
@@ -8,8 +8,12 @@
 //!
 //! * [`TcpListener`] and [`TcpStream`] provide functionality for communication over TCP
 //! * [`UdpSocket`] provides functionality for communication over UDP
+//! * [`TcpSocket`] and [`UdpSocketBuilder`] allow setting socket options before a socket is bound
+//!   or connected
 //! * [`IpAddr`] represents IP addresses of either IPv4 or IPv6; [`Ipv4Addr`] and
 //!   [`Ipv6Addr`] are respectively IPv4 and IPv6 addresses
+//! * [`IpNet`] represents IP networks of either IPv4 or IPv6; [`Ipv4Net`] and [`Ipv6Net`] are
+//!   respectively IPv4 and IPv6 networks
 //! * [`SocketAddr`] represents socket addresses of either IPv4 or IPv6; [`SocketAddrV4`]
 //!   and [`SocketAddrV6`] are respectively IPv4 and IPv6 socket addresses
 //! * [`ToSocketAddrs`] is a trait that is used for generic address resolution when interacting
@@ -24,6 +28,8 @@
 
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use core::net::AddrParseError;
+#[unstable(feature = "ip_net", issue = "none")]
+pub use core::net::{IpNet, Ipv4Net, Ipv4Subnets, Ipv6Net, Ipv6Subnets, PrefixLenError};
 
 #[unstable(feature = "gethostname", issue = "135142")]
 pub use self::hostname::hostname;
@@ -31,6 +37,8 @@
 pub use self::ip_addr::{IpAddr, Ipv4Addr, Ipv6Addr, Ipv6MulticastScope};
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use self::socket_addr::{SocketAddr, SocketAddrV4, SocketAddrV6, ToSocketAddrs};
+#[unstable(feature = "socket_builder", issue = "none")]
+pub use self::socket::{TcpSocket, UdpSocketBuilder};
 #[unstable(feature = "tcplistener_into_incoming", issue = "88373")]
 pub use self::tcp::IntoIncoming;
 #[stable(feature = "rust1", since = "1.0.0")]
@@ -40,6 +48,7 @@
 
 mod hostname;
 mod ip_addr;
+mod socket;
 mod socket_addr;
 mod tcp;
 #[cfg(test)]
@@ -0,0 +1,427 @@
+#[cfg(all(
+    test,
+    not(any(
+        target_os = "emscripten",
+        all(target_os = "wasi", target_env = "p1"),
+        target_os = "xous",
+        target_os = "trusty",
+    ))
+))]
+mod tests;
+
+use crate::fmt;
+use crate::io;
+use crate::net::{SocketAddr, TcpListener, TcpStream, UdpSocket};
+use crate::sys::{AsInner, FromInner, net as net_imp};
+use crate::time::Duration;
+
+/// A TCP socket that has not been bound or connected yet.
+///
+/// [`TcpStream::connect`] and [`TcpListener::bind`] create, configure and
+/// connect or bind a socket in a single step. A `TcpSocket` splits these steps
+/// up, so that socket options which only take effect before binding or
+/// connecting can be set, such as `SO_REUSEADDR` and `SO_REUSEPORT`, and so that
+/// a socket can be bound to a local address before it connects.
+///
+/// A `TcpSocket` is turned into a [`TcpListener`] with [`listen`], or into a
+/// [`TcpStream`] with [`connect`].
+///
+/// [`listen`]: TcpSocket::listen
+/// [`connect`]: TcpSocket::connect
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(socket_builder)]
+/// use std::net::TcpSocket;
+///
+/// let socket = TcpSocket::new_v4()?;
+/// socket.set_reuse_address(true)?;
+/// socket.bind("127.0.0.1:8080".parse().unwrap())?;
+/// let listener = socket.listen(1024)?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[unstable(feature = "socket_builder", issue = "none")]
+pub struct TcpSocket(net_imp::SocketBuilder);
+
+/// A builder for a [`UdpSocket`] that allows setting socket options before it
+/// is bound.
+///
+/// [`UdpSocket::bind`] creates and binds a socket in a single step. Options
+/// which only take effect before binding, such as `SO_REUSEADDR` and
+/// `SO_REUSEPORT`, can instead be set on a `UdpSocketBuilder`, which is then
+/// turned into a [`UdpSocket`] with [`bind`].
+///
+/// [`bind`]: UdpSocketBuilder::bind
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(socket_builder)]
+/// use std::net::UdpSocketBuilder;
+///
+/// let builder = UdpSocketBuilder::new_v6()?;
+/// builder.set_only_v6(true)?;
+/// builder.set_recv_buffer_size(1 << 20)?;
+/// let socket = builder.bind("[::]:5353".parse().unwrap())?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[unstable(feature = "socket_builder", issue = "none")]
+pub struct UdpSocketBuilder(net_imp::SocketBuilder);
+
+// Options shared by `TcpSocket` and `UdpSocketBuilder`.
+macro_rules! socket_options {
+    ($ty:ident) => {
+        impl $ty {
+            /// Sets the value of the `SO_REUSEADDR` option on this socket.
+            ///
+            /// On Unix-like platforms, this allows binding to an address which
+            /// still has connections lingering in the `TIME_WAIT` state. On
+            /// Windows, it allows binding to an address that is in use by
+            /// another socket, so it should be used with care.
+            #[unstable(feature = "socket_builder", issue = "none")]
+            pub fn set_reuse_address(&self, reuse: bool) -> io::Result<()> {
+                self.0.set_reuse_address(reuse)
+            }
+
+            /// Gets the value of the `SO_REUSEADDR` option on this socket.
+            ///
+            /// For more information about this option, see [`set_reuse_address`].
+            ///
+            /// [`set_reuse_address`]: Self::set_reuse_address
+            #[unstable(feature = "socket_builder", issue = "none")]
+            pub fn reuse_address(&self) -> io::Result<bool> {
+                self.0.reuse_address()
+            }
+
+            /// Sets the value of the `SO_REUSEPORT` option on this socket.
+            ///
+            /// This allows several sockets to bind to the same address and
+            /// port, if all of them set this option. Its exact semantics, such
+            /// as how incoming connections or datagrams are distributed among
+            /// the sockets, depend on the platform.
+            ///
+            /// Returns an error on platforms which do not support this option,
+            /// including Windows.
+            #[unstable(feature = "socket_builder", issue = "none")]
+            pub fn set_reuse_port(&self, reuse: bool) -> io::Result<()> {
+                self.0.set_reuse_port(reuse)
+            }
+
+            /// Gets the value of the `SO_REUSEPORT` option on this socket.
+            ///
+            /// For more information about this option, see [`set_reuse_port`].
+            ///
+            /// [`set_reuse_port`]: Self::set_reuse_port
+            #[unstable(feature = "socket_builder", issue = "none")]
+            pub fn reuse_port(&self) -> io::Result<bool> {
+                self.0.reuse_port()
+            }
+
+            /// Sets the value of the `IPV6_V6ONLY` option on this socket.
+            ///
+            /// If this is set to `true`, the socket is restricted to sending
+            /// and receiving IPv6 packets only, and binding it to the
+            /// unspecified address `[::]` does not accept IPv4 traffic. If it
+            /// is `false`, the socket can also communicate with IPv4 peers
+            /// through IPv4-mapped IPv6 addresses. The default depends on the
+            /// platform.
+            ///
+            /// This option only applies to IPv6 sockets.
+            #[unstable(feature = "socket_builder", issue = "none")]
+            pub fn set_only_v6(&self, only_v6: bool) -> io::Result<()> {
+                self.0.set_only_v6(only_v6)
+            }
+
+            /// Gets the value of the `IPV6_V6ONLY` option on this socket.
+            ///
+            /// For more information about this option, see [`set_only_v6`].
+            ///
+            /// [`set_only_v6`]: Self::set_only_v6
+            #[unstable(feature = "socket_builder", issue = "none")]
+            pub fn only_v6(&self) -> io::Result<bool> {
+                self.0.only_v6()
+            }
+
+            /// Sets the size of the send buffer (`SO_SNDBUF`) of this socket.
+            ///
+            /// The operating system may round the size or clamp it to system
+            /// limits, and on Linux it doubles the requested size to leave room
+            /// for bookkeeping. Use [`send_buffer_size`] to get the actual size.
+            ///
+            /// [`send_buffer_size`]: Self::send_buffer_size
+            #[unstable(feature = "socket_builder", issue = "none")]
+            pub fn set_send_buffer_size(&self, size: usize) -> io::Result<()> {
+                self.0.set_send_buffer_size(size)
+            }
+
+            /// Gets the size of the send buffer (`SO_SNDBUF`) of this socket.
+            #[unstable(feature = "socket_builder", issue = "none")]
+            pub fn send_buffer_size(&self) -> io::Result<usize> {
+                self.0.send_buffer_size()
+            }
+
+            /// Sets the size of the receive buffer (`SO_RCVBUF`) of this
+            /// socket.
+            ///
+            /// The operating system may round the size or clamp it to system
+            /// limits, and on Linux it doubles the requested size to leave room
+            /// for bookkeeping. Use [`recv_buffer_size`] to get the actual size.
+            ///
+            /// [`recv_buffer_size`]: Self::recv_buffer_size
+            #[unstable(feature = "socket_builder", issue = "none")]
+            pub fn set_recv_buffer_size(&self, size: usize) -> io::Result<()> {
+                self.0.set_recv_buffer_size(size)
+            }
+
+            /// Gets the size of the receive buffer (`SO_RCVBUF`) of this
+            /// socket.
+            #[unstable(feature = "socket_builder", issue = "none")]
+            pub fn recv_buffer_size(&self) -> io::Result<usize> {
+                self.0.recv_buffer_size()
+            }
+
+            /// Returns the local address that this socket is bound to.
+            #[unstable(feature = "socket_builder", issue = "none")]
+            pub fn local_addr(&self) -> io::Result<SocketAddr> {
+                self.0.socket_addr()
+            }
+        }
+
+        #[unstable(feature = "socket_builder", issue = "none")]
+        impl fmt::Debug for $ty {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.0.fmt(f)
+            }
+        }
+
+        impl AsInner<net_imp::SocketBuilder> for $ty {
+            #[inline]
+            fn as_inner(&self) -> &net_imp::SocketBuilder {
+                &self.0
+            }
+        }
+    };
+}
+
+socket_options!(TcpSocket);
+socket_options!(UdpSocketBuilder);
+
+impl TcpSocket {
+    /// Creates a new IPv4 TCP socket.
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn new_v4() -> io::Result<TcpSocket> {
+        net_imp::SocketBuilder::new_tcp(false).map(TcpSocket)
+    }
+
+    /// Creates a new IPv6 TCP socket.
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn new_v6() -> io::Result<TcpSocket> {
+        net_imp::SocketBuilder::new_tcp(true).map(TcpSocket)
+    }
+
+    /// Creates a new TCP socket that can bind or connect to addresses of the
+    /// same family as `addr`.
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn new_for_addr(addr: SocketAddr) -> io::Result<TcpSocket> {
+        net_imp::SocketBuilder::new_tcp(addr.is_ipv6()).map(TcpSocket)
+    }
+
+    /// Sets the value of the `SO_KEEPALIVE` option on this socket.
+    ///
+    /// See [`TcpStream::set_keepalive`] for more information about this
+    /// option. The timing of the keepalive probes can be configured with
+    /// [`set_keepalive_time`], [`set_keepalive_interval`] and
+    /// [`set_keepalive_retries`].
+    ///
+    /// [`set_keepalive_time`]: TcpSocket::set_keepalive_time
+    /// [`set_keepalive_interval`]: TcpSocket::set_keepalive_interval
+    /// [`set_keepalive_retries`]: TcpSocket::set_keepalive_retries
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn set_keepalive(&self, keepalive: bool) -> io::Result<()> {
+        self.0.set_keepalive(keepalive)
+    }
+
+    /// Gets the value of the `SO_KEEPALIVE` option on this socket.
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn keepalive(&self) -> io::Result<bool> {
+        self.0.keepalive()
+    }
+
+    /// Sets how long the connection must be idle before the first keepalive
+    /// probe is sent (`TCP_KEEPIDLE`, or `TCP_KEEPALIVE` on Apple platforms).
+    ///
+    /// The time is rounded down to whole seconds. Returns an error on
+    /// platforms which do not support configuring it.
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn set_keepalive_time(&self, time: Duration) -> io::Result<()> {
+        self.0.set_keepalive_time(time)
+    }
+
+    /// Gets how long the connection must be idle before the first keepalive
+    /// probe is sent.
+    ///
+    /// For more information about this option, see
+    /// [`TcpSocket::set_keepalive_time`].
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn keepalive_time(&self) -> io::Result<Duration> {
+        self.0.keepalive_time()
+    }
+
+    /// Sets the time between two keepalive probes that are not acknowledged
+    /// (`TCP_KEEPINTVL`).
+    ///
+    /// The interval is rounded down to whole seconds. Returns an error on
+    /// platforms which do not support configuring it.
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn set_keepalive_interval(&self, interval: Duration) -> io::Result<()> {
+        self.0.set_keepalive_interval(interval)
+    }
+
+    /// Gets the time between two keepalive probes that are not acknowledged.
+    ///
+    /// For more information about this option, see
+    /// [`TcpSocket::set_keepalive_interval`].
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn keepalive_interval(&self) -> io::Result<Duration> {
+        self.0.keepalive_interval()
+    }
+
+    /// Sets the number of unacknowledged keepalive probes after which the
+    /// connection is considered broken (`TCP_KEEPCNT`).
+    ///
+    /// Returns an error on platforms which do not support configuring it.
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn set_keepalive_retries(&self, retries: u32) -> io::Result<()> {
+        self.0.set_keepalive_retries(retries)
+    }
+
+    /// Gets the number of unacknowledged keepalive probes after which the
+    /// connection is considered broken.
+    ///
+    /// For more information about this option, see
+    /// [`TcpSocket::set_keepalive_retries`].
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn keepalive_retries(&self) -> io::Result<u32> {
+        self.0.keepalive_retries()
+    }
+
+    /// Sets the value of the `TCP_NODELAY` option on this socket.
+    ///
+    /// See [`TcpStream::set_nodelay`] for more information about this option.
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn set_nodelay(&self, nodelay: bool) -> io::Result<()> {
+        self.0.set_nodelay(nodelay)
+    }
+
+    /// Gets the value of the `TCP_NODELAY` option on this socket.
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn nodelay(&self) -> io::Result<bool> {
+        self.0.nodelay()
+    }
+
+    /// Binds this socket to the given local address.
+    ///
+    /// For a socket that is then [connected], this selects the local address
+    /// and port that the connection uses.
+    ///
+    /// [connected]: TcpSocket::connect
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn bind(&self, addr: SocketAddr) -> io::Result<()> {
+        self.0.bind(&addr)
+    }
+
+    /// Starts listening for incoming connections, turning this socket into a
+    /// [`TcpListener`].
+    ///
+    /// The socket must have been [bound] to a local address first.
+    /// `backlog` is the maximum number of pending connections, which the
+    /// operating system may lower.
+    ///
+    /// [bound]: TcpSocket::bind
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn listen(self, backlog: u32) -> io::Result<TcpListener> {
+        self.0.listen(backlog).map(TcpListener::from_inner)
+    }
+
+    /// Opens a connection to a remote host, turning this socket into a
+    /// [`TcpStream`].
+    ///
+    /// This blocks until the connection is established or fails; see
+    /// [`connect_timeout`] to bound the time that this may take.
+    ///
+    /// [`connect_timeout`]: TcpSocket::connect_timeout
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(socket_builder)]
+    /// use std::net::TcpSocket;
+    /// use std::time::Duration;
+    ///
+    /// let socket = TcpSocket::new_v4()?;
+    /// socket.set_keepalive(true)?;
+    /// socket.set_keepalive_time(Duration::from_secs(30))?;
+    /// // Connect from a fixed local address.
+    /// socket.bind("192.0.2.1:0".parse().unwrap())?;
+    /// let stream = socket.connect("192.0.2.2:8080".parse().unwrap())?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn connect(self, addr: SocketAddr) -> io::Result<TcpStream> {
+        self.0.connect(&addr).map(TcpStream::from_inner)
+    }
+
+    /// Opens a connection to a remote host with a timeout, turning this socket
+    /// into a [`TcpStream`].
+    ///
+    /// See [`TcpStream::connect_timeout`] for how the timeout is handled.
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn connect_timeout(self, addr: SocketAddr, timeout: Duration) -> io::Result<TcpStream> {
+        self.0.connect_timeout(&addr, timeout).map(TcpStream::from_inner)
+    }
+}
+
+impl UdpSocketBuilder {
+    /// Creates a builder for an IPv4 UDP socket.
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn new_v4() -> io::Result<UdpSocketBuilder> {
+        net_imp::SocketBuilder::new_udp(false).map(UdpSocketBuilder)
+    }
+
+    /// Creates a builder for an IPv6 UDP socket.
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn new_v6() -> io::Result<UdpSocketBuilder> {
+        net_imp::SocketBuilder::new_udp(true).map(UdpSocketBuilder)
+    }
+
+    /// Creates a builder for a UDP socket that can bind to addresses of the
+    /// same family as `addr`.
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn new_for_addr(addr: SocketAddr) -> io::Result<UdpSocketBuilder> {
+        net_imp::SocketBuilder::new_udp(addr.is_ipv6()).map(UdpSocketBuilder)
+    }
+
+    /// Sets the value of the `SO_BROADCAST` option on this socket.
+    ///
+    /// See [`UdpSocket::set_broadcast`] for more information about this
+    /// option.
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn set_broadcast(&self, broadcast: bool) -> io::Result<()> {
+        self.0.set_broadcast(broadcast)
+    }
+
+    /// Gets the value of the `SO_BROADCAST` option on this socket.
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn broadcast(&self) -> io::Result<bool> {
+        self.0.broadcast()
+    }
+
+    /// Binds the socket to the given local address, turning this builder into
+    /// a [`UdpSocket`].
+    #[unstable(feature = "socket_builder", issue = "none")]
+    pub fn bind(self, addr: SocketAddr) -> io::Result<UdpSocket> {
+        self.0.bind(&addr)?;
+        Ok(UdpSocket::from_inner(self.0.into_udp_socket()))
+    }
+}
@@ -0,0 +1,115 @@
+use crate::io::prelude::*;
+use crate::net::tests::{LOCALHOST_IP4, LOCALHOST_IP6};
+use crate::net::*;
+use crate::thread;
+use crate::time::Duration;
+
+macro_rules! t {
+    ($e:expr) => {
+        match $e {
+            Ok(t) => t,
+            Err(e) => panic!("received error for `{}`: {}", stringify!($e), e),
+        }
+    };
+}
+
+#[test]
+fn tcp_listen_and_connect() {
+    for addr in [LOCALHOST_IP4, LOCALHOST_IP6] {
+        let socket = t!(TcpSocket::new_for_addr(addr));
+        t!(socket.set_reuse_address(true));
+        t!(socket.bind(addr));
+        let local = t!(socket.local_addr());
+        let listener = t!(socket.listen(16));
+        assert_eq!(t!(listener.local_addr()), local);
+
+        let handle = thread::spawn(move || {
+            let mut stream = t!(TcpSocket::new_for_addr(local)).connect(local).unwrap();
+            t!(stream.write_all(b"hello"));
+        });
+
+        let mut buf = Vec::new();
+        t!(t!(listener.accept()).0.read_to_end(&mut buf));
+        assert_eq!(buf, b"hello");
+        handle.join().unwrap();
+    }
+}
+
+#[test]
+fn tcp_bind_before_connect() {
+    let listener = t!(TcpListener::bind(LOCALHOST_IP4));
+    let server = t!(listener.local_addr());
+
+    let socket = t!(TcpSocket::new_v4());
+    t!(socket.bind(LOCALHOST_IP4));
+    let local = t!(socket.local_addr());
+    let stream = t!(socket.connect_timeout(server, Duration::from_secs(10)));
+    assert_eq!(t!(stream.local_addr()), local);
+    assert_eq!(t!(listener.accept()).1, local);
+}
+
+#[test]
+fn tcp_options() {
+    let socket = t!(TcpSocket::new_v4());
+
+    t!(socket.set_reuse_address(true));
+    assert!(t!(socket.reuse_address()));
+    t!(socket.set_reuse_address(false));
+    assert!(!t!(socket.reuse_address()));
+
+    t!(socket.set_nodelay(true));
+    assert!(t!(socket.nodelay()));
+
+    t!(socket.set_keepalive(true));
+    assert!(t!(socket.keepalive()));
+
+    t!(socket.set_send_buffer_size(64 * 1024));
+    assert!(t!(socket.send_buffer_size()) >= 64 * 1024);
+    t!(socket.set_recv_buffer_size(64 * 1024));
+    assert!(t!(socket.recv_buffer_size()) >= 64 * 1024);
+}
+
+#[test]
+#[cfg(any(target_os = "linux", target_os = "android", target_vendor = "apple"))]
+fn tcp_keepalive_params() {
+    let socket = t!(TcpSocket::new_v4());
+    t!(socket.set_keepalive_time(Duration::from_secs(30)));
+    assert_eq!(t!(socket.keepalive_time()), Duration::from_secs(30));
+    t!(socket.set_keepalive_interval(Duration::from_secs(5)));
+    assert_eq!(t!(socket.keepalive_interval()), Duration::from_secs(5));
+    t!(socket.set_keepalive_retries(4));
+    assert_eq!(t!(socket.keepalive_retries()), 4);
+}
+
+#[test]
+#[cfg(any(target_os = "linux", target_os = "android", target_vendor = "apple"))]
+fn reuse_port() {
+    let first = t!(UdpSocketBuilder::new_v4());
+    t!(first.set_reuse_port(true));
+    assert!(t!(first.reuse_port()));
+    let first = t!(first.bind(LOCALHOST_IP4));
+    let addr = t!(first.local_addr());
+
+    // A second socket can bind to the same port only if both set the option.
+    let second = t!(UdpSocketBuilder::new_v4());
+    t!(second.set_reuse_port(true));
+    t!(second.bind(addr));
+    assert!(UdpSocketBuilder::new_v4().unwrap().bind(addr).is_err());
+}
+
+#[test]
+fn udp_bind() {
+    let builder = t!(UdpSocketBuilder::new_v6());
+    t!(builder.set_only_v6(true));
+    assert!(t!(builder.only_v6()));
+    t!(builder.set_broadcast(false));
+    assert!(!t!(builder.broadcast()));
+    let socket = t!(builder.bind(LOCALHOST_IP6));
+
+    let peer = t!(UdpSocket::bind(LOCALHOST_IP6));
+    t!(peer.send_to(b"ping", t!(socket.local_addr())));
+    let mut buf = [0; 4];
+    let (len, from) = t!(socket.recv_from(&mut buf));
+    assert_eq!(&buf[..len], b"ping");
+    assert_eq!(from, t!(peer.local_addr()));
+}
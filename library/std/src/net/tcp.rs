@@ -11,6 +11,8 @@
 ))]
 mod tests;
 
+mod happy_eyeballs;
+
 use crate::fmt;
 use crate::io::prelude::*;
 use crate::io::{self, BorrowedCursor, IoSlice, IoSliceMut};
@@ -185,6 +187,61 @@ pub fn connect_timeout(addr: &SocketAddr, timeout: Duration) -> io::Result<TcpSt
         net_imp::TcpStream::connect_timeout(addr, timeout).map(TcpStream)
     }
 
+    /// Opens a TCP connection to a remote host, racing connection attempts to
+    /// the resolved addresses as described by [RFC 8305] ("Happy Eyeballs").
+    ///
+    /// Where [`connect`] tries each address in turn and only moves on once an
+    /// attempt has failed, this method starts a new attempt every 250
+    /// milliseconds (or as soon as all earlier attempts have failed) while
+    /// the earlier ones are still in progress. The addresses are reordered so
+    /// that IPv6 and IPv4 alternate, starting with the family of the first
+    /// address yielded by `addr`. The first attempt to succeed is returned and
+    /// the others are abandoned, so a host with a broken IPv6 route costs a
+    /// quarter of a second rather than a full connect timeout.
+    ///
+    /// `timeout` bounds the whole operation. If no attempt succeeds before it
+    /// elapses, an error of kind [`TimedOut`] is returned; if every attempt
+    /// fails, the error from the last attempt to fail is returned.
+    ///
+    /// It is an error to pass a zero `Duration` to this function.
+    ///
+    /// The attempts that don't win are closed before this method returns.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// On platforms other than Unix (including WASI 0.2 and later) and
+    /// Windows, the addresses are tried one after another instead, like with
+    /// [`connect`], within `timeout`. On Windows, at most 64 attempts are
+    /// waited for at a time.
+    ///
+    /// Note that platform-specific behavior [may change in the future][changes].
+    ///
+    /// [changes]: io#platform-specific-behavior
+    /// [RFC 8305]: https://www.rfc-editor.org/rfc/rfc8305
+    /// [`connect`]: TcpStream::connect
+    /// [`TimedOut`]: io::ErrorKind::TimedOut
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(tcp_happy_eyeballs)]
+    ///
+    /// use std::net::TcpStream;
+    /// use std::time::Duration;
+    ///
+    /// let stream = TcpStream::connect_happy_eyeballs("example.com:80", Duration::from_secs(10))
+    ///     .expect("Couldn't connect to the server...");
+    /// println!("Connected to {}", stream.peer_addr().unwrap());
+    /// ```
+    #[unstable(feature = "tcp_happy_eyeballs", issue = "none")]
+    pub fn connect_happy_eyeballs<A: ToSocketAddrs>(
+        addr: A,
+        timeout: Duration,
+    ) -> io::Result<TcpStream> {
+        let addrs = addr.to_socket_addrs()?.collect();
+        happy_eyeballs::connect(addrs, timeout).map(TcpStream)
+    }
+
     /// Returns the socket address of the remote peer of this TCP connection.
     ///
     /// # Examples
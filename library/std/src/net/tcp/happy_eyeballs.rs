@@ -0,0 +1,50 @@
+//! Racing connection attempts across address families, as described by
+//! [RFC 8305] ("Happy Eyeballs Version 2").
+//!
+//! All attempts are nonblocking and waited for together, in the same way as
+//! `connect_timeout`. A new attempt is started every [`ATTEMPT_DELAY`], or as
+//! soon as every attempt started so far has failed. The first attempt to
+//! succeed wins, and the others are closed right away.
+//!
+//! [RFC 8305]: https://www.rfc-editor.org/rfc/rfc8305
+
+use crate::io;
+use crate::net::SocketAddr;
+use crate::sys::net as net_imp;
+use crate::time::Duration;
+
+/// The delay between starting two connection attempts, as recommended by
+/// section 5 of RFC 8305.
+pub(super) const ATTEMPT_DELAY: Duration = Duration::from_millis(250);
+
+/// Reorders `addrs` so that address families alternate, starting with the
+/// family of the first address (section 4 of RFC 8305). The relative order of
+/// addresses within a family is preserved.
+pub(super) fn interleave(addrs: Vec<SocketAddr>) -> Vec<SocketAddr> {
+    let Some(first) = addrs.first() else { return addrs };
+    let prefer_v6 = first.is_ipv6();
+    let (preferred, other): (Vec<_>, Vec<_>) =
+        addrs.into_iter().partition(|addr| addr.is_ipv6() == prefer_v6);
+
+    let mut result = Vec::with_capacity(preferred.len() + other.len());
+    let (mut preferred, mut other) = (preferred.into_iter(), other.into_iter());
+    loop {
+        match (preferred.next(), other.next()) {
+            (None, None) => return result,
+            (a, b) => result.extend(a.into_iter().chain(b)),
+        }
+    }
+}
+
+pub(super) fn connect(addrs: Vec<SocketAddr>, timeout: Duration) -> io::Result<net_imp::TcpStream> {
+    if timeout.is_zero() {
+        return Err(io::Error::ZERO_TIMEOUT);
+    }
+    let addrs = interleave(addrs);
+    match addrs[..] {
+        [] => Err(io::Error::NO_ADDRESSES),
+        // Nothing to race.
+        [addr] => net_imp::TcpStream::connect_timeout(&addr, timeout),
+        _ => net_imp::TcpStream::connect_racing(&addrs, ATTEMPT_DELAY, timeout),
+    }
+}
@@ -956,3 +956,40 @@ fn connect_timeout_valid() {
     let addr = listener.local_addr().unwrap();
     TcpStream::connect_timeout(&addr, Duration::from_secs(2)).unwrap();
 }
+
+#[test]
+#[cfg_attr(target_env = "sgx", ignore)] // FIXME: https://github.com/fortanix/rust-sgx/issues/31
+fn connect_happy_eyeballs() {
+    // Grab a port that nothing is listening on.
+    let refused = t!(t!(TcpListener::bind("127.0.0.1:0")).local_addr());
+    let listener = t!(TcpListener::bind("127.0.0.1:0"));
+    let addr = t!(listener.local_addr());
+
+    let stream =
+        t!(TcpStream::connect_happy_eyeballs(&[refused, addr][..], Duration::from_secs(5)));
+    assert_eq!(t!(stream.peer_addr()), addr);
+
+    let result = TcpStream::connect_happy_eyeballs(&[refused, refused][..], Duration::MAX);
+    assert!(!matches!(result, Err(e) if e.kind() == ErrorKind::TimedOut));
+
+    let result = TcpStream::connect_happy_eyeballs(addr, Duration::ZERO);
+    assert_eq!(result.unwrap_err().kind(), ErrorKind::InvalidInput);
+    let result = TcpStream::connect_happy_eyeballs(&[] as &[SocketAddr], Duration::from_secs(5));
+    assert_eq!(result.unwrap_err().kind(), ErrorKind::InvalidInput);
+}
+
+#[test]
+fn happy_eyeballs_interleave() {
+    use super::happy_eyeballs::interleave;
+
+    let v4 = |port| SocketAddr::from((Ipv4Addr::LOCALHOST, port));
+    let v6 = |port| SocketAddr::from((Ipv6Addr::LOCALHOST, port));
+
+    assert_eq!(
+        interleave(vec![v6(1), v6(2), v4(3), v4(4), v4(5)]),
+        [v6(1), v4(3), v6(2), v4(4), v4(5)]
+    );
+    assert_eq!(interleave(vec![v4(1), v4(2), v6(3)]), [v4(1), v6(3), v4(2)]);
+    assert_eq!(interleave(vec![v4(1), v4(2)]), [v4(1), v4(2)]);
+    assert!(interleave(vec![]).is_empty());
+}
@@ -1222,3 +1222,330 @@ pub fn chroot<P: AsRef<Path>>(dir: P) -> io::Result<()> {
 pub fn mkfifo<P: AsRef<Path>>(path: P, permissions: Permissions) -> io::Result<()> {
     sys::fs::mkfifo(path.as_ref(), permissions.mode())
 }
+
+/// Advice about how a memory map will be accessed, given with
+/// [`Mmap::advise`] or [`MmapMut::advise`].
+///
+/// This corresponds to the `advice` argument of `madvise`. The system is free
+/// to ignore it.
+#[unstable(feature = "unix_mmap", issue = "none")]
+#[cfg(not(any(target_os = "espidf", target_os = "horizon", target_os = "vita")))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Advice {
+    /// No particular access pattern. This is the default.
+    #[doc(alias = "MADV_NORMAL")]
+    Normal,
+    /// The map will be accessed in random order, so reading ahead is useless.
+    #[doc(alias = "MADV_RANDOM")]
+    Random,
+    /// The map will be accessed in sequential order, so it can be read ahead
+    /// aggressively, and pages can be freed soon after being accessed.
+    #[doc(alias = "MADV_SEQUENTIAL")]
+    Sequential,
+    /// The map will be accessed soon, so it can be read ahead now.
+    #[doc(alias = "MADV_WILLNEED")]
+    WillNeed,
+    /// The map won't be accessed soon, so its pages can be freed.
+    ///
+    /// The contents of the map are kept, and read from the file again when
+    /// next accessed.
+    #[doc(alias = "MADV_DONTNEED")]
+    DontNeed,
+}
+
+#[cfg(not(any(target_os = "espidf", target_os = "horizon", target_os = "vita")))]
+impl Advice {
+    fn as_raw(self) -> libc::c_int {
+        match self {
+            Advice::Normal => libc::MADV_NORMAL,
+            Advice::Random => libc::MADV_RANDOM,
+            Advice::Sequential => libc::MADV_SEQUENTIAL,
+            Advice::WillNeed => libc::MADV_WILLNEED,
+            Advice::DontNeed => libc::MADV_DONTNEED,
+        }
+    }
+}
+
+/// A read-only memory map of a file.
+///
+/// A `Mmap` dereferences to a `[u8]` slice holding the mapped contents of the
+/// file, which are read from the file on demand as they are accessed. The map
+/// is shared: it reflects changes to the file made in other ways, which is why
+/// creating one is `unsafe`. It is unmapped when dropped.
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(unix_mmap)]
+///
+/// use std::fs::File;
+/// use std::os::unix::fs::Mmap;
+///
+/// fn main() -> std::io::Result<()> {
+///     let file = File::open("index.bin")?;
+///     // SAFETY: nothing modifies `index.bin` while it is mapped.
+///     let map = unsafe { Mmap::map(&file)? };
+///     println!("header: {:?}", &map[..16]);
+///     Ok(())
+/// }
+/// ```
+#[unstable(feature = "unix_mmap", issue = "none")]
+#[cfg(not(any(target_os = "espidf", target_os = "horizon", target_os = "vita")))]
+pub struct Mmap {
+    inner: sys::fs::Mmap,
+}
+
+#[cfg(not(any(target_os = "espidf", target_os = "horizon", target_os = "vita")))]
+impl Mmap {
+    /// Maps the whole contents of `file` into memory, for reading.
+    ///
+    /// `file` must be open for reading. It can be closed once mapped: the map
+    /// stays valid until it is dropped.
+    ///
+    /// # Safety
+    ///
+    /// Until the map is dropped, the mapped part of the file must neither be
+    /// modified, whether by this process or any other, nor truncated.
+    /// Otherwise, the contents of the slice would change while borrowed, which
+    /// is undefined behavior, and accessing a part of it that no longer exists
+    /// in the file raises `SIGBUS`.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if `file` isn't open for reading, if
+    /// it can't be mapped, or if it is larger than the address space.
+    #[unstable(feature = "unix_mmap", issue = "none")]
+    pub unsafe fn map(file: &fs::File) -> io::Result<Mmap> {
+        let len = file_len(file)?;
+        // SAFETY: forwarded to the caller.
+        unsafe { Mmap::map_range(file, 0, len) }
+    }
+
+    /// Maps `len` bytes of `file` into memory from `offset`, for reading.
+    ///
+    /// `offset` doesn't need to be aligned to pages. The range should be
+    /// within the file: accessing a part of the map after the end of the file
+    /// raises `SIGBUS`.
+    ///
+    /// # Safety
+    ///
+    /// See [`Mmap::map`].
+    #[unstable(feature = "unix_mmap", issue = "none")]
+    pub unsafe fn map_range(file: &fs::File, offset: u64, len: usize) -> io::Result<Mmap> {
+        // SAFETY: forwarded to the caller.
+        let inner = unsafe { sys::fs::Mmap::map(file.as_fd(), offset, len, false)? };
+        Ok(Mmap { inner })
+    }
+
+    /// Tells the system how the map will be accessed.
+    #[unstable(feature = "unix_mmap", issue = "none")]
+    #[doc(alias = "madvise")]
+    pub fn advise(&self, advice: Advice) -> io::Result<()> {
+        self.inner.advise(advice.as_raw())
+    }
+}
+
+#[unstable(feature = "unix_mmap", issue = "none")]
+#[cfg(not(any(target_os = "espidf", target_os = "horizon", target_os = "vita")))]
+impl crate::ops::Deref for Mmap {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        // SAFETY: the map is valid and readable until dropped, and the caller
+        // of `map` promised that it wouldn't change.
+        unsafe { crate::slice::from_raw_parts(self.inner.as_ptr(), self.inner.len()) }
+    }
+}
+
+#[unstable(feature = "unix_mmap", issue = "none")]
+#[cfg(not(any(target_os = "espidf", target_os = "horizon", target_os = "vita")))]
+impl AsRef<[u8]> for Mmap {
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+#[unstable(feature = "unix_mmap", issue = "none")]
+#[cfg(not(any(target_os = "espidf", target_os = "horizon", target_os = "vita")))]
+impl crate::fmt::Debug for Mmap {
+    fn fmt(&self, f: &mut crate::fmt::Formatter<'_>) -> crate::fmt::Result {
+        f.debug_struct("Mmap")
+            .field("ptr", &self.inner.as_ptr())
+            .field("len", &self.inner.len())
+            .finish()
+    }
+}
+
+/// A writable memory map of a file.
+///
+/// A `MmapMut` dereferences to a mutable `[u8]` slice holding the mapped
+/// contents of the file. The map is shared: writes to it are written back to
+/// the file, eventually or when [`flush`] is called, and it reflects changes
+/// to the file made in other ways, which is why creating one is `unsafe`. It
+/// is unmapped when dropped.
+///
+/// [`flush`]: MmapMut::flush
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(unix_mmap)]
+///
+/// use std::fs::File;
+/// use std::os::unix::fs::MmapMut;
+///
+/// fn main() -> std::io::Result<()> {
+///     let file = File::options().read(true).write(true).open("counter.bin")?;
+///     file.set_len(8)?;
+///     // SAFETY: nothing else accesses `counter.bin` while it is mapped.
+///     let mut map = unsafe { MmapMut::map(&file)? };
+///     let count = u64::from_le_bytes(map[..8].try_into().unwrap());
+///     map[..8].copy_from_slice(&(count + 1).to_le_bytes());
+///     map.flush()
+/// }
+/// ```
+#[unstable(feature = "unix_mmap", issue = "none")]
+#[cfg(not(any(target_os = "espidf", target_os = "horizon", target_os = "vita")))]
+pub struct MmapMut {
+    inner: sys::fs::Mmap,
+}
+
+#[cfg(not(any(target_os = "espidf", target_os = "horizon", target_os = "vita")))]
+impl MmapMut {
+    /// Maps the whole contents of `file` into memory, for reading and writing.
+    ///
+    /// `file` must be open for both reading and writing. It can be closed once
+    /// mapped: the map stays valid until it is dropped.
+    ///
+    /// # Safety
+    ///
+    /// Until the map is dropped, the mapped part of the file must neither be
+    /// accessed other than through this map, whether by this process or any
+    /// other, nor truncated. Otherwise, the contents of the slice would change
+    /// while borrowed, which is undefined behavior, and accessing a part of it
+    /// that no longer exists in the file raises `SIGBUS`.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if `file` isn't open for reading and
+    /// writing, if it can't be mapped, or if it is larger than the address
+    /// space.
+    #[unstable(feature = "unix_mmap", issue = "none")]
+    pub unsafe fn map(file: &fs::File) -> io::Result<MmapMut> {
+        let len = file_len(file)?;
+        // SAFETY: forwarded to the caller.
+        unsafe { MmapMut::map_range(file, 0, len) }
+    }
+
+    /// Maps `len` bytes of `file` into memory from `offset`, for reading and
+    /// writing.
+    ///
+    /// `offset` doesn't need to be aligned to pages. The range should be
+    /// within the file: accessing a part of the map after the end of the file
+    /// raises `SIGBUS`, so extend the file with [`File::set_len`] first if
+    /// needed.
+    ///
+    /// [`File::set_len`]: fs::File::set_len
+    ///
+    /// # Safety
+    ///
+    /// See [`MmapMut::map`].
+    #[unstable(feature = "unix_mmap", issue = "none")]
+    pub unsafe fn map_range(file: &fs::File, offset: u64, len: usize) -> io::Result<MmapMut> {
+        // SAFETY: forwarded to the caller.
+        let inner = unsafe { sys::fs::Mmap::map(file.as_fd(), offset, len, true)? };
+        Ok(MmapMut { inner })
+    }
+
+    /// Writes the changes made to the map back to the file, and waits for
+    /// them to reach the disk.
+    #[unstable(feature = "unix_mmap", issue = "none")]
+    #[doc(alias = "msync")]
+    pub fn flush(&self) -> io::Result<()> {
+        self.inner.flush(0, self.inner.len(), false)
+    }
+
+    /// Starts writing the changes made to the map back to the file, without
+    /// waiting for them to reach the disk.
+    #[unstable(feature = "unix_mmap", issue = "none")]
+    pub fn flush_async(&self) -> io::Result<()> {
+        self.inner.flush(0, self.inner.len(), true)
+    }
+
+    /// Writes the changes made to `len` bytes of the map from `offset` back to
+    /// the file, and waits for them to reach the disk.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error of kind
+    /// [`io::ErrorKind::InvalidInput`] if the range is out of the bounds of
+    /// the map.
+    #[unstable(feature = "unix_mmap", issue = "none")]
+    pub fn flush_range(&self, offset: usize, len: usize) -> io::Result<()> {
+        self.inner.flush(offset, len, false)
+    }
+
+    /// Tells the system how the map will be accessed.
+    #[unstable(feature = "unix_mmap", issue = "none")]
+    #[doc(alias = "madvise")]
+    pub fn advise(&self, advice: Advice) -> io::Result<()> {
+        self.inner.advise(advice.as_raw())
+    }
+}
+
+#[unstable(feature = "unix_mmap", issue = "none")]
+#[cfg(not(any(target_os = "espidf", target_os = "horizon", target_os = "vita")))]
+impl crate::ops::Deref for MmapMut {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        // SAFETY: the map is valid and readable until dropped, and the caller
+        // of `map` promised that it would only be accessed through `self`.
+        unsafe { crate::slice::from_raw_parts(self.inner.as_ptr(), self.inner.len()) }
+    }
+}
+
+#[unstable(feature = "unix_mmap", issue = "none")]
+#[cfg(not(any(target_os = "espidf", target_os = "horizon", target_os = "vita")))]
+impl crate::ops::DerefMut for MmapMut {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        // SAFETY: as in `deref`, and the map is writable.
+        unsafe { crate::slice::from_raw_parts_mut(self.inner.as_ptr(), self.inner.len()) }
+    }
+}
+
+#[unstable(feature = "unix_mmap", issue = "none")]
+#[cfg(not(any(target_os = "espidf", target_os = "horizon", target_os = "vita")))]
+impl AsRef<[u8]> for MmapMut {
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+#[unstable(feature = "unix_mmap", issue = "none")]
+#[cfg(not(any(target_os = "espidf", target_os = "horizon", target_os = "vita")))]
+impl AsMut<[u8]> for MmapMut {
+    fn as_mut(&mut self) -> &mut [u8] {
+        self
+    }
+}
+
+#[unstable(feature = "unix_mmap", issue = "none")]
+#[cfg(not(any(target_os = "espidf", target_os = "horizon", target_os = "vita")))]
+impl crate::fmt::Debug for MmapMut {
+    fn fmt(&self, f: &mut crate::fmt::Formatter<'_>) -> crate::fmt::Result {
+        f.debug_struct("MmapMut")
+            .field("ptr", &self.inner.as_ptr())
+            .field("len", &self.inner.len())
+            .finish()
+    }
+}
+
+#[cfg(not(any(target_os = "espidf", target_os = "horizon", target_os = "vita")))]
+fn file_len(file: &fs::File) -> io::Result<usize> {
+    file.metadata()?
+        .len()
+        .try_into()
+        .map_err(|_| io::const_error!(io::ErrorKind::FileTooLarge, "file too large to map"))
+}
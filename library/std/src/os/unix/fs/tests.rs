@@ -75,3 +75,51 @@ fn test_mkfifo() {
 
     assert_eq!(s, "hello, world!");
 }
+
+#[test]
+fn mmap() {
+    let dir = crate::test_helpers::tmpdir();
+    let path = dir.join("mmap.bin");
+    let contents: Vec<u8> = (0..10_000u32).map(|i| i as u8).collect();
+    fs::write(&path, &contents).unwrap();
+
+    let file = fs::File::open(&path).unwrap();
+    let map = unsafe { Mmap::map(&file).unwrap() };
+    drop(file);
+    assert_eq!(&map[..], &contents[..]);
+    map.advise(Advice::Sequential).unwrap();
+
+    // Offsets don't need to be aligned to pages.
+    let file = fs::File::open(&path).unwrap();
+    let map = unsafe { Mmap::map_range(&file, 4097, 100).unwrap() };
+    assert_eq!(&map[..], &contents[4097..4197]);
+
+    // Empty files can be mapped too.
+    let empty = dir.join("empty.bin");
+    fs::write(&empty, b"").unwrap();
+    let map = unsafe { Mmap::map(&fs::File::open(&empty).unwrap()).unwrap() };
+    assert!(map.is_empty());
+}
+
+#[test]
+fn mmap_mut() {
+    let dir = crate::test_helpers::tmpdir();
+    let path = dir.join("mmap_mut.bin");
+    fs::write(&path, vec![0; 10_000]).unwrap();
+
+    let file = fs::File::options().read(true).write(true).open(&path).unwrap();
+    let mut map = unsafe { MmapMut::map_range(&file, 5000, 10).unwrap() };
+    map.copy_from_slice(b"0123456789");
+    map.flush_range(3, 4).unwrap();
+    map.flush().unwrap();
+    assert_eq!(map.flush_range(5, 6).unwrap_err().kind(), io::ErrorKind::InvalidInput);
+    drop(map);
+
+    let contents = fs::read(&path).unwrap();
+    assert_eq!(&contents[5000..5010], b"0123456789");
+    assert!(contents[..5000].iter().chain(&contents[5010..]).all(|&b| b == 0));
+
+    // Writable maps need a file open for writing.
+    let file = fs::File::open(&path).unwrap();
+    assert!(unsafe { MmapMut::map(&file) }.is_err());
+}
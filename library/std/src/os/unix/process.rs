@@ -217,6 +217,50 @@ fn arg0<S>(&mut self, arg: S) -> &mut process::Command
 
     #[unstable(feature = "process_setsid", issue = "105376")]
     fn setsid(&mut self, setsid: bool) -> &mut process::Command;
+
+    /// Kills the child process if its [`Child`] is dropped while the process is still running.
+    ///
+    /// Normally, dropping a [`Child`] leaves the process running in the background. With this
+    /// option, dropping it instead sends `SIGKILL` to the process, and waits for it to exit so
+    /// that it doesn't linger as a zombie. Nothing happens if the process has already been waited
+    /// on, for example with [`Child::wait`] or [`Child::try_wait`].
+    ///
+    /// The drop only waits for up to about a second, as a killed process may take arbitrarily
+    /// long to exit while it's stuck in an uninterruptible sleep, for example on an unresponsive
+    /// network file system. If it takes longer, it's left behind as a zombie.
+    ///
+    /// If the child is the leader of a new process group, because it was spawned with
+    /// [`process_group(0)`] or [`setsid(true)`], then the whole process group is killed instead.
+    /// This also terminates the processes the child spawned itself, as long as they didn't move
+    /// to another process group.
+    ///
+    /// This option is currently ignored on Fuchsia and VxWorks.
+    ///
+    /// [`Child`]: process::Child
+    /// [`Child::wait`]: process::Child::wait
+    /// [`Child::try_wait`]: process::Child::try_wait
+    /// [`process_group(0)`]: CommandExt::process_group
+    /// [`setsid(true)`]: CommandExt::setsid
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(process_kill_on_drop)]
+    ///
+    /// use std::os::unix::process::CommandExt;
+    /// use std::process::Command;
+    ///
+    /// let build = Command::new("make")
+    ///     .process_group(0)
+    ///     .kill_on_drop(true)
+    ///     .spawn()?;
+    ///
+    /// // If the build is cancelled, dropping `build` kills `make` and everything it started.
+    /// drop(build);
+    /// # std::io::Result::Ok(())
+    /// ```
+    #[unstable(feature = "process_kill_on_drop", issue = "none")]
+    fn kill_on_drop(&mut self, kill_on_drop: bool) -> &mut process::Command;
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
@@ -272,6 +316,11 @@ fn setsid(&mut self, setsid: bool) -> &mut process::Command {
         self.as_inner_mut().setsid(setsid);
         self
     }
+
+    fn kill_on_drop(&mut self, kill_on_drop: bool) -> &mut process::Command {
+        self.as_inner_mut().kill_on_drop(kill_on_drop);
+        self
+    }
 }
 
 /// Unix-specific extensions to [`ExitStatus`] and [`ExitStatusError`].
@@ -21,7 +21,7 @@
 use crate::io::try_set_output_capture;
 use crate::mem::{self, ManuallyDrop};
 use crate::panic::{BacktraceStyle, PanicHookInfo};
-use crate::sync::atomic::{Atomic, AtomicBool, Ordering};
+use crate::sync::atomic::{Atomic, AtomicBool, AtomicU8, Ordering};
 use crate::sync::nonpoison::RwLock;
 use crate::sys::backtrace;
 use crate::sys::stdio::panic_output;
@@ -110,6 +110,15 @@ fn into_box(self) -> Box<dyn Fn(&PanicHookInfo<'_>) + 'static + Sync + Send> {
 ///
 /// The default hook, which is registered at startup, prints a message to standard error and
 /// generates a backtrace if requested. This behavior can be customized using the `set_hook` function.
+///
+/// If the `RUST_PANIC_FORMAT` environment variable is set to `json`, the default hook instead
+/// writes each panic to standard error as a single line containing a JSON object. Its keys are
+/// `thread` (the thread name, or `null`), `thread_id`, `message`, `location` (an object with
+/// `file`, `line` and `column`), `payload_type` (`null` if the payload is neither a string
+/// slice nor a `String`) and `backtrace`. The backtrace is `null` unless one was requested, and
+/// otherwise an array of frames with the keys `ip`, `function`, `file`, `line` and `column`.
+/// This format is unstable and may gain keys in the future.
+///
 /// The current hook can be retrieved while reinstating the default hook with the [`take_hook`]
 /// function.
 ///
@@ -259,6 +268,13 @@ fn default_hook(info: &PanicHookInfo<'_>) {
         // Some platforms also require it when printing a backtrace, like `SymFromAddr` on Windows.
         let mut lock = backtrace::lock();
 
+        if json_output() {
+            thread::with_current_name(|name| {
+                let _ = write_json(&mut lock, err, name, location, info.payload(), msg, backtrace);
+            });
+            return;
+        }
+
         thread::with_current_name(|name| {
             let name = name.unwrap_or("<unnamed>");
             let tid = thread::current_os_id();
@@ -320,6 +336,70 @@ fn default_hook(info: &PanicHookInfo<'_>) {
     }
 }
 
+/// Checks whether the default hook should write panics as JSON, which is selected by setting the
+/// `RUST_PANIC_FORMAT` environment variable to `json`. Like `RUST_BACKTRACE`, the variable is only
+/// read once.
+fn json_output() -> bool {
+    // 0 if the variable has not been read yet, 1 for human-readable output and 2 for JSON.
+    static FORMAT: Atomic<u8> = AtomicU8::new(0);
+
+    match FORMAT.load(Ordering::Relaxed) {
+        0 => {
+            let json = crate::env::var_os("RUST_PANIC_FORMAT").is_some_and(|x| x == "json");
+            FORMAT.store(if json { 2 } else { 1 }, Ordering::Relaxed);
+            json
+        }
+        format => format == 2,
+    }
+}
+
+/// Writes a panic as a single line of JSON, for use by the default hook.
+#[optimize(size)]
+fn write_json(
+    lock: &mut backtrace::BacktraceLock<'_>,
+    err: &mut dyn crate::io::Write,
+    name: Option<&str>,
+    location: &Location<'_>,
+    payload: &(dyn Any + Send),
+    msg: &str,
+    backtrace: Option<BacktraceStyle>,
+) -> crate::io::Result<()> {
+    use crate::backtrace_rs::PrintFmt;
+    use crate::io::Write;
+    use crate::sys::backtrace::{JsonOption, JsonStr};
+
+    let payload_type = if payload.is::<&'static str>() {
+        Some(crate::any::type_name::<&'static str>())
+    } else if payload.is::<String>() {
+        Some(crate::any::type_name::<String>())
+    } else {
+        None
+    };
+
+    // Assemble the whole line first so that it is written to the output at once.
+    let mut line = Vec::new();
+    write!(
+        line,
+        "{{\"thread\":{},\"thread_id\":{},\"message\":{},\
+         \"location\":{{\"file\":{},\"line\":{},\"column\":{}}},\
+         \"payload_type\":{},\"backtrace\":",
+        JsonOption(name.map(JsonStr)),
+        thread::current_os_id(),
+        JsonStr(msg),
+        JsonStr(location.file()),
+        location.line(),
+        location.column(),
+        JsonOption(payload_type.map(JsonStr)),
+    )?;
+    match backtrace {
+        Some(BacktraceStyle::Short) => lock.print_json(&mut line, PrintFmt::Short)?,
+        Some(BacktraceStyle::Full) => lock.print_json(&mut line, PrintFmt::Full)?,
+        Some(BacktraceStyle::Off) | None => line.extend_from_slice(b"null"),
+    }
+    line.push(b'\n');
+    err.write_all(&line)
+}
+
 #[cfg(not(test))]
 #[doc(hidden)]
 #[cfg(panic = "immediate-abort")]
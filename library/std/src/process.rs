@@ -168,7 +168,8 @@
 use crate::num::NonZero;
 use crate::path::Path;
 use crate::sys::{AsInner, AsInnerMut, FromInner, IntoInner, process as imp};
-use crate::{fmt, format_args_nl, fs, str};
+use crate::time::{Duration, Instant};
+use crate::{fmt, format_args_nl, fs, panic, str, thread};
 
 /// Representation of a running or exited child process.
 ///
@@ -1140,6 +1141,72 @@ pub fn output(&mut self) -> io::Result<Output> {
         Ok(Output { status: ExitStatus(status), stdout, stderr })
     }
 
+    /// Executes the command as a child process, waiting for at most `timeout`
+    /// for it to finish, and collecting all of its output.
+    ///
+    /// This behaves like [`output`], except that if the child is still running
+    /// once `timeout` has elapsed, it is killed and an error of kind
+    /// [`io::ErrorKind::TimedOut`] is returned. Any output it produced until
+    /// then is discarded.
+    ///
+    /// The output is read from a separate thread while waiting, so that the
+    /// child can't block on a full pipe. Note that if the child spawned
+    /// processes of its own that keep its stdout or stderr open, this method
+    /// won't return before they exit or close them, even after a timeout.
+    ///
+    /// [`output`]: Command::output
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(process_wait_timeout)]
+    ///
+    /// use std::io;
+    /// use std::process::Command;
+    /// use std::time::Duration;
+    ///
+    /// match Command::new("slow-command").output_timeout(Duration::from_secs(10)) {
+    ///     Ok(output) => println!("status: {}", output.status),
+    ///     Err(e) if e.kind() == io::ErrorKind::TimedOut => println!("took too long"),
+    ///     Err(e) => println!("failed to run: {e}"),
+    /// }
+    /// ```
+    #[unstable(feature = "process_wait_timeout", issue = "none")]
+    pub fn output_timeout(&mut self, timeout: Duration) -> io::Result<Output> {
+        let deadline = Instant::now().checked_add(timeout);
+        let mut child = self.inner.spawn(imp::Stdio::MakePipe, false).map(Child::from_inner)?;
+        drop(child.stdin.take());
+
+        let (stdout, stderr) = (child.stdout.take(), child.stderr.take());
+        let reader = match thread::Builder::new().spawn(move || read_child_output(stdout, stderr)) {
+            Ok(reader) => reader,
+            Err(e) => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(e);
+            }
+        };
+        let status = match deadline {
+            Some(deadline) => child.wait_deadline(deadline),
+            None => child.wait().map(Some),
+        };
+        match status {
+            Ok(Some(status)) => {
+                let (stdout, stderr) = reader.join().unwrap_or_else(|e| panic::resume_unwind(e));
+                Ok(Output { status, stdout, stderr })
+            }
+            // Don't leave the child running behind, whether it timed out or waiting failed.
+            result => {
+                let _ = child.kill();
+                let _ = child.wait();
+                let _ = reader.join();
+                Err(result.err().unwrap_or_else(|| {
+                    io::const_error!(io::ErrorKind::TimedOut, "the process timed out")
+                }))
+            }
+        }
+    }
+
     /// Executes a command as a child process, waiting for it to finish and
     /// collecting its status.
     ///
@@ -2435,6 +2502,86 @@ pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
         Ok(self.handle.try_wait()?.map(ExitStatus))
     }
 
+    /// Waits for the child to exit for at most `timeout`, returning the status
+    /// that it exited with.
+    ///
+    /// If the child exits within `timeout`, then `Ok(Some(status))` is
+    /// returned, and on Unix the process ID is reaped. If it is still running
+    /// once `timeout` has elapsed, then `Ok(None)` is returned and the child is
+    /// left running. If an error occurs, then that error is returned.
+    ///
+    /// Like [`wait`], the stdin handle to the child process, if any, will be
+    /// closed before waiting.
+    ///
+    /// On Linux, this blocks on a pidfd for the child. On other Unix platforms
+    /// without a way to wait on a child with a timeout, the child is polled
+    /// periodically, so the exit may be noticed a few milliseconds late.
+    ///
+    /// [`wait`]: Child::wait
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(process_wait_timeout)]
+    ///
+    /// use std::process::Command;
+    /// use std::time::Duration;
+    ///
+    /// let mut child = Command::new("ls").spawn()?;
+    ///
+    /// match child.wait_timeout(Duration::from_secs(5))? {
+    ///     Some(status) => println!("exited with: {status}"),
+    ///     None => {
+    ///         println!("still running after 5 seconds, killing it");
+    ///         child.kill()?;
+    ///         child.wait()?;
+    ///     }
+    /// }
+    /// # std::io::Result::Ok(())
+    /// ```
+    #[unstable(feature = "process_wait_timeout", issue = "none")]
+    pub fn wait_timeout(&mut self, timeout: Duration) -> io::Result<Option<ExitStatus>> {
+        match Instant::now().checked_add(timeout) {
+            Some(deadline) => self.wait_deadline(deadline),
+            None => self.wait().map(Some),
+        }
+    }
+
+    /// Waits for the child to exit until `deadline` at most, returning the
+    /// status that it exited with.
+    ///
+    /// This is the same as [`wait_timeout`], with an [`Instant`] instead of a
+    /// [`Duration`]. If `deadline` has already passed, this behaves like
+    /// [`try_wait`], except that the stdin handle is closed.
+    ///
+    /// [`wait_timeout`]: Child::wait_timeout
+    /// [`try_wait`]: Child::try_wait
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(process_wait_timeout)]
+    ///
+    /// use std::process::Command;
+    /// use std::time::{Duration, Instant};
+    ///
+    /// let deadline = Instant::now() + Duration::from_secs(5);
+    /// let mut children = vec![Command::new("ls").spawn()?, Command::new("ls").spawn()?];
+    ///
+    /// // All the children share the same five seconds.
+    /// for child in &mut children {
+    ///     if child.wait_deadline(deadline)?.is_none() {
+    ///         child.kill()?;
+    ///     }
+    /// }
+    /// # std::io::Result::Ok(())
+    /// ```
+    #[unstable(feature = "process_wait_timeout", issue = "none")]
+    pub fn wait_deadline(&mut self, deadline: Instant) -> io::Result<Option<ExitStatus>> {
+        drop(self.stdin.take());
+        Ok(self.handle.wait_deadline(deadline)?.map(ExitStatus))
+    }
+
     /// Simultaneously waits for the child to exit and collect all remaining
     /// output on the stdout/stderr handles, returning an `Output`
     /// instance.
@@ -2471,28 +2618,36 @@ pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
     pub fn wait_with_output(mut self) -> io::Result<Output> {
         drop(self.stdin.take());
 
-        let (mut stdout, mut stderr) = (Vec::new(), Vec::new());
-        match (self.stdout.take(), self.stderr.take()) {
-            (None, None) => {}
-            (Some(mut out), None) => {
-                let res = out.read_to_end(&mut stdout);
-                res.unwrap();
-            }
-            (None, Some(mut err)) => {
-                let res = err.read_to_end(&mut stderr);
-                res.unwrap();
-            }
-            (Some(out), Some(err)) => {
-                let res = imp::read_output(out.inner, &mut stdout, err.inner, &mut stderr);
-                res.unwrap();
-            }
-        }
-
+        let (stdout, stderr) = read_child_output(self.stdout.take(), self.stderr.take());
         let status = self.wait()?;
         Ok(Output { status, stdout, stderr })
     }
 }
 
+/// Reads both of a child's output streams until they're closed.
+fn read_child_output(
+    stdout: Option<ChildStdout>,
+    stderr: Option<ChildStderr>,
+) -> (Vec<u8>, Vec<u8>) {
+    let (mut stdout_buf, mut stderr_buf) = (Vec::new(), Vec::new());
+    match (stdout, stderr) {
+        (None, None) => {}
+        (Some(mut out), None) => {
+            let res = out.read_to_end(&mut stdout_buf);
+            res.unwrap();
+        }
+        (None, Some(mut err)) => {
+            let res = err.read_to_end(&mut stderr_buf);
+            res.unwrap();
+        }
+        (Some(out), Some(err)) => {
+            let res = imp::read_output(out.inner, &mut stdout_buf, err.inner, &mut stderr_buf);
+            res.unwrap();
+        }
+    }
+    (stdout_buf, stderr_buf)
+}
+
 /// Terminates the current process with the specified exit code.
 ///
 /// This function will never return and will immediately terminate the current
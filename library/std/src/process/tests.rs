@@ -3,6 +3,7 @@
 use crate::io::{BorrowedBuf, ErrorKind};
 use crate::mem::MaybeUninit;
 use crate::str;
+use crate::time::{Duration, Instant};
 
 fn known_command() -> Command {
     if cfg!(windows) {
@@ -327,6 +328,73 @@ pub fn env_cmd() -> Command {
     cmd
 }
 
+fn sleep_cmd() -> Command {
+    if cfg!(target_os = "windows") {
+        let mut cmd = Command::new("cmd");
+        cmd.args(&["/C", "ping -n 60 127.0.0.1 >nul 2>nul"]);
+        cmd
+    } else {
+        let mut cmd = shell_cmd();
+        // `exec`, so that no process is left holding the output pipes once this one is killed.
+        cmd.arg("-c").arg("exec sleep 60");
+        cmd
+    }
+}
+
+#[test]
+#[cfg_attr(
+    any(target_os = "vxworks", all(target_vendor = "apple", not(target_os = "macos"))),
+    ignore = "no shell available"
+)]
+fn test_wait_timeout() {
+    let mut prog = sleep_cmd().spawn().unwrap();
+    let start = Instant::now();
+    assert!(prog.wait_timeout(Duration::from_millis(100)).unwrap().is_none());
+    assert!(start.elapsed() >= Duration::from_millis(100));
+    assert!(prog.try_wait().unwrap().is_none());
+
+    prog.kill().unwrap();
+    let status = prog.wait_timeout(Duration::from_secs(60)).unwrap().unwrap();
+    assert!(!status.success());
+    assert_eq!(prog.wait_deadline(Instant::now()).unwrap(), Some(status));
+}
+
+#[test]
+#[cfg_attr(
+    any(target_os = "vxworks", all(target_vendor = "apple", not(target_os = "macos"))),
+    ignore = "no shell available"
+)]
+fn test_wait_timeout_exited() {
+    let mut prog = if cfg!(target_os = "windows") {
+        Command::new("cmd").args(&["/C", "exit 1"]).spawn().unwrap()
+    } else {
+        shell_cmd().arg("-c").arg("false").spawn().unwrap()
+    };
+    let start = Instant::now();
+    let status = prog.wait_timeout(Duration::from_secs(60)).unwrap().unwrap();
+    assert!(start.elapsed() < Duration::from_secs(60));
+    assert_eq!(status.code(), Some(1));
+}
+
+#[test]
+#[cfg_attr(
+    any(target_os = "vxworks", all(target_vendor = "apple", not(target_os = "macos"))),
+    ignore = "no shell available"
+)]
+fn test_output_timeout() {
+    let Output { status, stdout, .. } = if cfg!(target_os = "windows") {
+        Command::new("cmd").args(&["/C", "echo hello"]).output_timeout(Duration::from_secs(60))
+    } else {
+        shell_cmd().arg("-c").arg("echo hello").output_timeout(Duration::from_secs(60))
+    }
+    .unwrap();
+    assert!(status.success());
+    assert_eq!(str::from_utf8(&stdout).unwrap().trim(), "hello");
+
+    let err = sleep_cmd().output_timeout(Duration::from_millis(100)).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::TimedOut);
+}
+
 #[test]
 #[cfg_attr(
     any(target_os = "vxworks", all(target_vendor = "apple", not(target_os = "macos"))),
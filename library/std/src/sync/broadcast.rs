@@ -0,0 +1,700 @@
+//! A multi-producer, multi-consumer broadcast channel.
+//!
+//! Unlike [`mpmc`](crate::sync::mpmc) channels, where each message is received by a single
+//! receiver, every message sent on a broadcast channel is received by every [`Receiver`] that
+//! existed when it was sent. Messages are cloned for each receiver, so they must implement
+//! [`Clone`].
+//!
+//! # Lagging
+//!
+//! A broadcast channel holds a bounded number of messages, chosen when calling [`channel`].
+//! Sending never blocks: when the channel is full, the oldest message is dropped to make room
+//! for the new one. A receiver that falls so far behind that messages it has not received yet
+//! are dropped is said to *lag*: its next receive operation fails with a `Lagged` error telling
+//! how many messages it missed, after which it continues with the oldest message still in the
+//! channel.
+//!
+//! This is an experimental module, so the API will likely change.
+//!
+//! # Examples
+//!
+//! ```
+//! #![feature(broadcast_channel)]
+//!
+//! use std::sync::broadcast;
+//! use std::thread;
+//!
+//! let (tx, mut rx1) = broadcast::channel(16);
+//! let mut rx2 = tx.subscribe();
+//!
+//! thread::spawn(move || {
+//!     tx.send(10).unwrap();
+//!     tx.send(20).unwrap();
+//! });
+//!
+//! assert_eq!(rx1.recv(), Ok(10));
+//! assert_eq!(rx1.recv(), Ok(20));
+//! assert_eq!(rx2.recv(), Ok(10));
+//! assert_eq!(rx2.recv(), Ok(20));
+//! ```
+
+use crate::collections::VecDeque;
+use crate::sync::Arc;
+use crate::sync::mpmc::context::Context;
+use crate::sync::mpmc::select::{Operation, Selected, Token};
+use crate::sync::mpmc::waker::SyncWaker;
+#[unstable(feature = "broadcast_channel", issue = "none")]
+pub use crate::sync::mpsc::SendError;
+use crate::sync::nonpoison::Mutex;
+use crate::time::{Duration, Instant};
+use crate::{error, fmt};
+
+/// Creates a new broadcast channel that can hold up to `capacity` messages, returning the
+/// sender/receiver halves.
+///
+/// More receivers can be created with [`Sender::subscribe`], or by cloning a [`Receiver`].
+///
+/// # Panics
+///
+/// Panics if `capacity` is zero.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(broadcast_channel)]
+///
+/// use std::sync::broadcast::{self, RecvError};
+///
+/// let (tx, mut rx) = broadcast::channel(2);
+///
+/// tx.send(1).unwrap();
+/// tx.send(2).unwrap();
+/// tx.send(3).unwrap();
+///
+/// // The channel could only hold two messages, so the first one was dropped.
+/// assert_eq!(rx.recv(), Err(RecvError::Lagged(1)));
+/// assert_eq!(rx.recv(), Ok(2));
+/// assert_eq!(rx.recv(), Ok(3));
+/// ```
+#[must_use]
+#[unstable(feature = "broadcast_channel", issue = "none")]
+pub fn channel<T: Clone>(capacity: usize) -> (Sender<T>, Receiver<T>) {
+    assert!(capacity > 0, "broadcast channel capacity must be positive");
+    let shared = Arc::new(Shared {
+        state: Mutex::new(State {
+            buffer: VecDeque::with_capacity(capacity),
+            capacity,
+            head: 0,
+            senders: 1,
+            receivers: 1,
+        }),
+        receivers: SyncWaker::new(),
+    });
+    (Sender { shared: shared.clone() }, Receiver { shared, next: 0 })
+}
+
+/// The state shared by all senders and receivers of a channel.
+struct Shared<T> {
+    /// The messages and counters, behind a single lock.
+    state: Mutex<State<T>>,
+
+    /// Receivers waiting for a message.
+    receivers: SyncWaker,
+}
+
+struct State<T> {
+    /// The messages that can still be received, oldest first.
+    buffer: VecDeque<T>,
+
+    /// The maximum number of messages in `buffer`.
+    capacity: usize,
+
+    /// The position of the oldest message in `buffer`, counting all messages ever sent.
+    head: u64,
+
+    /// The number of senders.
+    senders: usize,
+
+    /// The number of receivers.
+    receivers: usize,
+}
+
+impl<T> State<T> {
+    /// Returns the position of the next message to be sent.
+    fn tail(&self) -> u64 {
+        self.head + self.buffer.len() as u64
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Sender
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// The sending half of a broadcast channel.
+///
+/// Senders can be cloned to send to the same channel from multiple threads.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(broadcast_channel)]
+///
+/// use std::sync::broadcast;
+/// use std::thread;
+///
+/// let (tx, mut rx) = broadcast::channel(4);
+/// let tx2 = tx.clone();
+///
+/// thread::spawn(move || tx.send("hello").unwrap()).join().unwrap();
+/// thread::spawn(move || tx2.send("world").unwrap()).join().unwrap();
+///
+/// assert_eq!(rx.recv(), Ok("hello"));
+/// assert_eq!(rx.recv(), Ok("world"));
+/// ```
+#[unstable(feature = "broadcast_channel", issue = "none")]
+pub struct Sender<T> {
+    shared: Arc<Shared<T>>,
+}
+
+// SAFETY: messages are only ever accessed, moved or cloned while the state is locked, so sharing
+// a channel between threads is safe as long as the messages can be sent between threads.
+#[unstable(feature = "broadcast_channel", issue = "none")]
+unsafe impl<T: Send> Send for Sender<T> {}
+#[unstable(feature = "broadcast_channel", issue = "none")]
+unsafe impl<T: Send> Sync for Sender<T> {}
+
+impl<T> Sender<T> {
+    /// Sends a message to all receivers of this channel, without blocking.
+    ///
+    /// If the channel is full, the oldest message is dropped to make room for this one, which
+    /// makes the receivers that had not received it yet lag.
+    ///
+    /// On success, returns the number of receivers that will receive the message. This fails
+    /// only if there are no receivers at all, in which case the message is returned in the
+    /// [`SendError`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(broadcast_channel)]
+    ///
+    /// use std::sync::broadcast;
+    ///
+    /// let (tx, rx) = broadcast::channel(4);
+    /// let rx2 = tx.subscribe();
+    ///
+    /// assert_eq!(tx.send(1), Ok(2));
+    ///
+    /// drop((rx, rx2));
+    /// assert!(tx.send(2).is_err());
+    /// ```
+    #[unstable(feature = "broadcast_channel", issue = "none")]
+    pub fn send(&self, msg: T) -> Result<usize, SendError<T>> {
+        let mut state = self.shared.state.lock();
+        if state.receivers == 0 {
+            return Err(SendError(msg));
+        }
+
+        let dropped = if state.buffer.len() == state.capacity {
+            state.head += 1;
+            state.buffer.pop_front()
+        } else {
+            None
+        };
+        state.buffer.push_back(msg);
+        let receivers = state.receivers;
+        drop(state);
+
+        self.shared.receivers.notify_all();
+        // Drop the message that made room outside of the lock.
+        drop(dropped);
+        Ok(receivers)
+    }
+
+    /// Creates a new receiver for this channel.
+    ///
+    /// The new receiver only receives messages sent after this call.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(broadcast_channel)]
+    ///
+    /// use std::sync::broadcast::{self, TryRecvError};
+    ///
+    /// let (tx, _rx) = broadcast::channel(4);
+    ///
+    /// tx.send(1).unwrap();
+    /// let mut rx2 = tx.subscribe();
+    /// tx.send(2).unwrap();
+    ///
+    /// assert_eq!(rx2.try_recv(), Ok(2));
+    /// assert_eq!(rx2.try_recv(), Err(TryRecvError::Empty));
+    /// ```
+    #[unstable(feature = "broadcast_channel", issue = "none")]
+    pub fn subscribe(&self) -> Receiver<T> {
+        let mut state = self.shared.state.lock();
+        state.receivers += 1;
+        Receiver { shared: self.shared.clone(), next: state.tail() }
+    }
+
+    /// Returns the number of receivers of this channel.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(broadcast_channel)]
+    ///
+    /// use std::sync::broadcast;
+    ///
+    /// let (tx, rx) = broadcast::channel::<i32>(4);
+    /// assert_eq!(tx.receiver_count(), 1);
+    /// drop(rx);
+    /// assert_eq!(tx.receiver_count(), 0);
+    /// ```
+    #[unstable(feature = "broadcast_channel", issue = "none")]
+    pub fn receiver_count(&self) -> usize {
+        self.shared.state.lock().receivers
+    }
+}
+
+#[unstable(feature = "broadcast_channel", issue = "none")]
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        self.shared.state.lock().senders += 1;
+        Sender { shared: self.shared.clone() }
+    }
+}
+
+#[unstable(feature = "broadcast_channel", issue = "none")]
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let mut state = self.shared.state.lock();
+        state.senders -= 1;
+        let disconnected = state.senders == 0;
+        drop(state);
+
+        if disconnected {
+            self.shared.receivers.disconnect();
+        }
+    }
+}
+
+#[unstable(feature = "broadcast_channel", issue = "none")]
+impl<T> fmt::Debug for Sender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sender").finish_non_exhaustive()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Receiver
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// The receiving half of a broadcast channel.
+///
+/// Each receiver receives every message sent after it was created, unless it lags behind (see
+/// the [module-level documentation](self)).
+///
+/// Cloning a receiver creates a new receiver that will receive the same messages as the original
+/// one, starting with the next message the original one would receive.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(broadcast_channel)]
+///
+/// use std::sync::broadcast;
+/// use std::thread;
+///
+/// let (tx, rx) = broadcast::channel(4);
+///
+/// let handles: Vec<_> = (0..3)
+///     .map(|_| {
+///         let mut rx = rx.clone();
+///         thread::spawn(move || rx.recv().unwrap())
+///     })
+///     .collect();
+///
+/// tx.send(7).unwrap();
+/// for handle in handles {
+///     assert_eq!(handle.join().unwrap(), 7);
+/// }
+/// ```
+#[unstable(feature = "broadcast_channel", issue = "none")]
+pub struct Receiver<T> {
+    shared: Arc<Shared<T>>,
+
+    /// The position of the next message to receive.
+    next: u64,
+}
+
+// SAFETY: see `Sender`.
+#[unstable(feature = "broadcast_channel", issue = "none")]
+unsafe impl<T: Send> Send for Receiver<T> {}
+#[unstable(feature = "broadcast_channel", issue = "none")]
+unsafe impl<T: Send> Sync for Receiver<T> {}
+
+impl<T: Clone> Receiver<T> {
+    /// Attempts to receive the next message without blocking.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TryRecvError::Empty`] if there is no message to receive right now, and
+    /// [`TryRecvError::Disconnected`] if there is none and all senders have been dropped.
+    ///
+    /// Returns [`TryRecvError::Lagged`] if this receiver fell behind and missed some messages.
+    /// The next call will receive the oldest message still in the channel.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(broadcast_channel)]
+    ///
+    /// use std::sync::broadcast::{self, TryRecvError};
+    ///
+    /// let (tx, mut rx) = broadcast::channel(4);
+    /// assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+    ///
+    /// tx.send(1).unwrap();
+    /// drop(tx);
+    ///
+    /// assert_eq!(rx.try_recv(), Ok(1));
+    /// assert_eq!(rx.try_recv(), Err(TryRecvError::Disconnected));
+    /// ```
+    #[unstable(feature = "broadcast_channel", issue = "none")]
+    pub fn try_recv(&mut self) -> Result<T, TryRecvError> {
+        let state = self.shared.state.lock();
+        if self.next < state.head {
+            let missed = state.head - self.next;
+            self.next = state.head;
+            return Err(TryRecvError::Lagged(missed));
+        }
+        match state.buffer.get((self.next - state.head) as usize) {
+            Some(msg) => {
+                self.next += 1;
+                Ok(msg.clone())
+            }
+            None if state.senders == 0 => Err(TryRecvError::Disconnected),
+            None => Err(TryRecvError::Empty),
+        }
+    }
+
+    /// Receives the next message, blocking the current thread until one is sent.
+    ///
+    /// Messages sent before all senders were dropped can still be received afterwards.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RecvError::Disconnected`] if all senders have been dropped and there are no
+    /// messages left to receive.
+    ///
+    /// Returns [`RecvError::Lagged`] if this receiver fell behind and missed some messages.
+    /// The next call will receive the oldest message still in the channel.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(broadcast_channel)]
+    ///
+    /// use std::sync::broadcast::{self, RecvError};
+    /// use std::thread;
+    ///
+    /// let (tx, mut rx) = broadcast::channel(4);
+    ///
+    /// thread::spawn(move || {
+    ///     tx.send(1).unwrap();
+    /// });
+    ///
+    /// assert_eq!(rx.recv(), Ok(1));
+    /// assert_eq!(rx.recv(), Err(RecvError::Disconnected));
+    /// ```
+    #[unstable(feature = "broadcast_channel", issue = "none")]
+    pub fn recv(&mut self) -> Result<T, RecvError> {
+        self.recv_until(None).map_err(|err| match err {
+            RecvTimeoutError::Timeout => unreachable!(),
+            RecvTimeoutError::Disconnected => RecvError::Disconnected,
+            RecvTimeoutError::Lagged(missed) => RecvError::Lagged(missed),
+        })
+    }
+
+    /// Receives the next message, blocking the current thread for at most `timeout`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RecvTimeoutError::Timeout`] if no message was sent before the timeout elapsed.
+    /// The other errors are the same as for [`recv`](Self::recv).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(broadcast_channel)]
+    ///
+    /// use std::sync::broadcast::{self, RecvTimeoutError};
+    /// use std::time::Duration;
+    ///
+    /// let (_tx, mut rx) = broadcast::channel::<i32>(4);
+    ///
+    /// assert_eq!(rx.recv_timeout(Duration::from_millis(10)), Err(RecvTimeoutError::Timeout));
+    /// ```
+    #[unstable(feature = "broadcast_channel", issue = "none")]
+    pub fn recv_timeout(&mut self, timeout: Duration) -> Result<T, RecvTimeoutError> {
+        match Instant::now().checked_add(timeout) {
+            Some(deadline) => self.recv_deadline(deadline),
+            // So far in the future that it's practically the same as waiting indefinitely.
+            None => self.recv_until(None),
+        }
+    }
+
+    /// Receives the next message, blocking the current thread until `deadline` at most.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RecvTimeoutError::Timeout`] if no message was sent before the deadline.
+    /// The other errors are the same as for [`recv`](Self::recv).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(broadcast_channel)]
+    ///
+    /// use std::sync::broadcast::{self, RecvTimeoutError};
+    /// use std::time::{Duration, Instant};
+    ///
+    /// let (_tx, mut rx) = broadcast::channel::<i32>(4);
+    ///
+    /// let deadline = Instant::now() + Duration::from_millis(10);
+    /// assert_eq!(rx.recv_deadline(deadline), Err(RecvTimeoutError::Timeout));
+    /// ```
+    #[unstable(feature = "broadcast_channel", issue = "none")]
+    pub fn recv_deadline(&mut self, deadline: Instant) -> Result<T, RecvTimeoutError> {
+        self.recv_until(Some(deadline))
+    }
+
+    fn recv_until(&mut self, deadline: Option<Instant>) -> Result<T, RecvTimeoutError> {
+        let token = &mut Token::default();
+        loop {
+            // Try receiving a message.
+            match self.try_recv() {
+                Ok(msg) => return Ok(msg),
+                Err(TryRecvError::Empty) => {}
+                Err(TryRecvError::Disconnected) => return Err(RecvTimeoutError::Disconnected),
+                Err(TryRecvError::Lagged(missed)) => return Err(RecvTimeoutError::Lagged(missed)),
+            }
+
+            if let Some(d) = deadline {
+                if Instant::now() >= d {
+                    return Err(RecvTimeoutError::Timeout);
+                }
+            }
+
+            Context::with(|cx| {
+                // Prepare for blocking until a sender wakes us up.
+                let oper = Operation::hook(token);
+                self.shared.receivers.register(oper, cx);
+
+                // Has the channel become ready just now?
+                let state = self.shared.state.lock();
+                if self.next != state.tail() || state.senders == 0 {
+                    let _ = cx.try_select(Selected::Aborted);
+                }
+                drop(state);
+
+                // Block the current thread.
+                // SAFETY: the context belongs to the current thread.
+                let sel = unsafe { cx.wait_until(deadline) };
+
+                match sel {
+                    Selected::Waiting => unreachable!(),
+                    Selected::Aborted | Selected::Disconnected => {
+                        self.shared.receivers.unregister(oper).unwrap();
+                        // If the channel was disconnected, we still have to check for remaining
+                        // messages.
+                    }
+                    Selected::Operation(_) => {}
+                }
+            });
+        }
+    }
+}
+
+impl<T> Receiver<T> {
+    /// Returns the number of messages this receiver has yet to receive.
+    ///
+    /// This is never more than the capacity of the channel: a receiver that lags behind only has
+    /// the messages still in the channel left to receive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(broadcast_channel)]
+    ///
+    /// use std::sync::broadcast;
+    ///
+    /// let (tx, mut rx) = broadcast::channel(4);
+    /// tx.send(1).unwrap();
+    /// tx.send(2).unwrap();
+    /// assert_eq!(rx.len(), 2);
+    ///
+    /// rx.recv().unwrap();
+    /// assert_eq!(rx.len(), 1);
+    /// ```
+    #[unstable(feature = "broadcast_channel", issue = "none")]
+    pub fn len(&self) -> usize {
+        let state = self.shared.state.lock();
+        (state.tail() - self.next.max(state.head)) as usize
+    }
+
+    /// Returns `true` if this receiver has no messages left to receive.
+    #[unstable(feature = "broadcast_channel", issue = "none")]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[unstable(feature = "broadcast_channel", issue = "none")]
+impl<T> Clone for Receiver<T> {
+    fn clone(&self) -> Self {
+        self.shared.state.lock().receivers += 1;
+        Receiver { shared: self.shared.clone(), next: self.next }
+    }
+}
+
+#[unstable(feature = "broadcast_channel", issue = "none")]
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        let mut state = self.shared.state.lock();
+        state.receivers -= 1;
+        if state.receivers == 0 {
+            // Nobody can receive the remaining messages anymore, and new receivers only receive
+            // new messages, so don't keep them around.
+            state.head = state.tail();
+            let buffer = crate::mem::take(&mut state.buffer);
+            drop(state);
+            drop(buffer);
+        }
+    }
+}
+
+#[unstable(feature = "broadcast_channel", issue = "none")]
+impl<T> fmt::Debug for Receiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Receiver").finish_non_exhaustive()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Errors
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// An error returned from the [`Receiver::recv`] method.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[unstable(feature = "broadcast_channel", issue = "none")]
+pub enum RecvError {
+    /// All senders have been dropped, and there are no messages left to receive.
+    Disconnected,
+
+    /// The receiver lagged behind, and this many messages were dropped before it could receive
+    /// them.
+    Lagged(u64),
+}
+
+/// An error returned from the [`Receiver::try_recv`] method.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[unstable(feature = "broadcast_channel", issue = "none")]
+pub enum TryRecvError {
+    /// There is no message to receive right now, but more could be sent.
+    Empty,
+
+    /// All senders have been dropped, and there are no messages left to receive.
+    Disconnected,
+
+    /// The receiver lagged behind, and this many messages were dropped before it could receive
+    /// them.
+    Lagged(u64),
+}
+
+/// An error returned from the [`Receiver::recv_timeout`] and [`Receiver::recv_deadline`]
+/// methods.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[unstable(feature = "broadcast_channel", issue = "none")]
+pub enum RecvTimeoutError {
+    /// No message was sent before the timeout elapsed.
+    Timeout,
+
+    /// All senders have been dropped, and there are no messages left to receive.
+    Disconnected,
+
+    /// The receiver lagged behind, and this many messages were dropped before it could receive
+    /// them.
+    Lagged(u64),
+}
+
+fn fmt_lagged(missed: u64, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    if missed == 1 {
+        "receiver lagged behind by 1 message".fmt(f)
+    } else {
+        write!(f, "receiver lagged behind by {missed} messages")
+    }
+}
+
+#[unstable(feature = "broadcast_channel", issue = "none")]
+impl fmt::Display for RecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            RecvError::Disconnected => "receiving on a closed channel".fmt(f),
+            RecvError::Lagged(missed) => fmt_lagged(missed, f),
+        }
+    }
+}
+
+#[unstable(feature = "broadcast_channel", issue = "none")]
+impl error::Error for RecvError {}
+
+#[unstable(feature = "broadcast_channel", issue = "none")]
+impl fmt::Display for TryRecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            TryRecvError::Empty => "receiving on an empty channel".fmt(f),
+            TryRecvError::Disconnected => "receiving on a closed channel".fmt(f),
+            TryRecvError::Lagged(missed) => fmt_lagged(missed, f),
+        }
+    }
+}
+
+#[unstable(feature = "broadcast_channel", issue = "none")]
+impl error::Error for TryRecvError {}
+
+#[unstable(feature = "broadcast_channel", issue = "none")]
+impl From<RecvError> for TryRecvError {
+    fn from(err: RecvError) -> TryRecvError {
+        match err {
+            RecvError::Disconnected => TryRecvError::Disconnected,
+            RecvError::Lagged(missed) => TryRecvError::Lagged(missed),
+        }
+    }
+}
+
+#[unstable(feature = "broadcast_channel", issue = "none")]
+impl fmt::Display for RecvTimeoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            RecvTimeoutError::Timeout => "timed out waiting on channel".fmt(f),
+            RecvTimeoutError::Disconnected => "receiving on a closed channel".fmt(f),
+            RecvTimeoutError::Lagged(missed) => fmt_lagged(missed, f),
+        }
+    }
+}
+
+#[unstable(feature = "broadcast_channel", issue = "none")]
+impl error::Error for RecvTimeoutError {}
+
+#[unstable(feature = "broadcast_channel", issue = "none")]
+impl From<RecvError> for RecvTimeoutError {
+    fn from(err: RecvError) -> RecvTimeoutError {
+        match err {
+            RecvError::Disconnected => RecvTimeoutError::Disconnected,
+            RecvError::Lagged(missed) => RecvTimeoutError::Lagged(missed),
+        }
+    }
+}
@@ -181,11 +181,15 @@
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use alloc_crate::sync::{Arc, Weak};
 
+#[unstable(feature = "broadcast_channel", issue = "none")]
+pub mod broadcast;
 #[unstable(feature = "mpmc_channel", issue = "126840")]
 pub mod mpmc;
 pub mod mpsc;
 #[unstable(feature = "oneshot_channel", issue = "143674")]
 pub mod oneshot;
+#[unstable(feature = "watch_channel", issue = "none")]
+pub mod watch;
 
 pub(crate) mod once; // `pub(crate)` for the `sys::sync::once` implementations and `LazyLock`.
 
@@ -201,6 +205,7 @@
 mod lazy_lock;
 mod once_lock;
 mod reentrant_lock;
+mod semaphore;
 
 // These exist only in one flavor: no poisoning.
 #[stable(feature = "rust1", since = "1.0.0")]
@@ -211,6 +216,8 @@
 pub use self::once_lock::OnceLock;
 #[unstable(feature = "reentrant_lock", issue = "121440")]
 pub use self::reentrant_lock::{ReentrantLock, ReentrantLockGuard};
+#[unstable(feature = "semaphore", issue = "none")]
+pub use self::semaphore::{Semaphore, SemaphorePermit};
 
 // Note: in the future we will change the default version in `std::sync` to the non-poisoning
 // version over an edition.
@@ -243,6 +250,10 @@
 #[doc(inline)]
 pub use self::poison::{MappedMutexGuard, MappedRwLockReadGuard, MappedRwLockWriteGuard};
 
+#[unstable(feature = "rwlock_upgradable_read", issue = "none")]
+#[doc(inline)]
+pub use self::poison::RwLockUpgradableReadGuard;
+
 /// A type indicating whether a timed wait on a condition variable returned
 /// due to a time out or not.
 ///
@@ -41,6 +41,12 @@
 //!
 //! [`unwrap`]: Result::unwrap
 //!
+//! ## Selection
+//!
+//! The [`select!`] macro allows you to wait on several channel operations at
+//! once, and to complete whichever one becomes ready first. For a set of
+//! operations that is only known at runtime, use [`Select`] instead.
+//!
 //! # Examples
 //!
 //! Simple usage:
@@ -138,17 +144,24 @@
 // DEALINGS IN THE SOFTWARE.
 
 mod array;
-mod context;
+pub(super) mod context; // `pub(super)` for the `broadcast` and `watch` channels.
 mod counter;
 mod error;
 mod list;
-mod select;
+pub(super) mod select; // `pub(super)` for the `broadcast` and `watch` channels.
+mod select_macro;
 mod utils;
-mod waker;
+pub(super) mod waker; // `pub(super)` for the `broadcast` and `watch` channels.
 mod zero;
 
 pub use error::*;
+#[unstable(feature = "mpmc_channel", issue = "126840")]
+pub use select::{Select, SelectedOperation};
+#[unstable(feature = "mpmc_channel", issue = "126840")]
+pub use select_macro::select;
 
+use self::context::Context;
+use self::select::{Operation, SelectHandle, Token};
 use crate::fmt;
 use crate::panic::{RefUnwindSafe, UnwindSafe};
 use crate::time::{Duration, Instant};
@@ -653,6 +666,57 @@ pub fn is_disconnected(&self) -> bool {
     }
 }
 
+impl<T> Sender<T> {
+    /// Writes a message into the channel, after a send operation was selected.
+    ///
+    /// # Safety
+    ///
+    /// `token` must have been filled in by a successful `try_select` or `accept` on this sender.
+    unsafe fn write(&self, token: &mut Token, msg: T) -> Result<(), T> {
+        unsafe {
+            match &self.flavor {
+                SenderFlavor::Array(chan) => chan.write(token, msg),
+                SenderFlavor::List(chan) => chan.write(token, msg),
+                SenderFlavor::Zero(chan) => chan.write(token, msg),
+            }
+        }
+    }
+}
+
+impl<T> SelectHandle for Sender<T> {
+    fn try_select(&self, token: &mut Token) -> bool {
+        match &self.flavor {
+            SenderFlavor::Array(chan) => chan.sender().try_select(token),
+            SenderFlavor::List(chan) => chan.sender().try_select(token),
+            SenderFlavor::Zero(chan) => chan.sender().try_select(token),
+        }
+    }
+
+    fn register(&self, oper: Operation, cx: &Context) -> bool {
+        match &self.flavor {
+            SenderFlavor::Array(chan) => chan.sender().register(oper, cx),
+            SenderFlavor::List(chan) => chan.sender().register(oper, cx),
+            SenderFlavor::Zero(chan) => chan.sender().register(oper, cx),
+        }
+    }
+
+    fn unregister(&self, oper: Operation) {
+        match &self.flavor {
+            SenderFlavor::Array(chan) => chan.sender().unregister(oper),
+            SenderFlavor::List(chan) => chan.sender().unregister(oper),
+            SenderFlavor::Zero(chan) => chan.sender().unregister(oper),
+        }
+    }
+
+    fn accept(&self, token: &mut Token, cx: &Context) -> bool {
+        match &self.flavor {
+            SenderFlavor::Array(chan) => chan.sender().accept(token, cx),
+            SenderFlavor::List(chan) => chan.sender().accept(token, cx),
+            SenderFlavor::Zero(chan) => chan.sender().accept(token, cx),
+        }
+    }
+}
+
 #[unstable(feature = "mpmc_channel", issue = "126840")]
 impl<T> Drop for Sender<T> {
     fn drop(&mut self) {
@@ -1407,6 +1471,58 @@ pub fn is_disconnected(&self) -> bool {
     }
 }
 
+impl<T> Receiver<T> {
+    /// Reads a message from the channel, after a receive operation was selected.
+    ///
+    /// # Safety
+    ///
+    /// `token` must have been filled in by a successful `try_select` or `accept` on this
+    /// receiver.
+    unsafe fn read(&self, token: &mut Token) -> Result<T, ()> {
+        unsafe {
+            match &self.flavor {
+                ReceiverFlavor::Array(chan) => chan.read(token),
+                ReceiverFlavor::List(chan) => chan.read(token),
+                ReceiverFlavor::Zero(chan) => chan.read(token),
+            }
+        }
+    }
+}
+
+impl<T> SelectHandle for Receiver<T> {
+    fn try_select(&self, token: &mut Token) -> bool {
+        match &self.flavor {
+            ReceiverFlavor::Array(chan) => chan.receiver().try_select(token),
+            ReceiverFlavor::List(chan) => chan.receiver().try_select(token),
+            ReceiverFlavor::Zero(chan) => chan.receiver().try_select(token),
+        }
+    }
+
+    fn register(&self, oper: Operation, cx: &Context) -> bool {
+        match &self.flavor {
+            ReceiverFlavor::Array(chan) => chan.receiver().register(oper, cx),
+            ReceiverFlavor::List(chan) => chan.receiver().register(oper, cx),
+            ReceiverFlavor::Zero(chan) => chan.receiver().register(oper, cx),
+        }
+    }
+
+    fn unregister(&self, oper: Operation) {
+        match &self.flavor {
+            ReceiverFlavor::Array(chan) => chan.receiver().unregister(oper),
+            ReceiverFlavor::List(chan) => chan.receiver().unregister(oper),
+            ReceiverFlavor::Zero(chan) => chan.receiver().unregister(oper),
+        }
+    }
+
+    fn accept(&self, token: &mut Token, cx: &Context) -> bool {
+        match &self.flavor {
+            ReceiverFlavor::Array(chan) => chan.receiver().accept(token, cx),
+            ReceiverFlavor::List(chan) => chan.receiver().accept(token, cx),
+            ReceiverFlavor::Zero(chan) => chan.receiver().accept(token, cx),
+        }
+    }
+}
+
 #[unstable(feature = "mpmc_channel", issue = "126840")]
 impl<T> Drop for Receiver<T> {
     fn drop(&mut self) {
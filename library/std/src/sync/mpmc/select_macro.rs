@@ -0,0 +1,230 @@
+//! The `select!` macro.
+
+/// Selects from a set of channel operations.
+///
+/// This macro allows you to define a set of channel operations, wait until any one of them becomes
+/// ready, and finally execute it. If multiple operations are ready at the same time, a random one
+/// among them is selected.
+///
+/// It is also possible to define a `default` case that gets executed if none of the operations are
+/// ready, either right away or for a certain duration of time.
+///
+/// An operation is considered to be ready if it doesn't have to block. Note that it is ready even
+/// when it will simply return an error because the channel is disconnected.
+///
+/// The `select!` macro is a convenience wrapper around [`Select`]. However, it cannot select over
+/// a dynamically created list of channel operations.
+///
+/// [`Select`]: super::Select
+///
+/// # Syntax
+///
+/// Each case is one of:
+///
+/// * `recv(r) -> res => body`, which receives a message from the receiver `r`, and evaluates
+///   `body` with `res` bound to the [`Result`] of the operation.
+/// * `send(s, msg) -> res => body`, which sends `msg` into the sender `s`, and evaluates `body`
+///   with `res` bound to the [`Result`] of the operation. `msg` is only evaluated if the
+///   operation is selected.
+/// * `default => body`, which evaluates `body` if none of the operations are ready right away.
+/// * `default(timeout) => body`, which evaluates `body` if none of the operations become ready
+///   within the [`Duration`] `timeout`.
+///
+/// There can be at most one `default` case. Without one, `select!` blocks until an operation
+/// becomes ready, and blocks forever if there are no operations.
+///
+/// [`Duration`]: crate::time::Duration
+///
+/// # Examples
+///
+/// Block until a send or a receive operation is selected:
+///
+/// ```
+/// #![feature(mpmc_channel)]
+///
+/// use std::sync::mpmc::{channel, select};
+///
+/// let (s1, r1) = channel();
+/// let (s2, r2) = channel();
+/// s1.send(10).unwrap();
+///
+/// // Since both operations are initially ready, a random one will be executed.
+/// select! {
+///     recv(r1) -> msg => assert_eq!(msg, Ok(10)),
+///     send(s2, 20) -> res => {
+///         assert_eq!(res, Ok(()));
+///         assert_eq!(r2.recv(), Ok(20));
+///     }
+/// }
+/// ```
+///
+/// Select over a set of operations with a timeout:
+///
+/// ```
+/// #![feature(mpmc_channel)]
+///
+/// use std::sync::mpmc::{channel, select};
+/// use std::thread;
+/// use std::time::Duration;
+///
+/// let (s1, r1) = channel::<i32>();
+/// let (s2, r2) = channel::<i32>();
+///
+/// thread::spawn(move || {
+///     thread::sleep(Duration::from_secs(1));
+///     s1.send(10).unwrap();
+/// });
+/// thread::spawn(move || {
+///     thread::sleep(Duration::from_millis(500));
+///     s2.send(20).unwrap();
+/// });
+///
+/// // None of the two operations will become ready within 100 milliseconds.
+/// select! {
+///     recv(r1) -> _ => panic!(),
+///     recv(r2) -> _ => panic!(),
+///     default(Duration::from_millis(100)) => println!("timed out"),
+/// }
+/// ```
+#[unstable(feature = "mpmc_channel", issue = "126840")]
+#[rustc_macro_transparency = "semiopaque"]
+pub macro select {
+    // All the cases have been parsed.
+    (@list () $cases:tt $default:tt) => {
+        $crate::sync::mpmc::select!(@bind $cases () $default)
+    },
+    // Skip the commas separating cases.
+    (@list (, $($tail:tt)*) $cases:tt $default:tt) => {
+        $crate::sync::mpmc::select!(@list ($($tail)*) $cases $default)
+    },
+    (@list (recv($r:expr) -> $res:pat => $body:block $($tail:tt)*) ($($cases:tt)*) $default:tt) => {
+        $crate::sync::mpmc::select!(
+            @list ($($tail)*) ($($cases)* (recv ($r) ($res) $body)) $default
+        )
+    },
+    (@list (recv($r:expr) -> $res:pat => $body:expr $(, $($tail:tt)*)?) ($($cases:tt)*) $default:tt) => {
+        $crate::sync::mpmc::select!(
+            @list ($($($tail)*)?) ($($cases)* (recv ($r) ($res) { $body })) $default
+        )
+    },
+    (@list (send($s:expr, $m:expr) -> $res:pat => $body:block $($tail:tt)*) ($($cases:tt)*) $default:tt) => {
+        $crate::sync::mpmc::select!(
+            @list ($($tail)*) ($($cases)* (send ($s) ($m) ($res) $body)) $default
+        )
+    },
+    (@list (send($s:expr, $m:expr) -> $res:pat => $body:expr $(, $($tail:tt)*)?) ($($cases:tt)*) $default:tt) => {
+        $crate::sync::mpmc::select!(
+            @list ($($($tail)*)?) ($($cases)* (send ($s) ($m) ($res) { $body })) $default
+        )
+    },
+    (@list (default $(($t:expr))? => $($tail:tt)*) $cases:tt ($default:tt)) => {
+        $crate::compile_error!("there can be only one `default` case in a `select!` block")
+    },
+    (@list (default => $body:block $($tail:tt)*) $cases:tt ()) => {
+        $crate::sync::mpmc::select!(@list ($($tail)*) $cases ((now $body)))
+    },
+    (@list (default => $body:expr $(, $($tail:tt)*)?) $cases:tt ()) => {
+        $crate::sync::mpmc::select!(@list ($($($tail)*)?) $cases ((now { $body })))
+    },
+    (@list (default($t:expr) => $body:block $($tail:tt)*) $cases:tt ()) => {
+        $crate::sync::mpmc::select!(@list ($($tail)*) $cases ((timeout ($t) $body)))
+    },
+    (@list (default($t:expr) => $body:expr $(, $($tail:tt)*)?) $cases:tt ()) => {
+        $crate::sync::mpmc::select!(@list ($($($tail)*)?) $cases ((timeout ($t) { $body })))
+    },
+    (@list ($($tail:tt)*) $cases:tt $default:tt) => {
+        $crate::compile_error!(
+            "expected `recv(r) -> res => body`, `send(s, msg) -> res => body`, \
+             `default => body` or `default(timeout) => body` in `select!`"
+        )
+    },
+
+    // Evaluate every channel exactly once, and bind it to a reference.
+    (@bind () $cases:tt $default:tt) => {{
+        let mut sel = $crate::sync::mpmc::Select::new();
+        $crate::sync::mpmc::select!(@add sel $cases);
+        $crate::sync::mpmc::select!(@run sel $cases $default)
+    }},
+    (@bind ((recv ($r:expr) $res:tt $body:tt) $($rest:tt)*) ($($cases:tt)*) $default:tt) => {
+        match $r {
+            ref r => {
+                let r: &$crate::sync::mpmc::Receiver<_> = r;
+                $crate::sync::mpmc::select!(
+                    @bind ($($rest)*) ($($cases)* (recv r $res $body)) $default
+                )
+            }
+        }
+    },
+    (@bind ((send ($s:expr) $m:tt $res:tt $body:tt) $($rest:tt)*) ($($cases:tt)*) $default:tt) => {
+        match $s {
+            ref s => {
+                let s: &$crate::sync::mpmc::Sender<_> = s;
+                $crate::sync::mpmc::select!(
+                    @bind ($($rest)*) ($($cases)* (send s $m $res $body)) $default
+                )
+            }
+        }
+    },
+
+    // Add the operations to the `Select`, in order.
+    (@add $sel:ident ()) => {},
+    (@add $sel:ident ((recv $r:ident $res:tt $body:tt) $($rest:tt)*)) => {
+        $sel.recv($r);
+        $crate::sync::mpmc::select!(@add $sel ($($rest)*));
+    },
+    (@add $sel:ident ((send $s:ident $m:tt $res:tt $body:tt) $($rest:tt)*)) => {
+        $sel.send($s);
+        $crate::sync::mpmc::select!(@add $sel ($($rest)*));
+    },
+
+    // Wait for an operation, according to the `default` case.
+    (@run $sel:ident $cases:tt ()) => {{
+        let oper = $sel.select();
+        $crate::sync::mpmc::select!(@complete oper (0) $cases)
+    }},
+    (@run $sel:ident $cases:tt ((now $body:tt))) => {
+        match $sel.try_select() {
+            $crate::result::Result::Ok(oper) => {
+                $crate::sync::mpmc::select!(@complete oper (0) $cases)
+            }
+            $crate::result::Result::Err(_) => $body,
+        }
+    },
+    (@run $sel:ident $cases:tt ((timeout ($t:expr) $body:tt))) => {
+        match $sel.select_timeout($t) {
+            $crate::result::Result::Ok(oper) => {
+                $crate::sync::mpmc::select!(@complete oper (0) $cases)
+            }
+            $crate::result::Result::Err(_) => $body,
+        }
+    },
+
+    // Complete the selected operation, which has the index of its position in the cases.
+    (@complete $oper:ident ($($i:tt)*) ()) => {{
+        $crate::mem::forget($oper);
+        $crate::unreachable!("no operation was added to `select!`")
+    }},
+    (@complete $oper:ident ($($i:tt)*) ((recv $r:ident $res:tt $body:tt) $($rest:tt)*)) => {
+        if $oper.index() == $($i)* {
+            match $oper.recv($r) {
+                $res => $body,
+            }
+        } else {
+            $crate::sync::mpmc::select!(@complete $oper ($($i)* + 1) ($($rest)*))
+        }
+    },
+    (@complete $oper:ident ($($i:tt)*) ((send $s:ident ($m:expr) $res:tt $body:tt) $($rest:tt)*)) => {
+        if $oper.index() == $($i)* {
+            match $oper.send($s, $m) {
+                $res => $body,
+            }
+        } else {
+            $crate::sync::mpmc::select!(@complete $oper ($($i)* + 1) ($($rest)*))
+        }
+    },
+
+    // Entry point.
+    ($($tokens:tt)*) => {
+        $crate::sync::mpmc::select!(@list ($($tokens)*) () ())
+    },
+}
@@ -93,6 +93,40 @@ pub(crate) fn try_select(&mut self) -> Option<Entry> {
         }
     }
 
+    /// Selects the operations of all other threads' entries, and wakes them up.
+    #[inline]
+    pub(crate) fn try_select_all(&mut self) {
+        let thread_id = current_thread_id();
+
+        self.selectors.retain(|selector| {
+            // Keep the entries that belong to the current thread, or that can't be selected
+            // anymore: their threads will unregister them.
+            selector.cx.thread_id() == thread_id
+                || selector.cx.try_select(Selected::Operation(selector.oper)).is_err()
+                || {
+                    // Provide the packet.
+                    selector.cx.store_packet(selector.packet);
+                    // Wake the thread up.
+                    selector.cx.unpark();
+                    false
+                }
+        });
+    }
+
+    /// Returns `true` if there is an entry which can be selected by the current thread.
+    #[inline]
+    pub(crate) fn can_select(&self) -> bool {
+        if self.selectors.is_empty() {
+            false
+        } else {
+            let thread_id = current_thread_id();
+
+            self.selectors.iter().any(|entry| {
+                entry.cx.thread_id() != thread_id && entry.cx.selected() == Selected::Waiting
+            })
+        }
+    }
+
     /// Notifies all operations waiting to be ready.
     #[inline]
     pub(crate) fn notify(&mut self) {
@@ -182,6 +216,22 @@ pub(crate) fn notify(&self) {
         }
     }
 
+    /// Selects the operations of all threads (except the current one), and wakes them up.
+    #[inline]
+    pub(crate) fn notify_all(&self) {
+        if !self.is_empty.load(Ordering::SeqCst) {
+            let mut inner = self.inner.lock().unwrap();
+            if !self.is_empty.load(Ordering::SeqCst) {
+                inner.try_select_all();
+                inner.notify();
+                self.is_empty.store(
+                    inner.selectors.is_empty() && inner.observers.is_empty(),
+                    Ordering::SeqCst,
+                );
+            }
+        }
+    }
+
     /// Notifies all threads that the channel is disconnected.
     #[inline]
     pub(crate) fn disconnect(&self) {
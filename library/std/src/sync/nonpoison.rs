@@ -39,6 +39,8 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 pub use self::rwlock::{MappedRwLockReadGuard, MappedRwLockWriteGuard};
 #[unstable(feature = "nonpoison_rwlock", issue = "134645")]
 pub use self::rwlock::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+#[unstable(feature = "rwlock_upgradable_read", issue = "none")]
+pub use self::rwlock::RwLockUpgradableReadGuard;
 
 mod condvar;
 mod mutex;
@@ -115,6 +115,37 @@ impl<T: ?Sized> !Send for RwLockWriteGuard<'_, T> {}
 #[unstable(feature = "nonpoison_rwlock", issue = "134645")]
 unsafe impl<T: ?Sized + Sync> Sync for RwLockWriteGuard<'_, T> {}
 
+/// RAII structure used to release the upgradable read access of a lock when
+/// dropped.
+///
+/// An upgradable read lock is a read lock that can later be atomically upgraded to a write lock
+/// with [`upgrade`] or [`try_upgrade`]. Only one upgradable reader can hold the lock at a time, but
+/// it can share the lock with any number of regular readers.
+///
+/// This structure is created by the [`upgradable_read`] and [`try_upgradable_read`] methods on
+/// [`RwLock`].
+///
+/// [`upgrade`]: RwLockUpgradableReadGuard::upgrade
+/// [`try_upgrade`]: RwLockUpgradableReadGuard::try_upgrade
+/// [`upgradable_read`]: RwLock::upgradable_read
+/// [`try_upgradable_read`]: RwLock::try_upgradable_read
+#[must_use = "if unused the RwLock will immediately unlock"]
+#[must_not_suspend = "holding a RwLockUpgradableReadGuard across suspend \
+                      points can cause deadlocks, delays, \
+                      and cause Futures to not implement `Send`"]
+#[unstable(feature = "rwlock_upgradable_read", issue = "none")]
+#[clippy::has_significant_drop]
+pub struct RwLockUpgradableReadGuard<'rwlock, T: ?Sized + 'rwlock> {
+    /// A reference to the [`RwLock`] that we have upgradable-read-locked.
+    lock: &'rwlock RwLock<T>,
+}
+
+#[unstable(feature = "rwlock_upgradable_read", issue = "none")]
+impl<T: ?Sized> !Send for RwLockUpgradableReadGuard<'_, T> {}
+
+#[unstable(feature = "rwlock_upgradable_read", issue = "none")]
+unsafe impl<T: ?Sized + Sync> Sync for RwLockUpgradableReadGuard<'_, T> {}
+
 /// RAII structure used to release the shared read access of a lock when
 /// dropped, which can point to a subfield of the protected data.
 ///
@@ -440,6 +471,90 @@ pub fn try_write(&self) -> TryLockResult<RwLockWriteGuard<'_, T>> {
         }
     }
 
+    /// Locks this `RwLock` with upgradable read access, blocking the current thread until it can be
+    /// acquired.
+    ///
+    /// An upgradable read lock shares the lock with regular readers, but only one upgradable reader
+    /// can hold it at a time. The returned guard can later be turned into a write guard with
+    /// [`RwLockUpgradableReadGuard::upgrade`], without letting any writer in between, which makes
+    /// it possible to check a condition under a read lock and only take the write lock if needed.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// Upgradable read locks are only shared with regular readers on platforms with a futex-based
+    /// `RwLock`, such as Linux and Windows. Elsewhere, an upgradable read lock is taken as a write
+    /// lock from the start, so upgrading it never blocks but readers are kept out in the meantime.
+    ///
+    /// # Panics
+    ///
+    /// This function might panic when called if the lock is already held by the current thread.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(nonpoison_rwlock)]
+    /// #![feature(rwlock_upgradable_read)]
+    ///
+    /// use std::sync::nonpoison::{RwLock, RwLockUpgradableReadGuard};
+    ///
+    /// let lock = RwLock::new(Vec::new());
+    ///
+    /// let guard = lock.upgradable_read();
+    /// if guard.is_empty() {
+    ///     let mut guard = RwLockUpgradableReadGuard::upgrade(guard);
+    ///     guard.push(1);
+    /// }
+    /// assert_eq!(*lock.read(), [1]);
+    /// ```
+    #[inline]
+    #[unstable(feature = "rwlock_upgradable_read", issue = "none")]
+    pub fn upgradable_read(&self) -> RwLockUpgradableReadGuard<'_, T> {
+        unsafe {
+            self.inner.upgradable_read();
+            RwLockUpgradableReadGuard::new(self)
+        }
+    }
+
+    /// Attempts to acquire this `RwLock` with upgradable read access.
+    ///
+    /// If the access could not be granted at this time, then `Err` is returned.
+    /// Otherwise, an RAII guard is returned which will release the upgradable
+    /// access when it is dropped.
+    ///
+    /// This function does not block.
+    ///
+    /// # Errors
+    ///
+    /// This function will return the [`WouldBlock`] error if the `RwLock` could
+    /// not be acquired because it was already locked exclusively, or by another
+    /// upgradable reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(nonpoison_rwlock)]
+    /// #![feature(rwlock_upgradable_read)]
+    ///
+    /// use std::sync::nonpoison::RwLock;
+    ///
+    /// let lock = RwLock::new(1);
+    ///
+    /// let guard = lock.try_upgradable_read().unwrap();
+    /// assert_eq!(*guard, 1);
+    /// assert!(lock.try_upgradable_read().is_err());
+    /// ```
+    #[inline]
+    #[unstable(feature = "rwlock_upgradable_read", issue = "none")]
+    pub fn try_upgradable_read(&self) -> TryLockResult<RwLockUpgradableReadGuard<'_, T>> {
+        unsafe {
+            if self.inner.try_upgradable_read() {
+                Ok(RwLockUpgradableReadGuard::new(self))
+            } else {
+                Err(WouldBlock)
+            }
+        }
+    }
+
     /// Consumes this `RwLock`, returning the underlying data.
     ///
     /// # Examples
@@ -601,8 +716,8 @@ impl<'rwlock, T: ?Sized> RwLockReadGuard<'rwlock, T> {
     /// # Safety
     ///
     /// This function is safe if and only if the same thread has successfully and safely called
-    /// `lock.inner.read()`, `lock.inner.try_read()`, or `lock.inner.downgrade()` before
-    /// instantiating this object.
+    /// `lock.inner.read()`, `lock.inner.try_read()`, `lock.inner.downgrade()`, or
+    /// `lock.inner.downgrade_upgradable()` before instantiating this object.
     unsafe fn new(lock: &'rwlock RwLock<T>) -> RwLockReadGuard<'rwlock, T> {
         RwLockReadGuard {
             data: unsafe { NonNull::new_unchecked(lock.data.get()) },
@@ -834,6 +949,137 @@ pub fn filter_map<U, F>(orig: Self, f: F) -> Result<MappedRwLockWriteGuard<'rwlo
     }
 }
 
+impl<'rwlock, T: ?Sized> RwLockUpgradableReadGuard<'rwlock, T> {
+    /// Creates a new instance of `RwLockUpgradableReadGuard<T>` from a `RwLock<T>`.
+    ///
+    /// # Safety
+    ///
+    /// This function is safe if and only if the same thread has successfully and safely called
+    /// `lock.inner.upgradable_read()` or `lock.inner.try_upgradable_read()` before instantiating
+    /// this object.
+    unsafe fn new(lock: &'rwlock RwLock<T>) -> RwLockUpgradableReadGuard<'rwlock, T> {
+        RwLockUpgradableReadGuard { lock }
+    }
+
+    /// Upgrades an `RwLockUpgradableReadGuard` into a [`RwLockWriteGuard`], blocking the current
+    /// thread until all other readers have released the lock.
+    ///
+    /// No writer can get in between: the data seen through the upgradable guard is still the same
+    /// when the write guard is returned.
+    ///
+    /// This is an associated function that needs to be used as
+    /// `RwLockUpgradableReadGuard::upgrade(...)`. A method would interfere with methods of
+    /// the same name on the contents of the `RwLockUpgradableReadGuard` used through `Deref`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(nonpoison_rwlock)]
+    /// #![feature(rwlock_upgradable_read)]
+    ///
+    /// use std::sync::nonpoison::{RwLock, RwLockUpgradableReadGuard};
+    ///
+    /// let lock = RwLock::new(1);
+    ///
+    /// let guard = lock.upgradable_read();
+    /// let mut guard = RwLockUpgradableReadGuard::upgrade(guard);
+    /// *guard += 1;
+    /// drop(guard);
+    ///
+    /// assert_eq!(*lock.read(), 2);
+    /// ```
+    #[unstable(feature = "rwlock_upgradable_read", issue = "none")]
+    pub fn upgrade(s: Self) -> RwLockWriteGuard<'rwlock, T> {
+        let lock = s.lock;
+
+        // We don't want to call the destructor since that calls `upgradable_read_unlock`.
+        forget(s);
+
+        // SAFETY: We take ownership of an upgradable read guard, so we must already have the
+        // `RwLock` in upgradable read mode, satisfying the `upgrade` contract.
+        unsafe { lock.inner.upgrade() };
+
+        // SAFETY: We have just successfully called `upgrade`, so we fulfill the safety contract.
+        unsafe { RwLockWriteGuard::new(lock) }
+    }
+
+    /// Attempts to upgrade an `RwLockUpgradableReadGuard` into a [`RwLockWriteGuard`] without
+    /// blocking.
+    ///
+    /// If other readers still hold the lock, the original guard is returned as an `Err(...)`.
+    ///
+    /// This is an associated function that needs to be used as
+    /// `RwLockUpgradableReadGuard::try_upgrade(...)`. A method would interfere with methods of
+    /// the same name on the contents of the `RwLockUpgradableReadGuard` used through `Deref`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(nonpoison_rwlock)]
+    /// #![feature(rwlock_upgradable_read)]
+    ///
+    /// use std::sync::nonpoison::{RwLock, RwLockUpgradableReadGuard};
+    ///
+    /// let lock = RwLock::new(1);
+    ///
+    /// let guard = lock.upgradable_read();
+    /// let mut guard = RwLockUpgradableReadGuard::try_upgrade(guard).ok().unwrap();
+    /// *guard += 1;
+    /// ```
+    #[unstable(feature = "rwlock_upgradable_read", issue = "none")]
+    pub fn try_upgrade(s: Self) -> Result<RwLockWriteGuard<'rwlock, T>, Self> {
+        // SAFETY: We own an upgradable read guard, satisfying the `try_upgrade` contract.
+        if unsafe { s.lock.inner.try_upgrade() } {
+            let lock = s.lock;
+
+            // We don't want to call the destructor since that calls `upgradable_read_unlock`.
+            forget(s);
+
+            // SAFETY: We have just successfully called `try_upgrade`, so we fulfill the safety
+            // contract.
+            Ok(unsafe { RwLockWriteGuard::new(lock) })
+        } else {
+            Err(s)
+        }
+    }
+
+    /// Downgrades an `RwLockUpgradableReadGuard` into a regular [`RwLockReadGuard`].
+    ///
+    /// This lets another thread acquire the upgradable read lock, while this thread can keep
+    /// reading the protected data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(nonpoison_rwlock)]
+    /// #![feature(rwlock_upgradable_read)]
+    ///
+    /// use std::sync::nonpoison::{RwLock, RwLockUpgradableReadGuard};
+    ///
+    /// let lock = RwLock::new(1);
+    ///
+    /// let guard = lock.upgradable_read();
+    /// let guard = RwLockUpgradableReadGuard::downgrade(guard);
+    /// assert_eq!(*lock.try_read(), 1);
+    /// assert_eq!(*guard, 1);
+    /// ```
+    #[unstable(feature = "rwlock_upgradable_read", issue = "none")]
+    pub fn downgrade(s: Self) -> RwLockReadGuard<'rwlock, T> {
+        let lock = s.lock;
+
+        // We don't want to call the destructor since that calls `upgradable_read_unlock`.
+        forget(s);
+
+        // SAFETY: We take ownership of an upgradable read guard, satisfying the
+        // `downgrade_upgradable` contract.
+        unsafe { lock.inner.downgrade_upgradable() };
+
+        // SAFETY: We have just successfully called `downgrade_upgradable`, so we fulfill the safety
+        // contract.
+        unsafe { RwLockReadGuard::new(lock) }
+    }
+}
+
 impl<'rwlock, T: ?Sized> MappedRwLockReadGuard<'rwlock, T> {
     /// Makes a [`MappedRwLockReadGuard`] for a component of the borrowed data,
     /// e.g. an enum variant.
@@ -993,6 +1239,16 @@ fn drop(&mut self) {
     }
 }
 
+#[unstable(feature = "rwlock_upgradable_read", issue = "none")]
+impl<T: ?Sized> Drop for RwLockUpgradableReadGuard<'_, T> {
+    fn drop(&mut self) {
+        // SAFETY: the conditions of `RwLockUpgradableReadGuard::new` were satisfied when created.
+        unsafe {
+            self.lock.inner.upgradable_read_unlock();
+        }
+    }
+}
+
 #[unstable(feature = "mapped_lock_guards", issue = "117108")]
 // #[unstable(feature = "nonpoison_rwlock", issue = "134645")]
 impl<T: ?Sized> Drop for MappedRwLockReadGuard<'_, T> {
@@ -1045,6 +1301,16 @@ fn deref_mut(&mut self) -> &mut T {
     }
 }
 
+#[unstable(feature = "rwlock_upgradable_read", issue = "none")]
+impl<T: ?Sized> Deref for RwLockUpgradableReadGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // SAFETY: the conditions of `RwLockUpgradableReadGuard::new` were satisfied when created.
+        unsafe { &*self.lock.data.get() }
+    }
+}
+
 #[unstable(feature = "mapped_lock_guards", issue = "117108")]
 // #[unstable(feature = "nonpoison_rwlock", issue = "134645")]
 impl<T: ?Sized> Deref for MappedRwLockReadGuard<'_, T> {
@@ -1107,6 +1373,20 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     }
 }
 
+#[unstable(feature = "rwlock_upgradable_read", issue = "none")]
+impl<T: ?Sized + fmt::Debug> fmt::Debug for RwLockUpgradableReadGuard<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+#[unstable(feature = "rwlock_upgradable_read", issue = "none")]
+impl<T: ?Sized + fmt::Display> fmt::Display for RwLockUpgradableReadGuard<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
 #[unstable(feature = "mapped_lock_guards", issue = "117108")]
 // #[unstable(feature = "nonpoison_rwlock", issue = "134645")]
 impl<T: ?Sized + fmt::Debug> fmt::Debug for MappedRwLockReadGuard<'_, T> {
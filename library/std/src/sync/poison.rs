@@ -67,6 +67,8 @@
 pub use self::rwlock::{MappedRwLockReadGuard, MappedRwLockWriteGuard};
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use self::rwlock::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+#[unstable(feature = "rwlock_upgradable_read", issue = "none")]
+pub use self::rwlock::RwLockUpgradableReadGuard;
 use crate::error::Error;
 use crate::fmt;
 #[cfg(panic = "unwind")]
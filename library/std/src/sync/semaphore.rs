@@ -0,0 +1,330 @@
+use crate::fmt;
+use crate::panic::RefUnwindSafe;
+use crate::sys::sync as sys;
+use crate::time::Duration;
+
+/// A counting semaphore.
+///
+/// A semaphore holds a number of permits. Threads acquire permits with [`acquire`], blocking
+/// until enough of them are available, and give them back by dropping the returned
+/// [`SemaphorePermit`]. This is typically used to limit the number of threads that can access
+/// some resource at the same time.
+///
+/// Unlike [`Mutex`] and [`RwLock`], a semaphore does not protect any data, so there is nothing
+/// to poison: a thread panicking while holding permits simply gives them back as it unwinds.
+///
+/// [`acquire`]: Semaphore::acquire
+/// [`Mutex`]: crate::sync::Mutex
+/// [`RwLock`]: crate::sync::RwLock
+///
+/// # Examples
+///
+/// ```
+/// #![feature(semaphore)]
+///
+/// use std::sync::Semaphore;
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use std::thread;
+///
+/// // At most 3 threads may do work at the same time.
+/// let semaphore = Semaphore::new(3);
+/// let active = AtomicUsize::new(0);
+///
+/// thread::scope(|s| {
+///     for _ in 0..10 {
+///         s.spawn(|| {
+///             let _permit = semaphore.acquire();
+///             let n = active.fetch_add(1, Ordering::SeqCst) + 1;
+///             assert!(n <= 3);
+///             active.fetch_sub(1, Ordering::SeqCst);
+///         });
+///     }
+/// });
+/// ```
+#[unstable(feature = "semaphore", issue = "none")]
+pub struct Semaphore {
+    inner: sys::Semaphore,
+}
+
+#[unstable(feature = "semaphore", issue = "none")]
+impl RefUnwindSafe for Semaphore {}
+
+/// An RAII guard holding permits acquired from a [`Semaphore`].
+///
+/// The permits are given back to the semaphore when the guard is dropped.
+///
+/// This structure is created by the [`acquire`] and [`try_acquire`] methods (and their
+/// variants) on [`Semaphore`].
+///
+/// [`acquire`]: Semaphore::acquire
+/// [`try_acquire`]: Semaphore::try_acquire
+#[must_use = "if unused the permits will immediately be released"]
+#[must_not_suspend = "holding a SemaphorePermit across suspend \
+                      points can cause deadlocks, delays, \
+                      and cause Futures to not implement `Send`"]
+#[clippy::has_significant_drop]
+#[unstable(feature = "semaphore", issue = "none")]
+pub struct SemaphorePermit<'a> {
+    semaphore: &'a Semaphore,
+    permits: usize,
+}
+
+impl Semaphore {
+    /// The maximum number of permits a semaphore can hold.
+    ///
+    /// This depends on the platform, but is at least `i32::MAX as usize`.
+    #[unstable(feature = "semaphore", issue = "none")]
+    pub const MAX_PERMITS: usize = sys::Semaphore::MAX_PERMITS;
+
+    /// Creates a new semaphore with the given number of permits available.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `permits` is more than [`MAX_PERMITS`].
+    ///
+    /// [`MAX_PERMITS`]: Semaphore::MAX_PERMITS
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(semaphore)]
+    ///
+    /// use std::sync::Semaphore;
+    ///
+    /// let semaphore = Semaphore::new(4);
+    /// ```
+    #[unstable(feature = "semaphore", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn new(permits: usize) -> Semaphore {
+        assert!(permits <= Self::MAX_PERMITS, "too many permits in Semaphore");
+        Semaphore { inner: sys::Semaphore::new(permits) }
+    }
+
+    /// Acquires a single permit, blocking the current thread until one is available.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(semaphore)]
+    ///
+    /// use std::sync::Semaphore;
+    ///
+    /// let semaphore = Semaphore::new(1);
+    /// let permit = semaphore.acquire();
+    /// assert_eq!(semaphore.available_permits(), 0);
+    /// drop(permit);
+    /// assert_eq!(semaphore.available_permits(), 1);
+    /// ```
+    #[unstable(feature = "semaphore", issue = "none")]
+    pub fn acquire(&self) -> SemaphorePermit<'_> {
+        self.acquire_many(1)
+    }
+
+    /// Acquires `n` permits at once, blocking the current thread until they are all available.
+    ///
+    /// The permits are taken all at once, so two threads each acquiring several permits can't
+    /// deadlock by each holding some of the permits the other one is waiting for.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is more than [`MAX_PERMITS`], as that many permits can never be available.
+    ///
+    /// [`MAX_PERMITS`]: Semaphore::MAX_PERMITS
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(semaphore)]
+    ///
+    /// use std::sync::Semaphore;
+    ///
+    /// let semaphore = Semaphore::new(5);
+    /// let permit = semaphore.acquire_many(3);
+    /// assert_eq!(permit.num_permits(), 3);
+    /// assert_eq!(semaphore.available_permits(), 2);
+    /// ```
+    #[unstable(feature = "semaphore", issue = "none")]
+    pub fn acquire_many(&self, n: usize) -> SemaphorePermit<'_> {
+        assert!(n <= Self::MAX_PERMITS, "cannot acquire more than MAX_PERMITS permits");
+        self.inner.acquire(n, None);
+        SemaphorePermit { semaphore: self, permits: n }
+    }
+
+    /// Attempts to acquire a single permit without blocking.
+    ///
+    /// Returns [`None`] if no permit is available right now.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(semaphore)]
+    ///
+    /// use std::sync::Semaphore;
+    ///
+    /// let semaphore = Semaphore::new(1);
+    /// let permit = semaphore.try_acquire().unwrap();
+    /// assert!(semaphore.try_acquire().is_none());
+    /// drop(permit);
+    /// assert!(semaphore.try_acquire().is_some());
+    /// ```
+    #[unstable(feature = "semaphore", issue = "none")]
+    pub fn try_acquire(&self) -> Option<SemaphorePermit<'_>> {
+        self.try_acquire_many(1)
+    }
+
+    /// Attempts to acquire `n` permits at once without blocking.
+    ///
+    /// Returns [`None`] if fewer than `n` permits are available right now, in which case no
+    /// permits are taken.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(semaphore)]
+    ///
+    /// use std::sync::Semaphore;
+    ///
+    /// let semaphore = Semaphore::new(2);
+    /// assert!(semaphore.try_acquire_many(3).is_none());
+    /// assert!(semaphore.try_acquire_many(2).is_some());
+    /// ```
+    #[unstable(feature = "semaphore", issue = "none")]
+    pub fn try_acquire_many(&self, n: usize) -> Option<SemaphorePermit<'_>> {
+        self.inner.try_acquire(n).then(|| SemaphorePermit { semaphore: self, permits: n })
+    }
+
+    /// Acquires a single permit, blocking the current thread for at most `timeout`.
+    ///
+    /// Returns [`None`] if no permit became available before the timeout elapsed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(semaphore)]
+    ///
+    /// use std::sync::Semaphore;
+    /// use std::time::Duration;
+    ///
+    /// let semaphore = Semaphore::new(0);
+    /// assert!(semaphore.acquire_timeout(Duration::from_millis(10)).is_none());
+    /// ```
+    #[unstable(feature = "semaphore", issue = "none")]
+    pub fn acquire_timeout(&self, timeout: Duration) -> Option<SemaphorePermit<'_>> {
+        self.acquire_many_timeout(1, timeout)
+    }
+
+    /// Acquires `n` permits at once, blocking the current thread for at most `timeout`.
+    ///
+    /// Returns [`None`] if the permits did not all become available before the timeout
+    /// elapsed, in which case no permits are taken.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(semaphore)]
+    ///
+    /// use std::sync::Semaphore;
+    /// use std::time::Duration;
+    ///
+    /// let semaphore = Semaphore::new(1);
+    /// assert!(semaphore.acquire_many_timeout(2, Duration::from_millis(10)).is_none());
+    /// assert_eq!(semaphore.available_permits(), 1);
+    /// ```
+    #[unstable(feature = "semaphore", issue = "none")]
+    pub fn acquire_many_timeout(&self, n: usize, timeout: Duration) -> Option<SemaphorePermit<'_>> {
+        self.inner
+            .acquire(n, Some(timeout))
+            .then(|| SemaphorePermit { semaphore: self, permits: n })
+    }
+
+    /// Adds `n` permits to the semaphore, waking up threads waiting for them.
+    ///
+    /// This is also how permits given up with [`SemaphorePermit::forget`] can be given back.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this would make the number of available permits more than [`MAX_PERMITS`].
+    ///
+    /// [`MAX_PERMITS`]: Semaphore::MAX_PERMITS
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(semaphore)]
+    ///
+    /// use std::sync::Semaphore;
+    ///
+    /// let semaphore = Semaphore::new(0);
+    /// semaphore.add_permits(2);
+    /// assert_eq!(semaphore.available_permits(), 2);
+    /// ```
+    #[unstable(feature = "semaphore", issue = "none")]
+    pub fn add_permits(&self, n: usize) {
+        self.inner.release(n);
+    }
+
+    /// Returns the number of permits currently available.
+    ///
+    /// Other threads may acquire or release permits at any time, so the returned value should
+    /// only be relied on as a hint.
+    #[unstable(feature = "semaphore", issue = "none")]
+    #[must_use]
+    pub fn available_permits(&self) -> usize {
+        self.inner.available()
+    }
+}
+
+#[unstable(feature = "semaphore", issue = "none")]
+impl fmt::Debug for Semaphore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Semaphore")
+            .field("available_permits", &self.available_permits())
+            .finish_non_exhaustive()
+    }
+}
+
+impl SemaphorePermit<'_> {
+    /// Returns the number of permits held by this guard.
+    #[unstable(feature = "semaphore", issue = "none")]
+    #[must_use]
+    pub fn num_permits(&self) -> usize {
+        self.permits
+    }
+
+    /// Consumes the guard without giving its permits back to the semaphore.
+    ///
+    /// This permanently lowers the number of permits of the semaphore, unless they are added
+    /// back later with [`Semaphore::add_permits`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(semaphore)]
+    ///
+    /// use std::sync::Semaphore;
+    ///
+    /// let semaphore = Semaphore::new(2);
+    /// semaphore.acquire().forget();
+    /// assert_eq!(semaphore.available_permits(), 1);
+    /// ```
+    #[unstable(feature = "semaphore", issue = "none")]
+    pub fn forget(self) {
+        crate::mem::forget(self);
+    }
+}
+
+#[unstable(feature = "semaphore", issue = "none")]
+impl Drop for SemaphorePermit<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        self.semaphore.inner.release(self.permits);
+    }
+}
+
+#[unstable(feature = "semaphore", issue = "none")]
+impl fmt::Debug for SemaphorePermit<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SemaphorePermit").field("permits", &self.permits).finish_non_exhaustive()
+    }
+}
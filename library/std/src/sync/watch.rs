@@ -0,0 +1,553 @@
+//! A multi-producer, multi-consumer channel that only retains the latest value.
+//!
+//! A watch channel holds a single value. Senders replace it, and receivers can look at the
+//! current value at any time, or block until it changes. Receivers that don't keep up only ever
+//! see the latest value: intermediate values are never queued.
+//!
+//! This is typically used to broadcast configuration or state changes to many threads.
+//!
+//! This is an experimental module, so the API will likely change.
+//!
+//! # Examples
+//!
+//! ```
+//! #![feature(watch_channel)]
+//!
+//! use std::sync::watch;
+//! use std::thread;
+//!
+//! let (tx, mut rx) = watch::channel("initial");
+//!
+//! let handle = thread::spawn(move || {
+//!     rx.changed().unwrap();
+//!     *rx.borrow_and_update()
+//! });
+//!
+//! tx.send("updated").unwrap();
+//! assert_eq!(handle.join().unwrap(), "updated");
+//! ```
+
+use crate::ops::Deref;
+use crate::sync::Arc;
+use crate::sync::atomic::{Atomic, AtomicUsize, Ordering};
+use crate::sync::mpmc::context::Context;
+use crate::sync::mpmc::select::{Operation, Selected, Token};
+use crate::sync::mpmc::waker::SyncWaker;
+#[unstable(feature = "watch_channel", issue = "none")]
+pub use crate::sync::mpsc::{RecvError, RecvTimeoutError, SendError};
+use crate::sync::nonpoison::{RwLock, RwLockReadGuard};
+use crate::time::{Duration, Instant};
+use crate::{fmt, mem};
+
+/// Creates a new watch channel holding `init`, returning the sender/receiver halves.
+///
+/// The initial value is considered seen by the returned receiver.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(watch_channel)]
+///
+/// use std::sync::watch;
+///
+/// let (tx, rx) = watch::channel(1);
+/// assert_eq!(*rx.borrow(), 1);
+///
+/// tx.send(2).unwrap();
+/// assert_eq!(*rx.borrow(), 2);
+/// ```
+#[must_use]
+#[unstable(feature = "watch_channel", issue = "none")]
+pub fn channel<T>(init: T) -> (Sender<T>, Receiver<T>) {
+    let shared = Arc::new(Shared {
+        value: RwLock::new(init),
+        version: AtomicUsize::new(0),
+        senders: AtomicUsize::new(1),
+        receivers: AtomicUsize::new(1),
+        waiters: SyncWaker::new(),
+    });
+    (Sender { shared: shared.clone() }, Receiver { shared, seen: 0 })
+}
+
+/// The state shared by all senders and receivers of a channel.
+struct Shared<T> {
+    /// The current value.
+    value: RwLock<T>,
+
+    /// Incremented every time the value is replaced, while holding the write lock.
+    version: Atomic<usize>,
+
+    /// The number of senders.
+    senders: Atomic<usize>,
+
+    /// The number of receivers.
+    receivers: Atomic<usize>,
+
+    /// Receivers waiting for the value to change.
+    waiters: SyncWaker,
+}
+
+impl<T> Shared<T> {
+    /// Returns the current version. The ordering pairs with the `SeqCst` checks in `SyncWaker`
+    /// so that a receiver registering for a change can't miss a concurrent notification.
+    fn version(&self) -> usize {
+        self.version.load(Ordering::SeqCst)
+    }
+
+    fn is_disconnected(&self) -> bool {
+        self.senders.load(Ordering::SeqCst) == 0
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Sender
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// The sending half of a watch channel.
+///
+/// Senders can be cloned to update the same channel from multiple threads.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(watch_channel)]
+///
+/// use std::sync::watch;
+/// use std::thread;
+///
+/// let (tx, rx) = watch::channel(0);
+/// let tx2 = tx.clone();
+///
+/// thread::spawn(move || tx2.send(1).unwrap()).join().unwrap();
+/// assert_eq!(*rx.borrow(), 1);
+/// ```
+#[unstable(feature = "watch_channel", issue = "none")]
+pub struct Sender<T> {
+    shared: Arc<Shared<T>>,
+}
+
+// SAFETY: the value is only accessed through the `RwLock`, which requires `T: Send + Sync` to be
+// shared between threads.
+#[unstable(feature = "watch_channel", issue = "none")]
+unsafe impl<T: Send + Sync> Send for Sender<T> {}
+#[unstable(feature = "watch_channel", issue = "none")]
+unsafe impl<T: Send + Sync> Sync for Sender<T> {}
+
+impl<T> Sender<T> {
+    /// Replaces the value of the channel and notifies all receivers.
+    ///
+    /// This fails only if there are no receivers, in which case the value is not replaced and
+    /// is returned in the [`SendError`]. Use [`send_replace`] to update the value regardless.
+    ///
+    /// [`send_replace`]: Sender::send_replace
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(watch_channel)]
+    ///
+    /// use std::sync::watch;
+    ///
+    /// let (tx, rx) = watch::channel(0);
+    /// assert!(tx.send(1).is_ok());
+    ///
+    /// drop(rx);
+    /// assert_eq!(tx.send(2).unwrap_err().0, 2);
+    /// ```
+    #[unstable(feature = "watch_channel", issue = "none")]
+    pub fn send(&self, value: T) -> Result<(), SendError<T>> {
+        if self.receiver_count() == 0 {
+            return Err(SendError(value));
+        }
+        self.send_replace(value);
+        Ok(())
+    }
+
+    /// Replaces the value of the channel, notifies all receivers, and returns the previous value.
+    ///
+    /// Unlike [`send`](Sender::send), this updates the value even if there are no receivers, so
+    /// receivers created later with [`subscribe`](Sender::subscribe) see it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(watch_channel)]
+    ///
+    /// use std::sync::watch;
+    ///
+    /// let (tx, rx) = watch::channel(1);
+    /// drop(rx);
+    ///
+    /// assert_eq!(tx.send_replace(2), 1);
+    /// assert_eq!(*tx.subscribe().borrow(), 2);
+    /// ```
+    #[unstable(feature = "watch_channel", issue = "none")]
+    pub fn send_replace(&self, value: T) -> T {
+        let mut value = value;
+        self.send_modify(|old| mem::swap(old, &mut value));
+        value
+    }
+
+    /// Modifies the value of the channel in place, and notifies all receivers.
+    ///
+    /// The value is write-locked while `f` runs. If `f` panics, the receivers are not notified,
+    /// but whatever changes `f` made to the value are kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(watch_channel)]
+    ///
+    /// use std::sync::watch;
+    ///
+    /// let (tx, rx) = watch::channel(vec![1]);
+    /// tx.send_modify(|v| v.push(2));
+    /// assert_eq!(*rx.borrow(), [1, 2]);
+    /// ```
+    #[unstable(feature = "watch_channel", issue = "none")]
+    pub fn send_modify<F>(&self, f: F)
+    where
+        F: FnOnce(&mut T),
+    {
+        let mut value = self.shared.value.write();
+        f(&mut value);
+        self.shared.version.fetch_add(1, Ordering::SeqCst);
+        drop(value);
+
+        self.shared.waiters.notify_all();
+    }
+
+    /// Returns a reference to the current value.
+    ///
+    /// The value is read-locked as long as the returned [`Ref`] is alive, which blocks senders,
+    /// so it should be dropped quickly.
+    #[unstable(feature = "watch_channel", issue = "none")]
+    pub fn borrow(&self) -> Ref<'_, T> {
+        Ref { inner: self.shared.value.read() }
+    }
+
+    /// Creates a new receiver for this channel.
+    ///
+    /// The current value is considered seen by the new receiver.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(watch_channel)]
+    ///
+    /// use std::sync::watch;
+    ///
+    /// let (tx, _rx) = watch::channel(0);
+    /// tx.send(1).unwrap();
+    ///
+    /// let rx2 = tx.subscribe();
+    /// assert_eq!(rx2.has_changed(), Ok(false));
+    /// assert_eq!(*rx2.borrow(), 1);
+    /// ```
+    #[unstable(feature = "watch_channel", issue = "none")]
+    pub fn subscribe(&self) -> Receiver<T> {
+        self.shared.receivers.fetch_add(1, Ordering::Relaxed);
+        // Read the version under the lock, so it matches the value the receiver would see.
+        let value = self.shared.value.read();
+        let seen = self.shared.version();
+        drop(value);
+        Receiver { shared: self.shared.clone(), seen }
+    }
+
+    /// Returns the number of receivers of this channel.
+    #[unstable(feature = "watch_channel", issue = "none")]
+    pub fn receiver_count(&self) -> usize {
+        self.shared.receivers.load(Ordering::Relaxed)
+    }
+}
+
+#[unstable(feature = "watch_channel", issue = "none")]
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        self.shared.senders.fetch_add(1, Ordering::Relaxed);
+        Sender { shared: self.shared.clone() }
+    }
+}
+
+#[unstable(feature = "watch_channel", issue = "none")]
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        if self.shared.senders.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.shared.waiters.disconnect();
+        }
+    }
+}
+
+#[unstable(feature = "watch_channel", issue = "none")]
+impl<T> fmt::Debug for Sender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sender").finish_non_exhaustive()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Receiver
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// The receiving half of a watch channel.
+///
+/// Each receiver keeps track of whether it has seen the current value. Cloning a receiver
+/// creates a new receiver that has seen the same value as the original one.
+#[unstable(feature = "watch_channel", issue = "none")]
+pub struct Receiver<T> {
+    shared: Arc<Shared<T>>,
+
+    /// The version of the last value seen by this receiver.
+    seen: usize,
+}
+
+// SAFETY: see `Sender`.
+#[unstable(feature = "watch_channel", issue = "none")]
+unsafe impl<T: Send + Sync> Send for Receiver<T> {}
+#[unstable(feature = "watch_channel", issue = "none")]
+unsafe impl<T: Send + Sync> Sync for Receiver<T> {}
+
+impl<T> Receiver<T> {
+    /// Returns a reference to the current value, without marking it as seen.
+    ///
+    /// The value is read-locked as long as the returned [`Ref`] is alive, which blocks senders,
+    /// so it should be dropped quickly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(watch_channel)]
+    ///
+    /// use std::sync::watch;
+    ///
+    /// let (tx, rx) = watch::channel(1);
+    /// tx.send(2).unwrap();
+    ///
+    /// assert_eq!(*rx.borrow(), 2);
+    /// assert_eq!(rx.has_changed(), Ok(true));
+    /// ```
+    #[unstable(feature = "watch_channel", issue = "none")]
+    pub fn borrow(&self) -> Ref<'_, T> {
+        Ref { inner: self.shared.value.read() }
+    }
+
+    /// Returns a reference to the current value, and marks it as seen.
+    ///
+    /// The value is read-locked as long as the returned [`Ref`] is alive, which blocks senders,
+    /// so it should be dropped quickly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(watch_channel)]
+    ///
+    /// use std::sync::watch;
+    ///
+    /// let (tx, mut rx) = watch::channel(1);
+    /// tx.send(2).unwrap();
+    ///
+    /// assert_eq!(*rx.borrow_and_update(), 2);
+    /// assert_eq!(rx.has_changed(), Ok(false));
+    /// ```
+    #[unstable(feature = "watch_channel", issue = "none")]
+    pub fn borrow_and_update(&mut self) -> Ref<'_, T> {
+        let inner = self.shared.value.read();
+        self.seen = self.shared.version();
+        Ref { inner }
+    }
+
+    /// Returns whether the value has changed since this receiver last saw it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RecvError`] if the value has not changed and all senders have been dropped, as
+    /// it can then never change again.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(watch_channel)]
+    ///
+    /// use std::sync::watch;
+    ///
+    /// let (tx, rx) = watch::channel(1);
+    /// assert_eq!(rx.has_changed(), Ok(false));
+    ///
+    /// tx.send(2).unwrap();
+    /// drop(tx);
+    /// assert_eq!(rx.has_changed(), Ok(true));
+    /// ```
+    #[unstable(feature = "watch_channel", issue = "none")]
+    pub fn has_changed(&self) -> Result<bool, RecvError> {
+        // Check for disconnection first, so a change made right before it isn't missed.
+        let disconnected = self.shared.is_disconnected();
+        if self.shared.version() != self.seen {
+            Ok(true)
+        } else if disconnected {
+            Err(RecvError)
+        } else {
+            Ok(false)
+        }
+    }
+
+    /// Blocks the current thread until the value changes, and marks the new value as seen.
+    ///
+    /// Returns immediately if the value already changed since this receiver last saw it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RecvError`] if all senders have been dropped and the value has not changed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(watch_channel)]
+    ///
+    /// use std::sync::watch;
+    /// use std::thread;
+    ///
+    /// let (tx, mut rx) = watch::channel(0);
+    ///
+    /// thread::spawn(move || {
+    ///     tx.send(1).unwrap();
+    /// });
+    ///
+    /// assert!(rx.changed().is_ok());
+    /// assert_eq!(*rx.borrow(), 1);
+    /// assert!(rx.changed().is_err());
+    /// ```
+    #[unstable(feature = "watch_channel", issue = "none")]
+    pub fn changed(&mut self) -> Result<(), RecvError> {
+        self.changed_until(None).map_err(|err| match err {
+            RecvTimeoutError::Timeout => unreachable!(),
+            RecvTimeoutError::Disconnected => RecvError,
+        })
+    }
+
+    /// Blocks the current thread until the value changes, for at most `timeout`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RecvTimeoutError::Timeout`] if the value did not change before the timeout
+    /// elapsed, and [`RecvTimeoutError::Disconnected`] if all senders have been dropped and the
+    /// value has not changed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(watch_channel)]
+    ///
+    /// use std::sync::watch::{self, RecvTimeoutError};
+    /// use std::time::Duration;
+    ///
+    /// let (_tx, mut rx) = watch::channel(0);
+    ///
+    /// assert_eq!(rx.changed_timeout(Duration::from_millis(10)), Err(RecvTimeoutError::Timeout));
+    /// ```
+    #[unstable(feature = "watch_channel", issue = "none")]
+    pub fn changed_timeout(&mut self, timeout: Duration) -> Result<(), RecvTimeoutError> {
+        // If the deadline overflows, it's practically the same as waiting indefinitely.
+        self.changed_until(Instant::now().checked_add(timeout))
+    }
+
+    fn changed_until(&mut self, deadline: Option<Instant>) -> Result<(), RecvTimeoutError> {
+        let token = &mut Token::default();
+        loop {
+            match self.has_changed() {
+                Ok(true) => {
+                    self.seen = self.shared.version();
+                    return Ok(());
+                }
+                Ok(false) => {}
+                Err(RecvError) => return Err(RecvTimeoutError::Disconnected),
+            }
+
+            if let Some(d) = deadline {
+                if Instant::now() >= d {
+                    return Err(RecvTimeoutError::Timeout);
+                }
+            }
+
+            Context::with(|cx| {
+                // Prepare for blocking until a sender wakes us up.
+                let oper = Operation::hook(token);
+                self.shared.waiters.register(oper, cx);
+
+                // Has the value changed or the channel disconnected just now?
+                if self.shared.version() != self.seen || self.shared.is_disconnected() {
+                    let _ = cx.try_select(Selected::Aborted);
+                }
+
+                // Block the current thread.
+                // SAFETY: the context belongs to the current thread.
+                let sel = unsafe { cx.wait_until(deadline) };
+
+                match sel {
+                    Selected::Waiting => unreachable!(),
+                    Selected::Aborted | Selected::Disconnected => {
+                        self.shared.waiters.unregister(oper).unwrap();
+                    }
+                    Selected::Operation(_) => {}
+                }
+            });
+        }
+    }
+}
+
+#[unstable(feature = "watch_channel", issue = "none")]
+impl<T> Clone for Receiver<T> {
+    fn clone(&self) -> Self {
+        self.shared.receivers.fetch_add(1, Ordering::Relaxed);
+        Receiver { shared: self.shared.clone(), seen: self.seen }
+    }
+}
+
+#[unstable(feature = "watch_channel", issue = "none")]
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        self.shared.receivers.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+#[unstable(feature = "watch_channel", issue = "none")]
+impl<T> fmt::Debug for Receiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Receiver").finish_non_exhaustive()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Ref
+////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// A reference to the value of a watch channel.
+///
+/// The value is read-locked while this is alive, which blocks senders.
+///
+/// This structure is created by the [`Sender::borrow`], [`Receiver::borrow`] and
+/// [`Receiver::borrow_and_update`] methods.
+#[must_use = "if unused the value will immediately be unlocked"]
+#[must_not_suspend = "holding a watch::Ref across suspend \
+                      points can cause deadlocks, delays, \
+                      and cause Futures to not implement `Send`"]
+#[clippy::has_significant_drop]
+#[unstable(feature = "watch_channel", issue = "none")]
+pub struct Ref<'a, T: 'a> {
+    inner: RwLockReadGuard<'a, T>,
+}
+
+#[unstable(feature = "watch_channel", issue = "none")]
+impl<T> Deref for Ref<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.inner
+    }
+}
+
+#[unstable(feature = "watch_channel", issue = "none")]
+impl<T: fmt::Debug> fmt::Debug for Ref<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
@@ -48,6 +48,30 @@ fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
     }
 }
 
+impl BacktraceLock<'_> {
+    /// Writes the current backtrace as a JSON array with one object per symbol.
+    ///
+    /// Each object has the keys `ip`, `function`, `file`, `line` and `column`, the last four of
+    /// which are `null` when unknown. Inlined functions share the `ip` of their caller.
+    pub(crate) fn print_json(&mut self, w: &mut dyn Write, format: PrintFmt) -> io::Result<()> {
+        // See `print` for why this is skipped in test mode.
+        if cfg!(test) {
+            return w.write_all(b"[]");
+        }
+
+        struct DisplayBacktrace {
+            format: PrintFmt,
+        }
+        impl fmt::Display for DisplayBacktrace {
+            fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+                // SAFETY: the backtrace lock is held
+                unsafe { _print_json_fmt(fmt, self.format) }
+            }
+        }
+        write!(w, "{}", DisplayBacktrace { format })
+    }
+}
+
 /// # Safety
 ///
 /// This function is not Sync. The caller must hold a mutex lock, or there must be only one thread in the program.
@@ -155,6 +179,136 @@ unsafe fn _print_fmt(fmt: &mut fmt::Formatter<'_>, print_fmt: PrintFmt) -> fmt::
     Ok(())
 }
 
+/// # Safety
+///
+/// This function is not Sync. The caller must hold a mutex lock, or there must be only one thread in the program.
+unsafe fn _print_json_fmt(fmt: &mut fmt::Formatter<'_>, print_fmt: PrintFmt) -> fmt::Result {
+    fmt.write_str("[")?;
+    let mut idx = 0;
+    let mut first = true;
+    let mut res = Ok(());
+    // If we're using a short backtrace, ignore all frames until we're told to start printing.
+    let mut print = print_fmt != PrintFmt::Short;
+    set_image_base();
+    // SAFETY: we roll our own locking in this town
+    unsafe {
+        backtrace_rs::trace_unsynchronized(|frame| {
+            if print_fmt == PrintFmt::Short && idx > MAX_NB_FRAMES {
+                return false;
+            }
+
+            let mut hit = false;
+            if !cfg!(feature = "backtrace-trace-only") {
+                backtrace_rs::resolve_frame_unsynchronized(frame, |symbol| {
+                    hit = true;
+
+                    // Hide the same frames as the human-readable short backtrace.
+                    if print_fmt == PrintFmt::Short {
+                        if let Some(sym) = symbol.name().and_then(|s| s.as_str()) {
+                            if sym.contains("__rust_end_short_backtrace") {
+                                print = true;
+                                return;
+                            }
+                            if print && sym.contains("__rust_begin_short_backtrace") {
+                                print = false;
+                                return;
+                            }
+                        }
+                    }
+
+                    if print && res.is_ok() {
+                        res = write_json_frame(fmt, &mut first, frame.ip(), Some(symbol));
+                    }
+                });
+            }
+            if !hit && print && res.is_ok() {
+                res = write_json_frame(fmt, &mut first, frame.ip(), None);
+            }
+
+            idx += 1;
+            res.is_ok()
+        })
+    };
+    res?;
+    fmt.write_str("]")
+}
+
+fn write_json_frame(
+    fmt: &mut fmt::Formatter<'_>,
+    first: &mut bool,
+    ip: *mut core::ffi::c_void,
+    symbol: Option<&backtrace_rs::Symbol>,
+) -> fmt::Result {
+    if !crate::mem::take(first) {
+        fmt.write_str(",")?;
+    }
+    let name = symbol.and_then(|s| s.name());
+    let file = symbol.and_then(|s| s.filename_raw()).and_then(path_from_bytes_or_wide);
+    write!(
+        fmt,
+        "{{\"ip\":\"{ip:p}\",\"function\":{:#},\"file\":{},\"line\":{},\"column\":{}}}",
+        JsonOption(name.map(JsonStr)),
+        JsonOption(file.as_ref().map(|file| JsonStr(file.display()))),
+        JsonOption(symbol.and_then(|s| s.lineno())),
+        JsonOption(symbol.and_then(|s| s.colno())),
+    )
+}
+
+/// Displays a value as a quoted and escaped JSON string.
+pub(crate) struct JsonStr<T>(pub T);
+
+impl<T: fmt::Display> fmt::Display for JsonStr<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        struct Escape<'a, 'b>(&'a mut fmt::Formatter<'b>);
+
+        impl fmt::Write for Escape<'_, '_> {
+            fn write_str(&mut self, s: &str) -> fmt::Result {
+                let mut start = 0;
+                for (i, b) in s.bytes().enumerate() {
+                    let escaped = match b {
+                        b'"' => "\\\"",
+                        b'\\' => "\\\\",
+                        b'\n' => "\\n",
+                        b'\r' => "\\r",
+                        b'\t' => "\\t",
+                        0..0x20 => "",
+                        _ => continue,
+                    };
+                    self.0.write_str(&s[start..i])?;
+                    if escaped.is_empty() {
+                        write!(self.0, "\\u{b:04x}")?;
+                    } else {
+                        self.0.write_str(escaped)?;
+                    }
+                    start = i + 1;
+                }
+                self.0.write_str(&s[start..])
+            }
+        }
+
+        f.write_str("\"")?;
+        // Forward the alternate flag, which symbol names use to omit their hash.
+        if f.alternate() {
+            fmt::write(&mut Escape(f), format_args!("{:#}", self.0))?;
+        } else {
+            fmt::write(&mut Escape(f), format_args!("{}", self.0))?;
+        }
+        f.write_str("\"")
+    }
+}
+
+/// Displays a value, or `null` if there is none.
+pub(crate) struct JsonOption<T>(pub Option<T>);
+
+impl<T: fmt::Display> fmt::Display for JsonOption<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            Some(value) => value.fmt(f),
+            None => f.write_str("null"),
+        }
+    }
+}
+
 /// Fixed frame used to clean the backtrace with `RUST_BACKTRACE=1`. Note that
 /// this is only inline(never) when backtraces in std are enabled, otherwise
 /// it's fine to optimize away.
@@ -187,33 +341,39 @@ pub fn __rust_end_short_backtrace<F, T>(f: F) -> T
     result
 }
 
-/// Prints the filename of the backtrace frame.
-///
-/// See also `output`.
-pub fn output_filename(
-    fmt: &mut fmt::Formatter<'_>,
-    bows: BytesOrWideString<'_>,
-    print_fmt: PrintFmt,
-    cwd: Option<&PathBuf>,
-) -> fmt::Result {
-    let file: Cow<'_, Path> = match bows {
+/// Converts a file name reported by the symbolizer to a path, if it is representable on this
+/// platform.
+pub fn path_from_bytes_or_wide(bows: BytesOrWideString<'_>) -> Option<Cow<'_, Path>> {
+    match bows {
         #[cfg(unix)]
         BytesOrWideString::Bytes(bytes) => {
             use crate::os::unix::prelude::*;
-            Path::new(crate::ffi::OsStr::from_bytes(bytes)).into()
+            Some(Path::new(crate::ffi::OsStr::from_bytes(bytes)).into())
         }
         #[cfg(not(unix))]
         BytesOrWideString::Bytes(bytes) => {
-            Path::new(crate::str::from_utf8(bytes).unwrap_or("<unknown>")).into()
+            crate::str::from_utf8(bytes).ok().map(|s| Path::new(s).into())
         }
         #[cfg(windows)]
         BytesOrWideString::Wide(wide) => {
             use crate::os::windows::prelude::*;
-            Cow::Owned(crate::ffi::OsString::from_wide(wide).into())
+            Some(Cow::Owned(crate::ffi::OsString::from_wide(wide).into()))
         }
         #[cfg(not(windows))]
-        BytesOrWideString::Wide(_wide) => Path::new("<unknown>").into(),
-    };
+        BytesOrWideString::Wide(_wide) => None,
+    }
+}
+
+/// Prints the filename of the backtrace frame.
+///
+/// See also `output`.
+pub fn output_filename(
+    fmt: &mut fmt::Formatter<'_>,
+    bows: BytesOrWideString<'_>,
+    print_fmt: PrintFmt,
+    cwd: Option<&PathBuf>,
+) -> fmt::Result {
+    let file = path_from_bytes_or_wide(bows).unwrap_or(Path::new("<unknown>").into());
     if print_fmt == PrintFmt::Short && file.is_absolute() {
         if let Some(cwd) = cwd {
             if let Ok(stripped) = file.strip_prefix(&cwd) {
@@ -53,6 +53,46 @@ fn remove_dir_all_recursive(path: &Path) -> io::Result<()> {
     ignore_notfound(fs::remove_dir(path))
 }
 
+/// Like [`remove_dir_all`], but reports the outcome of removing each entry to `report`, and only
+/// stops early when it returns `false`.
+pub fn remove_dir_all_with(path: &Path, report: &mut dyn FnMut(&Path, io::Result<()>) -> bool) {
+    match fs::symlink_metadata(path) {
+        Ok(attr) if attr.file_type().is_symlink() => {
+            report(path, fs::remove_file(path));
+        }
+        Ok(_) => {
+            remove_dir_all_recursive_with(path, report);
+        }
+        Err(e) => {
+            report(path, Err(e));
+        }
+    }
+}
+
+/// Returns whether to go on.
+fn remove_dir_all_recursive_with(
+    path: &Path,
+    report: &mut dyn FnMut(&Path, io::Result<()>) -> bool,
+) -> bool {
+    let children = match fs::read_dir(path) {
+        Ok(children) => children,
+        Err(e) => return report(path, Err(e)),
+    };
+    for child in children {
+        let keep_going = match child.and_then(|child| Ok((child.path(), child.file_type()?))) {
+            Ok((child, file_type)) if file_type.is_dir() => {
+                remove_dir_all_recursive_with(&child, report)
+            }
+            Ok((child, _)) => report(&child, ignore_notfound(fs::remove_file(&child))),
+            Err(e) => report(path, Err(e)),
+        };
+        if !keep_going {
+            return false;
+        }
+    }
+    report(path, ignore_notfound(fs::remove_dir(path)))
+}
+
 pub fn exists(path: &Path) -> io::Result<bool> {
     match fs::metadata(path) {
         Ok(_) => Ok(true),
@@ -11,6 +11,13 @@
         use unix as imp;
         #[cfg(not(target_os = "wasi"))]
         pub use unix::{chown, fchown, lchown, mkfifo};
+        #[cfg(not(any(
+            target_os = "wasi",
+            target_os = "espidf",
+            target_os = "horizon",
+            target_os = "vita",
+        )))]
+        pub use unix::Mmap;
         #[cfg(not(any(target_os = "fuchsia", target_os = "wasi")))]
         pub use unix::chroot;
         #[cfg(not(target_os = "wasi"))]
@@ -68,6 +75,21 @@ pub fn read_dir(path: &Path) -> io::Result<ReadDir> {
     imp::readdir(path)
 }
 
+/// Opens a directory found by [`ReadDir`], to walk into it.
+///
+/// Where possible, the directory is opened relative to the one it was found in rather than through
+/// its path, so that the walk can't be redirected by replacing a directory along the way with a
+/// symlink.
+pub fn read_dir_entry(entry: &DirEntry, follow_symlinks: bool) -> io::Result<ReadDir> {
+    #[cfg(any(target_family = "unix", target_os = "wasi"))]
+    return entry.read_dir(follow_symlinks);
+    #[cfg(not(any(target_family = "unix", target_os = "wasi")))]
+    {
+        let _ = follow_symlinks;
+        read_dir(&entry.path())
+    }
+}
+
 pub fn remove_file(path: &Path) -> io::Result<()> {
     with_native_path(path, &imp::unlink)
 }
@@ -88,6 +110,20 @@ pub fn remove_dir_all(path: &Path) -> io::Result<()> {
     with_native_path(path, &imp::remove_dir_all)
 }
 
+/// Like [`remove_dir_all`], but reports the outcome of removing each entry to `report`, and only
+/// stops early when it returns `false`.
+///
+/// Where the walk isn't implemented by the platform, the whole tree is removed with
+/// [`remove_dir_all`] and reported once, as `path`.
+pub fn remove_dir_all_with(path: &Path, report: &mut dyn FnMut(&Path, io::Result<()>) -> bool) {
+    #[cfg(any(target_family = "unix", target_os = "wasi", target_os = "uefi"))]
+    imp::remove_dir_all_with(path, report);
+    #[cfg(not(any(target_family = "unix", target_os = "wasi", target_os = "uefi")))]
+    {
+        report(path, remove_dir_all(path));
+    }
+}
+
 pub fn read_link(path: &Path) -> io::Result<PathBuf> {
     with_native_path(path, &imp::readlink)
 }
@@ -102,6 +138,38 @@ pub fn symlink(original: &Path, link: &Path) -> io::Result<()> {
     })
 }
 
+/// Creates an unnamed temporary file in `dir`, which is deleted when closed unless it is named
+/// with [`link_tmpfile`] first.
+///
+/// Returns `None` where that isn't supported by the platform or by the filesystem of `dir`.
+pub fn create_tmpfile(dir: &Path) -> io::Result<Option<File>> {
+    #[cfg(target_os = "linux")]
+    return with_native_path(dir, &imp::create_tmpfile);
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = dir;
+        Ok(None)
+    }
+}
+
+/// Gives the name `link` to a file returned by [`create_tmpfile`].
+#[cfg(target_os = "linux")]
+pub fn link_tmpfile(file: &File, link: &Path) -> io::Result<()> {
+    with_native_path(link, &|link| imp::link_tmpfile(file, link))
+}
+
+/// Makes the changes to the entries of the directory `dir` durable, where that is needed and
+/// possible.
+pub fn sync_dir(dir: &Path) -> io::Result<()> {
+    #[cfg(target_family = "unix")]
+    return with_native_path(dir, &imp::sync_dir);
+    #[cfg(not(target_family = "unix"))]
+    {
+        let _ = dir;
+        Ok(())
+    }
+}
+
 pub fn hard_link(original: &Path, link: &Path) -> io::Result<()> {
     with_native_path(original, &|original| {
         with_native_path(link, &|link| imp::link(original, link))
@@ -6,6 +6,21 @@
 #[cfg(test)]
 mod tests;
 
+#[cfg(not(any(
+    target_os = "wasi",
+    target_os = "espidf",
+    target_os = "horizon",
+    target_os = "vita",
+)))]
+mod mmap;
+#[cfg(not(any(
+    target_os = "wasi",
+    target_os = "espidf",
+    target_os = "horizon",
+    target_os = "vita",
+)))]
+pub use mmap::Mmap;
+
 #[cfg(all(target_os = "linux", target_env = "gnu"))]
 use libc::c_char;
 #[cfg(any(
@@ -1255,6 +1270,51 @@ fn name_cstr(&self) -> &CStr {
     pub fn file_name_os_str(&self) -> &OsStr {
         OsStr::from_bytes(self.name_bytes())
     }
+
+    #[cfg(all(
+        any(
+            target_os = "linux",
+            target_os = "android",
+            target_os = "freebsd",
+            target_vendor = "apple",
+        ),
+        not(miri) // no dirfd on Miri
+    ))]
+    pub fn read_dir(&self, follow_symlinks: bool) -> io::Result<ReadDir> {
+        #[cfg(not(all(target_os = "linux", target_env = "gnu")))]
+        use libc::openat;
+        #[cfg(all(target_os = "linux", target_env = "gnu"))]
+        use libc::openat64 as openat;
+
+        let parent_fd = cvt(unsafe { libc::dirfd(self.dir.dirp.0) })?;
+        let mut flags = libc::O_CLOEXEC | libc::O_RDONLY | libc::O_DIRECTORY;
+        if !follow_symlinks {
+            flags |= libc::O_NOFOLLOW;
+        }
+        let fd = cvt_r(|| unsafe { openat(parent_fd, self.name_cstr().as_ptr(), flags) })?;
+        let fd = unsafe { OwnedFd::from_raw_fd(fd) };
+        let ptr = unsafe { libc::fdopendir(fd.as_raw_fd()) };
+        if ptr.is_null() {
+            return Err(Error::last_os_error());
+        }
+        // The file descriptor is closed by `closedir` from now on.
+        let _ = fd.into_raw_fd();
+        let inner = InnerReadDir { dirp: DirStream(ptr), root: self.path() };
+        Ok(ReadDir::new(inner))
+    }
+
+    #[cfg(any(
+        not(any(
+            target_os = "linux",
+            target_os = "android",
+            target_os = "freebsd",
+            target_vendor = "apple",
+        )),
+        miri
+    ))]
+    pub fn read_dir(&self, _follow_symlinks: bool) -> io::Result<ReadDir> {
+        readdir(&self.path())
+    }
 }
 
 impl OpenOptions {
@@ -2076,6 +2136,63 @@ pub fn link(original: &CStr, link: &CStr) -> io::Result<()> {
     Ok(())
 }
 
+/// Creates an unnamed file in the directory `dir`, or returns `None` if the filesystem doesn't
+/// support it.
+#[cfg(target_os = "linux")]
+pub fn create_tmpfile(dir: &CStr) -> io::Result<Option<File>> {
+    // Naming the file later goes through `/proc/self/fd` unless the process is privileged, so
+    // don't bother without it.
+    if unsafe { libc::access(c"/proc/self/fd".as_ptr(), libc::F_OK) } != 0 {
+        return Ok(None);
+    }
+    let flags = libc::O_CLOEXEC | libc::O_RDWR | libc::O_TMPFILE;
+    match cvt_r(|| unsafe { open64(dir.as_ptr(), flags, 0o666 as c_int) }) {
+        Ok(fd) => Ok(Some(File(unsafe { FileDesc::from_raw_fd(fd) }))),
+        // Kernels older than 3.11 only see `O_DIRECTORY` in `O_TMPFILE`, and fail with `EISDIR`.
+        Err(e) if matches!(e.raw_os_error(), Some(libc::EISDIR | libc::EOPNOTSUPP)) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Gives a name to a file created by [`create_tmpfile`].
+#[cfg(target_os = "linux")]
+pub fn link_tmpfile(file: &File, link: &CStr) -> io::Result<()> {
+    let fd = file.as_raw_fd();
+    // Linking a file descriptor directly requires `CAP_DAC_READ_SEARCH`.
+    let linked = cvt(unsafe {
+        libc::linkat(fd, c"".as_ptr(), libc::AT_FDCWD, link.as_ptr(), libc::AT_EMPTY_PATH)
+    });
+    match linked {
+        Err(e) if matches!(e.raw_os_error(), Some(libc::EPERM | libc::ENOENT)) => {}
+        result => return result.map(|_| ()),
+    }
+    let mut proc_path = format!("/proc/self/fd/{fd}").into_bytes();
+    proc_path.push(0);
+    let proc_path = CStr::from_bytes_with_nul(&proc_path).unwrap();
+    cvt(unsafe {
+        libc::linkat(
+            libc::AT_FDCWD,
+            proc_path.as_ptr(),
+            libc::AT_FDCWD,
+            link.as_ptr(),
+            libc::AT_SYMLINK_FOLLOW,
+        )
+    })?;
+    Ok(())
+}
+
+/// Flushes the entries of the directory `dir` to disk, such as a file just renamed into it.
+#[cfg(not(target_os = "wasi"))]
+pub fn sync_dir(dir: &CStr) -> io::Result<()> {
+    let fd = cvt_r(|| unsafe { open64(dir.as_ptr(), libc::O_CLOEXEC | libc::O_RDONLY) })?;
+    let dir = File(unsafe { FileDesc::from_raw_fd(fd) });
+    match dir.fsync() {
+        // Some filesystems can't sync directories, and have nothing to flush then.
+        Err(e) if e.raw_os_error() == Some(libc::EINVAL) => Ok(()),
+        result => result,
+    }
+}
+
 pub fn stat(p: &CStr) -> io::Result<FileAttr> {
     cfg_has_statx! {
         if let Some(ret) = unsafe { try_statx(
@@ -2420,7 +2537,7 @@ pub fn mkfifo(path: &Path, mode: u32) -> io::Result<()> {
     })
 }
 
-pub use remove_dir_impl::remove_dir_all;
+pub use remove_dir_impl::{remove_dir_all, remove_dir_all_with};
 
 // Fallback for REDOX, ESP-ID, Horizon, Vita, Vxworks and Miri
 #[cfg(any(
@@ -2434,7 +2551,7 @@ pub fn mkfifo(path: &Path, mode: u32) -> io::Result<()> {
     miri
 ))]
 mod remove_dir_impl {
-    pub use crate::sys::fs::common::remove_dir_all;
+    pub use crate::sys::fs::common::{remove_dir_all, remove_dir_all_with};
 }
 
 // Modern implementation using openat(), unlinkat() and fdopendir()
@@ -2458,10 +2575,10 @@ mod remove_dir_impl {
         AsRawFd, DirEntry, DirStream, FromRawFd, InnerReadDir, IntoRawFd, OwnedFd, RawFd, ReadDir,
         lstat,
     };
-    use crate::ffi::CStr;
+    use crate::ffi::{CStr, CString, OsStr};
     use crate::io;
     use crate::path::{Path, PathBuf};
-    use crate::sys::helpers::{ignore_notfound, run_path_with_cstr};
+    use crate::sys::helpers::{NUL_ERR, ignore_notfound, run_path_with_cstr};
     use crate::sys::{cvt, cvt_r};
 
     pub fn openat_nofollow_dironly(parent_fd: Option<RawFd>, p: &CStr) -> io::Result<OwnedFd> {
@@ -2606,4 +2723,95 @@ fn remove_dir_all_modern(p: &CStr) -> io::Result<()> {
     pub fn remove_dir_all(p: &Path) -> io::Result<()> {
         run_path_with_cstr(p, &remove_dir_all_modern)
     }
+
+    /// Like [`remove_dir_all`], but reports the outcome of removing each entry to `report`, and
+    /// only stops early when it returns `false`.
+    pub fn remove_dir_all_with(p: &Path, report: &mut dyn FnMut(&Path, io::Result<()>) -> bool) {
+        let root = match CString::new(p.as_os_str().as_encoded_bytes()) {
+            Ok(root) => root,
+            Err(_) => {
+                report(p, Err(NUL_ERR));
+                return;
+            }
+        };
+        // As in `remove_dir_all_modern`, a symlink is removed rather than walked into.
+        match lstat(&root) {
+            Ok(attr) if attr.file_type().is_symlink() => {
+                report(p, super::unlink(&root));
+            }
+            Ok(_) => {
+                remove_dir_all_recursive_with(None, &root, &mut p.to_owned(), report);
+            }
+            Err(e) => {
+                report(p, Err(e));
+            }
+        }
+    }
+
+    /// Removes `name` in `parent_fd`, which is found at `path`, along with its contents if it's a
+    /// directory. Returns whether to go on.
+    fn remove_dir_all_recursive_with(
+        parent_fd: Option<RawFd>,
+        name: &CStr,
+        path: &mut PathBuf,
+        report: &mut dyn FnMut(&Path, io::Result<()>) -> bool,
+    ) -> bool {
+        let parent = parent_fd.unwrap_or(libc::AT_FDCWD);
+        let fd = match openat_nofollow_dironly(parent_fd, name) {
+            Ok(fd) => fd,
+            Err(err) if matches!(err.raw_os_error(), Some(libc::ENOTDIR | libc::ELOOP)) => {
+                // Not a directory, don't traverse further. See `remove_dir_all_recursive`.
+                let result = match parent_fd {
+                    Some(fd) => cvt(unsafe { unlinkat(fd, name.as_ptr(), 0) }).map(drop),
+                    None => Err(err),
+                };
+                return report(path, ignore_notfound(result));
+            }
+            Err(err) => return report(path, ignore_notfound(Err(err))),
+        };
+        let (dir, fd) = match fdreaddir(fd) {
+            Ok(dir) => dir,
+            Err(err) => return report(path, Err(err)),
+        };
+
+        // See `remove_dir_all_recursive`.
+        #[cfg(target_os = "wasi")]
+        let dir = dir.collect::<Vec<_>>();
+
+        for child in dir {
+            let child = match child {
+                Ok(child) => child,
+                Err(err) => {
+                    // The rest of the directory can't be listed. Removing it will fail, and
+                    // report that too.
+                    if !report(path, Err(err)) {
+                        return false;
+                    }
+                    break;
+                }
+            };
+            let child_name = child.name_cstr();
+            // SAFETY: The name was returned by the OS, so it is a valid encoding.
+            path.push(unsafe { OsStr::from_encoded_bytes_unchecked(child_name.to_bytes()) });
+            let keep_going = match is_dir(&child) {
+                Some(false) => report(
+                    path,
+                    ignore_notfound(cvt(unsafe { unlinkat(fd, child_name.as_ptr(), 0) })),
+                ),
+                // See `remove_dir_all_recursive` for why an unknown type is walked into.
+                Some(true) | None => {
+                    remove_dir_all_recursive_with(Some(fd), child_name, path, report)
+                }
+            };
+            path.pop();
+            if !keep_going {
+                return false;
+            }
+        }
+
+        report(
+            path,
+            ignore_notfound(cvt(unsafe { unlinkat(parent, name.as_ptr(), libc::AT_REMOVEDIR) })),
+        )
+    }
 }
@@ -0,0 +1,118 @@
+//! Memory maps of files, for `std::os::unix::fs::{Mmap, MmapMut}`.
+
+use libc::{c_int, c_void};
+
+use crate::os::fd::{AsRawFd, BorrowedFd};
+use crate::sys::cvt;
+use crate::sys::pal::conf::page_size;
+use crate::{io, ptr};
+
+/// A shared mapping of (part of) a file.
+///
+/// Since `mmap` only takes page-aligned offsets, the mapping may start before the requested
+/// data, `offset` bytes before it.
+pub struct Mmap {
+    ptr: *mut c_void,
+    // The length of the mapping, including `offset`, or 0 if nothing is mapped.
+    len: usize,
+    offset: usize,
+}
+
+// SAFETY: the mapping is just memory, owned by this value.
+unsafe impl Send for Mmap {}
+unsafe impl Sync for Mmap {}
+
+impl Mmap {
+    /// Maps `len` bytes of the file `fd`, from `offset`.
+    ///
+    /// # Safety
+    ///
+    /// The caller must make sure that the mapped range of the file isn't truncated while it is
+    /// mapped, and that it is only modified through the mapping if `writable` is set.
+    pub unsafe fn map(
+        fd: BorrowedFd<'_>,
+        offset: u64,
+        len: usize,
+        writable: bool,
+    ) -> io::Result<Mmap> {
+        #[cfg(not(all(target_os = "linux", target_env = "gnu")))]
+        use libc::mmap;
+        #[cfg(all(target_os = "linux", target_env = "gnu"))]
+        use libc::mmap64 as mmap;
+
+        if len == 0 {
+            // `mmap` rejects empty mappings, and there is nothing to map anyway.
+            return Ok(Mmap { ptr: ptr::null_mut(), len: 0, offset: 0 });
+        }
+        let map_offset = (offset % page_size() as u64) as usize;
+        let (Some(map_len), Ok(file_offset)) =
+            (len.checked_add(map_offset), (offset - map_offset as u64).try_into())
+        else {
+            return Err(io::const_error!(io::ErrorKind::InvalidInput, "mapping too large"));
+        };
+        let prot = if writable { libc::PROT_READ | libc::PROT_WRITE } else { libc::PROT_READ };
+        let ptr = unsafe {
+            mmap(ptr::null_mut(), map_len, prot, libc::MAP_SHARED, fd.as_raw_fd(), file_offset)
+        };
+        if ptr == libc::MAP_FAILED {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(Mmap { ptr, len: map_len, offset: map_offset })
+    }
+
+    /// Returns a pointer to the mapped data, which is dangling if there is none.
+    pub fn as_ptr(&self) -> *mut u8 {
+        if self.len == 0 {
+            ptr::dangling_mut()
+        } else {
+            unsafe { self.ptr.cast::<u8>().add(self.offset) }
+        }
+    }
+
+    /// Returns the length of the mapped data.
+    pub fn len(&self) -> usize {
+        self.len - self.offset
+    }
+
+    /// Calls `madvise` on the whole mapping.
+    pub fn advise(&self, advice: c_int) -> io::Result<()> {
+        if self.len == 0 {
+            return Ok(());
+        }
+        cvt(unsafe { libc::madvise(self.ptr, self.len, advice) })?;
+        Ok(())
+    }
+
+    /// Writes the changes made to `len` bytes of the data, from `offset`, back to the file.
+    pub fn flush(&self, offset: usize, len: usize, asynchronous: bool) -> io::Result<()> {
+        if offset.checked_add(len).is_none_or(|end| end > self.len()) {
+            return Err(io::const_error!(
+                io::ErrorKind::InvalidInput,
+                "range to flush out of the bounds of the mapping",
+            ));
+        }
+        if len == 0 {
+            return Ok(());
+        }
+        // `msync` only takes page-aligned addresses.
+        let start = self.offset + offset;
+        let aligned_start = start - start % page_size();
+        let flags = if asynchronous { libc::MS_ASYNC } else { libc::MS_SYNC };
+        cvt(unsafe {
+            libc::msync(
+                self.ptr.cast::<u8>().add(aligned_start).cast(),
+                start + len - aligned_start,
+                flags,
+            )
+        })?;
+        Ok(())
+    }
+}
+
+impl Drop for Mmap {
+    fn drop(&mut self) {
+        if self.len != 0 {
+            unsafe { libc::munmap(self.ptr, self.len) };
+        }
+    }
+}
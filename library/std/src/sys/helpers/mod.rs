@@ -12,7 +12,7 @@
 mod tests;
 
 #[cfg_attr(not(target_os = "linux"), allow(unused))] // Not used on all platforms.
-pub use small_c_string::{run_path_with_cstr, run_with_cstr};
+pub use small_c_string::{NUL_ERR, run_path_with_cstr, run_with_cstr};
 #[cfg_attr(not(target_os = "windows"), allow(unused))] // Not used on all platforms.
 pub use wstr::WStrUnits;
 
@@ -10,7 +10,7 @@
 #[cfg(target_os = "espidf")]
 const MAX_STACK_ALLOCATION: usize = 32;
 
-const NUL_ERR: io::Error =
+pub const NUL_ERR: io::Error =
     io::const_error!(io::ErrorKind::InvalidInput, "file name contained an unexpected NUL byte");
 
 #[inline]
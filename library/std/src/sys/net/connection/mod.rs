@@ -35,6 +35,20 @@
     }
 }
 
+cfg_select! {
+    any(
+        all(target_family = "unix", not(target_os = "l4re")),
+        target_os = "windows",
+        target_os = "hermit",
+        all(target_os = "wasi", any(target_env = "p2", target_env = "p3")),
+        target_os = "solid_asp3",
+    ) => {}
+    _ => {
+        mod unsupported_builder;
+        pub use unsupported_builder::SocketBuilder;
+    }
+}
+
 #[cfg_attr(
     // Make sure that this is used on some platforms at least.
     not(any(target_os = "linux", target_os = "windows")),
@@ -60,6 +74,42 @@ fn each_addr<A: crate::net::ToSocketAddrs, F, T>(addr: A, mut f: F) -> crate::io
     }
 }
 
+// Default implementation, overridden where several connections can be waited for at once.
+#[cfg(not(any(
+    all(target_family = "unix", not(target_os = "l4re")),
+    target_os = "windows",
+    all(target_os = "wasi", any(target_env = "p2", target_env = "p3")),
+)))]
+impl TcpStream {
+    /// Connects to the first of `addrs` to accept a connection within `timeout`, trying them one
+    /// after another.
+    pub fn connect_racing(
+        addrs: &[crate::net::SocketAddr],
+        _attempt_delay: crate::time::Duration,
+        timeout: crate::time::Duration,
+    ) -> crate::io::Result<TcpStream> {
+        use crate::io;
+        use crate::time::Instant;
+
+        let deadline = Instant::now().checked_add(timeout);
+        let mut last_err = None;
+        for addr in addrs {
+            let timeout = match deadline {
+                Some(deadline) => deadline.saturating_duration_since(Instant::now()),
+                None => timeout,
+            };
+            if timeout.is_zero() {
+                return Err(io::const_error!(io::ErrorKind::TimedOut, "connection timed out"));
+            }
+            match TcpStream::connect_timeout(addr, timeout) {
+                Ok(stream) => return Ok(stream),
+                Err(e) => last_err = Some(e),
+            }
+        }
+        Err(last_err.unwrap_or(io::Error::NO_ADDRESSES))
+    }
+}
+
 // Default implementation, may be overridden by platform-specific implementations.
 #[cfg(not(all(target_vendor = "fortanix", target_env = "sgx")))]
 pub(crate) fn lookup_host_string(
@@ -291,6 +291,54 @@ pub fn nodelay(&self) -> io::Result<bool> {
         Ok(raw != 0)
     }
 
+    pub fn set_reuse_port(&self, _: bool) -> io::Result<()> {
+        Err(io::Error::UNSUPPORTED_PLATFORM)
+    }
+
+    pub fn reuse_port(&self) -> io::Result<bool> {
+        Err(io::Error::UNSUPPORTED_PLATFORM)
+    }
+
+    pub fn set_send_buffer_size(&self, _: usize) -> io::Result<()> {
+        Err(io::Error::UNSUPPORTED_PLATFORM)
+    }
+
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        Err(io::Error::UNSUPPORTED_PLATFORM)
+    }
+
+    pub fn set_recv_buffer_size(&self, _: usize) -> io::Result<()> {
+        Err(io::Error::UNSUPPORTED_PLATFORM)
+    }
+
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        Err(io::Error::UNSUPPORTED_PLATFORM)
+    }
+
+    pub fn set_keepalive_time(&self, _: Duration) -> io::Result<()> {
+        Err(io::Error::UNSUPPORTED_PLATFORM)
+    }
+
+    pub fn keepalive_time(&self) -> io::Result<Duration> {
+        Err(io::Error::UNSUPPORTED_PLATFORM)
+    }
+
+    pub fn set_keepalive_interval(&self, _: Duration) -> io::Result<()> {
+        Err(io::Error::UNSUPPORTED_PLATFORM)
+    }
+
+    pub fn keepalive_interval(&self) -> io::Result<Duration> {
+        Err(io::Error::UNSUPPORTED_PLATFORM)
+    }
+
+    pub fn set_keepalive_retries(&self, _: u32) -> io::Result<()> {
+        Err(io::Error::UNSUPPORTED_PLATFORM)
+    }
+
+    pub fn keepalive_retries(&self) -> io::Result<u32> {
+        Err(io::Error::UNSUPPORTED_PLATFORM)
+    }
+
     pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
         let mut nonblocking: i32 = if nonblocking { 1 } else { 0 };
         cvt(unsafe {
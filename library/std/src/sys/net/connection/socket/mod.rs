@@ -10,7 +10,7 @@
 use crate::sys::helpers::run_with_cstr;
 use crate::sys::net::connection::each_addr;
 use crate::sys::{AsInner, FromInner};
-use crate::time::Duration;
+use crate::time::{Duration, Instant};
 use crate::{cmp, fmt, mem, ptr};
 
 cfg_select! {
@@ -383,6 +383,78 @@ pub fn connect_timeout(addr: &SocketAddr, timeout: Duration) -> io::Result<TcpSt
         Ok(TcpStream { inner: sock })
     }
 
+    /// Connects to the first of `addrs` to accept a connection within `timeout`.
+    ///
+    /// A new attempt is started every `attempt_delay`, or as soon as every attempt started so far
+    /// has failed, while the earlier ones are still in progress. The attempts that didn't win are
+    /// closed before returning.
+    #[cfg(any(target_family = "unix", target_os = "wasi", target_os = "windows"))]
+    pub fn connect_racing(
+        addrs: &[SocketAddr],
+        attempt_delay: Duration,
+        timeout: Duration,
+    ) -> io::Result<TcpStream> {
+        init();
+
+        let deadline = Instant::now().checked_add(timeout);
+        let mut addrs = addrs.iter();
+        let mut attempts = Vec::new();
+        let mut last_err = None;
+        let mut next_start = Instant::now();
+        loop {
+            let now = Instant::now();
+            if deadline.is_some_and(|deadline| now >= deadline) {
+                return Err(io::const_error!(io::ErrorKind::TimedOut, "connection timed out"));
+            }
+
+            if now >= next_start || attempts.is_empty() {
+                if let Some(addr) = addrs.next() {
+                    next_start = now + attempt_delay;
+                    let sock = match Socket::new(addr_family(addr), c::SOCK_STREAM) {
+                        Ok(sock) => sock,
+                        Err(e) => {
+                            last_err = Some(e);
+                            continue;
+                        }
+                    };
+                    match sock.connect_nonblocking(addr) {
+                        Ok(true) => {
+                            sock.set_nonblocking(false)?;
+                            return Ok(TcpStream { inner: sock });
+                        }
+                        Ok(false) => attempts.push(sock),
+                        Err(e) => last_err = Some(e),
+                    }
+                    continue;
+                } else if attempts.is_empty() {
+                    return Err(last_err.unwrap_or(io::Error::NO_ADDRESSES));
+                }
+            }
+
+            // Wait for an attempt to finish, until it's time to start the next one.
+            let wake = if addrs.as_slice().is_empty() {
+                deadline
+            } else {
+                Some(deadline.map_or(next_start, |deadline| cmp::min(next_start, deadline)))
+            };
+            let socks: Vec<&Socket> = attempts.iter().collect();
+            let timeout = wake.map(|wake| wake.saturating_duration_since(now));
+            let done = Socket::poll_connect(&socks, timeout)?;
+            for &(i, ref result) in &done {
+                if result.is_ok() {
+                    // Dropping the other attempts closes them.
+                    let sock = attempts.swap_remove(i);
+                    sock.set_nonblocking(false)?;
+                    return Ok(TcpStream { inner: sock });
+                }
+            }
+            for (i, result) in done.into_iter().rev() {
+                attempts.remove(i);
+                last_err = result.err();
+            }
+        }
+    }
+
     #[inline]
     pub fn socket(&self) -> &Socket {
         &self.inner
@@ -895,3 +967,161 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         res.field(name, &self.inner.as_raw()).finish()
     }
 }
+
+////////////////////////////////////////////////////////////////////////////////
+// Socket builders
+////////////////////////////////////////////////////////////////////////////////
+
+/// A socket that has not been turned into a `TcpStream`, `TcpListener` or
+/// `UdpSocket` yet, so that options can be set before it is bound or connected.
+pub struct SocketBuilder {
+    inner: Socket,
+}
+
+impl SocketBuilder {
+    pub fn new_tcp(v6: bool) -> io::Result<SocketBuilder> {
+        init();
+        let family = if v6 { c::AF_INET6 } else { c::AF_INET };
+        Ok(SocketBuilder { inner: Socket::new(family, c::SOCK_STREAM)? })
+    }
+
+    pub fn new_udp(v6: bool) -> io::Result<SocketBuilder> {
+        init();
+        let family = if v6 { c::AF_INET6 } else { c::AF_INET };
+        Ok(SocketBuilder { inner: Socket::new(family, c::SOCK_DGRAM)? })
+    }
+
+    pub fn set_reuse_address(&self, reuse: bool) -> io::Result<()> {
+        unsafe { setsockopt(&self.inner, c::SOL_SOCKET, c::SO_REUSEADDR, reuse as c_int) }
+    }
+
+    pub fn reuse_address(&self) -> io::Result<bool> {
+        let raw: c_int = unsafe { getsockopt(&self.inner, c::SOL_SOCKET, c::SO_REUSEADDR)? };
+        Ok(raw != 0)
+    }
+
+    pub fn set_reuse_port(&self, reuse: bool) -> io::Result<()> {
+        self.inner.set_reuse_port(reuse)
+    }
+
+    pub fn reuse_port(&self) -> io::Result<bool> {
+        self.inner.reuse_port()
+    }
+
+    pub fn set_only_v6(&self, only_v6: bool) -> io::Result<()> {
+        unsafe { setsockopt(&self.inner, c::IPPROTO_IPV6, c::IPV6_V6ONLY, only_v6 as c_int) }
+    }
+
+    pub fn only_v6(&self) -> io::Result<bool> {
+        let raw: c_int = unsafe { getsockopt(&self.inner, c::IPPROTO_IPV6, c::IPV6_V6ONLY)? };
+        Ok(raw != 0)
+    }
+
+    pub fn set_send_buffer_size(&self, size: usize) -> io::Result<()> {
+        self.inner.set_send_buffer_size(size)
+    }
+
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        self.inner.send_buffer_size()
+    }
+
+    pub fn set_recv_buffer_size(&self, size: usize) -> io::Result<()> {
+        self.inner.set_recv_buffer_size(size)
+    }
+
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        self.inner.recv_buffer_size()
+    }
+
+    pub fn set_keepalive(&self, keepalive: bool) -> io::Result<()> {
+        self.inner.set_keepalive(keepalive)
+    }
+
+    pub fn keepalive(&self) -> io::Result<bool> {
+        self.inner.keepalive()
+    }
+
+    pub fn set_keepalive_time(&self, time: Duration) -> io::Result<()> {
+        self.inner.set_keepalive_time(time)
+    }
+
+    pub fn keepalive_time(&self) -> io::Result<Duration> {
+        self.inner.keepalive_time()
+    }
+
+    pub fn set_keepalive_interval(&self, interval: Duration) -> io::Result<()> {
+        self.inner.set_keepalive_interval(interval)
+    }
+
+    pub fn keepalive_interval(&self) -> io::Result<Duration> {
+        self.inner.keepalive_interval()
+    }
+
+    pub fn set_keepalive_retries(&self, retries: u32) -> io::Result<()> {
+        self.inner.set_keepalive_retries(retries)
+    }
+
+    pub fn keepalive_retries(&self) -> io::Result<u32> {
+        self.inner.keepalive_retries()
+    }
+
+    pub fn set_nodelay(&self, nodelay: bool) -> io::Result<()> {
+        self.inner.set_nodelay(nodelay)
+    }
+
+    pub fn nodelay(&self) -> io::Result<bool> {
+        self.inner.nodelay()
+    }
+
+    pub fn set_broadcast(&self, broadcast: bool) -> io::Result<()> {
+        unsafe { setsockopt(&self.inner, c::SOL_SOCKET, c::SO_BROADCAST, broadcast as c_int) }
+    }
+
+    pub fn broadcast(&self) -> io::Result<bool> {
+        let raw: c_int = unsafe { getsockopt(&self.inner, c::SOL_SOCKET, c::SO_BROADCAST)? };
+        Ok(raw != 0)
+    }
+
+    pub fn bind(&self, addr: &SocketAddr) -> io::Result<()> {
+        let (addr, len) = socket_addr_to_c(addr);
+        cvt(unsafe { c::bind(self.inner.as_raw(), addr.as_ptr(), len as _) })?;
+        Ok(())
+    }
+
+    pub fn socket_addr(&self) -> io::Result<SocketAddr> {
+        unsafe { sockname(|buf, len| c::getsockname(self.inner.as_raw(), buf, len)) }
+    }
+
+    pub fn listen(self, backlog: u32) -> io::Result<TcpListener> {
+        let backlog = cmp::min(backlog, c_int::MAX as u32) as c_int;
+        cvt(unsafe { c::listen(self.inner.as_raw(), backlog) })?;
+        Ok(TcpListener { inner: self.inner })
+    }
+
+    pub fn connect(self, addr: &SocketAddr) -> io::Result<TcpStream> {
+        self.inner.connect(addr)?;
+        Ok(TcpStream { inner: self.inner })
+    }
+
+    pub fn connect_timeout(self, addr: &SocketAddr, timeout: Duration) -> io::Result<TcpStream> {
+        self.inner.connect_timeout(addr, timeout)?;
+        Ok(TcpStream { inner: self.inner })
+    }
+
+    pub fn into_udp_socket(self) -> UdpSocket {
+        UdpSocket { inner: self.inner }
+    }
+}
+
+impl fmt::Debug for SocketBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut res = f.debug_struct("SocketBuilder");
+
+        if let Ok(addr) = self.socket_addr() {
+            res.field("addr", &addr);
+        }
+
+        let name = if cfg!(windows) { "socket" } else { "fd" };
+        res.field(name, &self.inner.as_raw()).finish()
+    }
+}
@@ -355,6 +355,54 @@ pub fn nodelay(&self) -> io::Result<bool> {
         Ok(raw != 0)
     }
 
+    pub fn set_reuse_port(&self, _: bool) -> io::Result<()> {
+        unsupported()
+    }
+
+    pub fn reuse_port(&self) -> io::Result<bool> {
+        unsupported()
+    }
+
+    pub fn set_send_buffer_size(&self, _: usize) -> io::Result<()> {
+        unsupported()
+    }
+
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        unsupported()
+    }
+
+    pub fn set_recv_buffer_size(&self, _: usize) -> io::Result<()> {
+        unsupported()
+    }
+
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        unsupported()
+    }
+
+    pub fn set_keepalive_time(&self, _: Duration) -> io::Result<()> {
+        unsupported()
+    }
+
+    pub fn keepalive_time(&self) -> io::Result<Duration> {
+        unsupported()
+    }
+
+    pub fn set_keepalive_interval(&self, _: Duration) -> io::Result<()> {
+        unsupported()
+    }
+
+    pub fn keepalive_interval(&self) -> io::Result<Duration> {
+        unsupported()
+    }
+
+    pub fn set_keepalive_retries(&self, _: u32) -> io::Result<()> {
+        unsupported()
+    }
+
+    pub fn keepalive_retries(&self) -> io::Result<u32> {
+        unsupported()
+    }
+
     pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
         let mut nonblocking = nonblocking as c_int;
         cvt(unsafe {
@@ -21,6 +21,61 @@
     }
 }
 
+cfg_select! {
+    any(
+        target_os = "android",
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "openbsd",
+        target_os = "netbsd",
+        target_os = "fuchsia",
+        target_vendor = "apple",
+    ) => {
+        const SO_REUSEPORT: Option<c_int> = Some(libc::SO_REUSEPORT);
+    }
+    _ => {
+        const SO_REUSEPORT: Option<c_int> = None;
+    }
+}
+
+cfg_select! {
+    target_os = "wasi" => {
+        const SO_SNDBUF: Option<c_int> = None;
+        const SO_RCVBUF: Option<c_int> = None;
+    }
+    _ => {
+        const SO_SNDBUF: Option<c_int> = Some(libc::SO_SNDBUF);
+        const SO_RCVBUF: Option<c_int> = Some(libc::SO_RCVBUF);
+    }
+}
+
+cfg_select! {
+    any(
+        target_os = "android",
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "illumos",
+        target_os = "fuchsia",
+    ) => {
+        const TCP_KEEPIDLE: Option<c_int> = Some(libc::TCP_KEEPIDLE);
+        const TCP_KEEPINTVL: Option<c_int> = Some(libc::TCP_KEEPINTVL);
+        const TCP_KEEPCNT: Option<c_int> = Some(libc::TCP_KEEPCNT);
+    }
+    target_vendor = "apple" => {
+        const TCP_KEEPIDLE: Option<c_int> = Some(libc::TCP_KEEPALIVE);
+        const TCP_KEEPINTVL: Option<c_int> = Some(libc::TCP_KEEPINTVL);
+        const TCP_KEEPCNT: Option<c_int> = Some(libc::TCP_KEEPCNT);
+    }
+    _ => {
+        const TCP_KEEPIDLE: Option<c_int> = None;
+        const TCP_KEEPINTVL: Option<c_int> = None;
+        const TCP_KEEPCNT: Option<c_int> = None;
+    }
+}
+
 pub(super) use libc as netc;
 
 use super::{socket_addr_from_c, socket_addr_to_c};
@@ -240,6 +295,84 @@ pub fn connect_timeout(&self, addr: &SocketAddr, timeout: Duration) -> io::Resul
         }
     }
 
+    /// Starts connecting to `addr`, leaving the socket in nonblocking mode.
+    ///
+    /// Returns whether the connection was established right away. Otherwise, wait for it with
+    /// [`Socket::poll_connect`].
+    pub fn connect_nonblocking(&self, addr: &SocketAddr) -> io::Result<bool> {
+        self.set_nonblocking(true)?;
+        let r = unsafe {
+            let (addr, len) = socket_addr_to_c(addr);
+            cvt(libc::connect(self.as_raw_fd(), addr.as_ptr(), len))
+        };
+        match r {
+            Ok(_) => Ok(true),
+            Err(ref e) if e.raw_os_error() == Some(libc::EINPROGRESS) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Waits until at least one of `sockets`, which are connecting as started by
+    /// [`Socket::connect_nonblocking`], is done, or until `timeout` elapses.
+    ///
+    /// Returns the index and outcome of every connection that is done.
+    pub fn poll_connect(
+        sockets: &[&Socket],
+        timeout: Option<Duration>,
+    ) -> io::Result<Vec<(usize, io::Result<()>)>> {
+        let mut pollfds: Vec<_> = sockets
+            .iter()
+            .map(|socket| libc::pollfd {
+                fd: socket.as_raw_fd(),
+                events: libc::POLLOUT,
+                revents: 0,
+            })
+            .collect();
+        let timeout = match timeout {
+            Some(timeout) => {
+                let timeout = timeout
+                    .as_secs()
+                    .saturating_mul(1_000)
+                    .saturating_add(timeout.subsec_nanos() as u64 / 1_000_000);
+                cmp::min(cmp::max(timeout, 1), c_int::MAX as u64) as c_int
+            }
+            None => -1,
+        };
+
+        match unsafe { libc::poll(pollfds.as_mut_ptr(), pollfds.len() as _, timeout) } {
+            -1 => {
+                let err = io::Error::last_os_error();
+                if err.is_interrupted() { Ok(Vec::new()) } else { Err(err) }
+            }
+            _ => {
+                let mut done = Vec::new();
+                for (i, pollfd) in pollfds.iter().enumerate() {
+                    if pollfd.revents == 0 {
+                        continue;
+                    }
+                    // See `connect_timeout` for why this doesn't look for write readiness.
+                    let result = if cfg!(target_os = "vxworks")
+                        || pollfd.revents & (libc::POLLHUP | libc::POLLERR) != 0
+                    {
+                        match sockets[i].take_error() {
+                            Ok(Some(e)) => Err(e),
+                            Ok(None) if cfg!(target_os = "vxworks") => Ok(()),
+                            Ok(None) => Err(io::const_error!(
+                                io::ErrorKind::Uncategorized,
+                                "no error set after POLLHUP",
+                            )),
+                            Err(e) => Err(e),
+                        }
+                    } else {
+                        Ok(())
+                    };
+                    done.push((i, result));
+                }
+                Ok(done)
+            }
+        }
+    }
+
     pub fn accept(&self, storage: *mut sockaddr, len: *mut socklen_t) -> io::Result<Socket> {
         // Unfortunately the only known way right now to accept a socket and
         // atomically set the CLOEXEC flag is to use the `accept4` syscall on
@@ -476,6 +609,71 @@ pub fn nodelay(&self) -> io::Result<bool> {
         Ok(raw != 0)
     }
 
+    pub fn set_reuse_port(&self, reuse: bool) -> io::Result<()> {
+        self.set_int_option(libc::SOL_SOCKET, SO_REUSEPORT, reuse as c_int)
+    }
+
+    pub fn reuse_port(&self) -> io::Result<bool> {
+        Ok(self.int_option(libc::SOL_SOCKET, SO_REUSEPORT)? != 0)
+    }
+
+    pub fn set_send_buffer_size(&self, size: usize) -> io::Result<()> {
+        let size = cmp::min(size, c_int::MAX as usize) as c_int;
+        self.set_int_option(libc::SOL_SOCKET, SO_SNDBUF, size)
+    }
+
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        Ok(self.int_option(libc::SOL_SOCKET, SO_SNDBUF)? as usize)
+    }
+
+    pub fn set_recv_buffer_size(&self, size: usize) -> io::Result<()> {
+        let size = cmp::min(size, c_int::MAX as usize) as c_int;
+        self.set_int_option(libc::SOL_SOCKET, SO_RCVBUF, size)
+    }
+
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        Ok(self.int_option(libc::SOL_SOCKET, SO_RCVBUF)? as usize)
+    }
+
+    pub fn set_keepalive_time(&self, time: Duration) -> io::Result<()> {
+        let secs = cmp::min(time.as_secs(), c_int::MAX as u64) as c_int;
+        self.set_int_option(libc::IPPROTO_TCP, TCP_KEEPIDLE, secs)
+    }
+
+    pub fn keepalive_time(&self) -> io::Result<Duration> {
+        Ok(Duration::from_secs(self.int_option(libc::IPPROTO_TCP, TCP_KEEPIDLE)? as u64))
+    }
+
+    pub fn set_keepalive_interval(&self, interval: Duration) -> io::Result<()> {
+        let secs = cmp::min(interval.as_secs(), c_int::MAX as u64) as c_int;
+        self.set_int_option(libc::IPPROTO_TCP, TCP_KEEPINTVL, secs)
+    }
+
+    pub fn keepalive_interval(&self) -> io::Result<Duration> {
+        Ok(Duration::from_secs(self.int_option(libc::IPPROTO_TCP, TCP_KEEPINTVL)? as u64))
+    }
+
+    pub fn set_keepalive_retries(&self, retries: u32) -> io::Result<()> {
+        let retries = cmp::min(retries, c_int::MAX as u32) as c_int;
+        self.set_int_option(libc::IPPROTO_TCP, TCP_KEEPCNT, retries)
+    }
+
+    pub fn keepalive_retries(&self) -> io::Result<u32> {
+        Ok(self.int_option(libc::IPPROTO_TCP, TCP_KEEPCNT)? as u32)
+    }
+
+    /// Sets an integer socket option that may not exist on this platform.
+    fn set_int_option(&self, level: c_int, option: Option<c_int>, value: c_int) -> io::Result<()> {
+        let Some(option) = option else { return Err(io::Error::UNSUPPORTED_PLATFORM) };
+        unsafe { setsockopt(self, level, option, value) }
+    }
+
+    /// Gets an integer socket option that may not exist on this platform.
+    fn int_option(&self, level: c_int, option: Option<c_int>) -> io::Result<c_int> {
+        let Some(option) = option else { return Err(io::Error::UNSUPPORTED_PLATFORM) };
+        unsafe { getsockopt(self, level, option) }
+    }
+
     #[cfg(any(target_os = "android", target_os = "linux", target_os = "cygwin"))]
     pub fn set_quickack(&self, quickack: bool) -> io::Result<()> {
         unsafe { setsockopt(self, libc::IPPROTO_TCP, libc::TCP_QUICKACK, quickack as c_int) }
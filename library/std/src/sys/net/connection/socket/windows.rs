@@ -30,7 +30,7 @@ pub(super) mod netc {
         ADDRESS_FAMILY as sa_family_t, ADDRINFOA as addrinfo, IP_ADD_MEMBERSHIP,
         IP_DROP_MEMBERSHIP, IP_MULTICAST_LOOP, IP_MULTICAST_TTL, IP_TTL, IPPROTO_IP, IPPROTO_IPV6,
         IPV6_ADD_MEMBERSHIP, IPV6_DROP_MEMBERSHIP, IPV6_MULTICAST_LOOP, IPV6_V6ONLY, SO_BROADCAST,
-        SO_RCVTIMEO, SO_SNDTIMEO, SOCK_DGRAM, SOCK_STREAM, SOCKADDR as sockaddr,
+        SO_RCVTIMEO, SO_REUSEADDR, SO_SNDTIMEO, SOCK_DGRAM, SOCK_STREAM, SOCKADDR as sockaddr,
         SOCKADDR_STORAGE as sockaddr_storage, SOL_SOCKET, bind, connect, freeaddrinfo, getpeername,
         getsockname, getsockopt, listen, setsockopt,
     };
@@ -212,6 +212,67 @@ pub fn connect_timeout(&self, addr: &SocketAddr, timeout: Duration) -> io::Resul
         }
     }
 
+    /// Starts connecting to `addr`, leaving the socket in nonblocking mode.
+    ///
+    /// Returns whether the connection was established right away. Otherwise, wait for it with
+    /// [`Socket::poll_connect`].
+    pub fn connect_nonblocking(&self, addr: &SocketAddr) -> io::Result<bool> {
+        self.set_nonblocking(true)?;
+        match self.connect(addr) {
+            Ok(()) => Ok(true),
+            Err(ref error) if error.kind() == io::ErrorKind::WouldBlock => Ok(false),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Waits until at least one of `sockets`, which are connecting as started by
+    /// [`Socket::connect_nonblocking`], is done, or until `timeout` elapses.
+    ///
+    /// Returns the index and outcome of every connection that is done. Only as many sockets as
+    /// fit in an `FD_SET` are waited for, starting from the first one.
+    pub fn poll_connect(
+        sockets: &[&Socket],
+        timeout: Option<Duration>,
+    ) -> io::Result<Vec<(usize, io::Result<()>)>> {
+        let mut fds = unsafe { mem::zeroed::<c::FD_SET>() };
+        for (slot, socket) in fds.fd_array.iter_mut().zip(sockets) {
+            *slot = socket.as_raw();
+            fds.fd_count += 1;
+        }
+        let mut writefds = fds;
+        let mut errorfds = fds;
+
+        let timeout = timeout.map(|timeout| {
+            let mut timeout = c::TIMEVAL {
+                tv_sec: cmp::min(timeout.as_secs(), c_long::MAX as u64) as c_long,
+                tv_usec: timeout.subsec_micros() as c_long,
+            };
+            if timeout.tv_sec == 0 && timeout.tv_usec == 0 {
+                timeout.tv_usec = 1;
+            }
+            timeout
+        });
+        let timeout = timeout.as_ref().map_or(ptr::null(), |timeout| timeout as *const _);
+
+        cvt(unsafe { c::select(1, ptr::null_mut(), &mut writefds, &mut errorfds, timeout) })?;
+
+        let contains = |set: &c::FD_SET, socket: c::SOCKET| {
+            set.fd_array[..set.fd_count as usize].contains(&socket)
+        };
+        let mut done = Vec::new();
+        for (i, socket) in sockets.iter().enumerate().take(fds.fd_count as usize) {
+            if contains(&errorfds, socket.as_raw()) {
+                let error = socket.take_error()?.unwrap_or_else(|| {
+                    io::const_error!(io::ErrorKind::Uncategorized, "no error set after select")
+                });
+                done.push((i, Err(error)));
+            } else if contains(&writefds, socket.as_raw()) {
+                done.push((i, Ok(())));
+            }
+        }
+        Ok(done)
+    }
+
     pub fn accept(&self, storage: *mut c::SOCKADDR, len: *mut c_int) -> io::Result<Socket> {
         let socket = unsafe { c::accept(self.as_raw(), storage, len) };
 
@@ -444,6 +505,64 @@ pub fn nodelay(&self) -> io::Result<bool> {
         Ok(raw != 0)
     }
 
+    pub fn set_reuse_port(&self, _: bool) -> io::Result<()> {
+        Err(io::Error::UNSUPPORTED_PLATFORM)
+    }
+
+    pub fn reuse_port(&self) -> io::Result<bool> {
+        Err(io::Error::UNSUPPORTED_PLATFORM)
+    }
+
+    pub fn set_send_buffer_size(&self, size: usize) -> io::Result<()> {
+        let size = cmp::min(size, c_int::MAX as usize) as c_int;
+        unsafe { setsockopt(self, c::SOL_SOCKET, c::SO_SNDBUF, size) }
+    }
+
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        let raw: c_int = unsafe { getsockopt(self, c::SOL_SOCKET, c::SO_SNDBUF)? };
+        Ok(raw as usize)
+    }
+
+    pub fn set_recv_buffer_size(&self, size: usize) -> io::Result<()> {
+        let size = cmp::min(size, c_int::MAX as usize) as c_int;
+        unsafe { setsockopt(self, c::SOL_SOCKET, c::SO_RCVBUF, size) }
+    }
+
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        let raw: c_int = unsafe { getsockopt(self, c::SOL_SOCKET, c::SO_RCVBUF)? };
+        Ok(raw as usize)
+    }
+
+    // The `TCP_KEEP*` options are available since Windows 10, version 1709.
+    pub fn set_keepalive_time(&self, time: Duration) -> io::Result<()> {
+        let secs = cmp::min(time.as_secs(), c_ulong::MAX as u64) as c_ulong;
+        unsafe { setsockopt(self, c::IPPROTO_TCP, c::TCP_KEEPIDLE, secs) }
+    }
+
+    pub fn keepalive_time(&self) -> io::Result<Duration> {
+        let raw: c_ulong = unsafe { getsockopt(self, c::IPPROTO_TCP, c::TCP_KEEPIDLE)? };
+        Ok(Duration::from_secs(raw as u64))
+    }
+
+    pub fn set_keepalive_interval(&self, interval: Duration) -> io::Result<()> {
+        let secs = cmp::min(interval.as_secs(), c_ulong::MAX as u64) as c_ulong;
+        unsafe { setsockopt(self, c::IPPROTO_TCP, c::TCP_KEEPINTVL, secs) }
+    }
+
+    pub fn keepalive_interval(&self) -> io::Result<Duration> {
+        let raw: c_ulong = unsafe { getsockopt(self, c::IPPROTO_TCP, c::TCP_KEEPINTVL)? };
+        Ok(Duration::from_secs(raw as u64))
+    }
+
+    pub fn set_keepalive_retries(&self, retries: u32) -> io::Result<()> {
+        unsafe { setsockopt(self, c::IPPROTO_TCP, c::TCP_KEEPCNT, retries as c_ulong) }
+    }
+
+    pub fn keepalive_retries(&self) -> io::Result<u32> {
+        let raw: c_ulong = unsafe { getsockopt(self, c::IPPROTO_TCP, c::TCP_KEEPCNT)? };
+        Ok(raw as u32)
+    }
+
     pub fn take_error(&self) -> io::Result<Option<io::Error>> {
         let raw: c_int = unsafe { getsockopt(self, c::SOL_SOCKET, c::SO_ERROR)? };
         if raw == 0 { Ok(None) } else { Ok(Some(io::Error::from_raw_os_error(raw as i32))) }
@@ -0,0 +1,139 @@
+//! A `SocketBuilder` for platforms whose networking support does not allow
+//! configuring sockets before they are bound or connected.
+
+use super::{TcpListener, TcpStream, UdpSocket};
+use crate::fmt;
+use crate::io;
+use crate::net::SocketAddr;
+use crate::sys::unsupported;
+use crate::time::Duration;
+
+pub struct SocketBuilder(!);
+
+impl SocketBuilder {
+    pub fn new_tcp(_: bool) -> io::Result<SocketBuilder> {
+        unsupported()
+    }
+
+    pub fn new_udp(_: bool) -> io::Result<SocketBuilder> {
+        unsupported()
+    }
+
+    pub fn set_reuse_address(&self, _: bool) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn reuse_address(&self) -> io::Result<bool> {
+        self.0
+    }
+
+    pub fn set_reuse_port(&self, _: bool) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn reuse_port(&self) -> io::Result<bool> {
+        self.0
+    }
+
+    pub fn set_only_v6(&self, _: bool) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn only_v6(&self) -> io::Result<bool> {
+        self.0
+    }
+
+    pub fn set_send_buffer_size(&self, _: usize) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        self.0
+    }
+
+    pub fn set_recv_buffer_size(&self, _: usize) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        self.0
+    }
+
+    pub fn set_keepalive(&self, _: bool) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn keepalive(&self) -> io::Result<bool> {
+        self.0
+    }
+
+    pub fn set_keepalive_time(&self, _: Duration) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn keepalive_time(&self) -> io::Result<Duration> {
+        self.0
+    }
+
+    pub fn set_keepalive_interval(&self, _: Duration) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn keepalive_interval(&self) -> io::Result<Duration> {
+        self.0
+    }
+
+    pub fn set_keepalive_retries(&self, _: u32) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn keepalive_retries(&self) -> io::Result<u32> {
+        self.0
+    }
+
+    pub fn set_nodelay(&self, _: bool) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn nodelay(&self) -> io::Result<bool> {
+        self.0
+    }
+
+    pub fn set_broadcast(&self, _: bool) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn broadcast(&self) -> io::Result<bool> {
+        self.0
+    }
+
+    pub fn bind(&self, _: &SocketAddr) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn socket_addr(&self) -> io::Result<SocketAddr> {
+        self.0
+    }
+
+    pub fn listen(self, _: u32) -> io::Result<TcpListener> {
+        self.0
+    }
+
+    pub fn connect(self, _: &SocketAddr) -> io::Result<TcpStream> {
+        self.0
+    }
+
+    pub fn connect_timeout(self, _: &SocketAddr, _: Duration) -> io::Result<TcpStream> {
+        self.0
+    }
+
+    pub fn into_udp_socket(self) -> UdpSocket {
+        self.0
+    }
+}
+
+impl fmt::Debug for SocketBuilder {
+    fn fmt(&self, _f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0
+    }
+}
@@ -3202,7 +3202,10 @@ fn default() -> Self {
 pub const SO_ERROR: i32 = 4103i32;
 pub const SO_KEEPALIVE: i32 = 8i32;
 pub const SO_LINGER: i32 = 128i32;
+pub const SO_RCVBUF: i32 = 4098i32;
 pub const SO_RCVTIMEO: i32 = 4102i32;
+pub const SO_REUSEADDR: i32 = 4i32;
+pub const SO_SNDBUF: i32 = 4097i32;
 pub const SO_SNDTIMEO: i32 = 4101i32;
 pub const SPECIFIC_RIGHTS_ALL: FILE_ACCESS_RIGHTS = 65535u32;
 #[repr(C)]
@@ -3330,6 +3333,9 @@ pub struct SYSTEM_INFO_0_0 {
     pub wProcessorArchitecture: PROCESSOR_ARCHITECTURE,
     pub wReserved: u16,
 }
+pub const TCP_KEEPCNT: i32 = 16i32;
+pub const TCP_KEEPIDLE: i32 = 3i32;
+pub const TCP_KEEPINTVL: i32 = 17i32;
 pub const TCP_NODELAY: i32 = 1i32;
 pub const THREAD_CREATE_RUN_IMMEDIATELY: THREAD_CREATION_FLAGS = 0u32;
 pub const THREAD_CREATE_SUSPENDED: THREAD_CREATION_FLAGS = 4u32;
@@ -38,6 +38,32 @@
     getpid, read_output,
 };
 
+/// Waits for a child to exit by polling `try_wait`, for platforms that have no way to block on a
+/// child with a timeout.
+// Unused on the platforms that can block with a timeout, and on those without processes at all.
+#[allow(dead_code)]
+fn wait_deadline_by_polling<S>(
+    mut try_wait: impl FnMut() -> crate::io::Result<Option<S>>,
+    deadline: crate::time::Instant,
+) -> crate::io::Result<Option<S>> {
+    use crate::time::{Duration, Instant};
+
+    // Poll often at first, so that short-lived children are noticed quickly, and then back off.
+    const MAX_INTERVAL: Duration = Duration::from_millis(50);
+    let mut interval = Duration::from_millis(1);
+    loop {
+        if let Some(status) = try_wait()? {
+            return Ok(Some(status));
+        }
+        let now = Instant::now();
+        if now >= deadline {
+            return Ok(None);
+        }
+        crate::thread::sleep(interval.min(deadline - now));
+        interval = (interval * 2).min(MAX_INTERVAL);
+    }
+}
+
 #[cfg(any(
     all(
         target_family = "unix",
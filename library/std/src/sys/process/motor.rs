@@ -9,6 +9,7 @@
 use crate::process::StdioPipes;
 use crate::sys::fs::File;
 use crate::sys::{AsInner, FromInner, map_motor_error};
+use crate::time::Instant;
 use crate::{fmt, io};
 
 pub enum Stdio {
@@ -286,6 +287,10 @@ pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
         }
     }
 
+    pub fn wait_deadline(&mut self, deadline: Instant) -> io::Result<Option<ExitStatus>> {
+        super::wait_deadline_by_polling(|| self.try_wait(), deadline)
+    }
+
     #[allow(unused)]
     pub fn handle(&self) -> u64 {
         self.handle
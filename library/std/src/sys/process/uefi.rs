@@ -11,6 +11,7 @@
 use crate::sys::io::error_string;
 use crate::sys::pal::helpers;
 use crate::sys::unsupported;
+use crate::time::Instant;
 use crate::{fmt, io};
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -325,6 +326,10 @@ pub fn wait(&mut self) -> io::Result<ExitStatus> {
     pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
         self.0
     }
+
+    pub fn wait_deadline(&mut self, _deadline: Instant) -> io::Result<Option<ExitStatus>> {
+        self.0
+    }
 }
 
 pub struct CommandArgs<'a> {
@@ -103,6 +103,7 @@ pub struct Command {
     create_pidfd: bool,
     pgroup: Option<pid_t>,
     setsid: bool,
+    kill_on_drop: bool,
 }
 
 // passed to do_exec() with configuration of what the child stdio should look
@@ -183,6 +184,7 @@ pub fn new(program: &OsStr) -> Command {
             create_pidfd: false,
             pgroup: None,
             setsid: false,
+            kill_on_drop: false,
         }
     }
 
@@ -221,6 +223,9 @@ pub fn chroot(&mut self, dir: &Path) {
     pub fn setsid(&mut self, setsid: bool) {
         self.setsid = setsid;
     }
+    pub fn kill_on_drop(&mut self, kill_on_drop: bool) {
+        self.kill_on_drop = kill_on_drop;
+    }
 
     #[cfg(target_os = "linux")]
     pub fn create_pidfd(&mut self, val: bool) {
@@ -311,6 +316,10 @@ pub fn get_chroot(&self) -> Option<&CStr> {
     pub fn get_setsid(&self) -> bool {
         self.setsid
     }
+    #[allow(dead_code)]
+    pub fn get_kill_on_drop(&self) -> bool {
+        self.kill_on_drop
+    }
 
     pub fn get_closures(&mut self) -> &mut Vec<Box<dyn FnMut() -> io::Result<()> + Send + Sync>> {
         &mut self.closures
@@ -526,6 +535,9 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
             if self.pgroup.is_some() {
                 debug_command.field("pgroup", &self.pgroup);
             }
+            if self.kill_on_drop {
+                debug_command.field("kill_on_drop", &self.kill_on_drop);
+            }
 
             #[cfg(target_os = "linux")]
             {
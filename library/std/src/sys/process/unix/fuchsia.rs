@@ -4,7 +4,8 @@
 use crate::num::NonZero;
 use crate::process::StdioPipes;
 use crate::sys::pal::fuchsia::*;
-use crate::{fmt, io, mem, ptr};
+use crate::time::Instant;
+use crate::{fmt, io, mem, ptr, sys};
 
 ////////////////////////////////////////////////////////////////////////////////
 // Command
@@ -227,6 +228,10 @@ pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
         }
         Ok(Some(ExitStatus(proc_info.return_code)))
     }
+
+    pub fn wait_deadline(&mut self, deadline: Instant) -> io::Result<Option<ExitStatus>> {
+        sys::process::wait_deadline_by_polling(|| self.try_wait(), deadline)
+    }
 }
 
 #[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
@@ -3,6 +3,7 @@
 use crate::os::fd::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
 use crate::sys::fd::FileDesc;
 use crate::sys::{AsInner, FromInner, IntoInner, cvt};
+use crate::time::Instant;
 
 #[cfg(test)]
 mod tests;
@@ -11,15 +12,18 @@
 pub struct PidFd(FileDesc);
 
 impl PidFd {
+    pub fn open(pid: libc::pid_t) -> io::Result<PidFd> {
+        let pidfd = cvt(unsafe { libc::syscall(libc::SYS_pidfd_open, pid, 0) })?;
+        Ok(unsafe { PidFd::from_raw_fd(pidfd as RawFd) })
+    }
+
     pub fn kill(&self) -> io::Result<()> {
         self.send_signal(libc::SIGKILL)
     }
 
     #[cfg(any(test, target_env = "gnu", target_env = "musl"))]
     pub fn current_process() -> io::Result<PidFd> {
-        let pid = crate::process::id();
-        let pidfd = cvt(unsafe { libc::syscall(libc::SYS_pidfd_open, pid, 0) })?;
-        Ok(unsafe { PidFd::from_raw_fd(pidfd as RawFd) })
+        PidFd::open(crate::process::id() as libc::pid_t)
     }
 
     #[cfg(any(test, target_env = "gnu", target_env = "musl"))]
@@ -121,6 +125,27 @@ pub fn wait(&self) -> io::Result<ExitStatus> {
     pub fn try_wait(&self) -> io::Result<Option<ExitStatus>> {
         self.waitid(libc::WEXITED | libc::WNOHANG)
     }
+
+    /// Blocks until the process exits or `deadline` passes, and returns whether it exited.
+    ///
+    /// Unlike `wait`, this doesn't reap the process.
+    pub fn wait_exit_deadline(&self, deadline: Instant) -> io::Result<bool> {
+        loop {
+            let timeout = deadline.saturating_duration_since(Instant::now());
+            // Round up, so that we don't wake up just before the deadline and spin.
+            let timeout_ms = timeout.as_nanos().div_ceil(1_000_000).try_into().unwrap_or(i32::MAX);
+            // A pidfd becomes readable once its process has exited.
+            let mut pollfd =
+                libc::pollfd { fd: self.0.as_raw_fd(), events: libc::POLLIN, revents: 0 };
+            match cvt(unsafe { libc::poll(&mut pollfd, 1, timeout_ms) }) {
+                Ok(0) if Instant::now() >= deadline => return Ok(false),
+                Ok(0) => {}
+                Ok(_) => return Ok(true),
+                Err(e) if e.is_interrupted() => {}
+                Err(e) => return Err(e),
+            }
+        }
+    }
 }
 
 impl AsInner<FileDesc> for PidFd {
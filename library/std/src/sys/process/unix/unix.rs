@@ -17,6 +17,7 @@
 use crate::sys::cvt;
 #[cfg(target_os = "linux")]
 use crate::sys::process::PidFd;
+use crate::time::{Duration, Instant};
 use crate::{fmt, mem, sys};
 
 cfg_select! {
@@ -70,7 +71,8 @@ pub fn spawn(
 
         let (ours, theirs) = self.setup_io(default, needs_stdin)?;
 
-        if let Some(ret) = self.posix_spawn(&theirs, envp.as_ref())? {
+        if let Some(mut ret) = self.posix_spawn(&theirs, envp.as_ref())? {
+            ret.kill_on_drop = self.kill_on_drop_target();
             return Ok((ret, ours));
         }
 
@@ -137,7 +139,10 @@ pub fn spawn(
         // loop to handle EINTR
         loop {
             match input.read(&mut bytes) {
-                Ok(0) => return Ok((p, ours)),
+                Ok(0) => {
+                    p.kill_on_drop = self.kill_on_drop_target();
+                    return Ok((p, ours));
+                }
                 Ok(8) => {
                     let (errno, footer) = bytes.split_at(4);
                     assert_eq!(
@@ -164,6 +169,18 @@ pub fn spawn(
         }
     }
 
+    fn kill_on_drop_target(&self) -> KillOnDrop {
+        if !self.get_kill_on_drop() {
+            KillOnDrop::Nothing
+        } else if self.get_pgroup() == Some(0) || self.get_setsid() {
+            // The child leads a process group of its own, which is what the caller wants to
+            // tear down along with it.
+            KillOnDrop::ProcessGroup
+        } else {
+            KillOnDrop::Process
+        }
+    }
+
     // WatchOS and TVOS headers mark the `fork`/`exec*` functions with
     // `__WATCHOS_PROHIBITED __TVOS_PROHIBITED`, and indicate that the
     // `posix_spawn*` functions should be used instead. It isn't entirely clear
@@ -963,6 +980,16 @@ pub struct Process {
     // (e.g. the `pidfd_open` syscall was not available).
     #[cfg(target_os = "linux")]
     pidfd: Option<PidFd>,
+    // What to kill if this is dropped before the process was reaped,
+    // as requested with `CommandExt::kill_on_drop`.
+    kill_on_drop: KillOnDrop,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum KillOnDrop {
+    Nothing,
+    Process,
+    ProcessGroup,
 }
 
 impl Process {
@@ -978,12 +1005,12 @@ unsafe fn new(pid: pid_t, pidfd: pid_t) -> Self {
         use crate::sys::FromInner;
         // Safety: If `pidfd` is nonnegative, we assume it's valid and otherwise unowned.
         let pidfd = (pidfd >= 0).then(|| PidFd::from_inner(sys::fd::FileDesc::from_raw_fd(pidfd)));
-        Process { pid, status: None, pidfd }
+        Process { pid, status: None, pidfd, kill_on_drop: KillOnDrop::Nothing }
     }
 
     #[cfg(not(target_os = "linux"))]
     unsafe fn new(pid: pid_t, _pidfd: pid_t) -> Self {
-        Process { pid, status: None }
+        Process { pid, status: None, kill_on_drop: KillOnDrop::Nothing }
     }
 
     pub fn id(&self) -> u32 {
@@ -1060,6 +1087,77 @@ pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
             Ok(Some(ExitStatus::new(status)))
         }
     }
+
+    pub fn wait_deadline(&mut self, deadline: Instant) -> io::Result<Option<ExitStatus>> {
+        if let Some(status) = self.status {
+            return Ok(Some(status));
+        }
+        #[cfg(target_os = "linux")]
+        {
+            // Block on a pidfd if possible, opening one just for this if none was requested when
+            // spawning. We haven't reaped the child yet, so its pid can't have been reused.
+            let opened;
+            let pidfd = match &self.pidfd {
+                Some(pidfd) => Ok(pidfd),
+                None => {
+                    opened = PidFd::open(self.pid);
+                    opened.as_ref()
+                }
+            };
+            if let Ok(pidfd) = pidfd {
+                return if pidfd.wait_exit_deadline(deadline)? {
+                    self.wait().map(Some)
+                } else {
+                    Ok(None)
+                };
+            }
+        }
+        sys::process::wait_deadline_by_polling(|| self.try_wait(), deadline)
+    }
+}
+
+impl Process {
+    /// Kills the child as requested with `CommandExt::kill_on_drop`, and reaps it if it exits
+    /// in time. Returns its exit status if it was reaped, now or before.
+    fn kill_for_drop(&mut self) -> Option<ExitStatus> {
+        // A child killed with `SIGKILL` normally exits right away, but it may take arbitrarily
+        // long while it's stuck in an uninterruptible sleep, so don't block the drop on it.
+        const REAP_TIMEOUT: Duration = Duration::from_secs(1);
+
+        if let Some(status) = self.status {
+            return Some(status);
+        }
+        let killed = match self.kill_on_drop {
+            KillOnDrop::Nothing => return None,
+            KillOnDrop::Process => self.kill(),
+            KillOnDrop::ProcessGroup => {
+                // The group's ID is the child's pid, which can't have been reused since the child
+                // hasn't been reaped yet. Unlike signaling a group through a pidfd, this doesn't
+                // need a recent kernel.
+                match cvt(unsafe { libc::killpg(self.pid, libc::SIGKILL) }) {
+                    // The child got the signal only if it was still in its group.
+                    Ok(_) if unsafe { libc::getpgid(self.pid) } == self.pid => Ok(()),
+                    // It moved to another group since, so the group signal, if sent at all,
+                    // missed it. Signal it directly instead.
+                    _ => self.kill(),
+                }
+            }
+        };
+        // Reap the child, so that it doesn't linger as a zombie. Only wait for it if it was
+        // actually killed; otherwise it may keep running indefinitely, so just check once.
+        let reaped = if killed.is_ok() {
+            self.wait_deadline(Instant::now() + REAP_TIMEOUT)
+        } else {
+            self.try_wait()
+        };
+        reaped.ok().flatten()
+    }
+}
+
+impl Drop for Process {
+    fn drop(&mut self) {
+        self.kill_for_drop();
+    }
 }
 
 /// Unix exit statuses
@@ -1307,11 +1405,10 @@ fn pidfd(&self) -> io::Result<&os::PidFd> {
         }
 
         fn into_pidfd(mut self) -> Result<os::PidFd, Self> {
-            self.handle
-                .pidfd
-                .take()
-                .map(|fd| <os::PidFd as FromInner<imp::PidFd>>::from_inner(fd))
-                .ok_or_else(|| self)
+            let Some(fd) = self.handle.pidfd.take() else { return Err(self) };
+            // Whoever owns the pidfd now owns the process, so it mustn't be killed with the `Child`.
+            self.handle.kill_on_drop = super::KillOnDrop::Nothing;
+            Ok(<os::PidFd as FromInner<imp::PidFd>>::from_inner(fd))
         }
     }
 }
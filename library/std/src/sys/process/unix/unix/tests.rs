@@ -74,3 +74,43 @@ fn test_command_fork_no_unwind() {
             || signal == libc::SIGSEGV
     );
 }
+
+#[test]
+#[cfg_attr(all(target_vendor = "apple", not(target_os = "macos")), ignore = "no shell available")]
+fn test_kill_on_drop() {
+    use crate::sys::IntoInner;
+
+    let child = Command::new("sleep").arg("60").kill_on_drop(true).spawn().unwrap();
+    // Check what dropping the child reaps, rather than whether its pid is gone afterwards, as an
+    // unrelated process may already have been given the same pid by then.
+    let mut process = child.into_inner();
+    let status = process.kill_for_drop().expect("the killed child wasn't reaped");
+    assert_eq!(status.signal(), Some(libc::SIGKILL));
+}
+
+#[test]
+#[cfg_attr(all(target_vendor = "apple", not(target_os = "macos")), ignore = "no shell available")]
+fn test_kill_on_drop_process_group() {
+    use crate::io::{BufRead, BufReader, Read};
+    use crate::process::Stdio;
+    use crate::time::{Duration, Instant};
+
+    let mut child = Command::new("/bin/sh")
+        .arg("-c")
+        .arg("sleep 60 & echo started; wait")
+        .stdout(Stdio::piped())
+        .process_group(0)
+        .kill_on_drop(true)
+        .spawn()
+        .unwrap();
+    let mut stdout = BufReader::new(child.stdout.take().unwrap());
+    let mut line = String::new();
+    stdout.read_line(&mut line).unwrap();
+    assert_eq!(line, "started\n");
+
+    let start = Instant::now();
+    drop(child);
+    // The grandchild holds on to the pipe as well, so this only returns once it was killed too.
+    stdout.read_to_end(&mut Vec::new()).unwrap();
+    assert!(start.elapsed() < Duration::from_secs(30));
+}
@@ -5,6 +5,7 @@
 use crate::num::NonZero;
 use crate::process::StdioPipes;
 use crate::sys::pal::unsupported::*;
+use crate::time::Instant;
 
 ////////////////////////////////////////////////////////////////////////////////
 // Command
@@ -60,6 +61,10 @@ pub fn wait(&mut self) -> io::Result<ExitStatus> {
     pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
         unsupported()
     }
+
+    pub fn wait_deadline(&mut self, _deadline: Instant) -> io::Result<Option<ExitStatus>> {
+        unsupported()
+    }
 }
 
 mod wait_status;
@@ -6,6 +6,7 @@
 use crate::num::NonZero;
 use crate::process::StdioPipes;
 use crate::sys::{cvt, thread};
+use crate::time::Instant;
 use crate::{fmt, sys};
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -203,6 +204,10 @@ pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
             Ok(Some(ExitStatus::new(status)))
         }
     }
+
+    pub fn wait_deadline(&mut self, deadline: Instant) -> io::Result<Option<ExitStatus>> {
+        sys::process::wait_deadline_by_polling(|| self.try_wait(), deadline)
+    }
 }
 
 /// Unix exit statuses
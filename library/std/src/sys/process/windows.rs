@@ -22,8 +22,9 @@
 use crate::sys::fs::{File, OpenOptions};
 use crate::sys::handle::Handle;
 use crate::sys::pal::api::{self, WinError, utf16};
-use crate::sys::pal::{ensure_no_nuls, fill_utf16_buf};
+use crate::sys::pal::{dur2timeout, ensure_no_nuls, fill_utf16_buf};
 use crate::sys::{IntoInner, cvt, path, stdio};
+use crate::time::Instant;
 use crate::{cmp, env, fmt, ptr};
 
 mod child_pipe;
@@ -740,6 +741,19 @@ pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
         }
     }
 
+    pub fn wait_deadline(&mut self, deadline: Instant) -> io::Result<Option<ExitStatus>> {
+        loop {
+            let timeout = dur2timeout(deadline.saturating_duration_since(Instant::now()));
+            match unsafe { c::WaitForSingleObject(self.handle.as_raw_handle(), timeout) } {
+                c::WAIT_OBJECT_0 => return self.try_wait(),
+                // Timeouts are rounded to milliseconds, so make sure the deadline really passed.
+                c::WAIT_TIMEOUT if Instant::now() >= deadline => return Ok(None),
+                c::WAIT_TIMEOUT => {}
+                _ => return Err(io::Error::last_os_error()),
+            }
+        }
+    }
+
     pub fn handle(&self) -> &Handle {
         &self.handle
     }
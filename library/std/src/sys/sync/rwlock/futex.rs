@@ -7,6 +7,7 @@ pub struct RwLock {
     //   0: Unlocked
     //   1..=0x3FFF_FFFE: Locked by N readers
     //   0x3FFF_FFFF: Write locked
+    //   The upgradable reader, of which there is at most one, counts as 0x2000_0000 readers.
     // Bit 30: Readers are waiting on this futex.
     // Bit 31: Writers are waiting on the writer_notify futex.
     state: Futex,
@@ -20,6 +21,10 @@ pub struct RwLock {
 const WRITE_LOCKED: Primitive = MASK;
 const DOWNGRADE: Primitive = READ_LOCKED.wrapping_sub(WRITE_LOCKED); // READ_LOCKED - WRITE_LOCKED
 const MAX_READERS: Primitive = MASK - 1;
+// Large enough to tell the upgradable reader apart from plain ones, as long as there are less than
+// that many of them. Upgrading turns it into WRITE_LOCKED once it's the only reader left.
+const UPGRADABLE_READ_LOCKED: Primitive = 1 << 29;
+const UPGRADE: Primitive = WRITE_LOCKED - UPGRADABLE_READ_LOCKED;
 const READERS_WAITING: Primitive = 1 << 30;
 const WRITERS_WAITING: Primitive = 1 << 31;
 
@@ -72,6 +77,19 @@ fn is_read_lockable_after_wakeup(state: Primitive) -> bool {
         && !is_unlocked(state)
 }
 
+#[inline]
+fn is_upgradable_read_lockable(state: Primitive) -> bool {
+    // This also returns false if there already is an upgradable reader, if the lock is write
+    // locked, or if the counter could overflow if we tried to lock it.
+    state & MASK <= MAX_READERS - UPGRADABLE_READ_LOCKED
+}
+
+#[inline]
+fn is_upgradable(state: Primitive) -> bool {
+    // The upgradable reader is the only reader left.
+    state & MASK == UPGRADABLE_READ_LOCKED
+}
+
 #[inline]
 fn has_reached_max_readers(state: Primitive) -> bool {
     state & MASK == MAX_READERS
@@ -118,6 +136,12 @@ pub unsafe fn read_unlock(&self) {
         if is_unlocked(state) && has_writers_waiting(state) {
             self.wake_writer_or_readers(state);
         }
+
+        // Wake up the upgradable reader if we were the last reader besides it, in case it's
+        // waiting to upgrade. It waits alongside writers, so they all have to be woken up.
+        if is_upgradable(state) && has_writers_waiting(state) {
+            self.wake_writers();
+        }
     }
 
     #[cold]
@@ -205,6 +229,176 @@ pub unsafe fn downgrade(&self) {
         }
     }
 
+    #[inline]
+    pub fn try_upgradable_read(&self) -> bool {
+        self.state
+            .try_update(Acquire, Relaxed, |s| {
+                (is_upgradable_read_lockable(s) && is_read_lockable(s))
+                    .then(|| s + UPGRADABLE_READ_LOCKED)
+            })
+            .is_ok()
+    }
+
+    #[inline]
+    pub fn upgradable_read(&self) {
+        if !self.try_upgradable_read() {
+            self.upgradable_read_contended();
+        }
+    }
+
+    /// # Safety
+    ///
+    /// The `RwLock` must be locked by the upgradable reader in order to call this.
+    #[inline]
+    pub unsafe fn upgradable_read_unlock(&self) {
+        let state = self.state.fetch_sub(UPGRADABLE_READ_LOCKED, Release) - UPGRADABLE_READ_LOCKED;
+
+        debug_assert!(!has_readers_waiting(state) || has_writers_waiting(state));
+
+        if has_writers_waiting(state) {
+            if is_unlocked(state) {
+                self.wake_writer_or_readers(state);
+            } else {
+                // Another upgradable reader might be waiting for us, and not for the other readers.
+                self.wake_writers();
+            }
+        }
+    }
+
+    /// Turns the upgradable read lock into a plain read lock.
+    ///
+    /// # Safety
+    ///
+    /// The `RwLock` must be locked by the upgradable reader in order to call this.
+    #[inline]
+    pub unsafe fn downgrade_upgradable(&self) {
+        let state = self.state.fetch_sub(UPGRADABLE_READ_LOCKED - READ_LOCKED, Release);
+
+        // Another upgradable reader might be waiting for us.
+        if has_writers_waiting(state) {
+            self.wake_writers();
+        }
+    }
+
+    /// # Safety
+    ///
+    /// The `RwLock` must be locked by the upgradable reader in order to call this.
+    #[inline]
+    pub unsafe fn try_upgrade(&self) -> bool {
+        self.state.try_update(Acquire, Relaxed, |s| is_upgradable(s).then(|| s + UPGRADE)).is_ok()
+    }
+
+    /// # Safety
+    ///
+    /// The `RwLock` must be locked by the upgradable reader in order to call this.
+    #[inline]
+    pub unsafe fn upgrade(&self) {
+        if !unsafe { self.try_upgrade() } {
+            self.upgrade_contended();
+        }
+    }
+
+    #[cold]
+    fn upgradable_read_contended(&self) {
+        // The upgradable reader waits like a writer, since it's kept out by the same things, except
+        // for plain readers.
+        let mut state = self.spin_write();
+
+        let mut other_writers_waiting = 0;
+
+        loop {
+            if is_upgradable_read_lockable(state) {
+                match self.state.compare_exchange_weak(
+                    state,
+                    (state + UPGRADABLE_READ_LOCKED) | other_writers_waiting,
+                    Acquire,
+                    Relaxed,
+                ) {
+                    Ok(_) => return, // Locked!
+                    Err(s) => {
+                        state = s;
+                        continue;
+                    }
+                }
+            }
+
+            // Set the waiting bit indicating that we're waiting on it.
+            if !has_writers_waiting(state) {
+                if let Err(s) =
+                    self.state.compare_exchange(state, state | WRITERS_WAITING, Relaxed, Relaxed)
+                {
+                    state = s;
+                    continue;
+                }
+            }
+
+            // Writers might be waiting now too, so we should make sure we keep that bit on once we
+            // manage to lock it.
+            other_writers_waiting = WRITERS_WAITING;
+
+            // Examine the notification counter before we check if `state` has changed,
+            // to make sure we don't miss any notifications.
+            let seq = self.writer_notify.load(Acquire);
+
+            // Don't go to sleep if the lock has become available,
+            // or if the writers waiting bit is no longer set.
+            state = self.state.load(Relaxed);
+            if is_upgradable_read_lockable(state) || !has_writers_waiting(state) {
+                continue;
+            }
+
+            // Wait for the state to change.
+            futex_wait(&self.writer_notify, seq, None);
+
+            // Spin again after waking up.
+            state = self.spin_write();
+        }
+    }
+
+    #[cold]
+    fn upgrade_contended(&self) {
+        // Writers and other upgradable readers can't lock the lock while we hold it, so we only
+        // have to wait for the plain readers to leave.
+        let mut state = self.state.load(Relaxed);
+
+        loop {
+            if is_upgradable(state) {
+                // Keep the writers waiting bit on, since writers might be waiting too.
+                match self.state.compare_exchange_weak(state, state + UPGRADE, Acquire, Relaxed) {
+                    Ok(_) => return, // Locked!
+                    Err(s) => {
+                        state = s;
+                        continue;
+                    }
+                }
+            }
+
+            // Set the writers waiting bit to keep new readers out, and to be woken up by the last
+            // reader to leave. Nobody else can unset it while the lock is read-locked.
+            if !has_writers_waiting(state) {
+                if let Err(s) =
+                    self.state.compare_exchange(state, state | WRITERS_WAITING, Relaxed, Relaxed)
+                {
+                    state = s;
+                    continue;
+                }
+            }
+
+            // Examine the notification counter before we check if `state` has changed,
+            // to make sure we don't miss any notifications.
+            let seq = self.writer_notify.load(Acquire);
+
+            state = self.state.load(Relaxed);
+            if is_upgradable(state) {
+                continue;
+            }
+
+            // Wait for the state to change.
+            futex_wait(&self.writer_notify, seq, None);
+            state = self.state.load(Relaxed);
+        }
+    }
+
     #[cold]
     fn write_contended(&self) {
         let mut state = self.spin_write();
@@ -329,6 +523,14 @@ fn wake_writer(&self) -> bool {
         // well in case both readers and writers were waiting.
     }
 
+    /// Wakes up all writers, and the upgradable readers waiting alongside them, without unsetting
+    /// the writers waiting bit. The ones that still can't lock the lock go back to sleep.
+    #[cold]
+    fn wake_writers(&self) {
+        self.writer_notify.fetch_add(1, Release);
+        futex_wake_all(&self.writer_notify);
+    }
+
     /// Spin for a while, but stop directly at the given condition.
     #[inline]
     fn spin_until(&self, f: impl Fn(Primitive) -> bool) -> Primitive {
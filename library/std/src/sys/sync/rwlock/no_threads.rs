@@ -74,4 +74,48 @@ pub unsafe fn write_unlock(&self) {
     pub unsafe fn downgrade(&self) {
         assert_eq!(self.mode.replace(1), -1, "rwlock has not been locked for writing");
     }
+
+    // With a single thread, the upgradable reader might as well take the write lock.
+
+    #[inline]
+    pub fn try_upgradable_read(&self) -> bool {
+        self.try_write()
+    }
+
+    #[inline]
+    pub fn upgradable_read(&self) {
+        self.write()
+    }
+
+    /// # Safety
+    ///
+    /// The `RwLock` must be locked by the upgradable reader in order to call this.
+    #[inline]
+    pub unsafe fn upgradable_read_unlock(&self) {
+        unsafe { self.write_unlock() }
+    }
+
+    /// Turns the upgradable read lock into a plain read lock.
+    ///
+    /// # Safety
+    ///
+    /// The `RwLock` must be locked by the upgradable reader in order to call this.
+    #[inline]
+    pub unsafe fn downgrade_upgradable(&self) {
+        unsafe { self.downgrade() }
+    }
+
+    /// # Safety
+    ///
+    /// The `RwLock` must be locked by the upgradable reader in order to call this.
+    #[inline]
+    pub unsafe fn try_upgrade(&self) -> bool {
+        true
+    }
+
+    /// # Safety
+    ///
+    /// The `RwLock` must be locked by the upgradable reader in order to call this.
+    #[inline]
+    pub unsafe fn upgrade(&self) {}
 }
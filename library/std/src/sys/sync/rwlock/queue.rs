@@ -576,6 +576,51 @@ pub unsafe fn downgrade(&self) {
         }
     }
 
+    // The queue has no room for an upgradable mode, so the upgradable reader takes the write lock
+    // from the start, and upgrading is a no-op. This keeps other readers out in the meantime.
+
+    #[inline]
+    pub fn try_upgradable_read(&self) -> bool {
+        self.try_write()
+    }
+
+    #[inline]
+    pub fn upgradable_read(&self) {
+        self.write()
+    }
+
+    /// # Safety
+    ///
+    /// The `RwLock` must be locked by the upgradable reader in order to call this.
+    #[inline]
+    pub unsafe fn upgradable_read_unlock(&self) {
+        unsafe { self.write_unlock() }
+    }
+
+    /// Turns the upgradable read lock into a plain read lock.
+    ///
+    /// # Safety
+    ///
+    /// The `RwLock` must be locked by the upgradable reader in order to call this.
+    #[inline]
+    pub unsafe fn downgrade_upgradable(&self) {
+        unsafe { self.downgrade() }
+    }
+
+    /// # Safety
+    ///
+    /// The `RwLock` must be locked by the upgradable reader in order to call this.
+    #[inline]
+    pub unsafe fn try_upgrade(&self) -> bool {
+        true
+    }
+
+    /// # Safety
+    ///
+    /// The `RwLock` must be locked by the upgradable reader in order to call this.
+    #[inline]
+    pub unsafe fn upgrade(&self) {}
+
     /// Downgrades the lock from write-locked to read-locked in the case that there are threads
     /// waiting on the wait queue.
     ///
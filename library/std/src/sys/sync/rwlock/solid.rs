@@ -85,6 +85,51 @@ pub unsafe fn downgrade(&self) {
         // The SOLID platform does not support the `downgrade` operation for reader writer locks, so
         // this function is simply a no-op as only 1 reader can read: the original writer.
     }
+
+    // SOLID reader-writer locks can't be upgraded, so the upgradable reader takes the write lock
+    // from the start, and upgrading is a no-op.
+
+    #[inline]
+    pub fn try_upgradable_read(&self) -> bool {
+        self.try_write()
+    }
+
+    #[inline]
+    pub fn upgradable_read(&self) {
+        self.write()
+    }
+
+    /// # Safety
+    ///
+    /// The `RwLock` must be locked by the upgradable reader in order to call this.
+    #[inline]
+    pub unsafe fn upgradable_read_unlock(&self) {
+        unsafe { self.write_unlock() }
+    }
+
+    /// Turns the upgradable read lock into a plain read lock.
+    ///
+    /// # Safety
+    ///
+    /// The `RwLock` must be locked by the upgradable reader in order to call this.
+    #[inline]
+    pub unsafe fn downgrade_upgradable(&self) {
+        unsafe { self.downgrade() }
+    }
+
+    /// # Safety
+    ///
+    /// The `RwLock` must be locked by the upgradable reader in order to call this.
+    #[inline]
+    pub unsafe fn try_upgrade(&self) -> bool {
+        true
+    }
+
+    /// # Safety
+    ///
+    /// The `RwLock` must be locked by the upgradable reader in order to call this.
+    #[inline]
+    pub unsafe fn upgrade(&self) {}
 }
 
 impl Drop for RwLock {
@@ -0,0 +1,97 @@
+use crate::sync::atomic::Ordering::{Acquire, Relaxed, Release};
+use crate::sys::futex::{Futex, Primitive, futex_wait, futex_wake_all};
+use crate::time::{Duration, Instant};
+
+pub struct Semaphore {
+    // The number of available permits in the lower 31 bits, and in the highest bit, whether threads
+    // are waiting on this futex for more.
+    state: Futex,
+}
+
+const WAITING: Primitive = 1 << 31;
+
+#[inline]
+fn permits(state: Primitive) -> usize {
+    (state & !WAITING) as usize
+}
+
+impl Semaphore {
+    pub const MAX_PERMITS: usize = (WAITING - 1) as usize;
+
+    /// `permits` must not be more than `MAX_PERMITS`.
+    #[inline]
+    pub const fn new(permits: usize) -> Self {
+        Self { state: Futex::new(permits as Primitive) }
+    }
+
+    #[inline]
+    pub fn available(&self) -> usize {
+        permits(self.state.load(Relaxed))
+    }
+
+    #[inline]
+    pub fn try_acquire(&self, n: usize) -> bool {
+        self.state
+            .try_update(Acquire, Relaxed, |s| (permits(s) >= n).then(|| s - n as Primitive))
+            .is_ok()
+    }
+
+    /// Returns `false` if the permits couldn't be acquired before the timeout.
+    pub fn acquire(&self, n: usize, timeout: Option<Duration>) -> bool {
+        // A timeout too long to represent is as good as no timeout.
+        let deadline = timeout.and_then(|timeout| Instant::now().checked_add(timeout));
+        let mut state = self.state.load(Relaxed);
+        loop {
+            if permits(state) >= n {
+                match self.state.compare_exchange_weak(
+                    state,
+                    state - n as Primitive,
+                    Acquire,
+                    Relaxed,
+                ) {
+                    Ok(_) => return true,
+                    Err(s) => {
+                        state = s;
+                        continue;
+                    }
+                }
+            }
+
+            // Make sure the waiting bit is set before we go to sleep.
+            if state & WAITING == 0 {
+                if let Err(s) =
+                    self.state.compare_exchange(state, state | WAITING, Relaxed, Relaxed)
+                {
+                    state = s;
+                    continue;
+                }
+                state |= WAITING;
+            }
+
+            let timeout = match deadline {
+                Some(deadline) => match deadline.checked_duration_since(Instant::now()) {
+                    Some(timeout) if !timeout.is_zero() => Some(timeout),
+                    _ => return false,
+                },
+                None => None,
+            };
+            futex_wait(&self.state, state, timeout);
+            state = self.state.load(Relaxed);
+        }
+    }
+
+    pub fn release(&self, n: usize) {
+        let state = self
+            .state
+            .try_update(Release, Relaxed, |s| {
+                // Waking everyone up clears the waiting bit. Threads that still can't get enough
+                // permits will set it again.
+                let permits = permits(s).checked_add(n).filter(|&p| p <= Self::MAX_PERMITS)?;
+                Some(permits as Primitive)
+            })
+            .unwrap_or_else(|_| panic!("too many permits in Semaphore"));
+        if state & WAITING != 0 {
+            futex_wake_all(&self.state);
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use crate::cell::UnsafeCell;
+use crate::sys::sync::{Condvar, Mutex};
+use crate::time::{Duration, Instant};
+
+pub struct Semaphore {
+    mutex: Mutex,
+    condvar: Condvar,
+    // Protected by `mutex`.
+    permits: UnsafeCell<usize>,
+}
+
+unsafe impl Send for Semaphore {}
+unsafe impl Sync for Semaphore {}
+
+impl Semaphore {
+    pub const MAX_PERMITS: usize = usize::MAX;
+
+    #[inline]
+    pub const fn new(permits: usize) -> Self {
+        Self { mutex: Mutex::new(), condvar: Condvar::new(), permits: UnsafeCell::new(permits) }
+    }
+
+    /// Runs `f` on the number of permits, with the mutex locked.
+    fn with_permits<R>(&self, f: impl FnOnce(&mut usize) -> R) -> R {
+        self.mutex.lock();
+        // SAFETY: the mutex is locked.
+        let r = f(unsafe { &mut *self.permits.get() });
+        // SAFETY: it was locked just above.
+        unsafe { self.mutex.unlock() };
+        r
+    }
+
+    pub fn available(&self) -> usize {
+        self.with_permits(|permits| *permits)
+    }
+
+    pub fn try_acquire(&self, n: usize) -> bool {
+        self.with_permits(|permits| {
+            let acquired = *permits >= n;
+            if acquired {
+                *permits -= n;
+            }
+            acquired
+        })
+    }
+
+    /// Returns `false` if the permits couldn't be acquired before the timeout.
+    pub fn acquire(&self, n: usize, timeout: Option<Duration>) -> bool {
+        // A timeout too long to represent is as good as no timeout.
+        let deadline = timeout.and_then(|timeout| Instant::now().checked_add(timeout));
+        self.mutex.lock();
+        let acquired = loop {
+            // SAFETY: the mutex is locked, also after waiting on the condition variable.
+            let permits = unsafe { &mut *self.permits.get() };
+            if *permits >= n {
+                *permits -= n;
+                break true;
+            }
+            match deadline {
+                Some(deadline) => match deadline.checked_duration_since(Instant::now()) {
+                    Some(timeout) if !timeout.is_zero() => unsafe {
+                        self.condvar.wait_timeout(&self.mutex, timeout);
+                    },
+                    _ => break false,
+                },
+                None => unsafe { self.condvar.wait(&self.mutex) },
+            }
+        };
+        unsafe { self.mutex.unlock() };
+        acquired
+    }
+
+    pub fn release(&self, n: usize) {
+        self.with_permits(|permits| {
+            *permits = permits.checked_add(n).expect("too many permits in Semaphore");
+        });
+        // Waiters may need different numbers of permits, so wake them all to check.
+        self.condvar.notify_all();
+    }
+}
@@ -0,0 +1,21 @@
+cfg_select! {
+    any(
+        all(target_os = "windows", not(target_vendor = "win7")),
+        target_os = "linux",
+        target_os = "android",
+        target_os = "freebsd",
+        target_os = "openbsd",
+        target_os = "dragonfly",
+        target_os = "motor",
+        target_os = "fuchsia",
+        all(target_family = "wasm", target_feature = "atomics"),
+        target_os = "hermit",
+    ) => {
+        mod futex;
+        pub use futex::Semaphore;
+    }
+    _ => {
+        mod generic;
+        pub use generic::Semaphore;
+    }
+}
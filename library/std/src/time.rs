@@ -36,11 +36,15 @@
 #[stable(feature = "duration_checked_float", since = "1.66.0")]
 pub use core::time::TryFromFloatSecsError;
 
+#[unstable(feature = "time_rfc3339", issue = "none")]
+pub use self::utc::{ParseUtcDateTimeError, UtcDateTime};
 use crate::error::Error;
 use crate::fmt;
 use crate::ops::{Add, AddAssign, Sub, SubAssign};
 use crate::sys::{FromInner, IntoInner, time};
 
+mod utc;
+
 /// A measurement of a monotonically nondecreasing clock.
 /// Opaque and useful only with [`Duration`].
 ///
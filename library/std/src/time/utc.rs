@@ -0,0 +1,453 @@
+//! Conversion of [`SystemTime`] to and from UTC calendar dates, and the
+//! [RFC 3339] timestamp format.
+//!
+//! Only UTC is supported: there is no time zone database, and offsets in
+//! parsed timestamps are applied and then discarded. Leap seconds are not
+//! represented, matching the POSIX interpretation of [`UNIX_EPOCH`].
+//!
+//! [RFC 3339]: https://www.rfc-editor.org/rfc/rfc3339
+
+use super::{Duration, SystemTime, UNIX_EPOCH};
+use crate::error::Error;
+use crate::fmt;
+use crate::str::FromStr;
+
+const SECS_PER_DAY: i128 = 86_400;
+const NANOS_PER_SEC: u32 = 1_000_000_000;
+
+/// A date and time of day in UTC, with nanosecond precision.
+///
+/// This is the civil (calendar) representation of a [`SystemTime`], using the
+/// proleptic Gregorian calendar for all dates. It can be obtained with
+/// [`SystemTime::to_utc`] and converted back with [`to_system_time`].
+///
+/// The [`Display`] implementation writes the date and time in the [RFC 3339]
+/// format, and the [`FromStr`] implementation parses it.
+///
+/// [`to_system_time`]: UtcDateTime::to_system_time
+/// [`Display`]: fmt::Display
+/// [RFC 3339]: https://www.rfc-editor.org/rfc/rfc3339
+///
+/// # Examples
+///
+/// ```
+/// #![feature(time_rfc3339)]
+/// use std::time::{Duration, SystemTime, UtcDateTime};
+///
+/// let time = SystemTime::UNIX_EPOCH + Duration::new(1_700_000_000, 250_000_000);
+/// let utc = time.to_utc();
+/// assert_eq!((utc.year(), utc.month(), utc.day()), (2023, 11, 14));
+/// assert_eq!((utc.hour(), utc.minute(), utc.second()), (22, 13, 20));
+/// assert_eq!(utc.to_string(), "2023-11-14T22:13:20.25Z");
+///
+/// let parsed: UtcDateTime = "2023-11-15T00:13:20.25+02:00".parse().unwrap();
+/// assert_eq!(parsed, utc);
+/// assert_eq!(parsed.to_system_time(), Some(time));
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[unstable(feature = "time_rfc3339", issue = "none")]
+pub struct UtcDateTime {
+    // The field order makes the derived `Ord` chronological.
+    year: i64,
+    month: u8,
+    day: u8,
+    hour: u8,
+    minute: u8,
+    second: u8,
+    nanosecond: u32,
+}
+
+impl UtcDateTime {
+    /// Creates a new `UtcDateTime` from its components.
+    ///
+    /// Returns `None` if any component is out of range: `month` must be in
+    /// `1..=12`, `day` must exist in that month, `hour` must be below 24,
+    /// `minute` and `second` below 60 and `nanosecond` below one billion.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(time_rfc3339)]
+    /// use std::time::UtcDateTime;
+    ///
+    /// assert!(UtcDateTime::new(2024, 2, 29, 12, 0, 0, 0).is_some());
+    /// assert!(UtcDateTime::new(2023, 2, 29, 12, 0, 0, 0).is_none());
+    /// assert!(UtcDateTime::new(2024, 1, 1, 24, 0, 0, 0).is_none());
+    /// ```
+    #[must_use]
+    #[unstable(feature = "time_rfc3339", issue = "none")]
+    pub const fn new(
+        year: i64,
+        month: u8,
+        day: u8,
+        hour: u8,
+        minute: u8,
+        second: u8,
+        nanosecond: u32,
+    ) -> Option<UtcDateTime> {
+        if month < 1
+            || month > 12
+            || day < 1
+            || day > days_in_month(year, month)
+            || hour >= 24
+            || minute >= 60
+            || second >= 60
+            || nanosecond >= NANOS_PER_SEC
+        {
+            return None;
+        }
+        Some(UtcDateTime { year, month, day, hour, minute, second, nanosecond })
+    }
+
+    /// Returns the year. Years before 1 AD are numbered astronomically, so the
+    /// year before 1 AD is 0.
+    #[must_use]
+    #[unstable(feature = "time_rfc3339", issue = "none")]
+    pub const fn year(&self) -> i64 {
+        self.year
+    }
+
+    /// Returns the month, from 1 (January) to 12 (December).
+    #[must_use]
+    #[unstable(feature = "time_rfc3339", issue = "none")]
+    pub const fn month(&self) -> u8 {
+        self.month
+    }
+
+    /// Returns the day of the month, starting at 1.
+    #[must_use]
+    #[unstable(feature = "time_rfc3339", issue = "none")]
+    pub const fn day(&self) -> u8 {
+        self.day
+    }
+
+    /// Returns the hour, from 0 to 23.
+    #[must_use]
+    #[unstable(feature = "time_rfc3339", issue = "none")]
+    pub const fn hour(&self) -> u8 {
+        self.hour
+    }
+
+    /// Returns the minute, from 0 to 59.
+    #[must_use]
+    #[unstable(feature = "time_rfc3339", issue = "none")]
+    pub const fn minute(&self) -> u8 {
+        self.minute
+    }
+
+    /// Returns the second, from 0 to 59.
+    #[must_use]
+    #[unstable(feature = "time_rfc3339", issue = "none")]
+    pub const fn second(&self) -> u8 {
+        self.second
+    }
+
+    /// Returns the fraction of the second in nanoseconds, below one billion.
+    #[must_use]
+    #[unstable(feature = "time_rfc3339", issue = "none")]
+    pub const fn nanosecond(&self) -> u32 {
+        self.nanosecond
+    }
+
+    /// Converts this date and time to a [`SystemTime`].
+    ///
+    /// Returns `None` if the result cannot be represented by `SystemTime` on
+    /// this platform.
+    #[must_use]
+    #[unstable(feature = "time_rfc3339", issue = "none")]
+    pub fn to_system_time(&self) -> Option<SystemTime> {
+        let secs = days_from_civil(self.year, self.month, self.day) * SECS_PER_DAY
+            + self.hour as i128 * 3600
+            + self.minute as i128 * 60
+            + self.second as i128;
+        if secs >= 0 {
+            UNIX_EPOCH.checked_add(Duration::new(u64::try_from(secs).ok()?, self.nanosecond))
+        } else {
+            UNIX_EPOCH
+                .checked_sub(Duration::from_secs(u64::try_from(-secs).ok()?))?
+                .checked_add(Duration::from_nanos(self.nanosecond.into()))
+        }
+    }
+
+    /// Creates the date and time lying `secs` seconds and `nanosecond`
+    /// nanoseconds after the Unix epoch.
+    ///
+    /// Returns `None` if the year does not fit in an `i64`.
+    fn from_unix(secs: i128, nanosecond: u32) -> Option<UtcDateTime> {
+        let days = secs.div_euclid(SECS_PER_DAY);
+        let secs_of_day = secs.rem_euclid(SECS_PER_DAY) as u32;
+        let (year, month, day) = civil_from_days(days);
+        Some(UtcDateTime {
+            year: i64::try_from(year).ok()?,
+            month,
+            day,
+            hour: (secs_of_day / 3600) as u8,
+            minute: (secs_of_day / 60 % 60) as u8,
+            second: (secs_of_day % 60) as u8,
+            nanosecond,
+        })
+    }
+}
+
+impl SystemTime {
+    /// Converts this system time to a date and time in UTC.
+    ///
+    /// The result does not account for leap seconds: like [`UNIX_EPOCH`],
+    /// every day is taken to be exactly 86400 seconds long.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(time_rfc3339)]
+    /// use std::time::{Duration, SystemTime};
+    ///
+    /// let time = SystemTime::UNIX_EPOCH + Duration::from_secs(951_782_400);
+    /// assert_eq!(time.to_utc().to_string(), "2000-02-29T00:00:00Z");
+    /// ```
+    #[must_use]
+    #[unstable(feature = "time_rfc3339", issue = "none")]
+    pub fn to_utc(&self) -> UtcDateTime {
+        let (secs, nanos) = match self.duration_since(UNIX_EPOCH) {
+            Ok(d) => (d.as_secs() as i128, d.subsec_nanos()),
+            Err(e) => {
+                let d = e.duration();
+                match d.subsec_nanos() {
+                    0 => (-(d.as_secs() as i128), 0),
+                    nanos => (-(d.as_secs() as i128) - 1, NANOS_PER_SEC - nanos),
+                }
+            }
+        };
+        // A `SystemTime` spans far less than `i64::MAX` years on every platform.
+        UtcDateTime::from_unix(secs, nanos).unwrap()
+    }
+}
+
+/// Formats the date and time as an [RFC 3339] timestamp in UTC, such as
+/// `2023-11-14T22:13:20.25Z`.
+///
+/// By default, the fraction of the second is written without trailing zeros,
+/// and omitted entirely if it is zero. A precision, as in `{:.3}`, writes
+/// exactly that many fractional digits (at most nine), truncating the rest.
+///
+/// Years outside of `0..=9999` cannot be represented in RFC 3339. They are
+/// written with an explicit sign, as in the expanded format of ISO 8601.
+///
+/// [RFC 3339]: https://www.rfc-editor.org/rfc/rfc3339
+#[unstable(feature = "time_rfc3339", issue = "none")]
+impl fmt::Display for UtcDateTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if (0..=9999).contains(&self.year) {
+            write!(f, "{:04}", self.year)?;
+        } else {
+            write!(f, "{:+05}", self.year)?;
+        }
+        write!(
+            f,
+            "-{:02}-{:02}T{:02}:{:02}:{:02}",
+            self.month, self.day, self.hour, self.minute, self.second
+        )?;
+
+        let digits = match f.precision() {
+            Some(precision) => precision.min(9),
+            None => {
+                // Drop the trailing zeros.
+                let mut digits = 9;
+                let mut nanos = self.nanosecond;
+                while digits > 0 && nanos % 10 == 0 {
+                    nanos /= 10;
+                    digits -= 1;
+                }
+                digits
+            }
+        };
+        if digits > 0 {
+            let fraction = self.nanosecond / 10u32.pow(9 - digits as u32);
+            write!(f, ".{fraction:0digits$}")?;
+        }
+        f.write_str("Z")
+    }
+}
+
+/// Parses an [RFC 3339] timestamp, such as `2023-11-14T22:13:20.25Z` or
+/// `1996-12-19T16:39:57-08:00`.
+///
+/// The date and time are separated by `T`, `t` or a space, and the offset
+/// from UTC is either `Z`, `z` or a signed `hh:mm` offset, which is applied
+/// to the result. Fractional seconds beyond nanosecond precision are
+/// truncated. A leap second (`60`) is accepted and folded into the following
+/// second.
+///
+/// [RFC 3339]: https://www.rfc-editor.org/rfc/rfc3339
+#[unstable(feature = "time_rfc3339", issue = "none")]
+impl FromStr for UtcDateTime {
+    type Err = ParseUtcDateTimeError;
+
+    fn from_str(s: &str) -> Result<UtcDateTime, ParseUtcDateTimeError> {
+        Parser { s: s.as_bytes() }.read_timestamp().ok_or(ParseUtcDateTimeError(()))
+    }
+}
+
+/// An error which can be returned when parsing a [`UtcDateTime`].
+///
+/// This error is used as the error type for the [`FromStr`] implementation
+/// for [`UtcDateTime`].
+///
+/// # Examples
+///
+/// ```
+/// #![feature(time_rfc3339)]
+/// use std::time::UtcDateTime;
+///
+/// assert!("2023-11-14".parse::<UtcDateTime>().is_err());
+/// assert!("2023-11-14T25:00:00Z".parse::<UtcDateTime>().is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[unstable(feature = "time_rfc3339", issue = "none")]
+pub struct ParseUtcDateTimeError(());
+
+#[unstable(feature = "time_rfc3339", issue = "none")]
+impl fmt::Display for ParseUtcDateTimeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid RFC 3339 timestamp")
+    }
+}
+
+#[unstable(feature = "time_rfc3339", issue = "none")]
+impl Error for ParseUtcDateTimeError {}
+
+struct Parser<'a> {
+    s: &'a [u8],
+}
+
+impl Parser<'_> {
+    fn read_byte(&mut self) -> Option<u8> {
+        let (&b, rest) = self.s.split_first()?;
+        self.s = rest;
+        Some(b)
+    }
+
+    fn read_given_byte(&mut self, expected: u8) -> Option<()> {
+        (self.read_byte()? == expected).then_some(())
+    }
+
+    fn read_digit(&mut self) -> Option<u32> {
+        let b = *self.s.first()?;
+        if !b.is_ascii_digit() {
+            return None;
+        }
+        self.s = &self.s[1..];
+        Some((b - b'0').into())
+    }
+
+    /// Reads exactly `digits` decimal digits.
+    fn read_number(&mut self, digits: usize) -> Option<u32> {
+        (0..digits).try_fold(0, |n, _| Some(n * 10 + self.read_digit()?))
+    }
+
+    fn read_timestamp(&mut self) -> Option<UtcDateTime> {
+        let year = self.read_number(4)?;
+        self.read_given_byte(b'-')?;
+        let month = self.read_number(2)?;
+        self.read_given_byte(b'-')?;
+        let day = self.read_number(2)?;
+        if !matches!(self.read_byte()?, b'T' | b't' | b' ') {
+            return None;
+        }
+        let hour = self.read_number(2)?;
+        self.read_given_byte(b':')?;
+        let minute = self.read_number(2)?;
+        self.read_given_byte(b':')?;
+        let second = self.read_number(2)?;
+
+        let mut nanosecond = 0;
+        if self.s.first() == Some(&b'.') {
+            self.s = &self.s[1..];
+            let mut scale = NANOS_PER_SEC;
+            nanosecond = self.read_digit()? * (scale / 10);
+            scale /= 10;
+            while let Some(digit) = self.read_digit() {
+                scale /= 10;
+                nanosecond += digit * scale;
+            }
+        }
+
+        let offset = match self.read_byte()? {
+            b'Z' | b'z' => 0,
+            sign @ (b'+' | b'-') => {
+                let hours = self.read_number(2)?;
+                self.read_given_byte(b':')?;
+                let minutes = self.read_number(2)?;
+                if hours >= 24 || minutes >= 60 {
+                    return None;
+                }
+                let offset = (hours * 3600 + minutes * 60) as i128;
+                if sign == b'-' { -offset } else { offset }
+            }
+            _ => return None,
+        };
+        if !self.s.is_empty() || second > 60 {
+            return None;
+        }
+
+        // Validate everything but the leap second, which is folded into the next second below.
+        let civil = UtcDateTime::new(
+            year.into(),
+            month as u8,
+            day as u8,
+            hour as u8,
+            minute as u8,
+            second.min(59) as u8,
+            0,
+        )?;
+        let secs = days_from_civil(civil.year, civil.month, civil.day) * SECS_PER_DAY
+            + (hour * 3600 + minute * 60 + second) as i128
+            - offset;
+        UtcDateTime::from_unix(secs, nanosecond)
+    }
+}
+
+const fn is_leap_year(year: i64) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+const fn days_in_month(year: i64, month: u8) -> u8 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Returns the number of days between the Unix epoch and the given date.
+///
+/// See <https://howardhinnant.github.io/date_algorithms.html#days_from_civil>.
+fn days_from_civil(year: i64, month: u8, day: u8) -> i128 {
+    let year = year as i128 - (month <= 2) as i128;
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    // Months are counted from March, so that the leap day comes last.
+    let month = (month as i128 + 9) % 12;
+    let day_of_year = (153 * month + 2) / 5 + day as i128 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Returns the year, month and day of the date the given number of days after
+/// the Unix epoch.
+///
+/// See <https://howardhinnant.github.io/date_algorithms.html#civil_from_days>.
+fn civil_from_days(days: i128) -> (i128, u8, u8) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    // Months are counted from March, so that the leap day comes last.
+    let month = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month + 2) / 5 + 1) as u8;
+    let month = (if month < 10 { month + 3 } else { month - 9 }) as u8;
+    let year = year_of_era + era * 400 + (month <= 2) as i128;
+    (year, month, day)
+}
@@ -0,0 +1,156 @@
+use std::sync::broadcast::{self, RecvError, RecvTimeoutError, TryRecvError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[test]
+fn smoke() {
+    let (tx, mut rx) = broadcast::channel(4);
+    assert_eq!(tx.send(1), Ok(1));
+    assert_eq!(rx.recv(), Ok(1));
+    assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+}
+
+#[test]
+fn every_receiver_sees_every_message() {
+    let (tx, mut rx1) = broadcast::channel(8);
+    let mut rx2 = tx.subscribe();
+    let mut rx3 = rx1.clone();
+
+    for i in 0..5 {
+        assert_eq!(tx.send(i), Ok(3));
+    }
+    for rx in [&mut rx1, &mut rx2, &mut rx3] {
+        for i in 0..5 {
+            assert_eq!(rx.try_recv(), Ok(i));
+        }
+        assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+    }
+}
+
+#[test]
+fn subscribe_only_sees_new_messages() {
+    let (tx, mut rx1) = broadcast::channel(4);
+    tx.send(1).unwrap();
+    let mut rx2 = tx.subscribe();
+    tx.send(2).unwrap();
+
+    assert_eq!(rx1.len(), 2);
+    assert_eq!(rx2.len(), 1);
+    assert_eq!(rx2.recv(), Ok(2));
+    assert!(rx2.is_empty());
+}
+
+#[test]
+fn clone_keeps_position() {
+    let (tx, mut rx1) = broadcast::channel(4);
+    tx.send(1).unwrap();
+    tx.send(2).unwrap();
+    assert_eq!(rx1.recv(), Ok(1));
+
+    let mut rx2 = rx1.clone();
+    assert_eq!(rx1.recv(), Ok(2));
+    assert_eq!(rx2.recv(), Ok(2));
+}
+
+#[test]
+fn lagged() {
+    let (tx, mut slow) = broadcast::channel(3);
+    let mut fast = tx.subscribe();
+
+    for i in 0..5 {
+        tx.send(i).unwrap();
+        assert_eq!(fast.recv(), Ok(i));
+    }
+
+    assert_eq!(slow.len(), 3);
+    assert_eq!(slow.recv(), Err(RecvError::Lagged(2)));
+    assert_eq!(slow.recv(), Ok(2));
+    assert_eq!(slow.recv(), Ok(3));
+    assert_eq!(slow.recv(), Ok(4));
+    assert_eq!(slow.try_recv(), Err(TryRecvError::Empty));
+}
+
+#[test]
+fn lagged_display() {
+    assert_eq!(RecvError::Lagged(1).to_string(), "receiver lagged behind by 1 message");
+    assert_eq!(TryRecvError::Lagged(2).to_string(), "receiver lagged behind by 2 messages");
+}
+
+#[test]
+fn disconnected_after_remaining_messages() {
+    let (tx, mut rx) = broadcast::channel(4);
+    let tx2 = tx.clone();
+    tx.send(1).unwrap();
+    drop(tx);
+    assert_eq!(rx.try_recv(), Ok(1));
+    assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+
+    tx2.send(2).unwrap();
+    drop(tx2);
+    assert_eq!(rx.recv(), Ok(2));
+    assert_eq!(rx.recv(), Err(RecvError::Disconnected));
+    assert_eq!(rx.try_recv(), Err(TryRecvError::Disconnected));
+}
+
+#[test]
+fn send_without_receivers() {
+    let (tx, rx) = broadcast::channel(4);
+    drop(rx);
+    assert_eq!(tx.receiver_count(), 0);
+    assert_eq!(tx.send(1).unwrap_err().0, 1);
+
+    // Receivers subscribing later don't see messages sent before.
+    let mut rx = tx.subscribe();
+    assert_eq!(tx.receiver_count(), 1);
+    assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+}
+
+#[test]
+fn recv_timeout() {
+    let (tx, mut rx) = broadcast::channel::<i32>(4);
+    let start = Instant::now();
+    assert_eq!(rx.recv_timeout(Duration::from_millis(50)), Err(RecvTimeoutError::Timeout));
+    assert!(start.elapsed() >= Duration::from_millis(50));
+
+    tx.send(1).unwrap();
+    assert_eq!(rx.recv_timeout(Duration::from_millis(50)), Ok(1));
+    drop(tx);
+    assert_eq!(rx.recv_timeout(Duration::MAX), Err(RecvTimeoutError::Disconnected));
+}
+
+#[test]
+fn blocking_receivers_are_all_woken() {
+    let (tx, rx) = broadcast::channel(16);
+
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let mut rx = rx.clone();
+            thread::spawn(move || {
+                let mut received = Vec::new();
+                while let Ok(msg) = rx.recv() {
+                    received.push(msg);
+                }
+                received
+            })
+        })
+        .collect();
+    drop(rx);
+
+    for i in 0..10 {
+        tx.send(i).unwrap();
+    }
+    drop(tx);
+
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), (0..10).collect::<Vec<_>>());
+    }
+}
+
+#[test]
+fn recv_woken_by_disconnect() {
+    let (tx, mut rx) = broadcast::channel::<i32>(4);
+    let handle = thread::spawn(move || rx.recv());
+    thread::sleep(Duration::from_millis(10));
+    drop(tx);
+    assert_eq!(handle.join().unwrap(), Err(RecvError::Disconnected));
+}
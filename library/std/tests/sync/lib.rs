@@ -1,3 +1,4 @@
+#![feature(broadcast_channel)]
 #![feature(const_default)]
 #![feature(const_trait_impl)]
 #![feature(mapped_lock_guards)]
@@ -6,15 +7,20 @@
 #![feature(once_cell_try)]
 #![feature(lock_value_accessors)]
 #![feature(reentrant_lock)]
+#![feature(rwlock_upgradable_read)]
+#![feature(semaphore)]
 #![feature(std_internals)]
 #![feature(sync_nonpoison)]
 #![feature(nonpoison_condvar)]
 #![feature(nonpoison_mutex)]
 #![feature(nonpoison_rwlock)]
+#![feature(watch_channel)]
 #![allow(internal_features)]
 #![feature(macro_metavar_expr_concat)] // For concatenating identifiers in macros.
 
 mod barrier;
+#[cfg(not(any(target_os = "emscripten", target_os = "wasi")))]
+mod broadcast;
 mod condvar;
 mod lazy_lock;
 #[cfg(not(any(target_os = "emscripten", target_os = "wasi")))]
@@ -34,6 +40,10 @@
 mod reentrant_lock;
 #[cfg(not(any(target_os = "emscripten", target_os = "wasi")))]
 mod rwlock;
+#[cfg(not(any(target_os = "emscripten", target_os = "wasi")))]
+mod semaphore;
+#[cfg(not(any(target_os = "emscripten", target_os = "wasi")))]
+mod watch;
 
 #[path = "../common/mod.rs"]
 mod common;
@@ -773,3 +773,207 @@ fn issue_39364() {
     let _tx = t.join().unwrap(); // delay dropping until end of test
     let _ = rx.recv_timeout(Duration::from_millis(500));
 }
+
+#[test]
+fn select_recv() {
+    let (tx1, rx1) = channel::<i32>();
+    let (tx2, rx2) = sync_channel::<i32>(1);
+    let (_tx3, rx3) = sync_channel::<i32>(0);
+    tx2.send(2).unwrap();
+
+    let mut sel = Select::new();
+    let oper1 = sel.recv(&rx1);
+    let oper2 = sel.recv(&rx2);
+    sel.recv(&rx3);
+    let oper = sel.select();
+    assert_eq!(oper.index(), oper2);
+    assert_eq!(oper.recv(&rx2), Ok(2));
+
+    tx1.send(1).unwrap();
+    let oper = sel.select();
+    assert_eq!(oper.index(), oper1);
+    assert_eq!(oper.recv(&rx1), Ok(1));
+}
+
+#[test]
+fn select_send() {
+    let (tx1, rx1) = sync_channel::<i32>(1);
+    let (tx2, _rx2) = sync_channel::<i32>(0);
+    tx1.send(0).unwrap();
+
+    let mut sel = Select::new();
+    let oper1 = sel.send(&tx1);
+    sel.send(&tx2);
+    assert!(sel.try_select().is_err());
+
+    assert_eq!(rx1.recv(), Ok(0));
+    let oper = sel.select();
+    assert_eq!(oper.index(), oper1);
+    assert_eq!(oper.send(&tx1, 1), Ok(()));
+    assert_eq!(rx1.recv(), Ok(1));
+}
+
+#[test]
+fn select_disconnected() {
+    let (tx, rx) = channel::<i32>();
+    drop(tx);
+
+    let mut sel = Select::new();
+    sel.recv(&rx);
+    let oper = sel.try_select().unwrap();
+    assert_eq!(oper.recv(&rx), Err(RecvError));
+}
+
+#[test]
+fn select_timeout() {
+    let (_tx, rx) = sync_channel::<i32>(0);
+
+    let mut sel = Select::new();
+    sel.recv(&rx);
+    let start = Instant::now();
+    assert_eq!(sel.select_timeout(Duration::from_millis(100)).err(), Some(SelectTimeoutError));
+    assert!(start.elapsed() >= Duration::from_millis(100));
+}
+
+#[test]
+fn select_remove() {
+    let (tx1, rx1) = channel::<i32>();
+    let (tx2, rx2) = channel::<i32>();
+    tx1.send(1).unwrap();
+    tx2.send(2).unwrap();
+
+    let mut sel = Select::new();
+    let oper1 = sel.recv(&rx1);
+    let oper2 = sel.recv(&rx2);
+    sel.remove(oper1);
+    let oper = sel.select();
+    assert_eq!(oper.index(), oper2);
+    assert_eq!(oper.recv(&rx2), Ok(2));
+}
+
+#[test]
+fn select_zero_threads() {
+    let (tx1, rx1) = sync_channel::<i32>(0);
+    let (tx2, rx2) = sync_channel::<i32>(0);
+
+    let t = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(50));
+        tx2.send(2).unwrap();
+        assert_eq!(rx1.recv(), Ok(1));
+    });
+
+    let mut sel = Select::new();
+    let oper1 = sel.send(&tx1);
+    let oper2 = sel.recv(&rx2);
+    let oper = sel.select();
+    assert_eq!(oper.index(), oper2);
+    assert_eq!(oper.recv(&rx2), Ok(2));
+
+    sel.remove(oper2);
+    let oper = sel.select();
+    assert_eq!(oper.index(), oper1);
+    assert_eq!(oper.send(&tx1, 1), Ok(()));
+    t.join().unwrap();
+}
+
+#[test]
+fn select_stress() {
+    const AMT: u32 = 10000;
+    let (tx1, rx1) = channel::<u32>();
+    let (tx2, rx2) = sync_channel::<u32>(0);
+
+    let t1 = thread::spawn(move || {
+        for i in 0..AMT {
+            tx1.send(i).unwrap();
+        }
+    });
+    let t2 = thread::spawn(move || {
+        for i in 0..AMT {
+            tx2.send(i).unwrap();
+        }
+    });
+
+    let (mut next1, mut next2) = (0, 0);
+    while next1 < AMT || next2 < AMT {
+        select! {
+            recv(rx1) -> msg => if let Ok(msg) = msg {
+                assert_eq!(msg, next1);
+                next1 += 1;
+            },
+            recv(rx2) -> msg => if let Ok(msg) = msg {
+                assert_eq!(msg, next2);
+                next2 += 1;
+            },
+        }
+    }
+    t1.join().unwrap();
+    t2.join().unwrap();
+}
+
+#[test]
+#[should_panic = "dropped `SelectedOperation` without completing the operation"]
+fn select_drop_selected() {
+    let (tx, rx) = channel::<i32>();
+    tx.send(1).unwrap();
+
+    let mut sel = Select::new();
+    sel.recv(&rx);
+    let _ = sel.select();
+}
+
+#[test]
+fn select_macro() {
+    let (tx1, rx1) = channel::<i32>();
+    let (tx2, rx2) = sync_channel::<i32>(1);
+
+    let msg = select! {
+        recv(rx1) -> _ => unreachable!(),
+        send(tx2, 2) -> res => {
+            res.unwrap();
+            rx2.recv().unwrap()
+        }
+    };
+    assert_eq!(msg, 2);
+
+    tx1.send(1).unwrap();
+    select! {
+        recv(rx1) -> msg => assert_eq!(msg, Ok(1)),
+        recv(rx2) -> _ => unreachable!(),
+    }
+}
+
+#[test]
+fn select_macro_default() {
+    let (_tx, rx) = channel::<i32>();
+
+    let mut ran_default = false;
+    select! {
+        recv(rx) -> _ => unreachable!(),
+        default => ran_default = true,
+    }
+    assert!(ran_default);
+
+    let start = Instant::now();
+    let timed_out = select! {
+        recv(rx) -> _ => false,
+        default(Duration::from_millis(100)) => true,
+    };
+    assert!(timed_out);
+    assert!(start.elapsed() >= Duration::from_millis(100));
+}
+
+#[test]
+fn select_macro_evaluates_once() {
+    let (tx, rx) = channel::<i32>();
+    tx.send(1).unwrap();
+
+    let mut evaluated = 0;
+    let mut receiver = || {
+        evaluated += 1;
+        &rx
+    };
+    select! {
+        recv(receiver()) -> msg => assert_eq!(msg, Ok(1)),
+    }
+    assert_eq!(evaluated, 1);
+}
@@ -451,6 +451,115 @@ fn do_stuff<'a>(_: MappedRwLockReadGuard<'_, &'a i32>, _: &'a i32) {}
     }
 );
 
+nonpoison_and_poison_unwrap_test!(
+    name: test_upgradable_read_basic,
+    test_body: {
+        use locks::{RwLock, RwLockUpgradableReadGuard};
+
+        let lock = RwLock::new(1);
+
+        let guard = maybe_unwrap(lock.upgradable_read());
+        assert_eq!(*guard, 1);
+        assert!(lock.try_write().is_err());
+        assert!(lock.try_upgradable_read().is_err());
+
+        let mut guard = RwLockUpgradableReadGuard::upgrade(guard);
+        *guard += 1;
+        assert!(lock.try_read().is_err());
+        drop(guard);
+
+        assert_eq!(*maybe_unwrap(lock.read()), 2);
+        assert!(lock.try_upgradable_read().is_ok());
+    }
+);
+
+nonpoison_and_poison_unwrap_test!(
+    name: test_upgradable_read_try_upgrade,
+    test_body: {
+        use locks::{RwLock, RwLockUpgradableReadGuard};
+
+        let lock = RwLock::new(1);
+
+        let guard = maybe_unwrap(lock.try_upgradable_read());
+        let mut guard = RwLockUpgradableReadGuard::try_upgrade(guard).ok().unwrap();
+        *guard = 3;
+        drop(guard);
+
+        assert_eq!(*maybe_unwrap(lock.read()), 3);
+    }
+);
+
+nonpoison_and_poison_unwrap_test!(
+    name: test_upgradable_read_downgrade,
+    test_body: {
+        use locks::{RwLock, RwLockUpgradableReadGuard};
+
+        let lock = RwLock::new(1);
+
+        let guard = maybe_unwrap(lock.upgradable_read());
+        let guard = RwLockUpgradableReadGuard::downgrade(guard);
+        assert_eq!(*guard, 1);
+        assert_eq!(*maybe_unwrap(lock.try_read()), 1);
+        assert!(lock.try_write().is_err());
+        drop(guard);
+
+        assert!(lock.try_write().is_ok());
+    }
+);
+
+// FIXME: On macOS we use a provenance-incorrect implementation and Miri catches that issue.
+// See <https://github.com/rust-lang/rust/issues/121950> for details.
+#[cfg(not(all(miri, target_os = "macos")))]
+nonpoison_and_poison_unwrap_test!(
+    name: test_upgradable_read_upgrade_atomic,
+    test_body: {
+        use locks::{RwLock, RwLockUpgradableReadGuard};
+
+        // This test spawns `U` upgraders that each increment a counter `N` times, but only after
+        // checking its value under the upgradable read lock, along with `R` plain readers. If an
+        // upgrade let anyone in between, the value checked would not be the one incremented.
+
+        const U: usize = 10;
+        const R: usize = 10;
+        const N: usize = if cfg!(miri) { 20 } else { 100 };
+
+        let rw = Arc::new(RwLock::new(0));
+
+        let upgraders: Vec<_> = (0..U)
+            .map(|_| {
+                let rw = rw.clone();
+                thread::spawn(move || {
+                    for _ in 0..N {
+                        let guard = maybe_unwrap(rw.upgradable_read());
+                        let seen = *guard;
+                        let mut guard = RwLockUpgradableReadGuard::upgrade(guard);
+                        assert_eq!(*guard, seen);
+                        *guard += 1;
+                    }
+                })
+            })
+            .collect();
+
+        let readers: Vec<_> = (0..R)
+            .map(|_| {
+                let rw = rw.clone();
+                thread::spawn(move || {
+                    for _ in 0..N {
+                        let n = *maybe_unwrap(rw.read());
+                        assert!(n <= U * N);
+                    }
+                })
+            })
+            .collect();
+
+        for handle in upgraders.into_iter().chain(readers) {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(*maybe_unwrap(rw.read()), U * N);
+    }
+);
+
 #[test]
 fn nonpoison_test_rwlock_try_write() {
     use std::sync::nonpoison::{RwLock, RwLockReadGuard, WouldBlock};
@@ -674,6 +783,20 @@ fn test_rw_arc_no_poison_rr() {
     assert_eq!(*lock, 1);
 }
 
+#[test]
+#[cfg_attr(not(panic = "unwind"), ignore = "test requires unwinding support")]
+fn test_rw_arc_no_poison_upgradable_r() {
+    let arc = Arc::new(RwLock::new(1));
+    let arc2 = arc.clone();
+    let _: Result<(), _> = thread::spawn(move || {
+        let _lock = arc2.upgradable_read().unwrap();
+        panic!();
+    })
+    .join();
+    let lock = arc.upgradable_read().unwrap();
+    assert_eq!(*lock, 1);
+}
+
 #[test]
 #[cfg_attr(not(panic = "unwind"), ignore = "test requires unwinding support")]
 fn test_rw_arc_no_poison_mapped_r_r() {
@@ -0,0 +1,115 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Semaphore};
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[test]
+fn smoke() {
+    let semaphore = Semaphore::new(2);
+    assert_eq!(semaphore.available_permits(), 2);
+
+    let a = semaphore.acquire();
+    let b = semaphore.try_acquire().unwrap();
+    assert_eq!(semaphore.available_permits(), 0);
+    assert!(semaphore.try_acquire().is_none());
+
+    drop(a);
+    assert_eq!(semaphore.available_permits(), 1);
+    drop(b);
+    assert_eq!(semaphore.available_permits(), 2);
+}
+
+#[test]
+fn acquire_many() {
+    let semaphore = Semaphore::new(5);
+
+    let permit = semaphore.acquire_many(3);
+    assert_eq!(permit.num_permits(), 3);
+    assert!(semaphore.try_acquire_many(3).is_none());
+    assert_eq!(semaphore.available_permits(), 2);
+
+    let rest = semaphore.try_acquire_many(2).unwrap();
+    assert_eq!(semaphore.available_permits(), 0);
+    drop((permit, rest));
+    assert_eq!(semaphore.available_permits(), 5);
+
+    // Acquiring no permits always succeeds.
+    let _none = semaphore.acquire_many(0);
+    assert_eq!(semaphore.available_permits(), 5);
+}
+
+#[test]
+fn forget_and_add_permits() {
+    let semaphore = Semaphore::new(1);
+    semaphore.acquire().forget();
+    assert_eq!(semaphore.available_permits(), 0);
+    semaphore.add_permits(3);
+    assert_eq!(semaphore.available_permits(), 3);
+}
+
+#[test]
+#[cfg_attr(not(panic = "unwind"), ignore = "test requires unwinding support")]
+#[should_panic = "too many permits in Semaphore"]
+fn add_permits_overflow() {
+    let semaphore = Semaphore::new(Semaphore::MAX_PERMITS);
+    semaphore.add_permits(1);
+}
+
+#[test]
+fn acquire_timeout() {
+    let semaphore = Semaphore::new(1);
+    let _permit = semaphore.acquire();
+
+    let timeout = Duration::from_millis(10);
+    let start = Instant::now();
+    assert!(semaphore.acquire_timeout(timeout).is_none());
+    assert!(start.elapsed() >= timeout);
+
+    // Waiting for too many permits times out as well, instead of blocking forever.
+    assert!(semaphore.acquire_many_timeout(Semaphore::MAX_PERMITS, timeout).is_none());
+    assert_eq!(semaphore.available_permits(), 0);
+}
+
+#[test]
+fn acquire_timeout_woken() {
+    let semaphore = Arc::new(Semaphore::new(0));
+    let semaphore2 = semaphore.clone();
+
+    let handle = thread::spawn(move || {
+        semaphore2.acquire_many_timeout(2, Duration::from_secs(60)).map(|p| p.num_permits())
+    });
+    semaphore.add_permits(1);
+    thread::sleep(Duration::from_millis(10));
+    semaphore.add_permits(1);
+
+    assert_eq!(handle.join().unwrap(), Some(2));
+    assert_eq!(semaphore.available_permits(), 0);
+}
+
+#[test]
+fn limits_concurrency() {
+    const PERMITS: usize = 3;
+    const THREADS: usize = 10;
+    const N: usize = if cfg!(miri) { 10 } else { 100 };
+
+    let semaphore = Semaphore::new(PERMITS);
+    let active = AtomicUsize::new(0);
+    let max_active = AtomicUsize::new(0);
+
+    thread::scope(|s| {
+        for _ in 0..THREADS {
+            s.spawn(|| {
+                for _ in 0..N {
+                    let _permit = semaphore.acquire();
+                    let n = active.fetch_add(1, Ordering::SeqCst) + 1;
+                    max_active.fetch_max(n, Ordering::SeqCst);
+                    thread::yield_now();
+                    active.fetch_sub(1, Ordering::SeqCst);
+                }
+            });
+        }
+    });
+
+    assert!(max_active.load(Ordering::SeqCst) <= PERMITS);
+    assert_eq!(semaphore.available_permits(), PERMITS);
+}
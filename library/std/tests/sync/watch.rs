@@ -0,0 +1,124 @@
+use std::sync::mpsc::{RecvError, RecvTimeoutError};
+use std::sync::watch;
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[test]
+fn smoke() {
+    let (tx, mut rx) = watch::channel(0);
+    assert_eq!(*rx.borrow(), 0);
+    assert_eq!(rx.has_changed(), Ok(false));
+
+    tx.send(1).unwrap();
+    assert_eq!(rx.has_changed(), Ok(true));
+    assert_eq!(*rx.borrow(), 1);
+    assert_eq!(rx.has_changed(), Ok(true));
+    assert_eq!(*rx.borrow_and_update(), 1);
+    assert_eq!(rx.has_changed(), Ok(false));
+}
+
+#[test]
+fn only_latest_value_is_kept() {
+    let (tx, mut rx) = watch::channel(0);
+    for i in 1..=5 {
+        tx.send(i).unwrap();
+    }
+    assert_eq!(rx.changed(), Ok(()));
+    assert_eq!(*rx.borrow(), 5);
+    assert_eq!(rx.has_changed(), Ok(false));
+}
+
+#[test]
+fn send_replace_and_modify() {
+    let (tx, rx) = watch::channel(vec![1]);
+    drop(rx);
+
+    assert!(tx.send(vec![2]).is_err());
+    assert_eq!(*tx.borrow(), [1]);
+
+    assert_eq!(tx.send_replace(vec![2]), [1]);
+    tx.send_modify(|v| v.push(3));
+    assert_eq!(*tx.borrow(), [2, 3]);
+
+    let rx = tx.subscribe();
+    assert_eq!(tx.receiver_count(), 1);
+    assert_eq!(rx.has_changed(), Ok(false));
+    assert_eq!(*rx.borrow(), [2, 3]);
+}
+
+#[test]
+fn clone_keeps_seen_version() {
+    let (tx, mut rx1) = watch::channel(0);
+    tx.send(1).unwrap();
+
+    let rx2 = rx1.clone();
+    assert_eq!(rx2.has_changed(), Ok(true));
+    rx1.borrow_and_update();
+    assert_eq!(rx1.has_changed(), Ok(false));
+    assert_eq!(rx2.has_changed(), Ok(true));
+    assert_eq!(tx.receiver_count(), 2);
+}
+
+#[test]
+fn disconnected() {
+    let (tx, mut rx) = watch::channel(0);
+    tx.send(1).unwrap();
+    drop(tx);
+
+    // The last change can still be observed.
+    assert_eq!(rx.has_changed(), Ok(true));
+    assert_eq!(rx.changed(), Ok(()));
+    assert_eq!(rx.has_changed(), Err(RecvError));
+    assert_eq!(rx.changed(), Err(RecvError));
+    assert_eq!(*rx.borrow(), 1);
+}
+
+#[test]
+fn changed_timeout() {
+    let (tx, mut rx) = watch::channel(0);
+    let start = Instant::now();
+    assert_eq!(rx.changed_timeout(Duration::from_millis(50)), Err(RecvTimeoutError::Timeout));
+    assert!(start.elapsed() >= Duration::from_millis(50));
+
+    tx.send(1).unwrap();
+    assert_eq!(rx.changed_timeout(Duration::from_millis(50)), Ok(()));
+    drop(tx);
+    assert_eq!(rx.changed_timeout(Duration::MAX), Err(RecvTimeoutError::Disconnected));
+}
+
+#[test]
+fn blocking_receivers_are_all_woken() {
+    let (tx, rx) = watch::channel(0);
+
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let mut rx = rx.clone();
+            thread::spawn(move || {
+                while rx.changed().is_ok() {
+                    if *rx.borrow_and_update() == 10 {
+                        return true;
+                    }
+                }
+                false
+            })
+        })
+        .collect();
+    drop(rx);
+
+    for i in 1..=10 {
+        tx.send(i).unwrap();
+    }
+
+    for handle in handles {
+        assert!(handle.join().unwrap());
+    }
+}
+
+#[test]
+fn changed_woken_by_disconnect() {
+    let (tx, mut rx) = watch::channel(0);
+    let handle = thread::spawn(move || rx.changed());
+    thread::sleep(Duration::from_millis(10));
+    drop(tx);
+    assert_eq!(handle.join().unwrap(), Err(RecvError));
+}
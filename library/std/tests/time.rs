@@ -1,10 +1,11 @@
 #![feature(duration_constants)]
 #![feature(duration_constructors)]
 #![feature(time_systemtime_limits)]
+#![feature(time_rfc3339)]
 #![feature(time_saturating_systemtime)]
 
 use std::fmt::Debug;
-use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH, UtcDateTime};
 
 macro_rules! assert_almost_eq {
     ($a:expr, $b:expr) => {{
@@ -310,3 +311,100 @@ fn system_time_saturating() {
         Duration::ZERO
     );
 }
+
+#[test]
+fn system_time_to_utc() {
+    let utc = UNIX_EPOCH.to_utc();
+    assert_eq!(utc, UtcDateTime::new(1970, 1, 1, 0, 0, 0, 0).unwrap());
+    assert_eq!(utc.to_system_time(), Some(UNIX_EPOCH));
+
+    let time = UNIX_EPOCH + Duration::new(1_700_000_000, 250_000_000);
+    let utc = time.to_utc();
+    assert_eq!(utc, UtcDateTime::new(2023, 11, 14, 22, 13, 20, 250_000_000).unwrap());
+    assert_eq!(utc.to_system_time(), Some(time));
+
+    // Leap days are handled.
+    let time = UNIX_EPOCH + Duration::from_secs(951_782_400);
+    assert_eq!(time.to_utc(), UtcDateTime::new(2000, 2, 29, 0, 0, 0, 0).unwrap());
+
+    if let Some(time) = UNIX_EPOCH.checked_sub(Duration::new(1, 500_000_000)) {
+        let utc = time.to_utc();
+        assert_eq!(utc, UtcDateTime::new(1969, 12, 31, 23, 59, 58, 500_000_000).unwrap());
+        assert_eq!(utc.to_system_time(), Some(time));
+    }
+}
+
+#[test]
+fn utc_date_time_new() {
+    assert!(UtcDateTime::new(2024, 2, 29, 0, 0, 0, 0).is_some());
+    assert!(UtcDateTime::new(2000, 2, 29, 0, 0, 0, 0).is_some());
+    assert!(UtcDateTime::new(1900, 2, 29, 0, 0, 0, 0).is_none());
+    assert!(UtcDateTime::new(2024, 4, 31, 0, 0, 0, 0).is_none());
+    assert!(UtcDateTime::new(2024, 0, 1, 0, 0, 0, 0).is_none());
+    assert!(UtcDateTime::new(2024, 13, 1, 0, 0, 0, 0).is_none());
+    assert!(UtcDateTime::new(2024, 1, 0, 0, 0, 0, 0).is_none());
+    assert!(UtcDateTime::new(2024, 1, 1, 0, 60, 0, 0).is_none());
+    assert!(UtcDateTime::new(2024, 1, 1, 0, 0, 60, 0).is_none());
+    assert!(UtcDateTime::new(2024, 1, 1, 0, 0, 0, 1_000_000_000).is_none());
+}
+
+#[test]
+fn utc_date_time_display() {
+    let utc = UtcDateTime::new(2023, 11, 14, 22, 13, 20, 250_000_000).unwrap();
+    assert_eq!(utc.to_string(), "2023-11-14T22:13:20.25Z");
+    assert_eq!(format!("{utc:.0}"), "2023-11-14T22:13:20Z");
+    assert_eq!(format!("{utc:.3}"), "2023-11-14T22:13:20.250Z");
+    assert_eq!(format!("{utc:.9}"), "2023-11-14T22:13:20.250000000Z");
+
+    let utc = UtcDateTime::new(1, 2, 3, 4, 5, 6, 7).unwrap();
+    assert_eq!(utc.to_string(), "0001-02-03T04:05:06.000000007Z");
+    assert_eq!(format!("{utc:.6}"), "0001-02-03T04:05:06.000000Z");
+
+    let utc = UtcDateTime::new(-1, 12, 31, 0, 0, 0, 0).unwrap();
+    assert_eq!(utc.to_string(), "-0001-12-31T00:00:00Z");
+    let utc = UtcDateTime::new(10000, 1, 1, 0, 0, 0, 0).unwrap();
+    assert_eq!(utc.to_string(), "+10000-01-01T00:00:00Z");
+}
+
+#[test]
+fn utc_date_time_parse() {
+    let parse = |s: &str| s.parse::<UtcDateTime>();
+    let utc = UtcDateTime::new(2023, 11, 14, 22, 13, 20, 250_000_000).unwrap();
+    assert_eq!(parse("2023-11-14T22:13:20.25Z"), Ok(utc));
+    assert_eq!(parse("2023-11-14t22:13:20.250z"), Ok(utc));
+    assert_eq!(parse("2023-11-14 22:13:20.250000000Z"), Ok(utc));
+    assert_eq!(parse("2023-11-15T00:13:20.25+02:00"), Ok(utc));
+    assert_eq!(parse("2023-11-14T12:43:20.25-09:30"), Ok(utc));
+    // Digits beyond nanoseconds are truncated.
+    assert_eq!(parse("2023-11-14T22:13:20.2500000009Z"), Ok(utc));
+
+    // Leap seconds are folded into the next second.
+    let utc = UtcDateTime::new(1991, 1, 1, 0, 0, 0, 0).unwrap();
+    assert_eq!(parse("1990-12-31T23:59:60Z"), Ok(utc));
+    // Offsets can move the date out of the four-digit years.
+    let utc = UtcDateTime::new(-1, 12, 31, 23, 59, 0, 0).unwrap();
+    assert_eq!(parse("0000-01-01T00:00:00+00:01"), Ok(utc));
+
+    for s in [
+        "",
+        "2023-11-14",
+        "2023-11-14T22:13:20",
+        "2023-11-14T22:13Z",
+        "2023-11-14T22:13:20.Z",
+        "2023-11-14T22:13:20Zx",
+        "2023-11-14T22:13:20+02",
+        "2023-11-14T22:13:20+24:00",
+        "2023-11-14T22:13:20+02:60",
+        "2023-11-14_22:13:20Z",
+        "23-11-14T22:13:20Z",
+        "+2023-11-14T22:13:20Z",
+        "2023-02-29T00:00:00Z",
+        "2023-00-14T22:13:20Z",
+        "2023-11-31T22:13:20Z",
+        "2023-11-14T24:00:00Z",
+        "2023-11-14T22:60:20Z",
+        "2023-11-14T22:13:61Z",
+    ] {
+        assert!(parse(s).is_err(), "{s:?} should not parse");
+    }
+}
@@ -40,7 +40,7 @@ class RustType(Enum):
     StdBoxStr = 32
 
 
-STD_STRING_REGEX = re.compile(r"^(alloc::([a-z_]+::)+)String$")
+STD_STRING_REGEX = re.compile(r"^(alloc::([a-z_]+::)+)String(<.+>)?$")
 STD_STR_REGEX = re.compile(r"^&(mut )?str$")
 STD_SLICE_REGEX = re.compile(r"^&(mut )?\[.+\]$")
 STD_OS_STRING_REGEX = re.compile(r"^(std::ffi::([a-z_]+::)+)OsString$")
@@ -881,6 +881,7 @@ fn build_macro(
                 clean::Macro {
                     source: utils::display_macro_source(tcx, name, &def),
                     macro_rules: def.macro_rules,
+                    rules: utils::macro_rules_matchers(tcx, &def),
                 },
                 macro_kinds,
             ),
@@ -2964,6 +2964,7 @@ fn get_name(tcx: TyCtxt<'_>, item: &hir::Item<'_>, renamed: Option<Symbol>) -> O
                     Macro {
                         source: display_macro_source(cx.tcx, name, macro_def),
                         macro_rules: macro_def.macro_rules,
+                        rules: macro_rules_matchers(cx.tcx, macro_def),
                     },
                     kinds,
                 ),
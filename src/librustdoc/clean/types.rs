@@ -2490,6 +2490,45 @@ pub(crate) struct Macro {
     pub(crate) source: String,
     /// Whether the macro was defined via `macro_rules!` as opposed to `macro`.
     pub(crate) macro_rules: bool,
+    /// The matchers of the macro's rules, in the order they are tried.
+    pub(crate) rules: ThinVec<MacroRule>,
+}
+
+/// The left-hand side of a declarative macro rule, e.g. `($x:expr)` in `($x:expr) => { ... }`.
+#[derive(Clone, Debug)]
+pub(crate) struct MacroRule {
+    pub(crate) kind: MacroKind,
+    /// The matcher for the arguments of an `attr(...)` rule.
+    pub(crate) args: Option<Vec<MacroMatcher>>,
+    pub(crate) matcher: Vec<MacroMatcher>,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) enum MacroMatcher {
+    /// A token that has to be matched literally, like `,` or `fn`.
+    Token(String),
+    Delimited(ast::token::Delimiter, Vec<MacroMatcher>),
+    /// A metavariable, like `$x:expr`.
+    Fragment {
+        name: Symbol,
+        kind: Symbol,
+    },
+    /// A repetition, like `$($x:expr),*`.
+    Repetition {
+        contents: Vec<MacroMatcher>,
+        separator: Option<String>,
+        op: KleeneOp,
+    },
+}
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum KleeneOp {
+    /// `*`
+    ZeroOrMore,
+    /// `+`
+    OneOrMore,
+    /// `?`
+    ZeroOrOne,
 }
 
 #[derive(Clone, Debug)]
@@ -5,8 +5,9 @@
 
 use rustc_ast as ast;
 use rustc_ast::join_path_idents;
-use rustc_ast::token::{Token, TokenKind};
-use rustc_ast::tokenstream::TokenTree;
+use rustc_ast::token::{Delimiter, Token, TokenKind};
+use rustc_ast::tokenstream::{TokenStream, TokenTree};
+use rustc_ast_pretty::pprust;
 use rustc_data_structures::thin_vec::{ThinVec, thin_vec};
 use rustc_hir as hir;
 use rustc_hir::attrs::DocAttribute;
@@ -16,6 +17,7 @@
 use rustc_metadata::rendered_const;
 use rustc_middle::mir;
 use rustc_middle::ty::{self, GenericArgKind, GenericArgsRef, TyCtxt, TypeVisitableExt};
+use rustc_span::hygiene::MacroKind;
 use rustc_span::symbol::{Symbol, kw, sym};
 use tracing::{debug, warn};
 
@@ -24,9 +26,9 @@
 use crate::clean::render_macro_matchers::render_macro_matcher;
 use crate::clean::{
     AssocItemConstraint, AssocItemConstraintKind, Crate, ExternalCrate, Generic, GenericArg,
-    GenericArgs, ImportSource, Item, ItemKind, Lifetime, Path, PathSegment, Primitive,
-    PrimitiveType, Term, Type, clean_doc_module, clean_middle_const, clean_middle_region,
-    clean_middle_ty, inline,
+    GenericArgs, ImportSource, Item, ItemKind, KleeneOp, Lifetime, MacroMatcher, MacroRule, Path,
+    PathSegment, Primitive, PrimitiveType, Term, Type, clean_doc_module, clean_middle_const,
+    clean_middle_region, clean_middle_ty, inline,
 };
 use crate::core::DocContext;
 use crate::display::Joined as _;
@@ -669,6 +671,148 @@ pub(super) fn display_macro_source(tcx: TyCtxt<'_>, name: Symbol, def: &ast::Mac
     }
 }
 
+/// Extract the matchers of all rules of a declarative macro, in the same way as
+/// [`display_macro_source`] does when rendering them.
+pub(super) fn macro_rules_matchers(tcx: TyCtxt<'_>, def: &ast::MacroDef) -> ThinVec<MacroRule> {
+    if !def.macro_rules && def.body.tokens.len() <= 4 {
+        return def
+            .body
+            .tokens
+            .get(0)
+            .map(|matcher| MacroRule {
+                kind: MacroKind::Bang,
+                args: None,
+                matcher: matchers_of_token_tree(matcher),
+            })
+            .into_iter()
+            .collect();
+    }
+
+    let mut rules = ThinVec::new();
+    let mut tokens = def.body.tokens.iter();
+    while let Some(mut token) = tokens.next() {
+        if let TokenTree::Token(t, _) = token
+            && t.is_keyword(kw::Unsafe)
+        {
+            let Some(next) = tokens.next() else { break };
+            token = next;
+        }
+        // `attr(args) (input) => {}` and `derive() (input) => {}` rules.
+        let (kind, args) = if let TokenTree::Token(t, _) = token {
+            let kind = if t.is_ident_named(sym::attr) {
+                MacroKind::Attr
+            } else if t.is_ident_named(sym::derive) {
+                MacroKind::Derive
+            } else {
+                // The compiler rejects rules that start with anything else.
+                tcx.dcx().span_delayed_bug(t.span, "unexpected token at the start of a macro rule");
+                break;
+            };
+            let args = tokens.next().map(matchers_of_token_tree);
+            let Some(next) = tokens.next() else { break };
+            token = next;
+            (kind, if kind == MacroKind::Attr { args } else { None })
+        } else {
+            (MacroKind::Bang, None)
+        };
+        rules.push(MacroRule { kind, args, matcher: matchers_of_token_tree(token) });
+        // Skip the `=>`, the body and the delimiter after it, like `render_macro_arms` does.
+        tokens.next();
+        tokens.next();
+        tokens.next();
+    }
+    rules
+}
+
+fn matchers_of_token_tree(tree: &TokenTree) -> Vec<MacroMatcher> {
+    match tree {
+        TokenTree::Delimited(_, _, _, stream) => matchers_of_token_stream(stream),
+        TokenTree::Token(..) => Vec::new(),
+    }
+}
+
+fn matchers_of_token_stream(stream: &TokenStream) -> Vec<MacroMatcher> {
+    fn kleene_op(tree: Option<&TokenTree>) -> Option<KleeneOp> {
+        match tree {
+            Some(TokenTree::Token(Token { kind: TokenKind::Star, .. }, _)) => {
+                Some(KleeneOp::ZeroOrMore)
+            }
+            Some(TokenTree::Token(Token { kind: TokenKind::Plus, .. }, _)) => {
+                Some(KleeneOp::OneOrMore)
+            }
+            Some(TokenTree::Token(Token { kind: TokenKind::Question, .. }, _)) => {
+                Some(KleeneOp::ZeroOrOne)
+            }
+            _ => None,
+        }
+    }
+
+    let mut matchers = Vec::new();
+    let mut tokens = stream.iter().peekable();
+    while let Some(tree) = tokens.next() {
+        let TokenTree::Token(Token { kind: TokenKind::Dollar, .. }, _) = tree else {
+            match tree {
+                TokenTree::Token(token, _) => {
+                    matchers.push(MacroMatcher::Token(pprust::token_to_string(token).into_owned()))
+                }
+                // Invisible delimiters come from other macro expansions and aren't written out.
+                TokenTree::Delimited(_, _, Delimiter::Invisible(_), stream) => {
+                    matchers.extend(matchers_of_token_stream(stream))
+                }
+                TokenTree::Delimited(_, _, delim, stream) => {
+                    matchers.push(MacroMatcher::Delimited(*delim, matchers_of_token_stream(stream)))
+                }
+            }
+            continue;
+        };
+
+        let mut lookahead = tokens.clone();
+        if let Some(TokenTree::Token(name, _)) = lookahead.next()
+            && let Some((name, _)) = name.ident()
+            && let Some(TokenTree::Token(Token { kind: TokenKind::Colon, .. }, _)) =
+                lookahead.next()
+            && let Some(TokenTree::Token(kind, _)) = lookahead.next()
+            && let Some((kind, _)) = kind.ident()
+        {
+            // `$name:kind`
+            matchers.push(MacroMatcher::Fragment { name: name.name, kind: kind.name });
+            tokens = lookahead;
+        } else if let Some(TokenTree::Delimited(_, _, Delimiter::Parenthesis, contents)) =
+            tokens.peek()
+        {
+            // `$(contents) separator op`
+            let contents = matchers_of_token_stream(contents);
+            tokens.next();
+            // Like in rustc, a leading `?`, `*` or `+` is always the operator: `$(a)* *` is a
+            // repetition without separator followed by a literal `*`. Any other token is the
+            // separator, and the operator comes right after it.
+            let (separator, op) = match kleene_op(tokens.peek().copied()) {
+                Some(op) => {
+                    tokens.next();
+                    (None, op)
+                }
+                None => {
+                    let separator = match tokens.next() {
+                        Some(TokenTree::Token(token, _)) => {
+                            Some(pprust::token_to_string(token).into_owned())
+                        }
+                        _ => None,
+                    };
+                    (separator, kleene_op(tokens.next()).unwrap_or(KleeneOp::ZeroOrMore))
+                }
+            };
+            matchers.push(MacroMatcher::Repetition { contents, separator, op });
+        } else {
+            // `$$`, which matches a literal `$`.
+            tokens.next_if(|tree| {
+                matches!(tree, TokenTree::Token(Token { kind: TokenKind::Dollar, .. }, _))
+            });
+            matchers.push(MacroMatcher::Token("$".to_owned()));
+        }
+    }
+    matchers
+}
+
 pub(crate) fn inherits_doc_hidden(
     tcx: TyCtxt<'_>,
     mut def_id: LocalDefId,
@@ -73,6 +73,18 @@ pub(crate) enum MergeDoctests {
     Auto,
 }
 
+/// How rustdoc communicates with the `--test-runtool`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub(crate) enum RuntoolProtocol {
+    /// The runtool receives the path to the doctest executable as last argument, and its exit
+    /// status and output are used as if they were the executable's.
+    #[default]
+    Plain,
+    /// The runtool receives a JSON description of the doctest on its standard input, and reports
+    /// the result as JSON on its standard output.
+    Json,
+}
+
 /// Configuration options for rustdoc.
 #[derive(Clone)]
 pub(crate) struct Options {
@@ -137,6 +149,8 @@ pub(crate) struct Options {
     pub(crate) test_runtool: Option<String>,
     /// Arguments to pass to the runtool
     pub(crate) test_runtool_args: Vec<String>,
+    /// How to communicate with the runtool
+    pub(crate) test_runtool_protocol: RuntoolProtocol,
     /// Do not run doctests, compile them if should_test is active.
     pub(crate) no_run: bool,
     /// What sources are being mapped.
@@ -221,6 +235,7 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
             .field("crate_version", &self.crate_version)
             .field("test_runtool", &self.test_runtool)
             .field("test_runtool_args", &self.test_runtool_args)
+            .field("test_runtool_protocol", &self.test_runtool_protocol)
             .field("run_check", &self.run_check)
             .field("no_run", &self.no_run)
             .field("test_builder_wrappers", &self.test_builder_wrappers)
@@ -316,6 +331,9 @@ pub(crate) struct RenderOptions {
     pub(crate) disable_minification: bool,
     /// If `true`, HTML source pages will generate the possibility to expand macros.
     pub(crate) generate_macro_expansion: bool,
+    /// If present, the path of a single HTML file to pack the documentation directory into once
+    /// rendering is done.
+    pub(crate) html_bundle: Option<PathBuf>,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -857,12 +875,26 @@ fn println_condition(condition: Condition) {
         let extern_html_root_takes_precedence =
             matches.opt_present("extern-html-root-takes-precedence");
         let html_no_source = matches.opt_present("html-no-source");
+        let html_bundle = matches.opt_str("html-bundle").map(PathBuf::from);
         let should_merge = match parse_merge(matches) {
             Ok(result) => result,
             Err(e) => dcx.fatal(format!("--merge option error: {e}")),
         };
         let merge_doctests = parse_merge_doctests(matches, edition, dcx);
         tracing::debug!("merge_doctests: {merge_doctests:?}");
+        let test_runtool_protocol = parse_runtool_protocol(matches, dcx);
+        if test_runtool_protocol == RuntoolProtocol::Json {
+            if test_runtool.is_none() {
+                dcx.fatal("`--test-runtool-protocol=json` requires `--test-runtool`");
+            }
+            // Merged doctests all run from a single executable, which cannot be described by a
+            // single runtool request.
+            if merge_doctests == MergeDoctests::Always {
+                dcx.fatal(
+                    "`--test-runtool-protocol=json` cannot be used with `--merge-doctests=yes`",
+                );
+            }
+        }
 
         if generate_link_to_definition && (show_coverage || output_format != OutputFormat::Html) {
             dcx.struct_warn(
@@ -878,6 +910,17 @@ fn println_condition(condition: Condition) {
             .with_note("`--generate-macro-expansion` option will be ignored")
             .emit();
         }
+        if html_bundle.is_some() && (show_coverage || output_format != OutputFormat::Html) {
+            dcx.struct_warn("`--html-bundle` option can only be used with HTML output format")
+                .with_note("`--html-bundle` option will be ignored")
+                .emit();
+        }
+        if html_bundle.is_some() && static_root_path.is_some() {
+            dcx.fatal(
+                "`--html-bundle` cannot be used with `--static-root-path`, \
+                 because the static files would be missing from the bundle",
+            );
+        }
 
         let scrape_examples_options = ScrapeExamplesOptions::new(matches, dcx);
         let with_examples = matches.opt_strs("with-examples");
@@ -917,6 +960,7 @@ fn println_condition(condition: Condition) {
             merge_doctests,
             test_runtool,
             test_runtool_args,
+            test_runtool_protocol,
             test_builder,
             run_check,
             no_run,
@@ -966,6 +1010,7 @@ fn println_condition(condition: Condition) {
             include_parts_dir,
             parts_out_dir,
             disable_minification,
+            html_bundle,
         };
         Some((input, options, render_options, loaded_paths))
     }
@@ -1113,6 +1158,14 @@ fn parse_merge(m: &getopts::Matches) -> Result<ShouldMerge, &'static str> {
     }
 }
 
+fn parse_runtool_protocol(m: &getopts::Matches, dcx: DiagCtxtHandle<'_>) -> RuntoolProtocol {
+    match m.opt_str("test-runtool-protocol").as_deref() {
+        None | Some("plain") => RuntoolProtocol::Plain,
+        Some("json") => RuntoolProtocol::Json,
+        Some(_) => dcx.fatal("argument to --test-runtool-protocol must be `plain` or `json`"),
+    }
+}
+
 fn parse_merge_doctests(
     m: &getopts::Matches,
     edition: Edition,
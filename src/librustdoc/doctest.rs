@@ -2,6 +2,7 @@
 mod make;
 mod markdown;
 mod runner;
+mod runtool;
 mod rust;
 
 use std::fs::File;
@@ -35,7 +36,7 @@
 use tracing::{debug, info};
 
 use self::rust::HirCollector;
-use crate::config::{MergeDoctests, Options as RustdocOptions, OutputFormat};
+use crate::config::{MergeDoctests, Options as RustdocOptions, OutputFormat, RuntoolProtocol};
 use crate::html::markdown::{CodeLineMapping, ErrorCodes, Ignore, LangString, MdRelLine};
 use crate::lint::init_lints;
 
@@ -487,6 +488,8 @@ enum TestFailure {
     ExecutionFailure(process::Output),
     /// The test is marked `should_panic` but the test binary executed successfully.
     UnexpectedRunPass,
+    /// The runtool reported that the test binary failed, with `--test-runtool-protocol=json`.
+    RuntoolFailure(runtool::Response),
 }
 
 enum DirState {
@@ -544,6 +547,7 @@ fn wrapped_rustc_command(rustc_wrappers: &[PathBuf], rustc_binary: &Path) -> Com
 /// and everything needed to calculate the compiler's command-line arguments.
 /// The `# ` prefix on boring lines has also been stripped.
 pub(crate) struct RunnableDocTest {
+    name: String,
     full_test_code: String,
     full_test_line_offset: usize,
     test_opts: IndividualTestOptions,
@@ -849,7 +853,9 @@ fn drop(&mut self) {
         let tool = make_maybe_absolute_path(tool.into());
         cmd = Command::new(tool);
         cmd.args(&rustdoc_options.test_runtool_args);
-        cmd.arg(&output_file);
+        if rustdoc_options.test_runtool_protocol == RuntoolProtocol::Plain {
+            cmd.arg(&output_file);
+        }
     } else {
         cmd = Command::new(&output_file);
         if doctest.is_multiple_tests() {
@@ -862,6 +868,30 @@ fn drop(&mut self) {
 
     info!("running doctest executable: {cmd:?}");
 
+    if rustdoc_options.test_runtool_protocol == RuntoolProtocol::Json {
+        let request = runtool::Request::new(
+            &doctest.name,
+            &doctest.test_opts.path,
+            doctest.line,
+            &output_file,
+            langstr.should_panic,
+            doctest.edition,
+            rustdoc_options.test_run_directory.as_deref(),
+        );
+        let result = match runtool::run(cmd, &request) {
+            Err(e) => Err(TestFailure::ExecutionError(e)),
+            Ok(response) => match (response.outcome, langstr.should_panic) {
+                (runtool::Outcome::Error, _) => Err(TestFailure::ExecutionError(io::Error::other(
+                    response.message.unwrap_or_else(|| "the runtool reported an error".into()),
+                ))),
+                (runtool::Outcome::Success, true) => Err(TestFailure::UnexpectedRunPass),
+                (runtool::Outcome::Failure, false) => Err(TestFailure::RuntoolFailure(response)),
+                (runtool::Outcome::Success, false) | (runtool::Outcome::Failure, true) => Ok(()),
+            },
+        };
+        return (duration, result);
+    }
+
     let result = if doctest.is_multiple_tests() || rustdoc_options.no_capture {
         cmd.status().map(|status| process::Output {
             status,
@@ -1067,6 +1097,7 @@ fn add_test(&mut self, scraped_test: ScrapedDocTest, dcx: Option<DiagCtxtHandle<
             .build(dcx);
         let is_standalone = !doctest.can_be_merged
             || self.rustdoc_options.no_capture
+            || self.rustdoc_options.test_runtool_protocol == RuntoolProtocol::Json
             || self.rustdoc_options.test_args.iter().any(|arg| arg == "--show-output");
         if is_standalone {
             let test_desc = self.generate_test_desc_and_fn(doctest, scraped_test);
@@ -1168,6 +1199,7 @@ fn doctest_run_fn(
         Some(&global_opts.crate_name),
     );
     let runnable_test = RunnableDocTest {
+        name: scraped_test.name.clone(),
         full_test_code: wrapped.to_string(),
         full_test_line_offset,
         test_opts,
@@ -1213,20 +1245,17 @@ fn doctest_run_fn(
                 // The ordering could be preserved if the test process' stderr was
                 // redirected to stdout, but that functionality does not exist in the
                 // standard library, so it may not be portable enough.
-                let stdout = str::from_utf8(&out.stdout).unwrap_or_default();
-                let stderr = str::from_utf8(&out.stderr).unwrap_or_default();
-
-                if !stdout.is_empty() || !stderr.is_empty() {
-                    eprintln!();
-
-                    if !stdout.is_empty() {
-                        eprintln!("stdout:\n{stdout}");
-                    }
-
-                    if !stderr.is_empty() {
-                        eprintln!("stderr:\n{stderr}");
-                    }
+                print_test_output(
+                    str::from_utf8(&out.stdout).unwrap_or_default(),
+                    str::from_utf8(&out.stderr).unwrap_or_default(),
+                );
+            }
+            TestFailure::RuntoolFailure(response) => {
+                match response.exit_code {
+                    Some(code) => eprintln!("Test executable failed (exit status: {code})."),
+                    None => eprintln!("Test executable failed."),
                 }
+                print_test_output(&response.stdout, &response.stderr);
             }
         }
 
@@ -1235,6 +1264,20 @@ fn doctest_run_fn(
     Ok(())
 }
 
+fn print_test_output(stdout: &str, stderr: &str) {
+    if !stdout.is_empty() || !stderr.is_empty() {
+        eprintln!();
+
+        if !stdout.is_empty() {
+            eprintln!("stdout:\n{stdout}");
+        }
+
+        if !stderr.is_empty() {
+            eprintln!("stderr:\n{stderr}");
+        }
+    }
+}
+
 #[cfg(test)] // used in tests
 impl DocTestVisitor for Vec<usize> {
     fn visit_test(
@@ -17,7 +17,7 @@
 /// This integer is incremented with every breaking change to the API,
 /// and is returned along with the JSON blob into the `format_version` root field.
 /// Consuming code should assert that this value matches the format version(s) that it supports.
-const FORMAT_VERSION: u32 = 2;
+const FORMAT_VERSION: u32 = 3;
 
 #[derive(Serialize)]
 pub(crate) struct ExtractedDocTests {
@@ -65,6 +65,7 @@ pub(crate) fn add_test_with_edition(
         self.doctests.push(ExtractedDocTest {
             file: filename.display(RemapPathScopeComponents::DOCUMENTATION).to_string(),
             line,
+            edition: edition.to_string(),
             doctest_attributes: langstr.into(),
             doctest_code: match wrapped {
                 DocTestWrapResult::Valid { crate_level_code, wrapper, code } => Some(DocTest {
@@ -112,6 +113,9 @@ pub(crate) struct DocTest {
 pub(crate) struct ExtractedDocTest {
     file: String,
     line: usize,
+    /// The edition the doctest is compiled with, whether it comes from its attributes or from
+    /// the crate's edition.
+    edition: String,
     doctest_attributes: LangString,
     original_code: String,
     /// `None` if the code syntax is invalid.
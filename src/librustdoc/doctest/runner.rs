@@ -199,6 +199,7 @@ fn main() -> std::process::ExitCode {{
         )
         .expect("failed to generate test code");
         let runnable_test = RunnableDocTest {
+            name: format!("merged doctests ({edition})"),
             full_test_code: format!("{code_prefix}{code}", code = self.output),
             full_test_line_offset: 0,
             test_opts: test_options,
@@ -0,0 +1,122 @@
+//! The protocol used to talk to the `--test-runtool` with `--test-runtool-protocol=json`.
+//!
+//! The runtool is spawned once for every doctest to run, with the `--test-runtool-arg`s as its
+//! arguments. Rustdoc writes a single [`Request`] as one line of JSON on its standard input and
+//! closes it. The runtool is then expected to run the doctest executable however it sees fit, and
+//! to print a single [`Response`] as JSON on the last non-empty line of its standard output.
+
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use rustc_span::edition::Edition;
+use serde::{Deserialize, Serialize};
+
+/// The version of the protocol that this code implements.
+///
+/// This integer is incremented with every breaking change to the protocol, and is sent along with
+/// every request in the `format_version` field.
+const FORMAT_VERSION: u32 = 1;
+
+/// Everything a runtool needs to know to run a doctest.
+#[derive(Serialize)]
+pub(super) struct Request<'a> {
+    format_version: u32,
+    /// The name of the doctest, as displayed by the test harness.
+    name: &'a str,
+    file: &'a Path,
+    line: usize,
+    /// The compiled doctest to run.
+    executable: &'a Path,
+    /// Whether the executable is expected to fail.
+    should_panic: bool,
+    edition: String,
+    /// The directory the executable should be run in, if one was given with
+    /// `--test-run-directory`.
+    run_directory: Option<&'a Path>,
+}
+
+impl<'a> Request<'a> {
+    pub(super) fn new(
+        name: &'a str,
+        file: &'a Path,
+        line: usize,
+        executable: &'a Path,
+        should_panic: bool,
+        edition: Edition,
+        run_directory: Option<&'a Path>,
+    ) -> Self {
+        Self {
+            format_version: FORMAT_VERSION,
+            name,
+            file,
+            line,
+            executable,
+            should_panic,
+            edition: edition.to_string(),
+            run_directory,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub(super) enum Outcome {
+    /// The executable ran and exited successfully.
+    Success,
+    /// The executable ran and failed, because of a panic or otherwise.
+    Failure,
+    /// The runtool couldn't run the executable at all.
+    Error,
+}
+
+/// The result of a doctest, as reported by the runtool.
+#[derive(Deserialize, Debug)]
+pub(super) struct Response {
+    pub(super) outcome: Outcome,
+    #[serde(default)]
+    pub(super) exit_code: Option<i32>,
+    #[serde(default)]
+    pub(super) stdout: String,
+    #[serde(default)]
+    pub(super) stderr: String,
+    /// A description of what went wrong, mostly useful when `outcome` is `error`.
+    #[serde(default)]
+    pub(super) message: Option<String>,
+}
+
+/// Sends `request` to the runtool that `cmd` spawns, and waits for its response.
+pub(super) fn run(mut cmd: Command, request: &Request<'_>) -> io::Result<Response> {
+    let request = serde_json::to_string(request).map_err(io::Error::other)?;
+    cmd.stdin(Stdio::piped()).stdout(Stdio::piped()).stderr(Stdio::piped());
+    let mut child = cmd.spawn()?;
+    {
+        // Dropping `stdin` closes it, so runtools can read until the end of their input.
+        let mut stdin = child.stdin.take().expect("Failed to open stdin");
+        writeln!(stdin, "{request}")?;
+    }
+    let output = child.wait_with_output()?;
+    parse_response(&String::from_utf8_lossy(&output.stdout)).map_err(|error| {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if stderr.trim().is_empty() {
+            error
+        } else {
+            io::Error::new(error.kind(), format!("{error}\nrunner stderr:\n{stderr}"))
+        }
+    })
+}
+
+pub(super) fn parse_response(stdout: &str) -> io::Result<Response> {
+    let Some(line) = stdout.lines().rev().find(|line| !line.trim().is_empty()) else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "the runtool did not report any result",
+        ));
+    };
+    serde_json::from_str(line).map_err(|error| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("invalid result reported by the runtool (`{line}`): {error}"),
+        )
+    })
+}
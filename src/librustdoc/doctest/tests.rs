@@ -4,6 +4,7 @@
 use rustc_span::{DUMMY_SP, FileName};
 
 use super::extracted::ExtractedDocTests;
+use super::runtool::{Outcome, parse_response};
 use super::{BuildDocTestBuilder, GlobalTestOptions, ScrapedDocTest};
 use crate::html::markdown::LangString;
 
@@ -515,3 +516,26 @@ fn test_extracted_doctest_wrapper_field_with_info() {
     let doctest_code = extractor.doctests()[0].doctest_code.as_ref().unwrap();
     assert!(doctest_code.wrapper.is_some());
 }
+
+#[test]
+fn test_runtool_response() {
+    let response = parse_response(
+        "some output\n{\"outcome\":\"failure\",\"exit_code\":101,\"stderr\":\"oh no\"}\n\n",
+    )
+    .unwrap();
+    assert_eq!(response.outcome, Outcome::Failure);
+    assert_eq!(response.exit_code, Some(101));
+    assert_eq!(response.stdout, "");
+    assert_eq!(response.stderr, "oh no");
+    assert_eq!(response.message, None);
+
+    let response = parse_response("{\"outcome\":\"success\"}").unwrap();
+    assert_eq!(response.outcome, Outcome::Success);
+}
+
+#[test]
+fn test_runtool_invalid_response() {
+    assert!(parse_response("").is_err());
+    assert!(parse_response("{\"outcome\":\"success\"}\nnot json").is_err());
+    assert!(parse_response("{\"outcome\":\"maybe\"}").is_err());
+}
@@ -6,6 +6,7 @@
 
 use super::static_files::{STATIC_FILES, StaticFiles};
 use crate::externalfiles::ExternalHtml;
+use crate::html::escape::Escape;
 use crate::html::render::{StylePath, ensure_trailing_slash};
 
 #[cfg(test)]
@@ -146,6 +147,35 @@ pub(crate) fn redirect(url: &str) -> String {
     )
 }
 
+/// The loader of `--html-bundle` bundles, which is inlined into them instead of being written to
+/// the output directory like the other scripts.
+static BUNDLE_JS: &str = include_str!("static/js/bundle.js");
+
+/// Renders the single-file bundle of `--html-bundle`. `files_json` is the JSON object of the
+/// embedded files, and must not contain any `<`. The loader shows `start_page` unless the URL of
+/// the bundle names another page.
+pub(crate) fn bundle(title: &str, start_page: &str, files_json: &str) -> String {
+    debug_assert!(!files_json.contains('<'));
+    format!(
+        r##"<!DOCTYPE html>
+<html lang="en">
+<head>
+    <meta charset="utf-8">
+    <title>{title}</title>
+    <style>
+        html, body, iframe {{ margin: 0; border: 0; width: 100%; height: 100%; display: block; }}
+    </style>
+</head>
+<body data-start-page="{start_page}">
+    <script id="bundle-files" type="application/json">{files_json}</script>
+    <script>{BUNDLE_JS}</script>
+</body>
+</html>"##,
+        title = Escape(title),
+        start_page = Escape(start_page),
+    )
+}
+
 /// Conservatively determines if `href` is relative to the current origin,
 /// so that `crossorigin` may be safely removed from `<link>` elements.
 pub(crate) fn may_remove_crossorigin(href: &str) -> bool {
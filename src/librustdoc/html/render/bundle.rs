@@ -0,0 +1,103 @@
+//! Packing of the generated documentation into a single HTML file, for `--html-bundle`.
+//!
+//! The bundle embeds every file of the output directory (pages, static files, search index and
+//! source pages) in a JSON object, next to a small loader script. The loader renders the
+//! requested page into a frame and serves everything that page refers to from the embedded
+//! files, so the bundle can be opened from `file://` like any other web page, without a server
+//! or extracting anything first.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use rustc_span::Symbol;
+use serde::Serialize;
+
+use crate::html::layout;
+
+/// The files of a bundle, keyed by their path relative to the output directory, always with `/`
+/// as separator. Ordered maps keep bundles reproducible.
+#[derive(Default, Serialize)]
+struct Files {
+    /// Files that are valid UTF-8, like pages, scripts and stylesheets.
+    text: BTreeMap<String, String>,
+    /// Everything else, like fonts and images, encoded in base64.
+    base64: BTreeMap<String, String>,
+}
+
+/// Packs every file in `root` into a single HTML file at `bundle`.
+pub(super) fn write_bundle(root: &Path, bundle: &Path, crate_name: Symbol) -> io::Result<()> {
+    let mut paths = Vec::new();
+    collect_files(root, &mut paths)?;
+
+    // The bundle may be written inside the documentation directory itself, and must not end up
+    // containing a previous version of itself.
+    let bundle_in_root = bundle.parent().is_some_and(|parent| {
+        let parent = if parent.as_os_str().is_empty() { Path::new(".") } else { parent };
+        parent.canonicalize().ok() == root.canonicalize().ok()
+    });
+
+    let mut files = Files::default();
+    for path in &paths {
+        let relative = path.strip_prefix(root).unwrap();
+        if bundle_in_root && Some(relative.as_os_str()) == bundle.file_name() {
+            continue;
+        }
+        let Some(name) = bundle_path(relative) else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("cannot add non-UTF-8 path `{}` to the bundle", relative.display()),
+            ));
+        };
+        match String::from_utf8(fs::read(path)?) {
+            Ok(text) => files.text.insert(name, text),
+            Err(error) => files.base64.insert(name, BASE64.encode(error.into_bytes())),
+        };
+    }
+
+    // Without an `index.html` at the root, like when documenting a single crate, the bundle
+    // opens on the crate's page.
+    let start_page = if files.text.contains_key("index.html") {
+        "index.html".to_owned()
+    } else {
+        format!("{crate_name}/index.html")
+    };
+    let title = format!("{crate_name} - Rust documentation");
+    fs::write(bundle, layout::bundle(&title, &start_page, &files_json(&files)))
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        // Skip the `.lock` file Cargo leaves in the doc directory, and similar hidden files.
+        if entry.file_name().as_encoded_bytes().starts_with(b".") {
+            continue;
+        }
+        if entry.file_type()?.is_dir() {
+            collect_files(&entry.path(), files)?;
+        } else {
+            files.push(entry.path());
+        }
+    }
+    Ok(())
+}
+
+/// Paths in the bundle always use `/` as separator, whatever the host platform is.
+fn bundle_path(relative: &Path) -> Option<String> {
+    let components =
+        relative.components().map(|c| c.as_os_str().to_str()).collect::<Option<Vec<_>>>()?;
+    Some(components.join("/"))
+}
+
+/// Serializes the files into a JSON object that can be put into a `<script>` element as is:
+/// `<` is escaped everywhere, so no file can end the element early with `</script>`.
+fn files_json(files: &Files) -> String {
+    // `<` can only appear inside JSON strings, where `\u003c` stands for the same character.
+    serde_json::to_string(files).unwrap().replace('<', "\\u003c")
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,29 @@
+use std::collections::BTreeMap;
+
+use super::{Files, bundle_path, files_json};
+
+#[test]
+fn json_cannot_end_script() {
+    let mut files = Files::default();
+    files.text.insert("krate/<index>.html".to_owned(), "<script>a</script>".to_owned());
+    files.base64.insert("font.woff2".to_owned(), "AAE=".to_owned());
+    let json = files_json(&files);
+    assert!(!json.contains('<'));
+    assert_eq!(
+        json,
+        concat!(
+            r#"{"text":{"krate/\u003cindex>.html":"\u003cscript>a\u003c/script>"},"#,
+            r#""base64":{"font.woff2":"AAE="}}"#,
+        ),
+    );
+
+    // The escapes decode back to the original files.
+    let decoded: BTreeMap<String, BTreeMap<String, String>> = serde_json::from_str(&json).unwrap();
+    assert_eq!(decoded["text"]["krate/<index>.html"], "<script>a</script>");
+}
+
+#[test]
+fn paths_use_slashes() {
+    let relative: std::path::PathBuf = ["krate", "sub", "struct.Foo.html"].iter().collect();
+    assert_eq!(bundle_path(&relative).unwrap(), "krate/sub/struct.Foo.html");
+}
@@ -33,6 +33,7 @@
 use crate::html::escape::Escape;
 use crate::html::macro_expansion::ExpandedCode;
 use crate::html::markdown::{self, ErrorCodes, IdMap, plain_text_summary};
+use crate::html::render::bundle::write_bundle;
 use crate::html::render::write_shared::write_shared;
 use crate::html::span_map::{LinkFromSrc, Span, collect_spans_and_sources};
 use crate::html::url_parts_builder::UrlPartsBuilder;
@@ -151,6 +152,9 @@ pub(crate) struct SharedContext<'tcx> {
     /// Controls whether we read / write to cci files in the doc root. Defaults read=true,
     /// write=true
     should_merge: ShouldMerge,
+    /// `None` by default, depends on the `--html-bundle` option. If set, the whole output
+    /// directory is packed into a single HTML file at this path once everything else is written.
+    html_bundle: Option<PathBuf>,
 }
 
 impl SharedContext<'_> {
@@ -526,6 +530,7 @@ pub(crate) fn init(
             call_locations,
             no_emit_shared,
             html_no_source,
+            html_bundle,
             ..
         } = options;
 
@@ -617,6 +622,7 @@ pub(crate) fn init(
             call_locations,
             should_merge: options.should_merge,
             expanded_codes,
+            html_bundle,
         };
 
         let dst = output;
@@ -817,7 +823,15 @@ fn after_krate(mut self) -> Result<(), Error> {
         // Flush pending errors.
         self.shared.fs.close();
         let nb_errors = self.shared.errors.iter().map(|err| self.tcx().dcx().err(err)).count();
-        if nb_errors > 0 { Err(Error::new(io::Error::other("I/O error"), "")) } else { Ok(()) }
+        if nb_errors > 0 {
+            return Err(Error::new(io::Error::other("I/O error"), ""));
+        }
+
+        // The bundle has to be written last, once every other file is on disk.
+        if let Some(ref bundle) = self.shared.html_bundle {
+            try_err!(write_bundle(&self.dst, bundle, crate_name), bundle);
+        }
+        Ok(())
     }
 
     fn mod_item_in(&mut self, item: &clean::Item) -> Result<(), Error> {
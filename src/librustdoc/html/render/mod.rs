@@ -31,6 +31,7 @@
 #[cfg(test)]
 mod tests;
 
+mod bundle;
 mod context;
 mod ordered_json;
 mod print_item;
@@ -5,7 +5,7 @@
 use std::collections::hash_map::Entry;
 use std::path::Path;
 use std::string::FromUtf8Error;
-use std::{io, iter};
+use std::{io, iter, slice};
 
 use ::serde::de::{self, Deserializer, Error as _};
 use ::serde::ser::{SerializeSeq, Serializer};
@@ -372,6 +372,9 @@ pub(crate) fn union(mut self, other: &SerializedSearchIndex) -> SerializedSearch
                                         list
                                     })
                                     .collect(),
+                                // filled in below, once every path ID has been mapped
+                                implementors: Vec::new(),
+                                trait_impls: Vec::new(),
                             }),
                             (Some(mut self_type_data), Some(other_type_data)) => {
                                 for (size, other_list) in other_type_data
@@ -555,6 +558,9 @@ fn map_fn_sig_item(
                                 list
                             })
                             .collect(),
+                        // filled in below, once every path ID has been mapped
+                        implementors: Vec::new(),
+                        trait_impls: Vec::new(),
                         search_unbox: type_data.search_unbox,
                     }),
                     other.alias_pointers[other_entryid]
@@ -562,6 +568,35 @@ fn map_fn_sig_item(
                 );
             }
         }
+        // Trait impls refer to types by their path ID, and the implementor may
+        // be a path that was deduplicated above, so this is done separately.
+        let map_pathid = |id: usize| {
+            let id = map_other_pathid_to_self_pathid[id];
+            assert!(id != !0);
+            id
+        };
+        for (other_pathid, other_type_data) in other.type_data.iter().enumerate() {
+            let Some(other_type_data) = other_type_data else { continue };
+            if other_type_data.implementors.is_empty() && other_type_data.trait_impls.is_empty() {
+                continue;
+            }
+            let self_type_data = self.type_data[map_pathid(other_pathid)].as_mut().unwrap();
+            self_type_data.implementors.extend(
+                other_type_data
+                    .implementors
+                    .iter()
+                    .map(|&id| u32::try_from(map_pathid(usize::try_from(id).unwrap())).unwrap()),
+            );
+            self_type_data.implementors.sort_unstable();
+            self_type_data.implementors.dedup();
+            for trait_ in &other_type_data.trait_impls {
+                let mut trait_ = trait_.clone();
+                map_render_type_ids(&mut trait_, &map_pathid);
+                if !self_type_data.trait_impls.contains(&trait_) {
+                    self_type_data.trait_impls.push(trait_);
+                }
+            }
+        }
         if other.generic_inverted_index.len() > self.generic_inverted_index.len() {
             self.generic_inverted_index.resize(other.generic_inverted_index.len(), Vec::new());
         }
@@ -690,6 +725,8 @@ fn map_fn_sig_item(map: &FxHashMap<usize, usize>, ty: &mut RenderType) {
                          search_unbox,
                          inverted_function_inputs_index,
                          inverted_function_output_index,
+                         implementors,
+                         trait_impls,
                      }| {
                         let inverted_function_inputs_index: Vec<Vec<u32>> =
                             inverted_function_inputs_index
@@ -721,10 +758,27 @@ fn map_fn_sig_item(map: &FxHashMap<usize, usize>, ty: &mut RenderType) {
                                     list
                                 })
                                 .collect();
+                        let mut implementors: Vec<u32> = implementors
+                            .iter()
+                            .filter_map(|&id| {
+                                u32::try_from(*map.get(&usize::try_from(id).ok()?)?).ok()
+                            })
+                            .collect();
+                        implementors.sort();
+                        let trait_impls = trait_impls
+                            .iter()
+                            .cloned()
+                            .map(|mut trait_| {
+                                map_render_type_ids(&mut trait_, &|id| *map.get(&id).unwrap());
+                                trait_
+                            })
+                            .collect();
                         TypeData {
                             search_unbox: *search_unbox,
                             inverted_function_inputs_index,
                             inverted_function_output_index,
+                            implementors,
+                            trait_impls,
                         }
                     },
                 ),
@@ -893,6 +947,27 @@ fn perform_write_postings(
     }
 }
 
+/// Replace every path ID in `ty` with `map(id)`. Generic parameters are left as-is.
+fn map_render_type_ids(ty: &mut RenderType, map: &impl Fn(usize) -> usize) {
+    let map_id = |id: RenderTypeId| match id {
+        RenderTypeId::Index(generic) if generic < 0 => id,
+        RenderTypeId::Index(id) => {
+            RenderTypeId::Index(isize::try_from(map(usize::try_from(id).unwrap())).unwrap())
+        }
+        _ => unreachable!(),
+    };
+    ty.id = ty.id.map(map_id);
+    for generic in ty.generics.iter_mut().flatten() {
+        map_render_type_ids(generic, map);
+    }
+    for (param, constraints) in ty.bindings.iter_mut().flatten() {
+        *param = map_id(*param);
+        for constraint in constraints {
+            map_render_type_ids(constraint, map);
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 struct EntryData {
     krate: usize,
@@ -1071,6 +1146,15 @@ struct TypeData {
     /// List of functions that mention this type in their type signature,
     /// on the right side of the `->` arrow.
     inverted_function_output_index: Vec<Vec<u32>>,
+    /// List of types that implement this trait, sorted by row ID.
+    ///
+    /// This is only filled in for traits. The full trait reference, with
+    /// generics and associated types, is stored in the implementor's
+    /// `trait_impls`.
+    implementors: Vec<u32>,
+    /// List of traits implemented by this type, including their generics and
+    /// the associated types from the impl block, such as `Iterator<Item=u8>`.
+    trait_impls: Vec<RenderType>,
 }
 
 impl Serialize for TypeData {
@@ -1089,8 +1173,23 @@ fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
         encode::write_postings_to_string(&self.inverted_function_output_index, &mut buf);
         stringdex_internals::encode::write_base64_to_bytes(&buf, &mut serialized_result).unwrap();
         seq.serialize_element(&str::from_utf8(&serialized_result).unwrap())?;
-        if self.search_unbox {
-            seq.serialize_element(&1)?;
+        let has_trait_impls = !self.implementors.is_empty() || !self.trait_impls.is_empty();
+        if self.search_unbox || has_trait_impls {
+            seq.serialize_element(&if self.search_unbox { 1 } else { 0 })?;
+        }
+        if has_trait_impls {
+            buf.clear();
+            serialized_result.clear();
+            encode::write_postings_to_string(slice::from_ref(&self.implementors), &mut buf);
+            stringdex_internals::encode::write_base64_to_bytes(&buf, &mut serialized_result)
+                .unwrap();
+            seq.serialize_element(&str::from_utf8(&serialized_result).unwrap())?;
+            let mut trait_impls = String::from("{");
+            for trait_ in &self.trait_impls {
+                trait_.write_to_string(&mut trait_impls);
+            }
+            trait_impls.push('}');
+            seq.serialize_element(&trait_impls)?;
         }
         seq.end()
     }
@@ -1111,6 +1210,8 @@ fn visit_none<E>(self) -> Result<TypeData, E> {
                 Ok(TypeData {
                     inverted_function_inputs_index: vec![],
                     inverted_function_output_index: vec![],
+                    implementors: vec![],
+                    trait_impls: vec![],
                     search_unbox: false,
                 })
             }
@@ -1120,6 +1221,8 @@ fn visit_seq<A: de::SeqAccess<'de>>(self, mut v: A) -> Result<TypeData, A::Error
                 let inverted_function_output_index: String =
                     v.next_element()?.unwrap_or(String::new());
                 let search_unbox: u32 = v.next_element()?.unwrap_or(0);
+                let implementors: String = v.next_element()?.unwrap_or(String::new());
+                let serialized_trait_impls: String = v.next_element()?.unwrap_or(String::new());
                 let mut idx: Vec<u8> = Vec::new();
                 stringdex_internals::decode::read_base64_from_bytes(
                     inverted_function_inputs_index.as_bytes(),
@@ -1136,9 +1239,32 @@ fn visit_seq<A: de::SeqAccess<'de>>(self, mut v: A) -> Result<TypeData, A::Error
                 .unwrap();
                 let mut inverted_function_output_index = Vec::new();
                 encode::read_postings_from_string(&mut inverted_function_output_index, &idx);
+                idx.clear();
+                stringdex_internals::decode::read_base64_from_bytes(
+                    implementors.as_bytes(),
+                    &mut idx,
+                )
+                .unwrap();
+                let mut implementors = Vec::new();
+                encode::read_postings_from_string(&mut implementors, &idx);
+                let implementors = implementors.pop().unwrap_or_default();
+                let mut trait_impls = Vec::new();
+                if let Some(mut string) = serialized_trait_impls
+                    .as_bytes()
+                    .strip_prefix(b"{")
+                    .and_then(|string| string.strip_suffix(b"}"))
+                {
+                    while !string.is_empty() {
+                        let (trait_, offset) = RenderType::read_from_bytes(string);
+                        string = &string[offset..];
+                        trait_impls.push(trait_);
+                    }
+                }
                 Ok(TypeData {
                     inverted_function_inputs_index,
                     inverted_function_output_index,
+                    implementors,
+                    trait_impls,
                     search_unbox: search_unbox == 1,
                 })
             }
@@ -1325,13 +1451,27 @@ fn key(i: &IndexItem) -> (&[Symbol], &str, ItemType, Option<(DefIndex, CrateNum)
             Entry::Occupied(index) => {
                 let index = *index.get();
                 serialized_index.descs[index] = crate_doc;
-                for type_data in serialized_index.type_data.iter_mut() {
+                let is_from_this_crate = |entry_data: &Option<EntryData>| {
+                    entry_data.as_ref().is_some_and(|entry| entry.krate == index)
+                };
+                for (id, type_data) in serialized_index.type_data.iter_mut().enumerate() {
                     if let Some(TypeData {
                         inverted_function_inputs_index,
                         inverted_function_output_index,
+                        implementors,
+                        trait_impls,
                         ..
                     }) = type_data
                     {
+                        // trait impls will be re-added, if they still exist
+                        implementors.retain(|id| {
+                            !is_from_this_crate(
+                                &serialized_index.entry_data[usize::try_from(*id).unwrap()],
+                            )
+                        });
+                        if is_from_this_crate(&serialized_index.entry_data[id]) {
+                            trait_impls.clear();
+                        }
                         for list in inverted_function_inputs_index
                             .iter_mut()
                             .chain(inverted_function_output_index.iter_mut())
@@ -1567,273 +1707,6 @@ fn key(i: &IndexItem) -> (&[Symbol], &str, ItemType, Option<(DefIndex, CrateNum)
 
         // Function signature reverse index
         // --------------------------------
-        fn insert_into_map(
-            ty: ItemType,
-            path: &[Symbol],
-            exact_path: Option<&[Symbol]>,
-            search_unbox: bool,
-            serialized_index: &mut SerializedSearchIndex,
-            used_in_function_signature: &mut BTreeSet<isize>,
-        ) -> RenderTypeId {
-            let pathid = serialized_index.names.len();
-            let pathid = match serialized_index.crate_paths_index.entry((ty, path.to_vec())) {
-                Entry::Occupied(entry) => {
-                    let id = *entry.get();
-                    if serialized_index.type_data[id].as_mut().is_none() {
-                        serialized_index.type_data[id] = Some(TypeData {
-                            search_unbox,
-                            inverted_function_inputs_index: Vec::new(),
-                            inverted_function_output_index: Vec::new(),
-                        });
-                    } else if search_unbox {
-                        serialized_index.type_data[id].as_mut().unwrap().search_unbox = true;
-                    }
-                    id
-                }
-                Entry::Vacant(entry) => {
-                    entry.insert(pathid);
-                    let (name, path) = path.split_last().unwrap();
-                    serialized_index.push_type(
-                        name.to_string(),
-                        PathData {
-                            ty,
-                            module_path: path.to_vec(),
-                            exact_module_path: if let Some(exact_path) = exact_path
-                                && let Some((name2, exact_path)) = exact_path.split_last()
-                                && name == name2
-                            {
-                                Some(exact_path.to_vec())
-                            } else {
-                                None
-                            },
-                        },
-                        TypeData {
-                            inverted_function_inputs_index: Vec::new(),
-                            inverted_function_output_index: Vec::new(),
-                            search_unbox,
-                        },
-                    );
-                    pathid
-                }
-            };
-            used_in_function_signature.insert(isize::try_from(pathid).unwrap());
-            RenderTypeId::Index(isize::try_from(pathid).unwrap())
-        }
-
-        fn convert_render_type_id(
-            id: RenderTypeId,
-            cache: &mut Cache,
-            serialized_index: &mut SerializedSearchIndex,
-            used_in_function_signature: &mut BTreeSet<isize>,
-            tcx: TyCtxt<'_>,
-        ) -> Option<RenderTypeId> {
-            use crate::clean::PrimitiveType;
-            let Cache { ref paths, ref external_paths, ref exact_paths, .. } = *cache;
-            let search_unbox = match id {
-                RenderTypeId::Mut => false,
-                RenderTypeId::DefId(defid) => {
-                    utils::has_doc_flag(tcx, defid, |d| d.search_unbox.is_some())
-                }
-                RenderTypeId::Primitive(
-                    PrimitiveType::Reference | PrimitiveType::RawPointer | PrimitiveType::Tuple,
-                ) => true,
-                RenderTypeId::Primitive(..) => false,
-                RenderTypeId::AssociatedType(..) => false,
-                // this bool is only used by `insert_into_map`, so it doesn't matter what we set here
-                // because Index means we've already inserted into the map
-                RenderTypeId::Index(_) => false,
-            };
-            match id {
-                RenderTypeId::Mut => Some(insert_into_map(
-                    ItemType::Keyword,
-                    &[kw::Mut],
-                    None,
-                    search_unbox,
-                    serialized_index,
-                    used_in_function_signature,
-                )),
-                RenderTypeId::DefId(defid) => {
-                    if let Some(&(ref fqp, item_type)) =
-                        paths.get(&defid).or_else(|| external_paths.get(&defid))
-                    {
-                        if tcx.lang_items().fn_mut_trait() == Some(defid)
-                            || tcx.lang_items().fn_once_trait() == Some(defid)
-                            || tcx.lang_items().fn_trait() == Some(defid)
-                        {
-                            let name = *fqp.last().unwrap();
-                            // Make absolutely sure we use this single, correct path,
-                            // because search.js needs to match. If we don't do this,
-                            // there are three different paths that these traits may
-                            // appear to come from.
-                            Some(insert_into_map(
-                                item_type,
-                                &[sym::core, sym::ops, name],
-                                Some(&[sym::core, sym::ops, name]),
-                                search_unbox,
-                                serialized_index,
-                                used_in_function_signature,
-                            ))
-                        } else {
-                            let exact_fqp = exact_paths
-                                .get(&defid)
-                                .or_else(|| external_paths.get(&defid).map(|(fqp, _)| fqp))
-                                .map(|v| &v[..])
-                                // Re-exports only count if the name is exactly the same.
-                                // This is a size optimization, since it means we only need
-                                // to store the name once (and the path is re-used for everything
-                                // exported from this same module). It's also likely to Do
-                                // What I Mean, since if a re-export changes the name, it might
-                                // also be a change in semantic meaning.
-                                .filter(|this_fqp| this_fqp.last() == fqp.last());
-                            Some(insert_into_map(
-                                item_type,
-                                fqp,
-                                exact_fqp,
-                                search_unbox,
-                                serialized_index,
-                                used_in_function_signature,
-                            ))
-                        }
-                    } else {
-                        None
-                    }
-                }
-                RenderTypeId::Primitive(primitive) => {
-                    let sym = primitive.as_sym();
-                    Some(insert_into_map(
-                        ItemType::Primitive,
-                        &[sym],
-                        None,
-                        search_unbox,
-                        serialized_index,
-                        used_in_function_signature,
-                    ))
-                }
-                RenderTypeId::Index(index) => {
-                    used_in_function_signature.insert(index);
-                    Some(id)
-                }
-                RenderTypeId::AssociatedType(sym) => Some(insert_into_map(
-                    ItemType::AssocType,
-                    &[sym],
-                    None,
-                    search_unbox,
-                    serialized_index,
-                    used_in_function_signature,
-                )),
-            }
-        }
-
-        fn convert_render_type(
-            ty: &mut RenderType,
-            cache: &mut Cache,
-            serialized_index: &mut SerializedSearchIndex,
-            used_in_function_signature: &mut BTreeSet<isize>,
-            tcx: TyCtxt<'_>,
-        ) {
-            if let Some(generics) = &mut ty.generics {
-                for item in generics {
-                    convert_render_type(
-                        item,
-                        cache,
-                        serialized_index,
-                        used_in_function_signature,
-                        tcx,
-                    );
-                }
-            }
-            if let Some(bindings) = &mut ty.bindings {
-                bindings.retain_mut(|(associated_type, constraints)| {
-                    let converted_associated_type = convert_render_type_id(
-                        *associated_type,
-                        cache,
-                        serialized_index,
-                        used_in_function_signature,
-                        tcx,
-                    );
-                    let Some(converted_associated_type) = converted_associated_type else {
-                        return false;
-                    };
-                    *associated_type = converted_associated_type;
-                    for constraint in constraints {
-                        convert_render_type(
-                            constraint,
-                            cache,
-                            serialized_index,
-                            used_in_function_signature,
-                            tcx,
-                        );
-                    }
-                    true
-                });
-            }
-            let Some(id) = ty.id else {
-                assert!(ty.generics.is_some());
-                return;
-            };
-            ty.id = convert_render_type_id(
-                id,
-                cache,
-                serialized_index,
-                used_in_function_signature,
-                tcx,
-            );
-            use crate::clean::PrimitiveType;
-            // These cases are added to the inverted index, but not actually included
-            // in the signature. There's a matching set of cases in the
-            // `unifyFunctionTypeIsMatchCandidate` function, for the slow path.
-            match id {
-                // typeNameIdOfArrayOrSlice
-                RenderTypeId::Primitive(PrimitiveType::Array | PrimitiveType::Slice) => {
-                    insert_into_map(
-                        ItemType::Primitive,
-                        &[sym::empty_brackets],
-                        None,
-                        false,
-                        serialized_index,
-                        used_in_function_signature,
-                    );
-                }
-                RenderTypeId::Primitive(PrimitiveType::Tuple | PrimitiveType::Unit) => {
-                    // typeNameIdOfArrayOrSlice
-                    insert_into_map(
-                        ItemType::Primitive,
-                        &[sym::empty_parens],
-                        None,
-                        false,
-                        serialized_index,
-                        used_in_function_signature,
-                    );
-                }
-                // typeNameIdOfHof
-                RenderTypeId::Primitive(PrimitiveType::Fn) => {
-                    insert_into_map(
-                        ItemType::Primitive,
-                        &[sym::right_arrow],
-                        None,
-                        false,
-                        serialized_index,
-                        used_in_function_signature,
-                    );
-                }
-                RenderTypeId::DefId(did)
-                    if tcx.lang_items().fn_mut_trait() == Some(did)
-                        || tcx.lang_items().fn_once_trait() == Some(did)
-                        || tcx.lang_items().fn_trait() == Some(did) =>
-                {
-                    insert_into_map(
-                        ItemType::Primitive,
-                        &[sym::right_arrow],
-                        None,
-                        false,
-                        serialized_index,
-                        used_in_function_signature,
-                    );
-                }
-                // not special
-                _ => {}
-            }
-        }
         if let Some(search_type) = &mut item.info.search_type {
             let mut used_in_function_inputs = BTreeSet::new();
             let mut used_in_function_output = BTreeSet::new();
@@ -1950,9 +1823,318 @@ fn from_type_data(self, type_data: &mut TypeData) -> &mut Vec<Vec<u32>> {
         }
     }
 
+    // Trait implementation index
+    // --------------------------
+    //
+    // This powers queries like `impl Read` and `: Iterator<Item = u8>`.
+    // Only impls written in this crate are recorded, so that merging the
+    // search index of several crates doesn't duplicate them.
+    let trait_impls: Vec<(RenderTypeId, RenderType)> = cache
+        .implementors
+        .values()
+        .flatten()
+        .filter(|impl_| impl_.impl_item.item_id.is_local())
+        .filter_map(|impl_| get_trait_impl_for_search(impl_.inner_impl(), tcx, cache))
+        .collect();
+    let mut used_in_trait_impls = BTreeSet::new();
+    for (for_, mut trait_) in trait_impls {
+        let Some(RenderTypeId::Index(for_)) = convert_render_type_id(
+            for_,
+            cache,
+            &mut serialized_index,
+            &mut used_in_trait_impls,
+            tcx,
+        ) else {
+            continue;
+        };
+        convert_render_type(
+            &mut trait_,
+            cache,
+            &mut serialized_index,
+            &mut used_in_trait_impls,
+            tcx,
+        );
+        let Some(RenderTypeId::Index(trait_id)) = trait_.id else {
+            continue;
+        };
+        let for_ = usize::try_from(for_).unwrap();
+        let trait_id = usize::try_from(trait_id).unwrap();
+        serialized_index.type_data[trait_id]
+            .as_mut()
+            .unwrap()
+            .implementors
+            .push(u32::try_from(for_).unwrap());
+        let for_type_data = serialized_index.type_data[for_].as_mut().unwrap();
+        if !for_type_data.trait_impls.contains(&trait_) {
+            for_type_data.trait_impls.push(trait_);
+        }
+    }
+    for type_data in serialized_index.type_data.iter_mut().flatten() {
+        type_data.implementors.sort_unstable();
+        type_data.implementors.dedup();
+    }
+
     Ok(serialized_index.sort())
 }
 
+fn insert_into_map(
+    ty: ItemType,
+    path: &[Symbol],
+    exact_path: Option<&[Symbol]>,
+    search_unbox: bool,
+    serialized_index: &mut SerializedSearchIndex,
+    used_in_function_signature: &mut BTreeSet<isize>,
+) -> RenderTypeId {
+    let pathid = serialized_index.names.len();
+    let pathid = match serialized_index.crate_paths_index.entry((ty, path.to_vec())) {
+        Entry::Occupied(entry) => {
+            let id = *entry.get();
+            if serialized_index.type_data[id].as_mut().is_none() {
+                serialized_index.type_data[id] = Some(TypeData {
+                    search_unbox,
+                    inverted_function_inputs_index: Vec::new(),
+                    inverted_function_output_index: Vec::new(),
+                    implementors: Vec::new(),
+                    trait_impls: Vec::new(),
+                });
+            } else if search_unbox {
+                serialized_index.type_data[id].as_mut().unwrap().search_unbox = true;
+            }
+            id
+        }
+        Entry::Vacant(entry) => {
+            entry.insert(pathid);
+            let (name, path) = path.split_last().unwrap();
+            serialized_index.push_type(
+                name.to_string(),
+                PathData {
+                    ty,
+                    module_path: path.to_vec(),
+                    exact_module_path: if let Some(exact_path) = exact_path
+                        && let Some((name2, exact_path)) = exact_path.split_last()
+                        && name == name2
+                    {
+                        Some(exact_path.to_vec())
+                    } else {
+                        None
+                    },
+                },
+                TypeData {
+                    inverted_function_inputs_index: Vec::new(),
+                    inverted_function_output_index: Vec::new(),
+                    implementors: Vec::new(),
+                    trait_impls: Vec::new(),
+                    search_unbox,
+                },
+            );
+            pathid
+        }
+    };
+    used_in_function_signature.insert(isize::try_from(pathid).unwrap());
+    RenderTypeId::Index(isize::try_from(pathid).unwrap())
+}
+
+fn convert_render_type_id(
+    id: RenderTypeId,
+    cache: &mut Cache,
+    serialized_index: &mut SerializedSearchIndex,
+    used_in_function_signature: &mut BTreeSet<isize>,
+    tcx: TyCtxt<'_>,
+) -> Option<RenderTypeId> {
+    use crate::clean::PrimitiveType;
+    let Cache { ref paths, ref external_paths, ref exact_paths, .. } = *cache;
+    let search_unbox = match id {
+        RenderTypeId::Mut => false,
+        RenderTypeId::DefId(defid) => utils::has_doc_flag(tcx, defid, |d| d.search_unbox.is_some()),
+        RenderTypeId::Primitive(
+            PrimitiveType::Reference | PrimitiveType::RawPointer | PrimitiveType::Tuple,
+        ) => true,
+        RenderTypeId::Primitive(..) => false,
+        RenderTypeId::AssociatedType(..) => false,
+        // this bool is only used by `insert_into_map`, so it doesn't matter what we set here
+        // because Index means we've already inserted into the map
+        RenderTypeId::Index(_) => false,
+    };
+    match id {
+        RenderTypeId::Mut => Some(insert_into_map(
+            ItemType::Keyword,
+            &[kw::Mut],
+            None,
+            search_unbox,
+            serialized_index,
+            used_in_function_signature,
+        )),
+        RenderTypeId::DefId(defid) => {
+            if let Some(&(ref fqp, item_type)) =
+                paths.get(&defid).or_else(|| external_paths.get(&defid))
+            {
+                if tcx.lang_items().fn_mut_trait() == Some(defid)
+                    || tcx.lang_items().fn_once_trait() == Some(defid)
+                    || tcx.lang_items().fn_trait() == Some(defid)
+                {
+                    let name = *fqp.last().unwrap();
+                    // Make absolutely sure we use this single, correct path,
+                    // because search.js needs to match. If we don't do this,
+                    // there are three different paths that these traits may
+                    // appear to come from.
+                    Some(insert_into_map(
+                        item_type,
+                        &[sym::core, sym::ops, name],
+                        Some(&[sym::core, sym::ops, name]),
+                        search_unbox,
+                        serialized_index,
+                        used_in_function_signature,
+                    ))
+                } else {
+                    let exact_fqp = exact_paths
+                        .get(&defid)
+                        .or_else(|| external_paths.get(&defid).map(|(fqp, _)| fqp))
+                        .map(|v| &v[..])
+                        // Re-exports only count if the name is exactly the same.
+                        // This is a size optimization, since it means we only need
+                        // to store the name once (and the path is re-used for everything
+                        // exported from this same module). It's also likely to Do
+                        // What I Mean, since if a re-export changes the name, it might
+                        // also be a change in semantic meaning.
+                        .filter(|this_fqp| this_fqp.last() == fqp.last());
+                    Some(insert_into_map(
+                        item_type,
+                        fqp,
+                        exact_fqp,
+                        search_unbox,
+                        serialized_index,
+                        used_in_function_signature,
+                    ))
+                }
+            } else {
+                None
+            }
+        }
+        RenderTypeId::Primitive(primitive) => {
+            let sym = primitive.as_sym();
+            Some(insert_into_map(
+                ItemType::Primitive,
+                &[sym],
+                None,
+                search_unbox,
+                serialized_index,
+                used_in_function_signature,
+            ))
+        }
+        RenderTypeId::Index(index) => {
+            used_in_function_signature.insert(index);
+            Some(id)
+        }
+        RenderTypeId::AssociatedType(sym) => Some(insert_into_map(
+            ItemType::AssocType,
+            &[sym],
+            None,
+            search_unbox,
+            serialized_index,
+            used_in_function_signature,
+        )),
+    }
+}
+
+fn convert_render_type(
+    ty: &mut RenderType,
+    cache: &mut Cache,
+    serialized_index: &mut SerializedSearchIndex,
+    used_in_function_signature: &mut BTreeSet<isize>,
+    tcx: TyCtxt<'_>,
+) {
+    if let Some(generics) = &mut ty.generics {
+        for item in generics {
+            convert_render_type(item, cache, serialized_index, used_in_function_signature, tcx);
+        }
+    }
+    if let Some(bindings) = &mut ty.bindings {
+        bindings.retain_mut(|(associated_type, constraints)| {
+            let converted_associated_type = convert_render_type_id(
+                *associated_type,
+                cache,
+                serialized_index,
+                used_in_function_signature,
+                tcx,
+            );
+            let Some(converted_associated_type) = converted_associated_type else {
+                return false;
+            };
+            *associated_type = converted_associated_type;
+            for constraint in constraints {
+                convert_render_type(
+                    constraint,
+                    cache,
+                    serialized_index,
+                    used_in_function_signature,
+                    tcx,
+                );
+            }
+            true
+        });
+    }
+    let Some(id) = ty.id else {
+        assert!(ty.generics.is_some());
+        return;
+    };
+    ty.id = convert_render_type_id(id, cache, serialized_index, used_in_function_signature, tcx);
+    use crate::clean::PrimitiveType;
+    // These cases are added to the inverted index, but not actually included
+    // in the signature. There's a matching set of cases in the
+    // `unifyFunctionTypeIsMatchCandidate` function, for the slow path.
+    match id {
+        // typeNameIdOfArrayOrSlice
+        RenderTypeId::Primitive(PrimitiveType::Array | PrimitiveType::Slice) => {
+            insert_into_map(
+                ItemType::Primitive,
+                &[sym::empty_brackets],
+                None,
+                false,
+                serialized_index,
+                used_in_function_signature,
+            );
+        }
+        RenderTypeId::Primitive(PrimitiveType::Tuple | PrimitiveType::Unit) => {
+            // typeNameIdOfArrayOrSlice
+            insert_into_map(
+                ItemType::Primitive,
+                &[sym::empty_parens],
+                None,
+                false,
+                serialized_index,
+                used_in_function_signature,
+            );
+        }
+        // typeNameIdOfHof
+        RenderTypeId::Primitive(PrimitiveType::Fn) => {
+            insert_into_map(
+                ItemType::Primitive,
+                &[sym::right_arrow],
+                None,
+                false,
+                serialized_index,
+                used_in_function_signature,
+            );
+        }
+        RenderTypeId::DefId(did)
+            if tcx.lang_items().fn_mut_trait() == Some(did)
+                || tcx.lang_items().fn_once_trait() == Some(did)
+                || tcx.lang_items().fn_trait() == Some(did) =>
+        {
+            insert_into_map(
+                ItemType::Primitive,
+                &[sym::right_arrow],
+                None,
+                false,
+                serialized_index,
+                used_in_function_signature,
+            );
+        }
+        // not special
+        _ => {}
+    }
+}
+
 pub(crate) fn get_function_type_for_search(
     item: &clean::Item,
     tcx: TyCtxt<'_>,
@@ -2015,6 +2197,49 @@ pub(crate) fn get_function_type_for_search(
     Some(IndexItemFunctionType { inputs, output, where_clause, param_names })
 }
 
+/// Lower a trait impl into the implementing type and the implemented trait.
+///
+/// The associated types defined in the impl are added to the trait as bindings,
+/// so `impl Iterator for Bytes { type Item = u8; }` becomes `Iterator<Item=u8>`.
+/// Blanket and auto impls don't have a single implementing type, and are skipped.
+fn get_trait_impl_for_search(
+    impl_: &clean::Impl,
+    tcx: TyCtxt<'_>,
+    cache: &Cache,
+) -> Option<(RenderTypeId, RenderType)> {
+    let trait_ = impl_.trait_.as_ref()?;
+    if impl_.is_negative_trait_impl() || impl_.kind.is_auto() || impl_.kind.is_blanket() {
+        return None;
+    }
+    let mut rgen: FxIndexMap<SimplifiedParam, (isize, Vec<RenderType>)> = Default::default();
+    let for_ = get_index_type_id(&impl_.for_, &mut rgen)?;
+    let trait_ty = Type::Path { path: trait_.clone() };
+    let mut trait_ =
+        simplify_fn_type(None, &impl_.generics, &trait_ty, tcx, 0, &mut rgen, false, cache)?;
+    let bindings: Vec<_> = impl_
+        .items
+        .iter()
+        .filter_map(|item| {
+            let clean::AssocTypeItem(ref alias, _) = item.kind else { return None };
+            let constraints = simplify_fn_type(
+                None,
+                &impl_.generics,
+                &alias.type_,
+                tcx,
+                0,
+                &mut rgen,
+                false,
+                cache,
+            );
+            Some((RenderTypeId::AssociatedType(item.name?), constraints.into_iter().collect()))
+        })
+        .collect();
+    if !bindings.is_empty() {
+        trait_.bindings.get_or_insert_default().extend(bindings);
+    }
+    Some((for_, trait_))
+}
+
 fn get_index_type(
     clean_type: &clean::Type,
     generics: Vec<RenderType>,
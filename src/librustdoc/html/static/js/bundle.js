@@ -0,0 +1,263 @@
+// bundle.js is the loader of the single-file documentation bundles written with `--html-bundle`.
+// Unlike the other scripts, it is not copied into the output directory, but inlined into the
+// bundle, next to a JSON object holding every file of the documentation.
+//
+// The page named by the fragment of the bundle's URL is rendered into a frame. Any script,
+// stylesheet, font or image that the page refers to is served from the embedded files through
+// a `blob:` URL, so that the bundle can be browsed from `file://` without a server.
+"use strict";
+
+(function() {
+    /**
+     * @typedef {{text: Object<string, string>, base64: Object<string, string>}} EmbeddedFiles
+     */
+
+    const filesElement = document.getElementById("bundle-files");
+    /** @type {EmbeddedFiles} */
+    const files = JSON.parse(filesElement === null ? "{}" : filesElement.textContent || "{}");
+    const startPage = document.body.dataset.startPage || "index.html";
+
+    // Paths within the bundle are resolved as URLs relative to this made-up origin, so that
+    // `../`, fragments and queries are handled like the browser would.
+    const BUNDLE_ORIGIN = "https://bundle.invalid";
+
+    /** @type {Object<string, string>} */
+    const MIME_TYPES = {
+        css: "text/css",
+        html: "text/html",
+        js: "text/javascript",
+        json: "application/json",
+        md: "text/markdown",
+        png: "image/png",
+        svg: "image/svg+xml",
+        ttf: "font/ttf",
+        woff: "font/woff",
+        woff2: "font/woff2",
+    };
+
+    /** @type {Map<string, string>} */
+    const blobUrls = new Map();
+    // The path of the page currently shown, which relative URLs are resolved against.
+    let currentPage = startPage;
+
+    /**
+     * @param {string} path
+     * @returns {boolean}
+     */
+    function hasFile(path) {
+        return Object.hasOwn(files.text, path) || Object.hasOwn(files.base64, path);
+    }
+
+    /**
+     * Resolves `url` relative to the file at `base`.
+     *
+     * @param {string} url
+     * @param {string} base
+     * @returns {{path: string, fragment: string}|null} The path of the file that `url` refers
+     *          to, and the fragment of `url`, or `null` if `url` refers to no file of the bundle.
+     */
+    function resolve(url, base) {
+        let resolved;
+        try {
+            resolved = new URL(url, `${BUNDLE_ORIGIN}/${base}`);
+        } catch (_error) {
+            return null;
+        }
+        if (resolved.origin !== BUNDLE_ORIGIN) {
+            return null;
+        }
+        let path = decodeURIComponent(resolved.pathname.slice(1));
+        if (path === "" || path.endsWith("/")) {
+            path += "index.html";
+        }
+        return hasFile(path) ? { path, fragment: resolved.hash.slice(1) } : null;
+    }
+
+    /**
+     * @param {string} path
+     * @returns {string|Uint8Array<ArrayBuffer>}
+     */
+    function contents(path) {
+        if (Object.hasOwn(files.text, path)) {
+            return files.text[path];
+        }
+        const binary = atob(files.base64[path]);
+        const bytes = new Uint8Array(binary.length);
+        for (let i = 0; i < binary.length; i += 1) {
+            bytes[i] = binary.charCodeAt(i);
+        }
+        return bytes;
+    }
+
+    /**
+     * Returns a `blob:` URL for the file at `path`, creating it on first use.
+     *
+     * @param {string} path
+     * @returns {string}
+     */
+    function blobUrl(path) {
+        let url = blobUrls.get(path);
+        if (url === undefined) {
+            let data = contents(path);
+            if (typeof data === "string" && path.endsWith(".css")) {
+                // Stylesheets refer to fonts and images relative to themselves.
+                data = data.replace(
+                    /url\(\s*(?:"([^"]*)"|'([^']*)'|([^"')\s]+))\s*\)/g,
+                    (match, doubleQuoted, singleQuoted, unquoted) => {
+                        const file = resolve(doubleQuoted || singleQuoted || unquoted, path);
+                        return file === null ? match : `url("${blobUrl(file.path)}")`;
+                    },
+                );
+            }
+            const extension = path.slice(path.lastIndexOf(".") + 1);
+            const type = MIME_TYPES[extension] || "application/octet-stream";
+            url = URL.createObjectURL(new Blob([data], { type }));
+            blobUrls.set(path, url);
+        }
+        return url;
+    }
+
+    /**
+     * Returns the `blob:` URL of the file that `url` refers to, relative to the current page,
+     * or `url` itself if it doesn't refer to any file of the bundle.
+     *
+     * @param {string} url
+     * @returns {string}
+     */
+    function resourceUrl(url) {
+        const file = resolve(url, currentPage);
+        return file === null ? url : blobUrl(file.path);
+    }
+
+    /**
+     * Gives access to the globals of a frame, like its own `HTMLScriptElement`, which differs
+     * from the one of the bundle.
+     *
+     * @param {Window} frameWindow
+     * @returns {typeof window}
+     */
+    function globalsOf(frameWindow) {
+        return /** @type {typeof window} */ (/** @type {unknown} */ (frameWindow));
+    }
+
+    /**
+     * Makes the scripts and stylesheets that rustdoc's scripts add to a page load from the
+     * bundle too.
+     *
+     * @param {typeof window} win
+     */
+    function interceptResourceUrls(win) {
+        /** @type {[Function, string][]} */
+        const properties = [[win.HTMLScriptElement, "src"], [win.HTMLLinkElement, "href"]];
+        for (const [element, name] of properties) {
+            const property = Object.getOwnPropertyDescriptor(element.prototype, name);
+            if (property === undefined || property.set === undefined) {
+                continue;
+            }
+            const set = property.set;
+            Object.defineProperty(element.prototype, name, {
+                ...property,
+                /**
+                 * @this {HTMLElement}
+                 * @param {string} value
+                 */
+                set(value) {
+                    set.call(this, resourceUrl(value));
+                },
+            });
+        }
+    }
+
+    /**
+     * Follows clicks on links to other pages of the bundle.
+     *
+     * @param {typeof window} win
+     * @param {MouseEvent} event
+     */
+    function onClick(win, event) {
+        // Rustdoc's own handlers, and the browser for modified clicks, take precedence.
+        if (event.defaultPrevented || event.button !== 0 ||
+            event.ctrlKey || event.shiftKey || event.altKey || event.metaKey
+        ) {
+            return;
+        }
+        const target = event.target;
+        const link = target instanceof win.Element ? target.closest("a[href]") : null;
+        const href = link === null ? null : link.getAttribute("href");
+        if (link === null || href === null || href.startsWith("#")) {
+            return;
+        }
+        const file = resolve(href, currentPage);
+        if (file === null) {
+            // Links that leave the bundle replace it, rather than load into the frame.
+            link.setAttribute("target", "_top");
+        } else if (file.path.endsWith(".html")) {
+            event.preventDefault();
+            const fragment = file.fragment === "" ? "" : `#${file.fragment}`;
+            window.location.hash = file.path + fragment;
+        } else {
+            event.preventDefault();
+            window.open(blobUrl(file.path));
+        }
+    }
+
+    /**
+     * Renders the page at `path` into a new frame, and scrolls to `fragment`, if any.
+     *
+     * Every page gets a new frame, because rustdoc's scripts expect to start in a fresh window.
+     *
+     * @param {string} path
+     * @param {string} fragment
+     */
+    function showPage(path, fragment) {
+        const page = new DOMParser().parseFromString(String(contents(path)), "text/html");
+        currentPage = path;
+        page.querySelectorAll("script[src], img[src], link[href]").forEach(element => {
+            const name = element.hasAttribute("src") ? "src" : "href";
+            element.setAttribute(name, resourceUrl(element.getAttribute(name) || ""));
+        });
+
+        const frame = document.createElement("iframe");
+        frame.id = "bundle-frame";
+        const oldFrame = document.getElementById("bundle-frame");
+        if (oldFrame === null) {
+            document.body.prepend(frame);
+        } else {
+            oldFrame.replaceWith(frame);
+        }
+        const frameDocument = frame.contentDocument;
+        if (frame.contentWindow === null || frameDocument === null) {
+            return;
+        }
+        const win = globalsOf(frame.contentWindow);
+        frameDocument.open();
+        interceptResourceUrls(win);
+        frameDocument.write("<!DOCTYPE html>" + page.documentElement.outerHTML);
+        frameDocument.close();
+        document.title = frameDocument.title;
+        frame.title = frameDocument.title;
+        win.addEventListener("click", event => onClick(win, event));
+        win.addEventListener("load", () => {
+            if (fragment !== "") {
+                win.location.replace(`#${fragment}`);
+            }
+        });
+    }
+
+    // The fragment of the bundle's URL is the path of the page to show, possibly followed by
+    // the fragment within that page, as in `#krate/struct.Foo.html#method.bar`.
+    function showCurrentPage() {
+        const hash = decodeURIComponent(window.location.hash.slice(1));
+        const split = hash.indexOf("#");
+        const path = split === -1 ? hash : hash.slice(0, split);
+        const fragment = split === -1 ? "" : hash.slice(split + 1);
+        if (hasFile(path) && path.endsWith(".html")) {
+            showPage(path, fragment);
+        } else {
+            showPage(startPage, "");
+        }
+    }
+
+    window.addEventListener("hashchange", showCurrentPage);
+    showCurrentPage();
+}());
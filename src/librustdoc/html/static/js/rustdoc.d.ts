@@ -225,6 +225,11 @@ declare namespace rustdoc {
         totalElems: number,
         literalSearch: boolean,
         hasReturnArrow: boolean,
+        /**
+         * `true` for `impl Trait` and `: Trait` queries, which look for
+         * types implementing the trait in `elems[0]`.
+         */
+        implementsTrait: boolean,
         correction: string|null,
         proposeCorrectionFrom: string|null,
         proposeCorrectionTo: string|null,
@@ -295,6 +300,10 @@ declare namespace rustdoc {
         searchUnbox: boolean,
         invertedFunctionInputsIndex: RoaringBitmap[],
         invertedFunctionOutputIndex: RoaringBitmap[],
+        /**
+         * For traits, the types that implement it.
+         */
+        implementors: RoaringBitmap,
     }
 
     type TypeInvertedIndexPolarity = "invertedFunctionInputsIndex" | "invertedFunctionOutputIndex";
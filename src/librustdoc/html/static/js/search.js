@@ -1400,6 +1400,7 @@ class DocSearch {
                 totalElems: 0,
                 literalSearch: false,
                 hasReturnArrow: false,
+                implementsTrait: false,
                 error: null,
                 correction: null,
                 proposeCorrectionFrom: null,
@@ -1518,7 +1519,16 @@ class DocSearch {
         let query = newParsedQuery(userQuery);
 
         try {
+            // `impl Trait` and `: Trait` look for types that implement the trait
+            const implPrefix = /^(?:impl\s+|:(?!:)\s*)/.exec(userQuery);
+            if (implPrefix !== null) {
+                query.implementsTrait = true;
+                parserState.pos = implPrefix[0].length;
+            }
             parseInput(query, parserState);
+            if (implPrefix !== null && (query.elems.length !== 1 || query.hasReturnArrow)) {
+                throw ["Expected a single trait after ", implPrefix[0].trim()];
+            }
 
             // Scan for invalid type filters, so that we can report the error
             // outside the search loop.
@@ -1787,8 +1797,9 @@ class DocSearch {
             return null;
         }
         /**
-         * function_signature, param_names
-         * @type {[string, string, number] | [string, string] | [] | null}
+         * inputs, output, search_unbox, implementors, trait_impls
+         * @type {[string, string, number, string, string] | [string, string, number] |
+         *     [string, string] | [] | null}
          */
         const raw = JSON.parse(encoded);
 
@@ -1799,6 +1810,7 @@ class DocSearch {
         let searchUnbox = false;
         const invertedFunctionInputsIndex = [];
         const invertedFunctionOutputIndex = [];
+        let implementors = RoaringBitmap.empty();
 
         if (typeof raw[0] === "string") {
             if (raw[2]) {
@@ -1832,11 +1844,55 @@ class DocSearch {
                     invertedFunctionOutputIndex.push(bitmap);
                 }
             }
+            // if this is a trait, the list of types that implement it
+            if (typeof raw[3] === "string") {
+                pb = makeUint8ArrayFromBase64(raw[3]);
+                if (pb.length !== 0 && pb[0] !== 0) {
+                    implementors = new RoaringBitmap(pb, 0);
+                }
+            }
         } else if (raw[0]) {
             searchUnbox = true;
         }
 
-        return { searchUnbox, invertedFunctionInputsIndex, invertedFunctionOutputIndex };
+        return {
+            searchUnbox,
+            invertedFunctionInputsIndex,
+            invertedFunctionOutputIndex,
+            implementors,
+        };
+    }
+
+    /**
+     * Get the traits implemented by a type, along with their generics and
+     * the associated types set in the impl block.
+     *
+     * @param {number} id
+     * @returns {Promise<rustdoc.FunctionType[]>}
+     */
+    async getTraitImpls(id) {
+        const ti = this.database.getData("type");
+        if (!ti) {
+            return EMPTY_GENERICS_ARRAY;
+        }
+        const encoded = this.utf8decoder.decode(await ti.at(id));
+        if (encoded === "" || encoded === undefined || encoded === null) {
+            return EMPTY_GENERICS_ARRAY;
+        }
+        /**
+         * inputs, output, search_unbox, implementors, trait_impls
+         * @type {[string, string, number, string, string] | []}
+         */
+        const raw = JSON.parse(encoded);
+        if (!raw || typeof raw[4] !== "string") {
+            return EMPTY_GENERICS_ARRAY;
+        }
+        const parser = new VlqHexDecoder(
+            raw[4],
+            // @ts-expect-error
+            traitImpls => this.buildItemSearchTypeAll(traitImpls),
+        );
+        return parser.next();
     }
 
     /**
@@ -4729,7 +4785,176 @@ class DocSearch {
             }
             .bind(this);
 
-        if (parsedQuery.foundElems === 1 && !parsedQuery.hasReturnArrow) {
+        const innerRunImplQuery =
+            /**
+             * Find the types that implement a trait, for queries like `impl Read`
+             * or `: Iterator<Item = u8>`.
+             *
+             * @this {DocSearch}
+             * @param {rustdoc.ParserQueryElement} elem
+             * @param {string|null} currentCrate
+             * @returns {AsyncGenerator<rustdoc.ResultObject>}
+             */
+            async function*(elem, currentCrate) {
+                const index = this.database.getData("normalizedName");
+                if (!index) {
+                    return;
+                }
+                /**
+                 * @typedef {{
+                 *     ids: Set<number>,
+                 *     isGeneric: boolean,
+                 *     generics: ResolvedElem[],
+                 *     bindings: [Set<number>, ResolvedElem[]][],
+                 * }} ResolvedElem
+                 */
+                /**
+                 * Find every row with this name and, if given, path.
+                 *
+                 * @param {string} normalizedName
+                 * @param {function(string, rustdoc.PathData): boolean} filter
+                 * @returns {Promise<Set<number>>}
+                 */
+                const findIds = async(normalizedName, filter) => {
+                    /** @type {Set<number>} */
+                    const ids = new Set();
+                    const searchResults = await index.search(normalizedName);
+                    if (!searchResults) {
+                        return ids;
+                    }
+                    for (const id of searchResults.matches().entries()) {
+                        const [name, path] = await Promise.all([
+                            this.getName(id),
+                            this.getPathData(id),
+                        ]);
+                        if (name !== null && path !== null && filter(name, path)) {
+                            ids.add(id);
+                        }
+                    }
+                    return ids;
+                };
+                /**
+                 * Look up the IDs of every type a query element could refer to.
+                 *
+                 * Like in type-based search, an unknown single-word name
+                 * is treated as a generic, and matches anything.
+                 *
+                 * @param {rustdoc.ParserQueryElement} elem
+                 * @returns {Promise<ResolvedElem>}
+                 */
+                const resolve = async elem => {
+                    const [ids, generics, bindings] = await Promise.all([
+                        findIds(elem.normalizedPathLast, (name, path) =>
+                            name.toLowerCase() === elem.pathLast &&
+                            (elem.pathWithoutLast.length === 0 ||
+                                checkPath(
+                                    elem.pathWithoutLast,
+                                    path.modulePath.split("::"),
+                                ) === 0),
+                        ),
+                        Promise.all(elem.generics.map(resolve)),
+                        Promise.all([...elem.bindings.entries()].map(
+                            /**
+                             * @param {[string, rustdoc.ParserQueryElement[]]} binding
+                             * @returns {Promise<[Set<number>, ResolvedElem[]]>}
+                             */
+                            ([key, constraints]) => Promise.all([
+                                findIds(key, (_name, path) =>
+                                    path.ty === itemTypes.associatedtype,
+                                ),
+                                Promise.all(constraints.map(resolve)),
+                            ]),
+                        )),
+                    ]);
+                    const isGeneric = ids.size === 0 &&
+                        elem.pathWithoutLast.length === 0 &&
+                        elem.generics.length === 0 &&
+                        elem.bindings.size === 0;
+                    return { ids, isGeneric, generics, bindings };
+                };
+                /**
+                 * Check if a trait from an impl block matches the query. Every
+                 * generic and binding in the query must appear in the impl,
+                 * but the impl may have more.
+                 *
+                 * @param {rustdoc.FunctionType} fnType
+                 * @param {ResolvedElem} queryElem
+                 * @returns {boolean}
+                 */
+                const implMatches = (fnType, queryElem) => {
+                    if (queryElem.isGeneric || (fnType.id !== null && fnType.id < 0)) {
+                        return true;
+                    }
+                    if (fnType.id === null || !queryElem.ids.has(fnType.id)) {
+                        return false;
+                    }
+                    return queryElem.generics.every(generic => {
+                        return fnType.generics.some(fnGeneric => implMatches(fnGeneric, generic));
+                    }) && queryElem.bindings.every(([keys, constraints]) => {
+                        for (const [key, fnConstraints] of fnType.bindings.entries()) {
+                            if (keys.has(key) && constraints.every(constraint => {
+                                return fnConstraints.some(fnConstraint => {
+                                    return implMatches(fnConstraint, constraint);
+                                });
+                            })) {
+                                return true;
+                            }
+                        }
+                        return false;
+                    });
+                };
+
+                const query = await resolve(elem);
+                /** @type {Set<number>} */
+                const implementors = new Set();
+                for (const id of query.ids) {
+                    const [path, typeData] = await Promise.all([
+                        this.getPathData(id),
+                        this.getTypeData(id),
+                    ]);
+                    if (path === null || typeData === null ||
+                        (path.ty !== itemTypes.trait && path.ty !== itemTypes.traitalias)
+                    ) {
+                        continue;
+                    }
+                    for (const implementor of typeData.implementors.entries()) {
+                        implementors.add(implementor);
+                    }
+                }
+                const results = await Promise.all([...implementors].map(async id => {
+                    const [traitImpls, item] = await Promise.all([
+                        this.getTraitImpls(id),
+                        this.getRow(id, false),
+                    ]);
+                    // types that aren't documented in this bundle don't have a page to link to
+                    if (item === null || item.entry === null ||
+                        !traitImpls.some(traitImpl => implMatches(traitImpl, query))
+                    ) {
+                        return null;
+                    }
+                    return {
+                        id,
+                        dist: 0,
+                        path_dist: 0,
+                        index: -1,
+                        elems: [],
+                        returned: [],
+                        is_alias: false,
+                        item,
+                    };
+                }));
+                yield* sortAndTransformResults(results, null, currentCrate, new Set());
+            }
+            .bind(this);
+
+        if (parsedQuery.implementsTrait && parsedQuery.error === null) {
+            return {
+                "in_args": innerRunTypeQuery(parsedQuery.elems, [], "elems", currentCrate),
+                "returned": innerRunTypeQuery([], parsedQuery.elems, "returned", currentCrate),
+                "others": innerRunImplQuery(parsedQuery.elems[0], currentCrate),
+                "query": parsedQuery,
+            };
+        } else if (parsedQuery.foundElems === 1 && !parsedQuery.hasReturnArrow) {
             // We never want the main tab to delay behind the other two tabs.
             // This is a bit of a hack (because JS's scheduler doesn't have much of an API),
             // along with making innerRunTypeQuery yield to the UI thread.
@@ -5256,6 +5481,10 @@ async function showResults(docSearch, results, goToFirst, filterCrates) {
     searchState.currentTab = 0;
     if (results.query.error !== null) {
         tabs.push(makeTab(0, "In Names", results.others, results.query, false, goToFirst));
+    } else if (results.query.implementsTrait) {
+        tabs.push(makeTab(0, "Implementors", results.others, results.query, false, goToFirst));
+        tabs.push(makeTab(1, "In Parameters", results.in_args, results.query, true, false));
+        tabs.push(makeTab(2, "In Return Types", results.returned, results.query, true, false));
     } else if (
         results.query.foundElems <= 1 &&
         results.query.returned.length === 0 &&
@@ -2,6 +2,8 @@
 //! the `clean` types but with some fields removed or stringified to simplify the output and not
 //! expose unstable compiler internals.
 
+use std::iter;
+
 use rustc_abi::ExternAbi;
 use rustc_ast::ast;
 use rustc_data_structures::fx::FxHashSet;
@@ -16,6 +18,7 @@
 use rustc_metadata::rendered_const;
 use rustc_middle::ty::TyCtxt;
 use rustc_middle::{bug, ty};
+use rustc_span::hygiene::ExpnKind;
 use rustc_span::{Pos, Symbol, kw, sym};
 use rustdoc_json_types::*;
 
@@ -97,6 +100,11 @@ pub(super) fn convert_item(&self, item: &clean::Item) -> Option<Item> {
         let const_stability = item.item_id.as_def_id().and_then(|def_id| {
             const_stability_for_def_id(self.tcx, def_id).map(|s| Box::new(s.into_json(self)))
         });
+        let expanded_from = item
+            .item_id
+            .as_def_id()
+            .map(|def_id| self.macro_expansions(def_id))
+            .unwrap_or_default();
 
         Some(Item {
             id,
@@ -109,11 +117,46 @@ pub(super) fn convert_item(&self, item: &clean::Item) -> Option<Item> {
             docs,
             attrs,
             deprecation: deprecation.into_json(self),
+            expanded_from,
             inner,
             links,
         })
     }
 
+    /// Walks up the macro invocations that produced the item `def_id`, starting with the
+    /// innermost one.
+    fn macro_expansions(&self, def_id: DefId) -> Vec<MacroExpansion> {
+        let tcx = self.tcx;
+        let mut expansions = Vec::new();
+        let mut span = tcx.def_span(def_id);
+        while span.from_expansion() {
+            let data = span.ctxt().outer_expn_data();
+            // Desugarings and compiler-internal passes aren't invocations the user can trace
+            // back, so only macros are listed.
+            if let ExpnKind::Macro(kind, name) = data.kind {
+                let path = match data.macro_def_id {
+                    Some(macro_def_id) => iter::once(tcx.crate_name(macro_def_id.krate))
+                        .chain(
+                            tcx.def_path(macro_def_id)
+                                .data
+                                .into_iter()
+                                .filter_map(|elem| elem.data.get_opt_name()),
+                        )
+                        .map(|sym| sym.to_string())
+                        .collect(),
+                    None => vec![name.to_string()],
+                };
+                expansions.push(MacroExpansion {
+                    kind: kind.into_json(self),
+                    path,
+                    call_site: clean::Span::new(data.call_site).into_json(self),
+                });
+            }
+            span = data.call_site;
+        }
+        expansions
+    }
+
     fn ids(&self, items: &[clean::Item]) -> Vec<Id> {
         items
             .iter()
@@ -393,7 +436,7 @@ fn from_clean_item(item: &clean::Item, renderer: &JsonRenderer<'_>) -> ItemEnum
             type_: ci.type_.into_json(renderer),
             const_: ci.kind.into_json(renderer),
         },
-        MacroItem(m, _) => ItemEnum::Macro(m.source.clone()),
+        MacroItem(m, _) => ItemEnum::Macro(m.into_json(renderer)),
         ProcMacroItem(m) => ItemEnum::ProcMacro(m.into_json(renderer)),
         PrimitiveItem(p) => {
             ItemEnum::Primitive(Primitive {
@@ -479,6 +522,7 @@ fn from_clean(struct_: &clean::Struct, renderer: &JsonRenderer<'_>) -> Self {
             kind,
             generics: generics.into_json(renderer),
             impls: Vec::new(), // Added in JsonRenderer::item
+            layout: None,      // Added in JsonRenderer::item
         }
     }
 }
@@ -492,6 +536,7 @@ fn from_clean(union_: &clean::Union, renderer: &JsonRenderer<'_>) -> Self {
             has_stripped_fields,
             fields: renderer.ids(fields),
             impls: Vec::new(), // Added in JsonRenderer::item
+            layout: None,      // Added in JsonRenderer::item
         }
     }
 }
@@ -872,6 +917,7 @@ fn from_clean(enum_: &clean::Enum, renderer: &JsonRenderer<'_>) -> Self {
             has_stripped_variants,
             variants: renderer.ids(&variants.as_slice().raw),
             impls: Vec::new(), // Added in JsonRenderer::item
+            layout: None,      // Added in JsonRenderer::item
         }
     }
 }
@@ -924,6 +970,55 @@ fn from_clean(import: &clean::Import, renderer: &JsonRenderer<'_>) -> Self {
     }
 }
 
+impl FromClean<clean::Macro> for Macro {
+    fn from_clean(mac: &clean::Macro, renderer: &JsonRenderer<'_>) -> Self {
+        Macro { source: mac.source.clone(), rules: mac.rules.into_json(renderer) }
+    }
+}
+
+impl FromClean<clean::MacroRule> for MacroRule {
+    fn from_clean(rule: &clean::MacroRule, renderer: &JsonRenderer<'_>) -> Self {
+        MacroRule {
+            kind: rule.kind.into_json(renderer),
+            args: rule.args.into_json(renderer),
+            matcher: rule.matcher.into_json(renderer),
+        }
+    }
+}
+
+impl FromClean<clean::MacroMatcher> for MacroMatcher {
+    fn from_clean(matcher: &clean::MacroMatcher, renderer: &JsonRenderer<'_>) -> Self {
+        use clean::MacroMatcher::*;
+        match matcher {
+            Token(token) => MacroMatcher::Token(token.clone()),
+            Delimited(delim, contents) => MacroMatcher::Delimited {
+                delimiter: match delim {
+                    rustc_ast::token::Delimiter::Parenthesis => MacroDelimiter::Parenthesis,
+                    rustc_ast::token::Delimiter::Brace => MacroDelimiter::Brace,
+                    rustc_ast::token::Delimiter::Bracket => MacroDelimiter::Bracket,
+                    // These are flattened into their contents in `clean`.
+                    rustc_ast::token::Delimiter::Invisible(_) => {
+                        bug!("invisible delimiter in macro matcher")
+                    }
+                },
+                contents: contents.into_json(renderer),
+            },
+            Fragment { name, kind } => {
+                MacroMatcher::Fragment { name: name.to_string(), kind: kind.to_string() }
+            }
+            Repetition { contents, separator, op } => MacroMatcher::Repetition {
+                contents: contents.into_json(renderer),
+                separator: separator.clone(),
+                op: match op {
+                    clean::KleeneOp::ZeroOrMore => MacroRepetitionOp::ZeroOrMore,
+                    clean::KleeneOp::OneOrMore => MacroRepetitionOp::OneOrMore,
+                    clean::KleeneOp::ZeroOrOne => MacroRepetitionOp::ZeroOrOne,
+                },
+            },
+        }
+    }
+}
+
 impl FromClean<clean::ProcMacro> for ProcMacro {
     fn from_clean(mac: &clean::ProcMacro, renderer: &JsonRenderer<'_>) -> Self {
         ProcMacro {
@@ -947,7 +1042,11 @@ fn from_clean(kind: &rustc_span::hygiene::MacroKind, _renderer: &JsonRenderer<'_
 impl FromClean<clean::TypeAlias> for TypeAlias {
     fn from_clean(type_alias: &clean::TypeAlias, renderer: &JsonRenderer<'_>) -> Self {
         let clean::TypeAlias { type_, generics, item_type: _, inner_type: _ } = type_alias;
-        TypeAlias { type_: type_.into_json(renderer), generics: generics.into_json(renderer) }
+        TypeAlias {
+            type_: type_.into_json(renderer),
+            generics: generics.into_json(renderer),
+            layout: None, // Added in JsonRenderer::item
+        }
     }
 }
 
@@ -1287,6 +1386,78 @@ fn format_integer_type(it: rustc_abi::IntegerType) -> String {
     .to_owned()
 }
 
+/// Computes the layout of the type defined (or aliased) by `def_id`, if it doesn't depend on
+/// generic parameters. This mirrors what `html::render::type_layout` shows in the HTML output.
+pub(super) fn type_layout(tcx: TyCtxt<'_>, def_id: DefId) -> Option<Layout> {
+    use rustc_abi::{BackendRepr, FieldsShape, Variants};
+
+    let typing_env = ty::TypingEnv::post_analysis(tcx, def_id);
+    let ty = tcx.type_of(def_id).instantiate_identity().skip_norm_wip();
+    let layout = tcx.layout_of(typing_env.as_query_input(ty)).ok()?;
+
+    let abi = match layout.backend_repr {
+        BackendRepr::Scalar(_) => LayoutAbi::Scalar,
+        BackendRepr::ScalarPair(..) => LayoutAbi::ScalarPair,
+        BackendRepr::SimdVector { count, .. } | BackendRepr::SimdScalableVector { count, .. } => {
+            LayoutAbi::Vector { count }
+        }
+        BackendRepr::Memory { .. } => LayoutAbi::Aggregate,
+    };
+    let niche = layout.largest_niche.map(|niche| Niche {
+        offset: niche.offset.bytes(),
+        size: niche.value.size(&tcx).bytes(),
+        valid_range_start: niche.valid_range.start.to_string(),
+        valid_range_end: niche.valid_range.end.to_string(),
+    });
+
+    let (field_offsets, tag, variants) = match &layout.variants {
+        Variants::Multiple { tag, tag_encoding, tag_field, variants } => {
+            let ty::Adt(adt, _) = ty.kind() else {
+                bug!("type with multiple variants is not an ADT: {ty:?}");
+            };
+            let tag = Tag {
+                offset: layout.fields.offset(tag_field.as_usize()).bytes(),
+                size: tag.primitive().size(&tcx).bytes(),
+                is_niche: matches!(tag_encoding, rustc_abi::TagEncoding::Niche { .. }),
+            };
+            let variants = variants
+                .iter_enumerated()
+                .map(|(variant_idx, variant)| VariantLayout {
+                    name: adt.variant(variant_idx).name.to_string(),
+                    size: variant.size.bytes(),
+                    is_uninhabited: variant.is_uninhabited(),
+                    field_offsets: variant.field_offsets.iter().map(|o| o.bytes()).collect(),
+                })
+                .collect();
+            (Vec::new(), Some(tag), variants)
+        }
+        Variants::Single { .. } | Variants::Empty => {
+            let field_offsets = match &layout.fields {
+                FieldsShape::Arbitrary { offsets, .. } => {
+                    offsets.iter().map(|o| o.bytes()).collect()
+                }
+                FieldsShape::Union(count) => vec![0; count.get()],
+                // Type aliases can refer to primitives and arrays, whose "fields" aren't fields
+                // in the source.
+                FieldsShape::Primitive | FieldsShape::Array { .. } => Vec::new(),
+            };
+            (field_offsets, None, Vec::new())
+        }
+    };
+
+    Some(Layout {
+        size: layout.size.bytes(),
+        align: layout.align.abi.bytes(),
+        is_unsized: layout.is_unsized(),
+        is_uninhabited: layout.is_uninhabited(),
+        abi,
+        field_offsets,
+        niche,
+        tag,
+        variants,
+    })
+}
+
 pub(super) fn target(sess: &rustc_session::Session) -> Target {
     // Build a set of which features are enabled on this target
     let globally_enabled_features: FxHashSet<&str> =
@@ -47,6 +47,8 @@ pub(crate) struct JsonRenderer<'tcx> {
     cache: Rc<Cache>,
     imported_items: DefIdSet,
     id_interner: RefCell<ids::IdInterner>,
+    /// Whether to include type layouts in the output, as requested by `--show-type-layout`.
+    show_type_layout: bool,
 }
 
 impl<'tcx> JsonRenderer<'tcx> {
@@ -104,6 +106,10 @@ fn get_impls(&mut self, id: DefId) -> Vec<types::Id> {
             })
             .unwrap_or_default()
     }
+
+    fn get_type_layout(&self, id: DefId) -> Option<types::Layout> {
+        if self.show_type_layout { conversions::type_layout(self.tcx, id) } else { None }
+    }
 }
 
 impl<'tcx> JsonRenderer<'tcx> {
@@ -125,6 +131,7 @@ pub(crate) fn init(
                 cache: Rc::new(cache),
                 imported_items,
                 id_interner: Default::default(),
+                show_type_layout: options.show_type_layout,
             },
             krate,
         ))
@@ -173,14 +180,21 @@ fn item(&mut self, item: &clean::Item) -> Result<(), Error> {
                 }
                 types::ItemEnum::Struct(ref mut s) => {
                     s.impls = self.get_impls(item_id.expect_def_id());
+                    s.layout = self.get_type_layout(item_id.expect_def_id());
                     false
                 }
                 types::ItemEnum::Enum(ref mut e) => {
                     e.impls = self.get_impls(item_id.expect_def_id());
+                    e.layout = self.get_type_layout(item_id.expect_def_id());
                     false
                 }
                 types::ItemEnum::Union(ref mut u) => {
                     u.impls = self.get_impls(item_id.expect_def_id());
+                    u.layout = self.get_type_layout(item_id.expect_def_id());
+                    false
+                }
+                types::ItemEnum::TypeAlias(ref mut t) => {
+                    t.layout = self.get_type_layout(item_id.expect_def_id());
                     false
                 }
                 types::ItemEnum::Primitive(ref mut p) => {
@@ -198,7 +212,6 @@ fn item(&mut self, item: &clean::Item) -> Result<(), Error> {
                 | types::ItemEnum::Variant(_)
                 | types::ItemEnum::TraitAlias(_)
                 | types::ItemEnum::Impl(_)
-                | types::ItemEnum::TypeAlias(_)
                 | types::ItemEnum::Constant { .. }
                 | types::ItemEnum::Static(_)
                 | types::ItemEnum::ExternType
@@ -517,6 +517,15 @@ fn opts() -> Vec<RustcOptGroup> {
             "",
             "One argument (of possibly many) to pass to the runtool",
         ),
+        opt(
+            Unstable,
+            Opt,
+            "",
+            "test-runtool-protocol",
+            "How to communicate with the runtool: `plain` passes it the doctest executable, \
+                `json` sends it a JSON request and expects a JSON result",
+            "plain|json",
+        ),
         opt(
             Unstable,
             Opt,
@@ -661,6 +670,15 @@ fn opts() -> Vec<RustcOptGroup> {
             "Add possibility to expand macros in the HTML source code pages",
             "",
         ),
+        opt(
+            Unstable,
+            Opt,
+            "",
+            "html-bundle",
+            "Also write the whole documentation into a single HTML file at PATH, \
+                which can be browsed offline without a server",
+            "PATH",
+        ),
         // deprecated / removed options
         opt(
             Stable,
@@ -114,8 +114,8 @@
 // will instead cause conflicts. See #94591 for more. (This paragraph and the "Latest feature" line
 // are deliberately not in a doc comment, because they need not be in public docs.)
 //
-// Latest feature: Add default-body stability metadata.
-pub const FORMAT_VERSION: u32 = 60;
+// Latest feature: Add macro expansion provenance to items, and matchers to macros.
+pub const FORMAT_VERSION: u32 = 62;
 
 /// The root of the emitted JSON blob.
 ///
@@ -334,10 +334,38 @@ pub struct Item {
     /// and associated items whose const behavior is controlled by a const trait or const impl.
     pub const_stability: Option<Box<Stability>>,
 
+    /// The macro invocations that produced this item, starting with the one that directly
+    /// expanded to it, and ending with the one written in the source code.
+    ///
+    /// Empty if the item wasn't produced by a macro. Note that items whose tokens were passed to a
+    /// macro unchanged, like the `struct` in `my_macro! { struct Foo; }` when `my_macro!` expands
+    /// to its input, are considered to be written by the user, and also have no expansions here.
+    pub expanded_from: Vec<MacroExpansion>,
+
     /// The type-specific fields describing this item.
     pub inner: ItemEnum,
 }
 
+/// A macro invocation that produced an [`Item`]. See [`Item::expanded_from`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "rkyv_0_8", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+#[cfg_attr(feature = "rkyv_0_8", rkyv(derive(Debug)))]
+pub struct MacroExpansion {
+    /// How the macro was invoked.
+    pub kind: MacroKind,
+    /// The path to the definition of the invoked macro, e.g. `["my_crate", "inner", "make_struct"]`.
+    ///
+    /// Like [`ItemSummary::path`], this is where the macro was defined, which can differ from the
+    /// path used at the invocation. For builtin macros without a definition, such as
+    /// `format_args!`, this only contains the macro's name.
+    pub path: Vec<String>,
+    /// The source location of the invocation, such as `make_struct!(Foo)` or `#[derive(Clone)]`.
+    ///
+    /// If the invocation was itself produced by a macro, this points to the outermost invocation
+    /// written in the source code instead, like [`Item::span`] does.
+    pub call_site: Option<Span>,
+}
+
 /// Stability information for an item.
 ///
 /// In [`Item::stability`], this refers to regular item stability: whether the item is
@@ -865,9 +893,8 @@ pub enum ItemEnum {
     /// See [the tracking issue](https://github.com/rust-lang/rust/issues/43467)
     ExternType,
 
-    /// A macro_rules! declarative macro. Contains a single string with the source
-    /// representation of the macro with the patterns stripped.
-    Macro(String),
+    /// A `macro_rules!` (or `macro`) declarative macro.
+    Macro(Macro),
     /// A procedural macro.
     ProcMacro(ProcMacro),
 
@@ -1004,6 +1031,8 @@ pub struct Union {
     ///
     /// All of the corresponding [`Item`]s are of kind [`ItemEnum::Impl`].
     pub impls: Vec<Id>,
+    /// The memory layout of this union, if requested and known. See [`Layout`].
+    pub layout: Option<Layout>,
 }
 
 /// A `struct`.
@@ -1019,6 +1048,8 @@ pub struct Struct {
     /// All impls (both of traits and inherent) for this struct.
     /// All of the corresponding [`Item`]s are of kind [`ItemEnum::Impl`].
     pub impls: Vec<Id>,
+    /// The memory layout of this struct, if requested and known. See [`Layout`].
+    pub layout: Option<Layout>,
 }
 
 /// The kind of a [`Struct`] and the data specific to it, i.e. fields.
@@ -1075,6 +1106,8 @@ pub struct Enum {
     pub variants: Vec<Id>,
     /// `impl`s for the enum.
     pub impls: Vec<Id>,
+    /// The memory layout of this enum, if requested and known. See [`Layout`].
+    pub layout: Option<Layout>,
 }
 
 /// A variant of an enum.
@@ -1153,6 +1186,111 @@ pub struct Discriminant {
     pub value: String,
 }
 
+/// The memory layout of a type, as computed by the compiler for the target being documented.
+///
+/// This is only emitted when rustdoc is passed `--show-type-layout`, and only for types whose
+/// layout doesn't depend on generic parameters. Like the HTML "Layout" section, it is **not** a
+/// stability guarantee: unless the type has a `#[repr]` that pins it down, the layout may differ
+/// between compiler versions or even between compilations.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "rkyv_0_8", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+#[cfg_attr(feature = "rkyv_0_8", rkyv(derive(Debug)))]
+pub struct Layout {
+    /// The size of the type in bytes.
+    ///
+    /// For unsized types, this is the size of the statically known prefix.
+    pub size: u64,
+    /// The ABI-required alignment of the type in bytes.
+    pub align: u64,
+    /// Whether the type is dynamically sized, i.e. doesn't implement [`Sized`].
+    pub is_unsized: bool,
+    /// Whether the type has no valid values.
+    pub is_uninhabited: bool,
+    /// How values of this type are passed around at the ABI level.
+    pub abi: LayoutAbi,
+    /// The offset in bytes of each field, in declaration order.
+    ///
+    /// This includes private and `#[doc(hidden)]` fields, as they still take up space. It is
+    /// empty for enums with more than one variant; see [`Self::variants`] instead.
+    pub field_offsets: Vec<u64>,
+    /// The range of invalid values with the most room in it, which enclosing enums can use to
+    /// store their tag in, if any.
+    pub niche: Option<Niche>,
+    /// The tag used to tell the variants of an enum apart, if it has more than one variant.
+    pub tag: Option<Tag>,
+    /// The layout of each variant of an enum, in declaration order, if it has more than one
+    /// variant.
+    ///
+    /// Like [`Self::field_offsets`], this includes variants that aren't documented.
+    pub variants: Vec<VariantLayout>,
+}
+
+/// How values of a type are passed around at the ABI level. See [`Layout::abi`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "rkyv_0_8", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+#[cfg_attr(feature = "rkyv_0_8", rkyv(derive(Debug)))]
+#[serde(rename_all = "snake_case")]
+pub enum LayoutAbi {
+    /// A single integer, float or pointer, such as `u32` or `NonNull<T>`.
+    Scalar,
+    /// A pair of scalars, such as `&[T]` or `(u8, u32)`.
+    ScalarPair,
+    /// A SIMD vector with `count` elements.
+    Vector { count: u64 },
+    /// Anything else, which is passed in memory.
+    Aggregate,
+}
+
+/// A range of values that are invalid for a type. See [`Layout::niche`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "rkyv_0_8", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+#[cfg_attr(feature = "rkyv_0_8", rkyv(derive(Debug)))]
+pub struct Niche {
+    /// The offset in bytes of the scalar containing the niche.
+    pub offset: u64,
+    /// The size in bytes of the scalar containing the niche.
+    pub size: u64,
+    /// The first valid value of the scalar.
+    ///
+    /// Stored as a string, like [`Discriminant::value`], due to JSON's poor support for large
+    /// integers.
+    pub valid_range_start: String,
+    /// The last valid value of the scalar, inclusive.
+    ///
+    /// The valid range may wrap around, in which case this is smaller than
+    /// [`Self::valid_range_start`].
+    pub valid_range_end: String,
+}
+
+/// The tag of an enum with more than one variant. See [`Layout::tag`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "rkyv_0_8", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+#[cfg_attr(feature = "rkyv_0_8", rkyv(derive(Debug)))]
+pub struct Tag {
+    /// The offset of the tag in bytes.
+    pub offset: u64,
+    /// The size of the tag in bytes.
+    pub size: u64,
+    /// Whether the tag is stored in the niche of one of the variants' fields, rather than in a
+    /// separate field of its own.
+    pub is_niche: bool,
+}
+
+/// The layout of a single enum variant. See [`Layout::variants`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "rkyv_0_8", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+#[cfg_attr(feature = "rkyv_0_8", rkyv(derive(Debug)))]
+pub struct VariantLayout {
+    /// The name of the variant.
+    pub name: String,
+    /// The size of the variant in bytes, including the tag.
+    pub size: u64,
+    /// Whether the variant has no valid values.
+    pub is_uninhabited: bool,
+    /// The offset in bytes of each field of the variant, in declaration order.
+    pub field_offsets: Vec<u64>,
+}
+
 /// A set of fundamental properties of a function.
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[cfg_attr(feature = "rkyv_0_8", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
@@ -1792,6 +1930,105 @@ pub struct ProcMacro {
     pub helpers: Vec<String>,
 }
 
+/// A declarative macro, defined with `macro_rules!` or `macro`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "rkyv_0_8", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+#[cfg_attr(feature = "rkyv_0_8", rkyv(derive(Debug)))]
+pub struct Macro {
+    /// The source representation of the macro with the bodies of its rules stripped.
+    pub source: String,
+    /// The rules of the macro, in the order in which the compiler tries them.
+    pub rules: Vec<MacroRule>,
+}
+
+/// The left-hand side of a rule of a declarative macro, e.g. `($x:expr)` in
+/// `($x:expr) => { ... }`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "rkyv_0_8", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+#[cfg_attr(feature = "rkyv_0_8", rkyv(derive(Debug)))]
+pub struct MacroRule {
+    /// How the macro has to be invoked for this rule to apply. [`MacroKind::Bang`] for all rules
+    /// of macros that aren't `attr` or `derive` rules.
+    pub kind: MacroKind,
+    /// For [`MacroKind::Attr`] rules, the matcher for the arguments of the attribute, e.g.
+    /// `$x:expr` in `attr($x:expr) ($item:item) => { ... }`.
+    pub args: Option<Vec<MacroMatcher>>,
+    /// The matcher for the input of the macro, without its outer delimiters.
+    pub matcher: Vec<MacroMatcher>,
+}
+
+/// A part of a [`MacroRule`] matcher.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "rkyv_0_8", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+#[cfg_attr(feature = "rkyv_0_8", rkyv(derive(Debug)))]
+#[cfg_attr(feature = "rkyv_0_8", rkyv(serialize_bounds(
+    __S: rkyv::ser::Writer + rkyv::ser::Allocator,
+    __S::Error: rkyv::rancor::Source,
+)))]
+#[cfg_attr(feature = "rkyv_0_8", rkyv(deserialize_bounds(
+    __D::Error: rkyv::rancor::Source,
+)))]
+#[cfg_attr(feature = "rkyv_0_8", rkyv(bytecheck(bounds(
+    __C: rkyv::validation::ArchiveContext,
+    <__C as rkyv::rancor::Fallible>::Error: rkyv::rancor::Source,
+))))]
+#[serde(rename_all = "snake_case")]
+pub enum MacroMatcher {
+    /// A token that has to appear literally, e.g. `,`, `fn` or `"text"`.
+    Token(String),
+    /// Matchers surrounded by a pair of delimiters, e.g. `[$x:expr]`.
+    Delimited {
+        delimiter: MacroDelimiter,
+        #[cfg_attr(feature = "rkyv_0_8", rkyv(omit_bounds))]
+        contents: Vec<MacroMatcher>,
+    },
+    /// A metavariable with a fragment specifier, e.g. `$x:expr`.
+    Fragment {
+        /// The name of the metavariable, without the leading `$`.
+        name: String,
+        /// The fragment specifier, e.g. `expr`, `ident` or `tt`.
+        kind: String,
+    },
+    /// A repetition, e.g. `$($x:expr),*`.
+    Repetition {
+        /// The matchers to repeat.
+        #[cfg_attr(feature = "rkyv_0_8", rkyv(omit_bounds))]
+        contents: Vec<MacroMatcher>,
+        /// The token between repetitions, if any, e.g. `,`.
+        separator: Option<String>,
+        /// How often the contents may be repeated.
+        op: MacroRepetitionOp,
+    },
+}
+
+/// The delimiters of a [`MacroMatcher::Delimited`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "rkyv_0_8", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+#[cfg_attr(feature = "rkyv_0_8", rkyv(derive(Debug)))]
+#[serde(rename_all = "snake_case")]
+pub enum MacroDelimiter {
+    /// `( ... )`
+    Parenthesis,
+    /// `{ ... }`
+    Brace,
+    /// `[ ... ]`
+    Bracket,
+}
+
+/// How often the contents of a [`MacroMatcher::Repetition`] may be repeated.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "rkyv_0_8", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+#[cfg_attr(feature = "rkyv_0_8", rkyv(derive(Debug)))]
+#[serde(rename_all = "snake_case")]
+pub enum MacroRepetitionOp {
+    /// `*`
+    ZeroOrMore,
+    /// `+`
+    OneOrMore,
+    /// `?`
+    ZeroOrOne,
+}
+
 /// The way a [`ProcMacro`] is declared to be used.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[cfg_attr(feature = "rkyv_0_8", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
@@ -1816,6 +2053,8 @@ pub struct TypeAlias {
     pub type_: Type,
     /// Information about the type parameters and `where` clauses of the alias.
     pub generics: Generics,
+    /// The memory layout of the aliased type, if requested and known. See [`Layout`].
+    pub layout: Option<Layout>,
 }
 
 /// A `static` declaration.
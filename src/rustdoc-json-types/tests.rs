@@ -6,6 +6,7 @@ fn test_struct_info_roundtrip() {
         generics: Generics { params: vec![], where_predicates: vec![] },
         kind: StructKind::Plain { fields: vec![], has_stripped_fields: false },
         impls: vec![],
+        layout: None,
     });
 
     // JSON
@@ -26,6 +27,7 @@ fn test_union_info_roundtrip() {
         has_stripped_fields: false,
         fields: vec![],
         impls: vec![],
+        layout: None,
     });
 
     // JSON
@@ -64,6 +66,7 @@ fn test_rkyv_roundtrip() {
             generics: Generics { params: vec![], where_predicates: vec![] },
             kind: StructKind::Plain { fields: vec![Id(1), Id(2)], has_stripped_fields: false },
             impls: vec![Id(3)],
+            layout: None,
         });
         rkyv_roundtrip(&s);
 
@@ -72,6 +75,7 @@ fn test_rkyv_roundtrip() {
             has_stripped_fields: false,
             fields: vec![Id(1)],
             impls: vec![],
+            layout: None,
         });
         rkyv_roundtrip(&u);
 
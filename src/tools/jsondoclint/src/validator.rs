@@ -4,8 +4,8 @@
 use rustdoc_json_types::{
     AssocItemConstraint, AssocItemConstraintKind, Constant, Crate, DynTrait, Enum, Function,
     FunctionPointer, FunctionSignature, GenericArg, GenericArgs, GenericBound, GenericParamDef,
-    Generics, Id, Impl, ItemEnum, ItemSummary, Module, Path, Primitive, ProcMacro, Static, Struct,
-    StructKind, Term, Trait, TraitAlias, Type, TypeAlias, Union, Use, Variant, VariantKind,
+    Generics, Id, Impl, ItemEnum, ItemSummary, Macro, Module, Path, Primitive, ProcMacro, Static,
+    Struct, StructKind, Term, Trait, TraitAlias, Type, TypeAlias, Union, Use, Variant, VariantKind,
     WherePredicate,
 };
 use serde_json::Value;
@@ -273,7 +273,7 @@ fn check_static(&mut self, x: &'a Static) {
         self.check_type(&x.type_);
     }
 
-    fn check_macro(&mut self, _: &'a str) {
+    fn check_macro(&mut self, _: &'a Macro) {
         // nop
     }
 
@@ -37,6 +37,7 @@ fn errors_on_missing_links() {
                 deprecation: None,
                 stability: None,
                 const_stability: None,
+                expanded_from: Vec::new(),
                 inner: ItemEnum::Module(Module {
                     is_crate: true,
                     items: vec![],
@@ -87,6 +88,7 @@ fn errors_on_local_in_paths_and_not_index() {
                     deprecation: None,
                     stability: None,
                     const_stability: None,
+                    expanded_from: Vec::new(),
                     inner: ItemEnum::Module(Module {
                         is_crate: true,
                         items: vec![Id(1)],
@@ -108,6 +110,7 @@ fn errors_on_local_in_paths_and_not_index() {
                     deprecation: None,
                     stability: None,
                     const_stability: None,
+                    expanded_from: Vec::new(),
                     inner: ItemEnum::Primitive(Primitive { name: "i32".to_owned(), impls: vec![] }),
                 },
             ),
@@ -163,6 +166,7 @@ fn errors_on_missing_path() {
                     deprecation: None,
                     stability: None,
                     const_stability: None,
+                    expanded_from: Vec::new(),
                     inner: ItemEnum::Module(Module {
                         is_crate: true,
                         items: vec![Id(1), Id(2)],
@@ -184,10 +188,12 @@ fn errors_on_missing_path() {
                     deprecation: None,
                     stability: None,
                     const_stability: None,
+                    expanded_from: Vec::new(),
                     inner: ItemEnum::Struct(Struct {
                         kind: StructKind::Unit,
                         generics: generics.clone(),
                         impls: vec![],
+                        layout: None,
                     }),
                 },
             ),
@@ -205,6 +211,7 @@ fn errors_on_missing_path() {
                     deprecation: None,
                     stability: None,
                     const_stability: None,
+                    expanded_from: Vec::new(),
                     inner: ItemEnum::Function(Function {
                         sig: FunctionSignature {
                             inputs: vec![],
@@ -270,6 +277,7 @@ fn krate_with_trait_item(inner: ItemEnum) -> Crate {
                     deprecation: None,
                     stability: None,
                     const_stability: None,
+                    expanded_from: Vec::new(),
                     inner: ItemEnum::Module(Module {
                         is_crate: true,
                         items: vec![Id(1)],
@@ -291,6 +299,7 @@ fn krate_with_trait_item(inner: ItemEnum) -> Crate {
                     deprecation: None,
                     stability: None,
                     const_stability: None,
+                    expanded_from: Vec::new(),
                     inner: ItemEnum::Trait(Trait {
                         is_auto: false,
                         is_unsafe: false,
@@ -316,6 +325,7 @@ fn krate_with_trait_item(inner: ItemEnum) -> Crate {
                     deprecation: None,
                     stability: None,
                     const_stability: None,
+                    expanded_from: Vec::new(),
                     inner,
                 },
             ),
@@ -419,6 +429,7 @@ fn checks_local_crate_id_is_correct() {
                 deprecation: None,
                 stability: None,
                 const_stability: None,
+                expanded_from: Vec::new(),
                 inner: ItemEnum::Module(Module {
                     is_crate: true,
                     items: vec![],
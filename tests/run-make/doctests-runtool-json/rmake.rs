@@ -0,0 +1,34 @@
+//@ ignore-cross-compile (needs to run host tool binary)
+
+// Tests rustdoc's `--test-runtool-protocol=json`, where the runtool receives a JSON description of
+// each doctest and reports the result as JSON.
+
+use std::path::Path;
+
+use run_make_support::{rfs, rustc, rustdoc};
+
+fn main() {
+    rustc().input("t.rs").crate_type("rlib").run();
+    rustc().input("runtool.rs").run();
+
+    let output = rustdoc()
+        .input("t.rs")
+        .arg("-Zunstable-options")
+        .arg("--test")
+        .arg("--test-runtool")
+        .arg(Path::new(".").join("runtool").with_extension(std::env::consts::EXE_EXTENSION))
+        .arg("--test-runtool-protocol=json")
+        .extern_("t", "libt.rlib")
+        .run_fail();
+    output
+        .assert_stdout_contains("test result: FAILED. 3 passed; 1 failed")
+        .assert_stdout_contains("Test executable failed (exit status: 101).");
+
+    // `no_run` doctests are only compiled, so the runtool only gets three requests.
+    let requests = rfs::read_to_string("requests.log");
+    let requests = requests.lines().collect::<Vec<_>>();
+    assert_eq!(requests.len(), 3, "{requests:#?}");
+    assert!(requests.iter().all(|request| request.starts_with(r#"{"format_version":1,"#)));
+    assert_eq!(requests.iter().filter(|r| r.contains(r#""should_panic":true"#)).count(), 1);
+    assert!(requests.iter().any(|r| r.contains(r#""name":"t.rs - expected_panic (line 6)""#)));
+}
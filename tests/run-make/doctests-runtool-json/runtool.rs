@@ -0,0 +1,47 @@
+// A runtool speaking `--test-runtool-protocol=json`: it runs the doctest executable described by
+// the request it receives, and reports how it went. Requests are also appended to `requests.log`,
+// so that the test can check what rustdoc sent.
+
+use std::fs::OpenOptions;
+use std::io::{Read, Write};
+use std::process::Command;
+
+// Good enough for the string fields rustdoc sends, which only use `\\` and `\"` escapes here.
+fn string_field(request: &str, name: &str) -> String {
+    let key = format!("\"{name}\":\"");
+    let start = request.find(&key).unwrap() + key.len();
+    let mut value = String::new();
+    let mut chars = request[start..].chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => break,
+            '\\' => value.push(chars.next().unwrap()),
+            c => value.push(c),
+        }
+    }
+    value
+}
+
+fn main() {
+    let mut request = String::new();
+    std::io::stdin().read_to_string(&mut request).unwrap();
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open("requests.log")
+        .unwrap()
+        .write_all(request.as_bytes())
+        .unwrap();
+
+    let executable = string_field(&request, "executable");
+    // Only the last line of the output is the result.
+    println!("running {executable}");
+    match Command::new(&executable).output() {
+        Ok(output) => {
+            let outcome = if output.status.success() { "success" } else { "failure" };
+            let exit_code = output.status.code().map_or("null".to_owned(), |c| c.to_string());
+            println!(r#"{{"outcome":"{outcome}","exit_code":{exit_code}}}"#);
+        }
+        Err(_) => println!(r#"{{"outcome":"error","message":"could not run the executable"}}"#),
+    }
+}
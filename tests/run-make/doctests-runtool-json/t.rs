@@ -0,0 +1,19 @@
+/// ```
+/// t::foople();
+/// ```
+pub fn foople() {}
+
+/// ```should_panic
+/// panic!("expected");
+/// ```
+pub fn expected_panic() {}
+
+/// ```
+/// assert_eq!(1 + 1, 3);
+/// ```
+pub fn broken() {}
+
+/// ```no_run
+/// loop {}
+/// ```
+pub fn never_run() {}
@@ -0,0 +1,48 @@
+// Checks that `--html-bundle` packs the whole documentation into a single HTML file, which embeds
+// every page and static file, and which is identical when built twice.
+
+//@ needs-target-std
+
+use run_make_support::{path, rfs, rustdoc};
+
+fn main() {
+    let out_dir = path("doc");
+    let bundle = path("doc.html");
+    rustdoc()
+        .input("foo.rs")
+        .out_dir(&out_dir)
+        .arg("-Zunstable-options")
+        .arg("--html-bundle")
+        .arg(&bundle)
+        .run();
+
+    let html = rfs::read_to_string(&bundle);
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains(r#"<body data-start-page="foo/index.html">"#));
+    // Pages, static files and the search index are all embedded, keyed by their path.
+    for file in ["\"foo/index.html\":", "\"foo/inner/struct.Bar.html\":", "\"search.index/"] {
+        assert!(html.contains(file), "{file} is missing from the bundle");
+    }
+    assert!(html.contains("\"static.files/rustdoc-"));
+    // Fonts aren't UTF-8, and are embedded in base64.
+    assert!(html.contains(".woff2\":\""));
+    // The embedded pages are full of `</script>` tags, but only the embedded files and the loader
+    // are scripts of the bundle.
+    assert_eq!(html.matches("<script").count(), 2);
+    assert_eq!(html.matches("</script>").count(), 2);
+
+    // Writing the bundle into the documentation directory doesn't embed a previous version of
+    // the bundle in the new one, so that it comes out the same.
+    let bundle_in_doc = out_dir.join("bundle.html");
+    for _ in 0..2 {
+        rustdoc()
+            .input("foo.rs")
+            .out_dir(&out_dir)
+            .arg("-Zunstable-options")
+            .arg("--html-bundle")
+            .arg(&bundle_in_doc)
+            .run();
+    }
+    assert!(!rfs::read_to_string(&bundle_in_doc).contains("\"bundle.html\":"));
+    assert_eq!(rfs::read_to_string(&bundle_in_doc), html);
+}
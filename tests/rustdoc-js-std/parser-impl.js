@@ -0,0 +1,87 @@
+const PARSED = [
+    {
+        query: 'impl Read',
+        elems: [{
+            name: "Read",
+            fullPath: ["read"],
+            pathWithoutLast: [],
+            pathLast: "read",
+            normalizedPathLast: "read",
+            generics: [],
+            typeFilter: null,
+        }],
+        foundElems: 1,
+        userQuery: "impl Read",
+        returned: [],
+        implementsTrait: true,
+        error: null,
+    },
+    {
+        query: ': Iterator<Item = u8>',
+        elems: [{
+            name: "Iterator",
+            fullPath: ["iterator"],
+            pathWithoutLast: [],
+            pathLast: "iterator",
+            normalizedPathLast: "iterator",
+            generics: [],
+            bindings: [
+                [
+                    'item',
+                    [
+                        {
+                            name: "u8",
+                            fullPath: ["u8"],
+                            pathWithoutLast: [],
+                            pathLast: "u8",
+                            normalizedPathLast: "u8",
+                            generics: [],
+                            typeFilter: null,
+                        },
+                    ],
+                ],
+            ],
+            typeFilter: null,
+        }],
+        foundElems: 1,
+        userQuery: ": Iterator<Item = u8>",
+        returned: [],
+        implementsTrait: true,
+        error: null,
+    },
+    {
+        query: 'impl Read, Write',
+        elems: [],
+        foundElems: 0,
+        userQuery: "impl Read, Write",
+        returned: [],
+        implementsTrait: false,
+        error: "Expected a single trait after `impl`",
+    },
+    {
+        query: 'impl Read -> u8',
+        elems: [],
+        foundElems: 0,
+        userQuery: "impl Read -> u8",
+        returned: [],
+        implementsTrait: false,
+        error: "Expected a single trait after `impl`",
+    },
+    {
+        query: 'impl',
+        elems: [{
+            name: "impl",
+            fullPath: ["impl"],
+            pathWithoutLast: [],
+            pathLast: "impl",
+            normalizedPathLast: "impl",
+            generics: [],
+            typeFilter: null,
+        }],
+        foundElems: 1,
+        userQuery: "impl",
+        returned: [],
+        implementsTrait: false,
+        error: null,
+    },
+];
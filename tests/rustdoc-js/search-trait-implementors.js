@@ -0,0 +1,40 @@
+// exact-check
+// ignore-order
+
+const EXPECTED = [
+    {
+        'query': 'impl Source',
+        'others': [
+            { 'path': 'search_trait_implementors', 'name': 'Bytes' },
+            { 'path': 'search_trait_implementors', 'name': 'Chars' },
+        ],
+        'in_args': [
+            { 'path': 'search_trait_implementors', 'name': 'consume' },
+        ],
+        'returned': [
+            { 'path': 'search_trait_implementors', 'name': 'produce' },
+        ],
+    },
+    {
+        'query': ': Source<Item = u8>',
+        'others': [
+            { 'path': 'search_trait_implementors', 'name': 'Bytes' },
+        ],
+    },
+    {
+        'query': ': Source<Item = char>',
+        'others': [
+            { 'path': 'search_trait_implementors', 'name': 'Chars' },
+        ],
+    },
+    {
+        'query': 'impl sink',
+        'others': [
+            { 'path': 'search_trait_implementors', 'name': 'Drain' },
+        ],
+    },
+    {
+        'query': 'impl Bytes',
+        'others': [],
+    },
+];
@@ -0,0 +1,27 @@
+pub trait Sink {}
+
+pub trait Source {
+    type Item;
+}
+
+pub struct Bytes;
+
+impl Source for Bytes {
+    type Item = u8;
+}
+
+pub struct Chars;
+
+impl Source for Chars {
+    type Item = char;
+}
+
+pub struct Drain;
+
+impl Sink for Drain {}
+
+pub fn consume(_source: impl Source) {}
+
+pub fn produce() -> impl Source {
+    Bytes
+}
@@ -0,0 +1,33 @@
+//@ edition:2021
+
+mod inner {
+    macro_rules! make_struct {
+        ($name:ident) => {
+            pub struct $name;
+        };
+    }
+    pub(crate) use make_struct;
+
+    macro_rules! make_two {
+        ($a:ident, $b:ident) => {
+            make_struct!($a);
+            make_struct!($b);
+        };
+    }
+    pub(crate) use make_two;
+}
+
+//@ count "$.index[?(@.name=='Direct')].expanded_from[*]" 1
+//@ is "$.index[?(@.name=='Direct')].expanded_from[0].kind" '"bang"'
+//@ is "$.index[?(@.name=='Direct')].expanded_from[0].path" '["expanded_from", "inner", "make_struct"]'
+//@ is "$.index[?(@.name=='Direct')].expanded_from[0].call_site.begin" '[24, 1]'
+inner::make_struct!(Direct);
+
+//@ count "$.index[?(@.name=='Nested')].expanded_from[*]" 2
+//@ is "$.index[?(@.name=='Nested')].expanded_from[0].path" '["expanded_from", "inner", "make_struct"]'
+//@ is "$.index[?(@.name=='Nested')].expanded_from[1].path" '["expanded_from", "inner", "make_two"]'
+//@ is "$.index[?(@.name=='Nested')].expanded_from[1].call_site.begin" '[30, 1]'
+inner::make_two!(Nested, Other);
+
+//@ is "$.index[?(@.name=='Written')].expanded_from" '[]'
+pub struct Written;
@@ -0,0 +1,53 @@
+#![feature(macro_attr, macro_derive)]
+
+//@ is "$.index[?(@.name=='plain')].inner.macro.rules[0].kind" '"bang"'
+//@ is "$.index[?(@.name=='plain')].inner.macro.rules[0].args" null
+//@ is "$.index[?(@.name=='plain')].inner.macro.rules[0].matcher" '[]'
+//@ is "$.index[?(@.name=='plain')].inner.macro.rules[1].matcher[0].token" '"fn"'
+//@ is "$.index[?(@.name=='plain')].inner.macro.rules[1].matcher[1].fragment" '{"name": "name", "kind": "ident"}'
+//@ is "$.index[?(@.name=='plain')].inner.macro.rules[1].matcher[2].delimited.delimiter" '"bracket"'
+//@ is "$.index[?(@.name=='plain')].inner.macro.rules[1].matcher[2].delimited.contents[0].fragment.kind" '"ty"'
+#[macro_export]
+macro_rules! plain {
+    () => {};
+    (fn $name:ident [$t:ty]) => {};
+}
+
+//@ is "$.index[?(@.name=='repeat')].inner.macro.rules[0].matcher[0].repetition.separator" '","'
+//@ is "$.index[?(@.name=='repeat')].inner.macro.rules[0].matcher[0].repetition.op" '"zero_or_more"'
+//@ is "$.index[?(@.name=='repeat')].inner.macro.rules[0].matcher[0].repetition.contents[0].fragment.name" '"x"'
+//@ is "$.index[?(@.name=='repeat')].inner.macro.rules[0].matcher[1].repetition.separator" null
+//@ is "$.index[?(@.name=='repeat')].inner.macro.rules[0].matcher[1].repetition.op" '"zero_or_one"'
+//@ is "$.index[?(@.name=='repeat')].inner.macro.rules[0].matcher[1].repetition.contents[0].token" '";"'
+#[macro_export]
+macro_rules! repeat {
+    ($($x:expr),* $(;)?) => {};
+}
+
+// A `?`, `*` or `+` right after the repetition is its operator, never its separator.
+//@ is "$.index[?(@.name=='kleene')].inner.macro.rules[0].matcher[0].repetition.separator" null
+//@ is "$.index[?(@.name=='kleene')].inner.macro.rules[0].matcher[0].repetition.op" '"zero_or_more"'
+//@ is "$.index[?(@.name=='kleene')].inner.macro.rules[0].matcher[1].token" '"*"'
+//@ is "$.index[?(@.name=='kleene')].inner.macro.rules[0].matcher[2].repetition.separator" null
+//@ is "$.index[?(@.name=='kleene')].inner.macro.rules[0].matcher[2].repetition.op" '"zero_or_one"'
+//@ is "$.index[?(@.name=='kleene')].inner.macro.rules[0].matcher[3].token" '"+"'
+//@ is "$.index[?(@.name=='kleene')].inner.macro.rules[0].matcher[4].repetition.separator" '";"'
+//@ is "$.index[?(@.name=='kleene')].inner.macro.rules[0].matcher[4].repetition.op" '"one_or_more"'
+#[macro_export]
+macro_rules! kleene {
+    ($(a)* * $(b)? + $(c);+) => {};
+}
+
+//@ is "$.index[?(@.name=='mixed')].inner.macro.rules[0].kind" '"attr"'
+//@ is "$.index[?(@.name=='mixed')].inner.macro.rules[0].args[0].fragment.kind" '"tt"'
+//@ is "$.index[?(@.name=='mixed')].inner.macro.rules[0].matcher[0].fragment.kind" '"item"'
+//@ is "$.index[?(@.name=='mixed')].inner.macro.rules[1].kind" '"derive"'
+//@ is "$.index[?(@.name=='mixed')].inner.macro.rules[1].args" null
+//@ is "$.index[?(@.name=='mixed')].inner.macro.rules[2].kind" '"bang"'
+//@ is "$.index[?(@.name=='mixed')].inner.macro.rules[2].matcher" '[]'
+#[macro_export]
+macro_rules! mixed {
+    attr($a:tt) ($i:item) => {};
+    derive() ($i:item) => {};
+    () => {};
+}
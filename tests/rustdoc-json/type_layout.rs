@@ -0,0 +1,58 @@
+//@ compile-flags: --show-type-layout
+
+//@ is "$.index[?(@.name=='Pair')].inner.struct.layout.size" 8
+//@ is "$.index[?(@.name=='Pair')].inner.struct.layout.align" 4
+//@ is "$.index[?(@.name=='Pair')].inner.struct.layout.is_unsized" false
+//@ is "$.index[?(@.name=='Pair')].inner.struct.layout.field_offsets" '[0, 4]'
+//@ is "$.index[?(@.name=='Pair')].inner.struct.layout.tag" null
+//@ is "$.index[?(@.name=='Pair')].inner.struct.layout.variants" '[]'
+#[repr(C)]
+pub struct Pair {
+    pub small: u8,
+    big: u32,
+}
+
+//@ is "$.index[?(@.name=='Flag')].inner.struct.layout.abi" '"scalar"'
+//@ is "$.index[?(@.name=='Flag')].inner.struct.layout.niche.offset" 0
+//@ is "$.index[?(@.name=='Flag')].inner.struct.layout.niche.size" 1
+//@ is "$.index[?(@.name=='Flag')].inner.struct.layout.niche.valid_range_start" '"0"'
+//@ is "$.index[?(@.name=='Flag')].inner.struct.layout.niche.valid_range_end" '"1"'
+pub struct Flag(pub bool);
+
+//@ is "$.index[?(@.name=='Tagged')].inner.enum.layout.size" 8
+//@ is "$.index[?(@.name=='Tagged')].inner.enum.layout.field_offsets" '[]'
+//@ is "$.index[?(@.name=='Tagged')].inner.enum.layout.tag.offset" 0
+//@ is "$.index[?(@.name=='Tagged')].inner.enum.layout.tag.size" 1
+//@ is "$.index[?(@.name=='Tagged')].inner.enum.layout.tag.is_niche" false
+//@ is "$.index[?(@.name=='Tagged')].inner.enum.layout.variants[0].name" '"Full"'
+//@ is "$.index[?(@.name=='Tagged')].inner.enum.layout.variants[0].field_offsets" '[4]'
+//@ is "$.index[?(@.name=='Tagged')].inner.enum.layout.variants[1].name" '"Empty"'
+//@ is "$.index[?(@.name=='Tagged')].inner.enum.layout.variants[1].field_offsets" '[]'
+#[repr(u8)]
+pub enum Tagged {
+    Full(u32),
+    Empty,
+}
+
+//@ is "$.index[?(@.name=='Never')].inner.enum.layout.size" 0
+//@ is "$.index[?(@.name=='Never')].inner.enum.layout.is_uninhabited" true
+pub enum Never {}
+
+//@ is "$.index[?(@.name=='Bits')].inner.union.layout.size" 4
+//@ is "$.index[?(@.name=='Bits')].inner.union.layout.field_offsets" '[0, 0]'
+pub union Bits {
+    pub int: u32,
+    pub float: f32,
+}
+
+//@ is "$.index[?(@.name=='Bytes')].inner.type_alias.layout.size" 16
+//@ is "$.index[?(@.name=='Bytes')].inner.type_alias.layout.align" 1
+//@ is "$.index[?(@.name=='Bytes')].inner.type_alias.layout.field_offsets" '[]'
+pub type Bytes = [u8; 16];
+
+// Layouts that depend on generic parameters can't be computed.
+//@ is "$.index[?(@.name=='Wrapper')].inner.struct.layout" null
+pub struct Wrapper<T>(pub T);
+
+//@ is "$.index[?(@.name=='Boxed')].inner.type_alias.layout" null
+pub type Boxed<T> = Wrapper<T>;
@@ -5,6 +5,7 @@
 //@ normalize-stdout: "tests/rustdoc-ui" -> "$$DIR"
 //@ normalize-stdout: "[A-Z]:[\\/](?:[^\\/]+[\\/])*?\$DIR" -> "$$DIR"
 //@ check-pass
+//@ edition: 2021
 
 //! ```ignore (checking attributes)
 //! let x = 12;
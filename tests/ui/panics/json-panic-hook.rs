@@ -0,0 +1,13 @@
+//@ run-fail
+//@ check-run-results
+//@ exec-env:RUST_BACKTRACE=0
+//@ exec-env:RUST_PANIC_FORMAT=json
+//@ ignore-windows backslashes in paths are escaped in the JSON output
+//@ normalize-stderr: "\"thread_id\":\d+" -> "\"thread_id\":$$TID"
+
+// Test that the default hook writes the panic as a single line of JSON when
+// `RUST_PANIC_FORMAT=json` is set.
+
+fn main() {
+    panic!("a \"quoted\" message\nwith {} lines", 2)
+}